@@ -0,0 +1,737 @@
+use std::collections::HashMap;
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::pubkey::{MAX_SEEDS, MAX_SEED_LEN},
+};
+use mpl_token_metadata::{
+    accounts::Metadata,
+    types::{AuthorizationData, Payload, PayloadType, ProgrammableConfig, ProofInfo, SeedsVec},
+};
+use rmpv::Value;
+
+use crate::{constants::MPL_TOKEN_AUTH_RULES, errors::MMMErrorCode};
+
+// The authorization payload of pNFT transfers and delegations is derived from
+// the rule set in the metadata programmable_config. The rules of the operation
+// decide which payload entries are needed: the pool provides the seeds of its
+// own pda for the roles it takes in the instruction, and the caller can pass
+// the other entries the rules reference. Failures inside the rules program
+// abort the transaction and can't be remapped, so the rule set is checked here
+// before the cpi and the common failures are returned as typed errors.
+pub const MAX_AUTHORIZATION_PAYLOAD_ENTRIES: usize = 8;
+pub const MAX_AUTHORIZATION_PROOF_LEN: usize = 32;
+
+// layout of the rule set accounts of mpl-token-auth-rules: a header with the
+// key and the location of the revision map, then each revision prefixed with
+// its lib version. Only v1 (msgpack) revisions can be read here.
+const RULE_SET_KEY: u8 = 1;
+const RULE_SET_HEADER_LEN: usize = 9;
+const RULE_SET_REV_MAP_VERSION: u8 = 1;
+const RULE_SET_LIB_VERSION_V1: u8 = 1;
+
+// operations as named by token metadata
+pub const OPERATION_TRANSFER_OWNER: &str = "Transfer:Owner";
+pub const OPERATION_TRANSFER_DELEGATE: &str = "Transfer:TransferDelegate";
+pub const OPERATION_DELEGATE_TRANSFER: &str = "Delegate:Transfer";
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum PayloadTypeLocal {
+    Pubkey(Pubkey),
+    Seeds(Vec<Vec<u8>>),
+    MerkleProof(Vec<[u8; 32]>),
+    Number(u64),
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TaggedPayload {
+    pub name: String,
+    pub payload: PayloadTypeLocal,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct AuthorizationDataLocal {
+    pub payload: Vec<TaggedPayload>,
+}
+
+pub fn get_rule_set(metadata: &Metadata) -> Option<Pubkey> {
+    match &metadata.programmable_config {
+        Some(ProgrammableConfig::V1 { rule_set }) => *rule_set,
+        None => None,
+    }
+}
+
+// the authorization_rules account must be the rule set of the asset, it's
+// ignored by token metadata when the asset has no rule set
+pub fn assert_valid_authorization_rules(
+    metadata: &Metadata,
+    authorization_rules: &AccountInfo,
+) -> Result<()> {
+    match get_rule_set(metadata) {
+        Some(rule_set)
+            if authorization_rules.key() != rule_set
+                || authorization_rules.owner != &MPL_TOKEN_AUTH_RULES =>
+        {
+            msg!("authorization rules don't match the rule set {}", rule_set);
+            Err(MMMErrorCode::InvalidAuthorizationRules.into())
+        }
+        _ => Ok(()),
+    }
+}
+
+fn to_payload_type(payload: PayloadTypeLocal, amount: u64) -> Result<PayloadType> {
+    match payload {
+        PayloadTypeLocal::Pubkey(key) => Ok(PayloadType::Pubkey(key)),
+        PayloadTypeLocal::Seeds(seeds) => {
+            if seeds.len() > MAX_SEEDS || seeds.iter().any(|seed| seed.len() > MAX_SEED_LEN) {
+                return Err(MMMErrorCode::InvalidAuthorizationData.into());
+            }
+            Ok(PayloadType::Seeds(SeedsVec { seeds }))
+        }
+        PayloadTypeLocal::MerkleProof(proof) => {
+            if proof.len() > MAX_AUTHORIZATION_PROOF_LEN {
+                return Err(MMMErrorCode::InvalidAuthorizationData.into());
+            }
+            Ok(PayloadType::MerkleProof(ProofInfo { proof }))
+        }
+        PayloadTypeLocal::Number(number) => {
+            // token metadata fills in the transferred amount, a different
+            // amount would only fail inside the rules program
+            if number != amount {
+                return Err(MMMErrorCode::InvalidAuthorizationData.into());
+            }
+            Ok(PayloadType::Number(number))
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum PayloadKind {
+    Pubkey,
+    Seeds,
+    MerkleProof,
+    Number,
+}
+
+fn get_payload_kind(payload: &PayloadType) -> PayloadKind {
+    match payload {
+        PayloadType::Pubkey(_) => PayloadKind::Pubkey,
+        PayloadType::Seeds(_) => PayloadKind::Seeds,
+        PayloadType::MerkleProof(_) => PayloadKind::MerkleProof,
+        PayloadType::Number(_) => PayloadKind::Number,
+    }
+}
+
+// the payload fields read by a rule. Seeds and proofs are never filled in by
+// token metadata, so a rule reading them only passes when they are in the
+// payload
+#[derive(Debug)]
+enum RuleFields {
+    All(Vec<RuleFields>),
+    Any(Vec<RuleFields>),
+    Not(Box<RuleFields>),
+    Fields(Vec<(String, PayloadKind)>),
+}
+
+impl RuleFields {
+    fn collect(&self, fields: &mut Vec<(String, PayloadKind)>) {
+        match self {
+            RuleFields::All(rules) | RuleFields::Any(rules) => {
+                rules.iter().for_each(|rule| rule.collect(fields))
+            }
+            RuleFields::Not(rule) => rule.collect(fields),
+            RuleFields::Fields(rule_fields) => fields.extend(rule_fields.iter().cloned()),
+        }
+    }
+
+    fn is_satisfied(&self, map: &HashMap<String, PayloadType>) -> bool {
+        match self {
+            RuleFields::All(rules) => rules.iter().all(|rule| rule.is_satisfied(map)),
+            RuleFields::Any(rules) => rules.iter().any(|rule| rule.is_satisfied(map)),
+            // the negated rule fails without its fields as well
+            RuleFields::Not(_) => true,
+            RuleFields::Fields(fields) => fields.iter().all(|(name, kind)| {
+                !matches!(kind, PayloadKind::Seeds | PayloadKind::MerkleProof)
+                    || map.contains_key(name)
+            }),
+        }
+    }
+}
+
+// rules are serialized as externally tagged enums, either {name: [fields]} or
+// just the name for the rules without fields
+fn get_rule_variant(rule: &Value) -> Result<(&str, &[Value])> {
+    let variant = match rule {
+        Value::String(name) => name.as_str().map(|name| (name, &[][..])),
+        Value::Map(entries) if entries.len() == 1 => match &entries[0] {
+            (name, Value::Array(fields)) => name.as_str().map(|name| (name, fields.as_slice())),
+            (name, Value::Nil) => name.as_str().map(|name| (name, &[][..])),
+            _ => None,
+        },
+        _ => None,
+    };
+    variant.ok_or_else(|| MMMErrorCode::InvalidRuleSet.into())
+}
+
+fn parse_rules(rules: Option<&Value>) -> Result<Vec<RuleFields>> {
+    rules
+        .and_then(Value::as_array)
+        .ok_or(MMMErrorCode::InvalidRuleSet)?
+        .iter()
+        .map(parse_rule)
+        .collect()
+}
+
+fn parse_rule(rule: &Value) -> Result<RuleFields> {
+    let (name, args) = get_rule_variant(rule)?;
+    // the field names are the only strings in the fields of a rule, apart
+    // from the compare operator of Amount which comes before its field
+    let names: Vec<&str> = args.iter().filter_map(Value::as_str).collect();
+    let fields = match (name, names.as_slice()) {
+        ("All", _) => return Ok(RuleFields::All(parse_rules(args.first())?)),
+        ("Any", _) => return Ok(RuleFields::Any(parse_rules(args.first())?)),
+        ("Not", _) => {
+            let rule = args.first().ok_or(MMMErrorCode::InvalidRuleSet)?;
+            return Ok(RuleFields::Not(Box::new(parse_rule(rule)?)));
+        }
+        ("PDAMatch", [pda_field, seeds_field]) => vec![
+            (pda_field.to_string(), PayloadKind::Pubkey),
+            (seeds_field.to_string(), PayloadKind::Seeds),
+        ],
+        ("PubkeyTreeMatch" | "ProgramOwnedTree", [pubkey_field, proof_field]) => vec![
+            (pubkey_field.to_string(), PayloadKind::Pubkey),
+            (proof_field.to_string(), PayloadKind::MerkleProof),
+        ],
+        ("Amount", [.., field]) => vec![(field.to_string(), PayloadKind::Number)],
+        (
+            "PubkeyMatch" | "PubkeyListMatch" | "ProgramOwned" | "ProgramOwnedList" | "IsWallet",
+            [field],
+        ) => vec![(field.to_string(), PayloadKind::Pubkey)],
+        ("AdditionalSigner" | "Frequency" | "Pass" | "Namespace", _) => vec![],
+        _ => {
+            msg!("unsupported rule {}", name);
+            return Err(MMMErrorCode::InvalidRuleSet.into());
+        }
+    };
+    Ok(RuleFields::Fields(fields))
+}
+
+// returns the fields read by the rule of the operation in the latest revision
+// of the rule set, or None when the revision isn't a v1 rule set
+fn get_operation_rule_fields(
+    authorization_rules: &AccountInfo,
+    operation: &str,
+) -> Result<Option<RuleFields>> {
+    let data = authorization_rules.try_borrow_data()?;
+    if data.len() < RULE_SET_HEADER_LEN || data[0] != RULE_SET_KEY {
+        return Err(MMMErrorCode::InvalidRuleSet.into());
+    }
+    let rev_map_location = usize::try_from(u64::from_le_bytes(
+        data[1..RULE_SET_HEADER_LEN].try_into().unwrap(),
+    ))
+    .map_err(|_| MMMErrorCode::InvalidRuleSet)?;
+    if data.get(rev_map_location) != Some(&RULE_SET_REV_MAP_VERSION) {
+        return Err(MMMErrorCode::InvalidRuleSet.into());
+    }
+    let revisions: Vec<u64> = AnchorDeserialize::deserialize(&mut &data[rev_map_location + 1..])
+        .map_err(|_| MMMErrorCode::InvalidRuleSet)?;
+    let revision = revisions
+        .last()
+        .and_then(|start| usize::try_from(*start).ok())
+        .filter(|start| (RULE_SET_HEADER_LEN..rev_map_location).contains(start))
+        .ok_or(MMMErrorCode::InvalidRuleSet)?;
+    if data[revision] != RULE_SET_LIB_VERSION_V1 {
+        return Ok(None);
+    }
+
+    // RuleSetV1 is serialized as [lib_version, owner, name, operations]
+    let rule_set = rmpv::decode::read_value(&mut &data[revision + 1..rev_map_location])
+        .map_err(|_| MMMErrorCode::InvalidRuleSet)?;
+    let operations = match rule_set.as_array().map(Vec::as_slice) {
+        Some([_, _, _, operations]) => operations.as_map(),
+        _ => None,
+    }
+    .ok_or(MMMErrorCode::InvalidRuleSet)?;
+    let get_rule = |name: &str| {
+        operations
+            .iter()
+            .find(|(key, _)| key.as_str() == Some(name))
+            .map(|(_, rule)| rule)
+    };
+
+    // an operation without a rule of its own, or with a Namespace rule, uses
+    // the rule of its namespace
+    let namespace = operation.split(':').next().unwrap_or(operation);
+    let rule = match get_rule(operation) {
+        Some(rule) if get_rule_variant(rule)?.0 != "Namespace" => rule,
+        _ => get_rule(namespace).ok_or_else(|| {
+            msg!("operation {} is not in the rule set", operation);
+            MMMErrorCode::RuleSetOperationNotFound
+        })?,
+    };
+    parse_rule(rule).map(Some)
+}
+
+// returns None when the asset has no rule set, otherwise the payload read by the
+// rules of the operation: the seeds of the pool under the pool_seeds_keys the
+// rules reference, and the validated extra entries
+pub fn get_authorization_data(
+    metadata: &Metadata,
+    authorization_rules: &AccountInfo,
+    operation: &str,
+    pool_seeds_keys: &[&str],
+    pool_seeds: Vec<Vec<u8>>,
+    amount: u64,
+    extra: Option<AuthorizationDataLocal>,
+) -> Result<Option<AuthorizationData>> {
+    assert_valid_authorization_rules(metadata, authorization_rules)?;
+    if get_rule_set(metadata).is_none() {
+        return Ok(None);
+    }
+
+    let extra = extra.unwrap_or_default();
+    if extra.payload.len() > MAX_AUTHORIZATION_PAYLOAD_ENTRIES {
+        return Err(MMMErrorCode::InvalidAuthorizationData.into());
+    }
+
+    // newer rule set revisions can't be read, the pool then passes its seeds
+    // for all of its roles and the extra entries are passed as they are
+    let rule_fields = get_operation_rule_fields(authorization_rules, operation)?;
+    let mut fields = vec![];
+    if let Some(rule_fields) = &rule_fields {
+        rule_fields.collect(&mut fields);
+    }
+    let is_read = |name: &str, kind: PayloadKind| {
+        rule_fields.is_none()
+            || fields
+                .iter()
+                .any(|(field, field_kind)| field == name && *field_kind == kind)
+    };
+
+    let mut map = HashMap::new();
+    for key in pool_seeds_keys {
+        if is_read(key, PayloadKind::Seeds) {
+            map.insert(
+                key.to_string(),
+                PayloadType::Seeds(SeedsVec {
+                    seeds: pool_seeds.clone(),
+                }),
+            );
+        }
+    }
+    for entry in extra.payload {
+        // the seeds of the pool can't be overridden, each key can only be set
+        // once and must be read by the rules with the same type
+        let payload = to_payload_type(entry.payload, amount)?;
+        if !is_read(&entry.name, get_payload_kind(&payload)) || map.contains_key(&entry.name) {
+            msg!("invalid authorization payload key {}", entry.name);
+            return Err(MMMErrorCode::InvalidAuthorizationData.into());
+        }
+        map.insert(entry.name, payload);
+    }
+
+    if let Some(rule_fields) = &rule_fields {
+        if !rule_fields.is_satisfied(&map) {
+            msg!(
+                "authorization payload is missing fields of the {} rules",
+                operation
+            );
+            return Err(MMMErrorCode::MissingAuthorizationPayload.into());
+        }
+    }
+    Ok(Some(AuthorizationData {
+        payload: Payload { map },
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use mpl_token_metadata::types::{Key, TokenStandard};
+    use solana_program::account_info::AccountInfo;
+
+    use super::*;
+
+    fn metadata(rule_set: Option<Pubkey>) -> Metadata {
+        Metadata {
+            key: Key::MetadataV1,
+            update_authority: Pubkey::default(),
+            mint: Pubkey::default(),
+            name: String::new(),
+            symbol: String::new(),
+            uri: String::new(),
+            seller_fee_basis_points: 0,
+            creators: None,
+            primary_sale_happened: false,
+            is_mutable: false,
+            edition_nonce: None,
+            token_standard: Some(TokenStandard::ProgrammableNonFungible),
+            collection: None,
+            uses: None,
+            collection_details: None,
+            programmable_config: Some(ProgrammableConfig::V1 { rule_set }),
+        }
+    }
+
+    fn tagged(name: &str, payload: PayloadTypeLocal) -> TaggedPayload {
+        TaggedPayload {
+            name: name.to_string(),
+            payload,
+        }
+    }
+
+    fn rule(name: &str, fields: Vec<Value>) -> Value {
+        Value::Map(vec![(Value::from(name), Value::Array(fields))])
+    }
+
+    fn pubkey_value(key: &Pubkey) -> Value {
+        Value::Array(key.to_bytes().iter().map(|b| Value::from(*b)).collect())
+    }
+
+    fn pda_match(program: &Pubkey, field: &str) -> Value {
+        rule(
+            "All",
+            vec![Value::Array(vec![
+                rule(
+                    "ProgramOwned",
+                    vec![pubkey_value(program), Value::from(field)],
+                ),
+                rule(
+                    "PDAMatch",
+                    vec![
+                        pubkey_value(program),
+                        Value::from(field),
+                        Value::from(format!("{}Seeds", field)),
+                    ],
+                ),
+            ])],
+        )
+    }
+
+    // same layout as the rule sets written by mpl-token-auth-rules
+    fn rule_set_data(lib_version: u8, operations: Vec<(&str, Value)>) -> Vec<u8> {
+        let rule_set = Value::Array(vec![
+            Value::from(1),
+            pubkey_value(&Pubkey::new_unique()),
+            Value::from("rules"),
+            Value::Map(
+                operations
+                    .into_iter()
+                    .map(|(name, rule)| (Value::from(name), rule))
+                    .collect(),
+            ),
+        ]);
+        let mut revision = vec![lib_version];
+        rmpv::encode::write_value(&mut revision, &rule_set).unwrap();
+
+        let mut data = vec![RULE_SET_KEY];
+        data.extend(((RULE_SET_HEADER_LEN + revision.len()) as u64).to_le_bytes());
+        data.extend(revision);
+        data.push(RULE_SET_REV_MAP_VERSION);
+        data.extend(vec![RULE_SET_HEADER_LEN as u64].try_to_vec().unwrap());
+        data
+    }
+
+    fn pool_seeds() -> Vec<Vec<u8>> {
+        vec![b"mmm_pool".to_vec(), vec![1; 32], vec![2; 32]]
+    }
+
+    fn authorization_data(
+        metadata: &Metadata,
+        rule_set: &Pubkey,
+        data: &mut [u8],
+        operation: &str,
+        pool_seeds_keys: &[&str],
+        extra: Vec<TaggedPayload>,
+    ) -> Result<Option<AuthorizationData>> {
+        let mut lamports = 0;
+        let rules_ai = AccountInfo::new(
+            rule_set,
+            false,
+            false,
+            &mut lamports,
+            data,
+            &MPL_TOKEN_AUTH_RULES,
+            false,
+            0,
+        );
+        get_authorization_data(
+            metadata,
+            &rules_ai,
+            operation,
+            pool_seeds_keys,
+            pool_seeds(),
+            1,
+            Some(AuthorizationDataLocal { payload: extra }),
+        )
+    }
+
+    #[test]
+    fn test_get_authorization_data() {
+        let rule_set = Pubkey::new_unique();
+        let program = Pubkey::new_unique();
+        let destination = Pubkey::new_unique();
+        let mut data = rule_set_data(
+            RULE_SET_LIB_VERSION_V1,
+            vec![
+                (
+                    OPERATION_TRANSFER_OWNER,
+                    rule(
+                        "All",
+                        vec![Value::Array(vec![
+                            rule(
+                                "Amount",
+                                vec![Value::from(1), Value::from("Eq"), Value::from("Amount")],
+                            ),
+                            rule(
+                                "Any",
+                                vec![Value::Array(vec![
+                                    pda_match(&program, "Destination"),
+                                    pda_match(&program, "Source"),
+                                ])],
+                            ),
+                        ])],
+                    ),
+                ),
+                (OPERATION_TRANSFER_DELEGATE, Value::from("Namespace")),
+                (
+                    "Transfer",
+                    rule(
+                        "PubkeyTreeMatch",
+                        vec![
+                            Value::Array(vec![Value::from(0); 32]),
+                            Value::from("Destination"),
+                            Value::from("DestinationProof"),
+                        ],
+                    ),
+                ),
+            ],
+        );
+        let metadata = metadata(Some(rule_set));
+
+        // only the seeds read by the rules are passed
+        let map = authorization_data(
+            &metadata,
+            &rule_set,
+            &mut data,
+            OPERATION_TRANSFER_OWNER,
+            &["SourceSeeds", "AuthoritySeeds"],
+            vec![
+                tagged("Amount", PayloadTypeLocal::Number(1)),
+                tagged("Destination", PayloadTypeLocal::Pubkey(destination)),
+            ],
+        )
+        .unwrap()
+        .unwrap()
+        .payload
+        .map;
+        assert_eq!(map.len(), 3);
+        assert_eq!(
+            map.get("SourceSeeds"),
+            Some(&PayloadType::Seeds(SeedsVec {
+                seeds: pool_seeds()
+            }))
+        );
+        assert_eq!(
+            map.get("Destination"),
+            Some(&PayloadType::Pubkey(destination))
+        );
+
+        // a rule without the seeds of the pool can be satisfied by the extra
+        // entries
+        assert_eq!(
+            authorization_data(
+                &metadata,
+                &rule_set,
+                &mut data,
+                OPERATION_TRANSFER_OWNER,
+                &["DelegateSeeds"],
+                vec![],
+            )
+            .unwrap_err(),
+            MMMErrorCode::MissingAuthorizationPayload.into()
+        );
+        let map = authorization_data(
+            &metadata,
+            &rule_set,
+            &mut data,
+            OPERATION_TRANSFER_OWNER,
+            &["DelegateSeeds"],
+            vec![tagged(
+                "DestinationSeeds",
+                PayloadTypeLocal::Seeds(vec![vec![3; 32]]),
+            )],
+        )
+        .unwrap()
+        .unwrap()
+        .payload
+        .map;
+        assert_eq!(map.len(), 1);
+
+        // Namespace rules use the rule of the namespace
+        assert_eq!(
+            authorization_data(
+                &metadata,
+                &rule_set,
+                &mut data,
+                OPERATION_TRANSFER_DELEGATE,
+                &["AuthoritySeeds"],
+                vec![],
+            )
+            .unwrap_err(),
+            MMMErrorCode::MissingAuthorizationPayload.into()
+        );
+        let map = authorization_data(
+            &metadata,
+            &rule_set,
+            &mut data,
+            OPERATION_TRANSFER_DELEGATE,
+            &["AuthoritySeeds"],
+            vec![tagged(
+                "DestinationProof",
+                PayloadTypeLocal::MerkleProof(vec![[0; 32]]),
+            )],
+        )
+        .unwrap()
+        .unwrap()
+        .payload
+        .map;
+        assert_eq!(map.len(), 1);
+
+        assert_eq!(
+            authorization_data(
+                &metadata,
+                &rule_set,
+                &mut data,
+                OPERATION_DELEGATE_TRANSFER,
+                &["DelegateSeeds"],
+                vec![],
+            )
+            .unwrap_err(),
+            MMMErrorCode::RuleSetOperationNotFound.into()
+        );
+
+        let invalid_extras = [
+            // set by the pool
+            tagged("SourceSeeds", PayloadTypeLocal::Seeds(vec![])),
+            // not read by the rules
+            tagged("Unknown", PayloadTypeLocal::Number(1)),
+            tagged("AuthoritySeeds", PayloadTypeLocal::Seeds(vec![])),
+            // wrong type
+            tagged("DestinationSeeds", PayloadTypeLocal::Pubkey(destination)),
+            tagged("Amount", PayloadTypeLocal::Number(2)),
+            tagged(
+                "DestinationSeeds",
+                PayloadTypeLocal::Seeds(vec![vec![0; 33]]),
+            ),
+            tagged(
+                "Destination",
+                PayloadTypeLocal::MerkleProof(vec![[0; 32]; MAX_AUTHORIZATION_PROOF_LEN + 1]),
+            ),
+        ];
+        for extra in invalid_extras.iter() {
+            assert_eq!(
+                authorization_data(
+                    &metadata,
+                    &rule_set,
+                    &mut data,
+                    OPERATION_TRANSFER_OWNER,
+                    &["SourceSeeds"],
+                    vec![extra.clone()],
+                )
+                .unwrap_err(),
+                MMMErrorCode::InvalidAuthorizationData.into()
+            );
+        }
+        // duplicated keys
+        assert!(authorization_data(
+            &metadata,
+            &rule_set,
+            &mut data,
+            OPERATION_TRANSFER_OWNER,
+            &["SourceSeeds"],
+            vec![
+                tagged("Amount", PayloadTypeLocal::Number(1)),
+                tagged("Amount", PayloadTypeLocal::Number(1)),
+            ],
+        )
+        .is_err());
+
+        // no rule set, no authorization data
+        assert_eq!(
+            authorization_data(
+                &self::metadata(None),
+                &rule_set,
+                &mut data,
+                OPERATION_TRANSFER_OWNER,
+                &["SourceSeeds"],
+                vec![],
+            )
+            .unwrap(),
+            None
+        );
+
+        // the authorization rules must be the rule set of the asset
+        assert_eq!(
+            authorization_data(
+                &self::metadata(Some(Pubkey::new_unique())),
+                &rule_set,
+                &mut data,
+                OPERATION_TRANSFER_OWNER,
+                &["SourceSeeds"],
+                vec![],
+            )
+            .unwrap_err(),
+            MMMErrorCode::InvalidAuthorizationRules.into()
+        );
+    }
+
+    #[test]
+    fn test_get_authorization_data_rule_set_versions() {
+        let rule_set = Pubkey::new_unique();
+        let metadata = metadata(Some(rule_set));
+
+        // newer revisions can't be read, the pool passes its seeds for all of
+        // its roles
+        let mut data = rule_set_data(2, vec![]);
+        let map = authorization_data(
+            &metadata,
+            &rule_set,
+            &mut data,
+            OPERATION_TRANSFER_OWNER,
+            &["SourceSeeds", "AuthoritySeeds"],
+            vec![],
+        )
+        .unwrap()
+        .unwrap()
+        .payload
+        .map;
+        assert_eq!(map.len(), 2);
+
+        let invalid_data = [
+            vec![],
+            vec![0; RULE_SET_HEADER_LEN + 1],
+            rule_set_data(
+                RULE_SET_LIB_VERSION_V1,
+                vec![(OPERATION_TRANSFER_OWNER, rule("Unknown", vec![]))],
+            ),
+            rule_set_data(
+                RULE_SET_LIB_VERSION_V1,
+                vec![(OPERATION_TRANSFER_OWNER, Value::from(1))],
+            ),
+        ];
+        for mut data in invalid_data {
+            assert_eq!(
+                authorization_data(
+                    &metadata,
+                    &rule_set,
+                    &mut data,
+                    OPERATION_TRANSFER_OWNER,
+                    &["SourceSeeds"],
+                    vec![],
+                )
+                .unwrap_err(),
+                MMMErrorCode::InvalidRuleSet.into()
+            );
+        }
+    }
+}
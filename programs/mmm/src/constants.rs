@@ -4,13 +4,24 @@ use spl_token_2022::extension::ExtensionType;
 pub const BUYSIDE_SOL_ESCROW_ACCOUNT_PREFIX: &str = "mmm_buyside_sol_escrow_account";
 pub const POOL_PREFIX: &str = "mmm_pool";
 pub const SELL_STATE_PREFIX: &str = "mmm_sell_state";
+pub const EXTENSION_POLICY_PREFIX: &str = "mmm_extension_policy";
+pub const ROYALTY_VAULT_PREFIX: &str = "mmm_royalty_vault";
+pub const ROYALTY_SCHEDULE_PREFIX: &str = "mmm_royalty_schedule";
+pub const GLOBAL_CONFIG_PREFIX: &str = "mmm_global_config";
+pub const REFERRAL_REGISTRY_PREFIX: &str = "mmm_referral_registry";
+pub const FEE_SCHEDULE_PREFIX: &str = "mmm_fee_schedule";
 
 pub const MAX_TOTAL_PRICE: u64 = 8_000_000 * 1_000_000_000; // 8_000_000 SOL
 pub const MAX_METADATA_CREATOR_ROYALTY_BP: u16 = 3000;
 pub const MAX_REFERRAL_FEE_BP: i16 = 500;
 pub const MAX_LP_FEE_BP: u16 = 2_000;
+pub const MAX_PROTOCOL_FEE_BP: u16 = 1_000;
+pub const MAX_TRAIT_MULTIPLIER_BP: u16 = 50_000; // 5x of the curve price
 pub const ALLOWLIST_MAX_LEN: usize = 6;
+pub const ROYALTY_SCHEDULE_MAX_BANDS: usize = 8;
+pub const REFERRAL_REGISTRY_MAX_RECIPIENTS: usize = 4;
 pub const MIN_SOL_ESCROW_BALANCE_BP: u16 = 100;
+pub const GLOBAL_CONFIG_VERSION: u8 = 1;
 
 pub const CANCEL_AUTHORITY: Pubkey = if cfg!(feature = "anchor-test") {
     pubkey!("testZY18qdvfWNn1mTn7PvywdLdwWWsgqLXvvztKAtD")
@@ -23,14 +34,29 @@ pub const M2_PROGRAM: Pubkey = pubkey!("M2mx93ekt1fmXSVkTrUL9xVFHkmME8HTUi5Cyc5a
 pub const M2_PREFIX: &str = "m2";
 pub const M2_AUCTION_HOUSE: Pubkey = pubkey!("E8cU1WiRWjanGxmn96ewBgk9vPTcL6AEZ1t6F6fkgUWe");
 
+pub const PAYMENT_PROXY_PROGRAM_ID: Pubkey = pubkey!("mpxdRTRiAzvxz8dgW6LQYzDATtKQBx2f1VJ6qsU28hn");
+pub const PAYMENT_PROXY_DISCRIMINATOR: [u8; 8] = [0xee, 0x4a, 0x13, 0x79, 0x5e, 0x99, 0xac, 0x48];
+pub const PAYMENT_PROXY_MIN_LEN: u32 = 512;
+
 pub const LIBREPLEX_ROYALTY_ENFORCEMENT_PROGRAM_ID: Pubkey =
     pubkey!("CZ1rQoAHSqWBoAEfqGsiLhgbM59dDrCWk3rnG5FXaoRV");
 
-pub const T22_EXTENSION_ALLOW_LIST: [spl_token_2022::extension::ExtensionType; 6] = [
+// default of GlobalConfig.transfer_hook_program_allow_list, the transfer hook
+// programs accepted for all pools. Empty slots are Pubkey::default(), pools can
+// additionally accept one more hook program via pool.transfer_hook_program_id
+pub const MAX_TRANSFER_HOOK_PROGRAMS: usize = 3;
+pub const TRANSFER_HOOK_PROGRAM_ALLOW_LIST: [Pubkey; MAX_TRANSFER_HOOK_PROGRAMS] = [
+    LIBREPLEX_ROYALTY_ENFORCEMENT_PROGRAM_ID,
+    Pubkey::new_from_array([0; 32]),
+    Pubkey::new_from_array([0; 32]),
+];
+
+pub const T22_EXTENSION_ALLOW_LIST: [spl_token_2022::extension::ExtensionType; 7] = [
     ExtensionType::GroupMemberPointer,
     ExtensionType::MetadataPointer,
     ExtensionType::MintCloseAuthority,
     ExtensionType::TokenMetadata,
     ExtensionType::TokenGroupMember,
     ExtensionType::TransferHook,
+    ExtensionType::TransferFeeConfig,
 ];
@@ -76,4 +76,52 @@ pub enum MMMErrorCode {
     InvalidCnftMetadata, // 0x1795
     #[msg("Invalid cnft metadata args")]
     InvalidCnftMetadataArgs, // 0x1796
+    #[msg("Invalid price override")]
+    InvalidPriceOverride, // 0x1795
+    #[msg("Invalid trait premium")]
+    InvalidTraitPremium, // 0x1796
+    #[msg("Transfer hook program is not allowed")]
+    InvalidTransferHookProgram, // 0x1797
+    #[msg("Invalid transfer hook accounts")]
+    InvalidTransferHookAccounts, // 0x1798
+    #[msg("Transfer fee exceeds transfer amount")]
+    InvalidTransferFee, // 0x1799
+    #[msg("Permanent delegate is not allowed")]
+    InvalidPermanentDelegate, // 0x179a
+    #[msg("Invalid extension policy")]
+    InvalidExtensionPolicy, // 0x179b
+    #[msg("Authorization rules don't match the rule set of the asset")]
+    InvalidAuthorizationRules, // 0x179c
+    #[msg("Invalid authorization payload")]
+    InvalidAuthorizationData, // 0x179d
+    #[msg("Invalid royalty schedule")]
+    InvalidRoyaltySchedule, // 0x179e
+    #[msg("Program is not allowed by the royalty rule set of the asset")]
+    ProgramNotAllowedByRuleSet, // 0x179f
+    #[msg("Invalid treasury")]
+    InvalidTreasury, // 0x17a0
+    #[msg("Invalid admin")]
+    InvalidAdmin, // 0x17a1
+    #[msg("Invalid global config")]
+    InvalidGlobalConfig, // 0x17a2
+    #[msg("Trading is paused")]
+    Paused, // 0x17a3
+    #[msg("Invalid referral registry")]
+    InvalidReferralRegistry, // 0x17a4
+    #[msg("Non-transferable mint is not allowed")]
+    NonTransferableMint, // 0x17a5
+    #[msg("Default account state is not allowed")]
+    InvalidDefaultAccountState, // 0x17a6
+    #[msg("Interest bearing mint is not allowed")]
+    InvalidInterestBearingConfig, // 0x17a7
+    #[msg("CPI guard is enabled on the token account")]
+    CpiGuardEnabled, // 0x17a8
+    #[msg("Listing is no longer available")]
+    ListingUnavailable, // 0x17a9
+    #[msg("Invalid rule set")]
+    InvalidRuleSet, // 0x17aa
+    #[msg("Operation is not in the rule set")]
+    RuleSetOperationNotFound, // 0x17ab
+    #[msg("Authorization payload doesn't satisfy the rule set")]
+    MissingAuthorizationPayload, // 0x17ac
 }
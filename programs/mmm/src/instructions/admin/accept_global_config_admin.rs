@@ -0,0 +1,25 @@
+use super::*;
+use crate::state::GlobalConfig;
+
+// AcceptGlobalConfigAdmin completes the admin handover started by the current
+// admin with update_global_config, signed by the pending admin.
+#[derive(Accounts)]
+pub struct AcceptGlobalConfigAdmin<'info> {
+    pub pending_admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_PREFIX.as_bytes()],
+        has_one = pending_admin @ MMMErrorCode::InvalidAdmin,
+        bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+pub fn handler(ctx: Context<AcceptGlobalConfigAdmin>) -> Result<()> {
+    let global_config = &mut ctx.accounts.global_config;
+
+    global_config.admin = global_config.pending_admin;
+    global_config.pending_admin = Pubkey::default();
+
+    Ok(())
+}
@@ -18,6 +18,14 @@ pub struct CreatePoolArgs {
     pub uuid: Pubkey, // randomly generated keypair
     pub payment_mint: Pubkey,
     pub allowlists: [Allowlist; ALLOWLIST_MAX_LEN],
+
+    // optional, defaults to disabled when not set
+    pub max_trait_multiplier_bp: Option<u16>,
+    pub trait_premium_root: Option<[u8; 32]>,
+    pub transfer_hook_program_id: Option<Pubkey>,
+    pub compound_lp_fee: Option<bool>,
+    pub dynamic_lp_fee_min_bp: Option<u16>,
+    pub dynamic_lp_fee_max_bp: Option<u16>,
 }
 
 #[derive(Accounts)]
@@ -34,13 +42,15 @@ pub struct CreatePool<'info> {
         seeds = [POOL_PREFIX.as_bytes(), owner.key().as_ref(), args.uuid.as_ref()],
         bump,
         space = Pool::LEN,
-        constraint = args.lp_fee_bp <= MAX_LP_FEE_BP @ MMMErrorCode::InvalidLPFee,
         constraint = args.buyside_creator_royalty_bp <= 10000 @ MMMErrorCode::InvalidBP,
         constraint = args.spot_price > 0 @ MMMErrorCode::InvalidSpotPrice,
         constraint = pool.payment_mint.eq(&Pubkey::default()) @ MMMErrorCode::InvalidPaymentMint, // remove this when we have spl token support
         constraint = args.referral.ne(owner.key) @ MMMErrorCode::InvalidReferral,
     )]
     pub pool: Box<Account<'info, Pool>>,
+    /// CHECK: it might not be initialized, loaded with get_global_config
+    #[account(seeds = [GLOBAL_CONFIG_PREFIX.as_bytes()], bump)]
+    pub global_config: UncheckedAccount<'info>,
     pub system_program: Program<'info, System>,
 }
 
@@ -49,6 +59,10 @@ pub fn handler(ctx: Context<CreatePool>, args: CreatePoolArgs) -> Result<()> {
     let owner = &ctx.accounts.owner;
     let cosigner = &ctx.accounts.cosigner;
 
+    let global_config = get_global_config(&ctx.accounts.global_config)?;
+    if args.lp_fee_bp > global_config.max_lp_fee_bp {
+        return Err(MMMErrorCode::InvalidLPFee.into());
+    }
     check_allowlists(&args.allowlists)?;
     check_curve(args.curve_type, args.curve_delta)?;
 
@@ -63,11 +77,20 @@ pub fn handler(ctx: Context<CreatePool>, args: CreatePoolArgs) -> Result<()> {
     pool.referral = args.referral;
     pool.cosigner_annotation = args.cosigner_annotation;
     pool.buyside_creator_royalty_bp = args.buyside_creator_royalty_bp;
+    pool.max_trait_multiplier_bp = args.max_trait_multiplier_bp.unwrap_or_default();
+    pool.trait_premium_root = args.trait_premium_root.unwrap_or_default();
+    pool.transfer_hook_program_id = args.transfer_hook_program_id.unwrap_or_default();
+    pool.compound_lp_fee = args.compound_lp_fee.unwrap_or_default();
+    pool.dynamic_lp_fee_min_bp = args.dynamic_lp_fee_min_bp.unwrap_or_default();
+    pool.dynamic_lp_fee_max_bp = args.dynamic_lp_fee_max_bp.unwrap_or_default();
+    check_dynamic_lp_fee(pool, &global_config)?;
+    check_max_trait_multiplier(pool.max_trait_multiplier_bp)?;
 
     // state variables
     pool.sellside_asset_amount = 0; // always equal to the number of NFTs in the pool
     pool.buyside_payment_amount = 0;
     pool.lp_fee_earned = 0;
+    pool.lp_fee_compounded = 0;
 
     // immutable
     pool.owner = owner.key();
@@ -0,0 +1,44 @@
+use super::*;
+use crate::state::GlobalConfig;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct InitGlobalConfigArgs {
+    pub admin: Pubkey,
+    pub treasury: Pubkey,
+    pub protocol_fee_bp: u16,
+}
+
+// InitGlobalConfig creates the GlobalConfig once, signed by the cancel
+// authority, the config is managed by the admin afterwards. The limits start
+// from the constants and can be changed with update_global_config.
+#[derive(Accounts)]
+#[instruction(args:InitGlobalConfigArgs)]
+pub struct InitGlobalConfig<'info> {
+    #[account(mut, address = CANCEL_AUTHORITY)]
+    pub authority: Signer<'info>,
+    #[account(
+        init,
+        payer = authority,
+        seeds = [GLOBAL_CONFIG_PREFIX.as_bytes()],
+        space = GlobalConfig::LEN,
+        bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitGlobalConfig>, args: InitGlobalConfigArgs) -> Result<()> {
+    let global_config = &mut ctx.accounts.global_config;
+
+    if args.protocol_fee_bp > MAX_PROTOCOL_FEE_BP {
+        return Err(MMMErrorCode::InvalidBP.into());
+    }
+    global_config.set_inner(GlobalConfig {
+        admin: args.admin,
+        treasury: args.treasury,
+        protocol_fee_bp: args.protocol_fee_bp,
+        ..GlobalConfig::default()
+    });
+
+    Ok(())
+}
@@ -2,14 +2,30 @@
 
 use super::*;
 
+pub mod accept_global_config_admin;
 pub mod create_pool;
+pub mod init_global_config;
+pub mod set_extension_policy;
+pub mod set_fee_schedule;
+pub mod set_pause_flags;
+pub mod set_protocol_fee_exemption;
+pub mod set_sell_state_price_override;
 pub mod set_shared_escrow;
 pub mod sol_close_pool;
 pub mod update_allowlists;
+pub mod update_global_config;
 pub mod update_pool;
 
+pub use accept_global_config_admin::*;
 pub use create_pool::*;
+pub use init_global_config::*;
+pub use set_extension_policy::*;
+pub use set_fee_schedule::*;
+pub use set_pause_flags::*;
+pub use set_protocol_fee_exemption::*;
+pub use set_sell_state_price_override::*;
 pub use set_shared_escrow::*;
 pub use sol_close_pool::*;
 pub use update_allowlists::*;
+pub use update_global_config::*;
 pub use update_pool::*;
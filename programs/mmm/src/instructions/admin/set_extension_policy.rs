@@ -0,0 +1,46 @@
+use super::*;
+use crate::state::ExtensionPolicy;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetExtensionPolicyArgs {
+    pub allowed_extensions: u64,
+    pub permanent_delegate: Pubkey,
+}
+
+#[derive(Accounts)]
+#[instruction(args:SetExtensionPolicyArgs)]
+pub struct SetExtensionPolicy<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(constraint = owner.key() != cosigner.key() @ MMMErrorCode::InvalidCosigner)]
+    pub cosigner: Signer<'info>,
+    #[account(
+        seeds = [POOL_PREFIX.as_bytes(), owner.key().as_ref(), pool.uuid.as_ref()],
+        bump,
+        has_one = owner @ MMMErrorCode::InvalidOwner,
+        has_one = cosigner @ MMMErrorCode::InvalidCosigner,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        seeds = [EXTENSION_POLICY_PREFIX.as_bytes(), pool.key().as_ref()],
+        space = ExtensionPolicy::LEN,
+        bump
+    )]
+    pub extension_policy: Account<'info, ExtensionPolicy>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SetExtensionPolicy>, args: SetExtensionPolicyArgs) -> Result<()> {
+    let extension_policy = &mut ctx.accounts.extension_policy;
+
+    extension_policy.pool = ctx.accounts.pool.key();
+    extension_policy.allowed_extensions = args.allowed_extensions;
+    extension_policy.permanent_delegate = args.permanent_delegate;
+    if !extension_policy.valid() {
+        return Err(MMMErrorCode::InvalidExtensionPolicy.into());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use super::*;
+use crate::state::{FeeSchedule, GlobalConfig};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetFeeScheduleArgs {
+    pub maker_fee_bp: i16,
+    pub taker_fee_bp: i16,
+}
+
+// SetFeeSchedule sets the highest maker and taker fees the cosigner can charge
+// for the referral, the default for other referrals is set in GlobalConfig.
+#[derive(Accounts)]
+#[instruction(args:SetFeeScheduleArgs)]
+pub struct SetFeeSchedule<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [GLOBAL_CONFIG_PREFIX.as_bytes()],
+        has_one = admin @ MMMErrorCode::InvalidAdmin,
+        bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    /// CHECK: the pool.referral the schedule applies to
+    pub referral: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        seeds = [FEE_SCHEDULE_PREFIX.as_bytes(), referral.key().as_ref()],
+        space = FeeSchedule::LEN,
+        bump
+    )]
+    pub fee_schedule: Account<'info, FeeSchedule>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SetFeeSchedule>, args: SetFeeScheduleArgs) -> Result<()> {
+    let fee_schedule = &mut ctx.accounts.fee_schedule;
+
+    fee_schedule.referral = ctx.accounts.referral.key();
+    fee_schedule.maker_fee_bp = args.maker_fee_bp;
+    fee_schedule.taker_fee_bp = args.taker_fee_bp;
+    if !fee_schedule.valid(&ctx.accounts.global_config) {
+        return Err(MMMErrorCode::InvalidMakerOrTakerFeeBP.into());
+    }
+
+    Ok(())
+}
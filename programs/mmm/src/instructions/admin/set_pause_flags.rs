@@ -0,0 +1,31 @@
+use super::*;
+use crate::state::GlobalConfig;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetPauseFlagsArgs {
+    pub pause_flags: u8, // bits of PAUSE_FLAG_*, 0 resumes trading
+}
+
+// SetPauseFlags halts fulfills and deposits globally or for some asset
+// families, withdrawals and closes stay allowed so owners can always exit.
+#[derive(Accounts)]
+#[instruction(args:SetPauseFlagsArgs)]
+pub struct SetPauseFlags<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_PREFIX.as_bytes()],
+        has_one = admin @ MMMErrorCode::InvalidAdmin,
+        bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+pub fn handler(ctx: Context<SetPauseFlags>, args: SetPauseFlagsArgs) -> Result<()> {
+    let global_config = &mut ctx.accounts.global_config;
+
+    global_config.pause_flags = args.pause_flags;
+    msg!("{{\"pause_flags\":{}}}", global_config.pause_flags);
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use super::*;
+use crate::state::GlobalConfig;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetProtocolFeeExemptionArgs {
+    pub exempt: bool,
+}
+
+// SetProtocolFeeExemption adds the pool to or removes it from the fee-free
+// tier, it's managed by the admin of GlobalConfig.
+#[derive(Accounts)]
+#[instruction(args:SetProtocolFeeExemptionArgs)]
+pub struct SetProtocolFeeExemption<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [GLOBAL_CONFIG_PREFIX.as_bytes()],
+        has_one = admin @ MMMErrorCode::InvalidAdmin,
+        bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(
+        mut,
+        seeds = [POOL_PREFIX.as_bytes(), pool.owner.as_ref(), pool.uuid.as_ref()],
+        bump
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+}
+
+pub fn handler(
+    ctx: Context<SetProtocolFeeExemption>,
+    args: SetProtocolFeeExemptionArgs,
+) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+
+    pool.protocol_fee_exempt = args.exempt;
+    log_pool("post_set_protocol_fee_exemption", pool)?;
+
+    Ok(())
+}
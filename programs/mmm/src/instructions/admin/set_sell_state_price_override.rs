@@ -0,0 +1,44 @@
+use super::*;
+use crate::state::{PriceOverride, SellState};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetSellStatePriceOverrideArgs {
+    pub price_override: PriceOverride,
+}
+
+#[derive(Accounts)]
+#[instruction(args:SetSellStatePriceOverrideArgs)]
+pub struct SetSellStatePriceOverride<'info> {
+    pub owner: Signer<'info>,
+    #[account(constraint = owner.key() != cosigner.key() @ MMMErrorCode::InvalidCosigner)]
+    pub cosigner: Signer<'info>,
+    #[account(
+        seeds = [POOL_PREFIX.as_bytes(), owner.key().as_ref(), pool.uuid.as_ref()],
+        bump,
+        has_one = owner @ MMMErrorCode::InvalidOwner,
+        has_one = cosigner @ MMMErrorCode::InvalidCosigner,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+    #[account(
+        mut,
+        seeds = [
+            SELL_STATE_PREFIX.as_bytes(),
+            pool.key().as_ref(),
+            sell_state.asset_mint.as_ref(),
+        ],
+        bump,
+        has_one = pool @ MMMErrorCode::InvalidAccountState,
+    )]
+    pub sell_state: Account<'info, SellState>,
+}
+
+pub fn handler(
+    ctx: Context<SetSellStatePriceOverride>,
+    args: SetSellStatePriceOverrideArgs,
+) -> Result<()> {
+    let sell_state = &mut ctx.accounts.sell_state;
+
+    set_sell_state_price_override(sell_state, args.price_override)?;
+
+    Ok(())
+}
@@ -20,21 +20,31 @@ pub struct SetSharedEscrow<'info> {
         has_one = cosigner @ MMMErrorCode::InvalidCosigner,
     )]
     pub pool: Box<Account<'info, Pool>>,
-    /// CHECK: it's checked with seeds of M2's PDA seeds, and linked to the owner
-    #[account(
-        seeds = [
-            M2_PREFIX.as_bytes(),
-            M2_AUCTION_HOUSE.as_ref(),
-            owner.key().as_ref(),
-        ],
-        bump,
-        seeds::program = M2_PROGRAM,
-    )]
+    /// CHECK: it's checked with seeds of M2's PDA seeds in the handler, and linked to the owner
     pub shared_escrow_account: UncheckedAccount<'info>,
+    /// CHECK: it might not be initialized, loaded with get_global_config
+    #[account(seeds = [GLOBAL_CONFIG_PREFIX.as_bytes()], bump)]
+    pub global_config: UncheckedAccount<'info>,
 }
 
 pub fn handler(ctx: Context<SetSharedEscrow>, args: SetSharedEscrowArgs) -> Result<()> {
     let pool = &mut ctx.accounts.pool;
+    let global_config = get_global_config(&ctx.accounts.global_config)?;
+
+    // the auction house of M2 is set in the global config
+    let (shared_escrow_pda, _) = Pubkey::find_program_address(
+        &[
+            M2_PREFIX.as_bytes(),
+            global_config.m2_auction_house.as_ref(),
+            ctx.accounts.owner.key().as_ref(),
+        ],
+        &M2_PROGRAM,
+    );
+    require_keys_eq!(
+        shared_escrow_pda,
+        ctx.accounts.shared_escrow_account.key(),
+        ErrorCode::ConstraintSeeds
+    );
 
     // if there is any buyside payment, we can't set shared escrow
     if pool.buyside_payment_amount > 0 {
@@ -0,0 +1,94 @@
+use super::*;
+use crate::state::GlobalConfig;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UpdateGlobalConfigArgs {
+    pub treasury: Option<Pubkey>,
+    pub protocol_fee_bp: Option<u16>,
+    // the new admin has to accept with accept_global_config_admin
+    pub pending_admin: Option<Pubkey>,
+
+    // limits, left unchanged when not set
+    pub cancel_authority: Option<Pubkey>,
+    pub max_lp_fee_bp: Option<u16>,
+    pub max_referral_fee_bp: Option<i16>,
+    pub max_metadata_creator_royalty_bp: Option<u16>,
+    pub min_sol_escrow_balance_bp: Option<u16>,
+    pub m2_auction_house: Option<Pubkey>,
+    pub payment_proxy_program_id: Option<Pubkey>,
+    pub payment_proxy_discriminator: Option<[u8; 8]>,
+    pub payment_proxy_min_len: Option<u32>,
+    // replaces the whole list, empty slots are Pubkey::default()
+    pub transfer_hook_program_allow_list: Option<[Pubkey; MAX_TRANSFER_HOOK_PROGRAMS]>,
+    pub default_maker_fee_bp: Option<i16>,
+    pub default_taker_fee_bp: Option<i16>,
+}
+
+#[derive(Accounts)]
+#[instruction(args:UpdateGlobalConfigArgs)]
+pub struct UpdateGlobalConfig<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_PREFIX.as_bytes()],
+        has_one = admin @ MMMErrorCode::InvalidAdmin,
+        bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+pub fn handler(ctx: Context<UpdateGlobalConfig>, args: UpdateGlobalConfigArgs) -> Result<()> {
+    let global_config = &mut ctx.accounts.global_config;
+
+    if let Some(treasury) = args.treasury {
+        global_config.treasury = treasury;
+    }
+    if let Some(protocol_fee_bp) = args.protocol_fee_bp {
+        global_config.protocol_fee_bp = protocol_fee_bp;
+    }
+    if let Some(pending_admin) = args.pending_admin {
+        global_config.pending_admin = pending_admin;
+    }
+    if let Some(cancel_authority) = args.cancel_authority {
+        global_config.cancel_authority = cancel_authority;
+    }
+    if let Some(max_lp_fee_bp) = args.max_lp_fee_bp {
+        global_config.max_lp_fee_bp = max_lp_fee_bp;
+    }
+    if let Some(max_referral_fee_bp) = args.max_referral_fee_bp {
+        global_config.max_referral_fee_bp = max_referral_fee_bp;
+    }
+    if let Some(max_metadata_creator_royalty_bp) = args.max_metadata_creator_royalty_bp {
+        global_config.max_metadata_creator_royalty_bp = max_metadata_creator_royalty_bp;
+    }
+    if let Some(min_sol_escrow_balance_bp) = args.min_sol_escrow_balance_bp {
+        global_config.min_sol_escrow_balance_bp = min_sol_escrow_balance_bp;
+    }
+    if let Some(m2_auction_house) = args.m2_auction_house {
+        global_config.m2_auction_house = m2_auction_house;
+    }
+    if let Some(payment_proxy_program_id) = args.payment_proxy_program_id {
+        global_config.payment_proxy_program_id = payment_proxy_program_id;
+    }
+    if let Some(payment_proxy_discriminator) = args.payment_proxy_discriminator {
+        global_config.payment_proxy_discriminator = payment_proxy_discriminator;
+    }
+    if let Some(payment_proxy_min_len) = args.payment_proxy_min_len {
+        global_config.payment_proxy_min_len = payment_proxy_min_len;
+    }
+    if let Some(transfer_hook_program_allow_list) = args.transfer_hook_program_allow_list {
+        global_config.transfer_hook_program_allow_list = transfer_hook_program_allow_list;
+    }
+    if let Some(default_maker_fee_bp) = args.default_maker_fee_bp {
+        global_config.default_maker_fee_bp = default_maker_fee_bp;
+    }
+    if let Some(default_taker_fee_bp) = args.default_taker_fee_bp {
+        global_config.default_taker_fee_bp = default_taker_fee_bp;
+    }
+
+    if !global_config.valid() {
+        return Err(MMMErrorCode::InvalidGlobalConfig.into());
+    }
+
+    Ok(())
+}
@@ -13,6 +13,14 @@ pub struct UpdatePoolArgs {
     pub referral: Pubkey,
     pub cosigner_annotation: [u8; 32],
     pub buyside_creator_royalty_bp: u16,
+
+    // optional, left unchanged when not set
+    pub max_trait_multiplier_bp: Option<u16>,
+    pub trait_premium_root: Option<[u8; 32]>,
+    pub transfer_hook_program_id: Option<Pubkey>,
+    pub compound_lp_fee: Option<bool>,
+    pub dynamic_lp_fee_min_bp: Option<u16>,
+    pub dynamic_lp_fee_max_bp: Option<u16>,
 }
 
 #[derive(Accounts)]
@@ -28,16 +36,22 @@ pub struct UpdatePool<'info> {
         bump,
         has_one = owner @ MMMErrorCode::InvalidOwner,
         has_one = cosigner @ MMMErrorCode::InvalidCosigner,
-        constraint = args.lp_fee_bp <= MAX_LP_FEE_BP @ MMMErrorCode::InvalidLPFee,
         constraint = args.buyside_creator_royalty_bp <= 10000 @ MMMErrorCode::InvalidBP,
         constraint = args.spot_price > 0 @ MMMErrorCode::InvalidSpotPrice,
         constraint = args.referral.ne(owner.key) @ MMMErrorCode::InvalidReferral,
     )]
     pub pool: Box<Account<'info, Pool>>,
+    /// CHECK: it might not be initialized, loaded with get_global_config
+    #[account(seeds = [GLOBAL_CONFIG_PREFIX.as_bytes()], bump)]
+    pub global_config: UncheckedAccount<'info>,
 }
 
 pub fn handler(ctx: Context<UpdatePool>, args: UpdatePoolArgs) -> Result<()> {
     let pool = &mut ctx.accounts.pool;
+    let global_config = get_global_config(&ctx.accounts.global_config)?;
+    if args.lp_fee_bp > global_config.max_lp_fee_bp {
+        return Err(MMMErrorCode::InvalidLPFee.into());
+    }
     check_curve(args.curve_type, args.curve_delta)?;
 
     // mutable
@@ -49,6 +63,26 @@ pub fn handler(ctx: Context<UpdatePool>, args: UpdatePoolArgs) -> Result<()> {
     pool.referral = args.referral;
     pool.cosigner_annotation = args.cosigner_annotation;
     pool.buyside_creator_royalty_bp = args.buyside_creator_royalty_bp;
+    if let Some(max_trait_multiplier_bp) = args.max_trait_multiplier_bp {
+        pool.max_trait_multiplier_bp = max_trait_multiplier_bp;
+    }
+    if let Some(trait_premium_root) = args.trait_premium_root {
+        pool.trait_premium_root = trait_premium_root;
+    }
+    if let Some(transfer_hook_program_id) = args.transfer_hook_program_id {
+        pool.transfer_hook_program_id = transfer_hook_program_id;
+    }
+    if let Some(compound_lp_fee) = args.compound_lp_fee {
+        pool.compound_lp_fee = compound_lp_fee;
+    }
+    if let Some(dynamic_lp_fee_min_bp) = args.dynamic_lp_fee_min_bp {
+        pool.dynamic_lp_fee_min_bp = dynamic_lp_fee_min_bp;
+    }
+    if let Some(dynamic_lp_fee_max_bp) = args.dynamic_lp_fee_max_bp {
+        pool.dynamic_lp_fee_max_bp = dynamic_lp_fee_max_bp;
+    }
+    check_dynamic_lp_fee(pool, &global_config)?;
+    check_max_trait_multiplier(pool.max_trait_multiplier_bp)?;
 
     // if we are using shared escrow, we can't change the reinvest setting
     if !pool.using_shared_escrow() {
@@ -6,13 +6,15 @@ use crate::{
     constants::*,
     errors::MMMErrorCode,
     index_ra,
-    state::{BubblegumProgram, Pool, SellState},
+    state::{BubblegumProgram, Pool, SellState, TraitPremium, PAUSE_FLAG_CNFT},
     util::{
-        assert_valid_fees_bp, check_allowlists_for_cnft, check_remaining_accounts_for_m2,
-        get_buyside_seller_receives, get_lp_fee_bp, get_sol_fee, get_sol_lp_fee,
-        get_sol_total_price_and_next_price, hash_creators_from_metadata_args, hash_metadata,
-        log_pool, pay_creator_fees_in_sol_cnft, transfer_compressed_nft, try_close_escrow,
-        try_close_pool, try_close_sell_state, withdraw_m2,
+        apply_trait_premium, assert_not_paused, assert_valid_fees_bp, check_allowlists_for_cnft,
+        check_remaining_accounts_for_m2, get_buyside_seller_receives, get_fee_schedule,
+        get_global_config, get_lp_fee_bp, get_protocol_fee, get_referral_registry,
+        get_royalty_schedule, get_sol_fee, get_sol_lp_fee, get_sol_total_price_and_next_price,
+        hash_creators_from_metadata_args, hash_metadata, log_pool, pay_creator_fees_in_sol_cnft,
+        pay_protocol_fee, pay_referral_fee, transfer_compressed_nft, try_close_escrow,
+        try_close_pool, try_close_sell_state, withdraw_m2, FulfillResult, RoyaltyPayment,
     },
     verify_referral::verify_referral,
 };
@@ -34,12 +36,21 @@ pub struct SolCnftFulfillBuyArgs {
 
     // === Contract args === //
     pub min_payment_amount: u64,
-    pub maker_fee_bp: i16, // will be checked by cosigner
-    pub taker_fee_bp: i16, // will be checked by cosigner
+    pub maker_fee_bp: i16, // set by cosigner, capped by the FeeSchedule
+    pub taker_fee_bp: i16, // set by cosigner, capped by the FeeSchedule
 
     // Metadata args for cnft hash
     // Reference: https://developers.metaplex.com/bubblegum/hashed-nft-data
     pub metadata_args: MetadataArgs,
+
+    // optional cosigner attested premium for the asset's traits
+    pub trait_premium: Option<TraitPremium>,
+
+    // number of royalty vaults passed after the creator accounts, the creator
+    // fee of an underfunded creator is skipped when its vault is not passed
+    pub royalty_vault_count: u8,
+    // number of referral registry recipients passed after the royalty vaults
+    pub referral_recipient_count: u8,
 }
 
 #[derive(Accounts)]
@@ -54,10 +65,25 @@ pub struct SolCnftFulfillBuy<'info> {
     pub cosigner: Signer<'info>,
     #[account(
         mut,
-        constraint = verify_referral(&pool, &referral) @ MMMErrorCode::InvalidReferral,
+        constraint = verify_referral(&pool, &referral, &global_config) @ MMMErrorCode::InvalidReferral,
     )]
     /// CHECK: use verify_referral to check the referral account
     pub referral: UncheckedAccount<'info>,
+    /// CHECK: it might not be initialized, loaded with get_global_config
+    #[account(seeds = [GLOBAL_CONFIG_PREFIX.as_bytes()], bump)]
+    pub global_config: UncheckedAccount<'info>,
+    /// CHECK: checked against the treasury of global_config in pay_protocol_fee
+    #[account(mut)]
+    pub treasury: UncheckedAccount<'info>,
+    /// CHECK: it might not be initialized, loaded with get_fee_schedule
+    #[account(seeds = [FEE_SCHEDULE_PREFIX.as_bytes(), pool.referral.as_ref()], bump)]
+    pub fee_schedule: UncheckedAccount<'info>,
+    /// CHECK: the royalty schedule pda of the collection, checked in
+    /// get_royalty_schedule, it might not be initialized
+    pub royalty_schedule: UncheckedAccount<'info>,
+    /// CHECK: it might not be initialized, loaded with get_referral_registry
+    #[account(seeds = [REFERRAL_REGISTRY_PREFIX.as_bytes(), pool.referral.as_ref()], bump)]
+    pub referral_registry: UncheckedAccount<'info>,
     #[account(
         mut,
         seeds = [POOL_PREFIX.as_bytes(), owner.key().as_ref(), pool.uuid.as_ref()],
@@ -119,10 +145,14 @@ pub struct SolCnftFulfillBuy<'info> {
     //   0: m2_program
     //   1: shared_escrow_account
     //   2-N: creator accounts
-    //.  N+: proof accounts
+    //   N-M: royalty vaults (royalty_vault_count)
+    //   M-K: referral recipients (referral_recipient_count)
+    //.  K+: proof accounts
     // Branch: not using shared escrow accounts
     //   0-N: creator accounts
-    //.  N+: proof accounts
+    //   N-M: royalty vaults (royalty_vault_count)
+    //   M-K: referral recipients (referral_recipient_count)
+    //.  K+: proof accounts
 }
 
 pub fn handler<'info>(
@@ -132,6 +162,10 @@ pub fn handler<'info>(
     // let payer = &ctx.accounts.payer;
     let owner = &ctx.accounts.owner;
     let pool = &mut ctx.accounts.pool;
+    let global_config = get_global_config(&ctx.accounts.global_config)?;
+    assert_not_paused(&global_config, PAUSE_FLAG_CNFT)?;
+    let fee_schedule =
+        get_fee_schedule(&pool.referral, &ctx.accounts.fee_schedule, &global_config)?;
     let buyside_sol_escrow_account = &ctx.accounts.buyside_sol_escrow_account;
     let sell_state = &mut ctx.accounts.sell_state;
     let payer = &ctx.accounts.payer;
@@ -144,8 +178,12 @@ pub fn handler<'info>(
         &[ctx.bumps.buyside_sol_escrow_account],
     ]];
     let system_program = &ctx.accounts.system_program;
-    // Remaining accounts are 1. (Optional) creator addresses and 2. Merkle proof path.
+    // Remaining accounts are 1. (Optional) creator addresses, 2. (Optional) royalty vaults,
+    // 3. (Optional) referral recipients and 4. Merkle proof path.
     let creator_length = args.metadata_args.creators.len();
+    let royalty_accounts_length = creator_length
+        .checked_add(usize::from(args.royalty_vault_count))
+        .ok_or(MMMErrorCode::NumericOverflow)?;
     let remaining_accounts = ctx.remaining_accounts;
 
     // 0. Verify allowlist
@@ -157,9 +195,23 @@ pub fn handler<'info>(
 
     // 1. Cacluate amount and fees
     let (total_price, next_price) = get_sol_total_price_and_next_price(pool, 1, true)?;
-    let metadata_royalty_bp = args.metadata_args.seller_fee_basis_points;
+    let total_price = apply_trait_premium(pool, &args.asset_id, total_price, &args.trait_premium)?;
+    let royalty_schedule = get_royalty_schedule(
+        args.metadata_args
+            .collection
+            .as_ref()
+            .filter(|collection| collection.verified)
+            .map(|collection| collection.key),
+        &ctx.accounts.royalty_schedule,
+    )?;
+    let metadata_royalty_bp = royalty_schedule.as_ref().map_or(
+        args.metadata_args.seller_fee_basis_points,
+        |royalty_schedule| {
+            royalty_schedule.get_royalty_bp(total_price, args.metadata_args.seller_fee_basis_points)
+        },
+    );
     let seller_receives = {
-        let lp_fee_bp = get_lp_fee_bp(pool, buyside_sol_escrow_account.lamports());
+        let lp_fee_bp = get_lp_fee_bp(pool, buyside_sol_escrow_account.lamports(), true)?;
         get_buyside_seller_receives(
             total_price,
             lp_fee_bp,
@@ -168,8 +220,18 @@ pub fn handler<'info>(
         )
     }?;
 
-    let lp_fee = get_sol_lp_fee(pool, buyside_sol_escrow_account.lamports(), seller_receives)?;
-    assert_valid_fees_bp(args.maker_fee_bp, args.taker_fee_bp)?;
+    let lp_fee = get_sol_lp_fee(
+        pool,
+        buyside_sol_escrow_account.lamports(),
+        seller_receives,
+        true,
+    )?;
+    assert_valid_fees_bp(
+        args.maker_fee_bp,
+        args.taker_fee_bp,
+        &global_config,
+        &fee_schedule,
+    )?;
     let maker_fee = get_sol_fee(seller_receives, args.maker_fee_bp)?;
     let taker_fee = get_sol_fee(seller_receives, args.taker_fee_bp)?;
     let referral_fee = u64::try_from(
@@ -178,12 +240,15 @@ pub fn handler<'info>(
             .ok_or(MMMErrorCode::NumericOverflow)?,
     )
     .map_err(|_| MMMErrorCode::NumericOverflow)?;
+    let protocol_fee = get_protocol_fee(&global_config, pool, seller_receives)?;
 
     // 2. Get creator accounts, verify creators
-    let (creator_accounts, proof_path) = if pool.using_shared_escrow() {
-        check_remaining_accounts_for_m2(remaining_accounts, &pool.owner.key())?;
+    let (royalty_accounts, recipients_and_proof_path) = if pool.using_shared_escrow() {
+        check_remaining_accounts_for_m2(remaining_accounts, &pool.owner.key(), &global_config)?;
 
-        let amount: u64 = (total_price as i64 + maker_fee) as u64;
+        // compounded lp fees stay in the shared escrow
+        let compounded_lp_fee = if pool.compound_lp_fee { lp_fee } else { 0 };
+        let amount: u64 = (total_price as i64 + maker_fee - compounded_lp_fee as i64) as u64;
         withdraw_m2(
             pool,
             ctx.bumps.pool,
@@ -193,16 +258,20 @@ pub fn handler<'info>(
             index_ra!(remaining_accounts, 0),
             pool.owner,
             amount,
+            &global_config,
         )?;
         pool.shared_escrow_count = pool
             .shared_escrow_count
             .checked_sub(1)
             .ok_or(MMMErrorCode::NumericOverflow)?;
 
-        remaining_accounts[2..].split_at(creator_length)
+        remaining_accounts[2..].split_at(royalty_accounts_length)
     } else {
-        remaining_accounts.split_at(creator_length)
+        remaining_accounts.split_at(royalty_accounts_length)
     };
+    let creator_accounts = &royalty_accounts[..creator_length];
+    let (referral_recipients, proof_path) =
+        recipients_and_proof_path.split_at(usize::from(args.referral_recipient_count));
 
     let creator_hash =
         hash_creators_from_metadata_args(creator_accounts.iter(), &args.metadata_args)?;
@@ -271,12 +340,16 @@ pub fn handler<'info>(
     }
 
     // 4. Pool owner as buyer pay royalties to creators
-    let royalty_paid = pay_creator_fees_in_sol_cnft(
+    let RoyaltyPayment {
+        total: royalty_paid,
+        accrued: royalty_accrued,
+    } = pay_creator_fees_in_sol_cnft(
         pool.buyside_creator_royalty_bp,
         seller_receives,
         &args.metadata_args,
-        creator_accounts,
+        royalty_accounts,
         buyside_sol_escrow_account.to_account_info(),
+        metadata_royalty_bp,
         buyside_sol_escrow_account_seeds,
         system_program.to_account_info(),
     )?;
@@ -288,6 +361,8 @@ pub fn handler<'info>(
         .checked_sub(taker_fee as u64)
         .ok_or(MMMErrorCode::NumericOverflow)?
         .checked_sub(royalty_paid)
+        .ok_or(MMMErrorCode::NumericOverflow)?
+        .checked_sub(protocol_fee)
         .ok_or(MMMErrorCode::NumericOverflow)?;
     if payment_amount < args.min_payment_amount {
         return Err(MMMErrorCode::InvalidRequestedPrice.into());
@@ -308,7 +383,8 @@ pub fn handler<'info>(
     )?;
 
     // 6. Pay lp fee
-    if lp_fee > 0 {
+    // compounded lp fees stay in the buyside escrow
+    if lp_fee > 0 && !pool.compound_lp_fee {
         anchor_lang::solana_program::program::invoke_signed(
             &anchor_lang::solana_program::system_instruction::transfer(
                 buyside_sol_escrow_account.key,
@@ -325,32 +401,38 @@ pub fn handler<'info>(
     }
 
     // 7. Pay referral fee
-    if referral_fee > 0 {
-        anchor_lang::solana_program::program::invoke_signed(
-            &anchor_lang::solana_program::system_instruction::transfer(
-                buyside_sol_escrow_account.key,
-                referral.key,
-                referral_fee,
-            ),
-            &[
-                buyside_sol_escrow_account.to_account_info(),
-                referral.to_account_info(),
-                system_program.to_account_info(),
-            ],
-            buyside_sol_escrow_account_seeds,
-        )?;
-    }
+    let referral_registry = get_referral_registry(&ctx.accounts.referral_registry)?;
+    pay_referral_fee(
+        &buyside_sol_escrow_account.to_account_info(),
+        &referral.to_account_info(),
+        referral_registry.as_ref(),
+        referral_recipients,
+        &system_program.to_account_info(),
+        buyside_sol_escrow_account_seeds,
+        referral_fee,
+    )?;
 
-    // 8. try close accounts
+    // 8. Pay protocol fee
+    pay_protocol_fee(
+        &buyside_sol_escrow_account.to_account_info(),
+        &ctx.accounts.treasury,
+        &global_config,
+        &system_program.to_account_info(),
+        buyside_sol_escrow_account_seeds,
+        protocol_fee,
+    )?;
+
+    // 9. try close accounts
     try_close_escrow(
         &buyside_sol_escrow_account.to_account_info(),
         pool,
         system_program,
         buyside_sol_escrow_account_seeds,
+        &global_config,
     )?;
     try_close_sell_state(sell_state, payer.to_account_info())?;
 
-    // 9. Return the remaining per pool escrow balance to the shared escrow account
+    // 10. Return the remaining per pool escrow balance to the shared escrow account
     if pool.using_shared_escrow() {
         let min_rent = Rent::get()?.minimum_balance(0);
         let shared_escrow_account = index_ra!(remaining_accounts, 1).to_account_info();
@@ -376,20 +458,42 @@ pub fn handler<'info>(
                 pool,
                 system_program,
                 buyside_sol_escrow_account_seeds,
+                &global_config,
             )?;
         }
     }
 
-    // 10. update pool state and log
+    // 11. update pool state and log
     pool.lp_fee_earned = pool
         .lp_fee_earned
         .checked_add(lp_fee)
         .ok_or(MMMErrorCode::NumericOverflow)?;
+    if pool.compound_lp_fee {
+        pool.lp_fee_compounded = pool
+            .lp_fee_compounded
+            .checked_add(lp_fee)
+            .ok_or(MMMErrorCode::NumericOverflow)?;
+    }
     pool.spot_price = next_price;
     pool.buyside_payment_amount = buyside_sol_escrow_account.lamports();
 
     log_pool("post_sol_cnft_fulfill_buy", pool)?;
     try_close_pool(pool, owner.to_account_info())?;
 
-    Ok(())
+    FulfillResult {
+        total_price,
+        lp_fee,
+        maker_fee,
+        taker_fee,
+        protocol_fee,
+        royalty_paid,
+        royalty_accrued,
+        payment_amount,
+        next_price: pool.spot_price,
+        sellside_asset_amount: pool.sellside_asset_amount,
+        transfer_fee_withheld: 0,
+        trade_in_total_price: 0,
+        trade_in_payment_received: 0,
+    }
+    .set_return_data()
 }
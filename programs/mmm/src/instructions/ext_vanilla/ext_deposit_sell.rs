@@ -9,8 +9,13 @@ use spl_token_2022::onchain::invoke_transfer_checked;
 use crate::{
     constants::*,
     errors::MMMErrorCode,
-    state::{Pool, SellState},
-    util::{check_allowlists_for_mint_ext, log_pool},
+    state::{Pool, SellState, PAUSE_FLAG_EXT},
+    util::{
+        assert_allowed_transfer_hook_program, assert_no_cpi_guard, assert_not_paused,
+        assert_valid_transfer_hook, check_allowlists_for_mint_ext, get_extension_policy,
+        get_global_config, get_transfer_fee_and_received_amount, harvest_withheld_transfer_fee,
+        log_pool, set_sell_state_price_override,
+    },
     DepositSellArgs,
 };
 
@@ -30,6 +35,7 @@ pub struct ExtDepositeSell<'info> {
     )]
     pub pool: Box<Account<'info, Pool>>,
     #[account(
+        mut,
         mint::token_program = token_program,
         constraint = asset_mint.supply == 1 && asset_mint.decimals == 0 @ MMMErrorCode::InvalidTokenMint,
     )]
@@ -61,6 +67,15 @@ pub struct ExtDepositeSell<'info> {
         bump
     )]
     pub sell_state: Account<'info, SellState>,
+    /// CHECK: the default extension policy is used if it's not initialized
+    #[account(
+        seeds = [EXTENSION_POLICY_PREFIX.as_bytes(), pool.key().as_ref()],
+        bump,
+    )]
+    pub extension_policy: UncheckedAccount<'info>,
+    /// CHECK: it might not be initialized, loaded with get_global_config
+    #[account(seeds = [GLOBAL_CONFIG_PREFIX.as_bytes()], bump)]
+    pub global_config: UncheckedAccount<'info>,
     pub system_program: Program<'info, System>,
     pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
@@ -76,18 +91,42 @@ pub fn handler<'info>(
     let sellside_escrow_token_account = &ctx.accounts.sellside_escrow_token_account;
     let token_program = &ctx.accounts.token_program;
     let pool = &mut ctx.accounts.pool;
+    let global_config = get_global_config(&ctx.accounts.global_config)?;
+    assert_not_paused(&global_config, PAUSE_FLAG_EXT)?;
     let sell_state = &mut ctx.accounts.sell_state;
 
     if pool.using_shared_escrow() {
         return Err(MMMErrorCode::InvalidAccountState.into());
     }
 
+    let extension_policy = get_extension_policy(
+        &pool.key(),
+        &ctx.accounts.extension_policy.to_account_info(),
+    )?;
     check_allowlists_for_mint_ext(
         &pool.allowlists,
         &asset_mint.to_account_info(),
         args.allowlist_aux,
+        &extension_policy,
+        ctx.remaining_accounts,
     )?;
+    assert_no_cpi_guard(&asset_token_account.to_account_info())?;
+    assert_no_cpi_guard(&sellside_escrow_token_account.to_account_info())?;
+    assert_allowed_transfer_hook_program(pool, &global_config, &asset_mint.to_account_info())?;
+
+    // only the amount that arrives in the escrow is credited to the pool
+    let (_, received_asset_amount) =
+        get_transfer_fee_and_received_amount(&asset_mint.to_account_info(), args.asset_amount)?;
 
+    assert_valid_transfer_hook(
+        &asset_token_account.to_account_info(),
+        &asset_mint.to_account_info(),
+        &sellside_escrow_token_account.to_account_info(),
+        &owner.to_account_info(),
+        ctx.remaining_accounts,
+        args.asset_amount,
+        0,
+    )?;
     invoke_transfer_checked(
         token_program.key,
         asset_token_account.to_account_info(),
@@ -101,6 +140,11 @@ pub fn handler<'info>(
     )?;
 
     if asset_token_account.amount == args.asset_amount {
+        harvest_withheld_transfer_fee(
+            &token_program.to_account_info(),
+            &asset_mint.to_account_info(),
+            &asset_token_account.to_account_info(),
+        )?;
         invoke(
             &spl_token_2022::instruction::close_account(
                 token_program.key,
@@ -118,7 +162,7 @@ pub fn handler<'info>(
 
     pool.sellside_asset_amount = pool
         .sellside_asset_amount
-        .checked_add(args.asset_amount)
+        .checked_add(received_asset_amount)
         .ok_or(MMMErrorCode::NumericOverflow)?;
 
     sell_state.pool = pool.key();
@@ -127,8 +171,11 @@ pub fn handler<'info>(
     sell_state.cosigner_annotation = pool.cosigner_annotation;
     sell_state.asset_amount = sell_state
         .asset_amount
-        .checked_add(args.asset_amount)
+        .checked_add(received_asset_amount)
         .ok_or(MMMErrorCode::NumericOverflow)?;
+    if let Some(price_override) = args.price_override {
+        set_sell_state_price_override(sell_state, price_override)?;
+    }
     log_pool("post_ext_deposit_sell", pool)?;
 
     Ok(())
@@ -10,7 +10,10 @@ use crate::{
     constants::*,
     errors::MMMErrorCode,
     state::{Pool, SellState},
-    util::{log_pool, try_close_pool, try_close_sell_state},
+    util::{
+        assert_no_cpi_guard, assert_valid_transfer_hook, harvest_withheld_transfer_fee, log_pool,
+        try_close_pool, try_close_sell_state,
+    },
     WithdrawSellArgs,
 };
 
@@ -30,6 +33,7 @@ pub struct ExtWithdrawSell<'info> {
     )]
     pub pool: Box<Account<'info, Pool>>,
     #[account(
+        mut,
         mint::token_program = token_program,
         constraint = asset_mint.supply == 1 && asset_mint.decimals == 0 @ MMMErrorCode::InvalidTokenMint,
     )]
@@ -98,7 +102,19 @@ pub fn handler<'info>(
     // Note that check_allowlists_for_mint_ext is optional for withdraw_sell
     // because sometimes the nft might be moved out of the collection
     // and we'd still like to enable the withdraw of those items for the pool owner.
+    assert_no_cpi_guard(&sellside_escrow_token_account.to_account_info())?;
 
+    // same for the transfer hook program, it might have been removed from the
+    // allow list after the deposit, only the hook accounts are required
+    assert_valid_transfer_hook(
+        &sellside_escrow_token_account.to_account_info(),
+        &asset_mint.to_account_info(),
+        &asset_token_account.to_account_info(),
+        &pool.to_account_info(),
+        ctx.remaining_accounts,
+        args.asset_amount,
+        0,
+    )?;
     invoke_transfer_checked(
         token_program.key,
         sellside_escrow_token_account.to_account_info(),
@@ -113,6 +129,11 @@ pub fn handler<'info>(
 
     // we can close the sellside_escrow_token_account if no amount left
     if sellside_escrow_token_account.amount == args.asset_amount {
+        harvest_withheld_transfer_fee(
+            &token_program.to_account_info(),
+            &asset_mint.to_account_info(),
+            &sellside_escrow_token_account.to_account_info(),
+        )?;
         close_account(CpiContext::new_with_signer(
             token_program.to_account_info(),
             CloseAccount {
@@ -14,14 +14,19 @@ use crate::{
     errors::MMMErrorCode,
     index_ra,
     instructions::{
-        check_remaining_accounts_for_m2, get_transfer_hook_program_id, log_pool,
-        pay_creator_fees_in_sol_ext, split_remaining_account_for_ext, try_close_pool, withdraw_m2,
+        check_remaining_accounts_for_m2, log_pool, pay_creator_fees_in_sol,
+        split_remaining_account_for_ext, try_close_pool, withdraw_m2,
     },
-    state::{Pool, SellState},
+    state::{Pool, SellState, PAUSE_FLAG_EXT},
     util::{
-        assert_valid_fees_bp, check_allowlists_for_mint_ext, get_buyside_seller_receives,
-        get_lp_fee_bp, get_sol_fee, get_sol_lp_fee, get_sol_total_price_and_next_price,
-        try_close_escrow, try_close_sell_state,
+        apply_trait_premium, assert_allowed_transfer_hook_program, assert_no_cpi_guard,
+        assert_not_paused, assert_valid_fees_bp, assert_valid_transfer_hook,
+        check_allowlists_for_mint_ext, get_buyside_seller_receives, get_ext_group,
+        get_extension_policy, get_fee_schedule, get_global_config, get_lp_fee_bp,
+        get_metadata_royalty_bp, get_protocol_fee, get_referral_registry, get_royalty_schedule,
+        get_sol_fee, get_sol_lp_fee, get_sol_total_price_and_next_price,
+        get_transfer_fee_and_received_amount, harvest_withheld_transfer_fee, pay_protocol_fee,
+        pay_referral_fee, try_close_escrow, try_close_sell_state, FulfillResult, RoyaltyPayment,
     },
     verify_referral::verify_referral,
     SolFulfillBuyArgs,
@@ -44,10 +49,25 @@ pub struct ExtSolFulfillBuy<'info> {
     pub cosigner: Signer<'info>,
     #[account(
         mut,
-        constraint = verify_referral(&pool, &referral) @ MMMErrorCode::InvalidReferral,
+        constraint = verify_referral(&pool, &referral, &global_config) @ MMMErrorCode::InvalidReferral,
     )]
     /// CHECK: use verify_referral to check the referral account
     pub referral: UncheckedAccount<'info>,
+    /// CHECK: it might not be initialized, loaded with get_global_config
+    #[account(seeds = [GLOBAL_CONFIG_PREFIX.as_bytes()], bump)]
+    pub global_config: UncheckedAccount<'info>,
+    /// CHECK: checked against the treasury of global_config in pay_protocol_fee
+    #[account(mut)]
+    pub treasury: UncheckedAccount<'info>,
+    /// CHECK: it might not be initialized, loaded with get_fee_schedule
+    #[account(seeds = [FEE_SCHEDULE_PREFIX.as_bytes(), pool.referral.as_ref()], bump)]
+    pub fee_schedule: UncheckedAccount<'info>,
+    /// CHECK: the royalty schedule pda of the collection, checked in
+    /// get_royalty_schedule, it might not be initialized
+    pub royalty_schedule: UncheckedAccount<'info>,
+    /// CHECK: it might not be initialized, loaded with get_referral_registry
+    #[account(seeds = [REFERRAL_REGISTRY_PREFIX.as_bytes(), pool.referral.as_ref()], bump)]
+    pub referral_registry: UncheckedAccount<'info>,
     #[account(
         mut,
         seeds = [POOL_PREFIX.as_bytes(), owner.key().as_ref(), pool.uuid.as_ref()],
@@ -66,6 +86,7 @@ pub struct ExtSolFulfillBuy<'info> {
     )]
     pub buyside_sol_escrow_account: UncheckedAccount<'info>,
     #[account(
+        mut,
         mint::token_program = token_program,
         constraint = asset_mint.supply == 1 && asset_mint.decimals == 0 @ MMMErrorCode::InvalidTokenMint,
     )]
@@ -97,6 +118,12 @@ pub struct ExtSolFulfillBuy<'info> {
         bump
     )]
     pub sell_state: Account<'info, SellState>,
+    /// CHECK: the default extension policy is used if it's not initialized
+    #[account(
+        seeds = [EXTENSION_POLICY_PREFIX.as_bytes(), pool.key().as_ref()],
+        bump,
+    )]
+    pub extension_policy: UncheckedAccount<'info>,
     pub system_program: Program<'info, System>,
     pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
@@ -105,9 +132,14 @@ pub struct ExtSolFulfillBuy<'info> {
     // Branch: using shared escrow accounts
     //   0: m2_program
     //   1: shared_escrow_account
-    //   2+: transfer hook accounts
+    //   2..n: creator accounts (only for royalty enforced assets)
+    //   n+: transfer hook accounts
     // Branch: not using shared escrow accounts
-    //   0+: transfer hook accounts
+    //   0..n: creator accounts (only for royalty enforced assets)
+    //   n+: transfer hook accounts
+    // The token22 group account is looked up by key for the ext creator and
+    // ext collection allowlists, it can be placed anywhere after the above.
+    // The royalty schedule of the group is looked up by key as well.
 }
 
 pub fn handler<'info>(
@@ -119,6 +151,10 @@ pub fn handler<'info>(
     let associated_token_program = &ctx.accounts.associated_token_program;
     let rent = &ctx.accounts.rent;
     let pool = &mut ctx.accounts.pool;
+    let global_config = get_global_config(&ctx.accounts.global_config)?;
+    assert_not_paused(&global_config, PAUSE_FLAG_EXT)?;
+    let fee_schedule =
+        get_fee_schedule(&pool.referral, &ctx.accounts.fee_schedule, &global_config)?;
     let sell_state = &mut ctx.accounts.sell_state;
     let owner = &ctx.accounts.owner;
     let referral = &ctx.accounts.referral;
@@ -134,27 +170,48 @@ pub fn handler<'info>(
     ]];
     let remaining_accounts = ctx.remaining_accounts;
     if pool.using_shared_escrow() {
-        check_remaining_accounts_for_m2(remaining_accounts, &pool.owner.key())?;
+        check_remaining_accounts_for_m2(remaining_accounts, &pool.owner.key(), &global_config)?;
     }
 
-    let (optional_creator_account, remaining_account_without_m2, sfbp) =
+    let (ext_metadata, creator_accounts, remaining_account_without_m2) =
         split_remaining_account_for_ext(
             remaining_accounts,
             &asset_mint.to_account_info(),
             pool.using_shared_escrow(),
         )?;
 
+    let extension_policy = get_extension_policy(
+        &pool.key(),
+        &ctx.accounts.extension_policy.to_account_info(),
+    )?;
     check_allowlists_for_mint_ext(
         &pool.allowlists,
         &asset_mint.to_account_info(),
         args.allowlist_aux,
+        &extension_policy,
+        ctx.remaining_accounts,
     )?;
+    assert_no_cpi_guard(&payer_asset_account.to_account_info())?;
+    assert_no_cpi_guard(&ctx.accounts.sellside_escrow_token_account.to_account_info())?;
+    assert_allowed_transfer_hook_program(pool, &global_config, &asset_mint.to_account_info())?;
 
+    // the pool only pays for the amount that arrives after the transfer fee
+    let (transfer_fee_withheld, received_asset_amount) =
+        get_transfer_fee_and_received_amount(&asset_mint.to_account_info(), args.asset_amount)?;
     let (total_price, next_price) =
-        get_sol_total_price_and_next_price(pool, args.asset_amount, true)?;
+        get_sol_total_price_and_next_price(pool, received_asset_amount, true)?;
+    let total_price =
+        apply_trait_premium(pool, &asset_mint.key(), total_price, &args.trait_premium)?;
+    let royalty_schedule = get_royalty_schedule(
+        get_ext_group(&asset_mint.to_account_info()),
+        &ctx.accounts.royalty_schedule,
+    )?;
+    let sfbp = ext_metadata.as_ref().map_or(0, |m| {
+        get_metadata_royalty_bp(total_price, m, None, royalty_schedule.as_ref())
+    });
     // TODO: update lp_fee_bp when shared escrow for both side is enabled
     let seller_receives = {
-        let lp_fee_bp = get_lp_fee_bp(pool, buyside_sol_escrow_account.lamports());
+        let lp_fee_bp = get_lp_fee_bp(pool, buyside_sol_escrow_account.lamports(), true)?;
         get_buyside_seller_receives(
             total_price,
             lp_fee_bp,
@@ -163,7 +220,12 @@ pub fn handler<'info>(
         )
     }?;
 
-    assert_valid_fees_bp(args.maker_fee_bp, args.taker_fee_bp)?;
+    assert_valid_fees_bp(
+        args.maker_fee_bp,
+        args.taker_fee_bp,
+        &global_config,
+        &fee_schedule,
+    )?;
     let maker_fee = get_sol_fee(seller_receives, args.maker_fee_bp)?;
     let taker_fee = get_sol_fee(seller_receives, args.taker_fee_bp)?;
     let referral_fee = u64::try_from(
@@ -172,13 +234,21 @@ pub fn handler<'info>(
             .ok_or(MMMErrorCode::NumericOverflow)?,
     )
     .map_err(|_| MMMErrorCode::NumericOverflow)?;
+    let protocol_fee = get_protocol_fee(&global_config, pool, seller_receives)?;
 
     // TODO: update lp_fee when shared escrow for both side is enabled
-    let lp_fee = get_sol_lp_fee(pool, buyside_sol_escrow_account.lamports(), seller_receives)?;
+    let lp_fee = get_sol_lp_fee(
+        pool,
+        buyside_sol_escrow_account.lamports(),
+        seller_receives,
+        true,
+    )?;
 
     // withdraw sol from M2 first if shared escrow is enabled
     if pool.using_shared_escrow() {
-        let amount: u64 = (total_price as i64 + maker_fee) as u64;
+        // compounded lp fees stay in the shared escrow
+        let compounded_lp_fee = if pool.compound_lp_fee { lp_fee } else { 0 };
+        let amount: u64 = (total_price as i64 + maker_fee - compounded_lp_fee as i64) as u64;
         withdraw_m2(
             pool,
             ctx.bumps.pool,
@@ -188,10 +258,11 @@ pub fn handler<'info>(
             index_ra!(remaining_accounts, 0),
             pool.owner,
             amount,
+            &global_config,
         )?;
         pool.shared_escrow_count = pool
             .shared_escrow_count
-            .checked_sub(args.asset_amount)
+            .checked_sub(received_asset_amount)
             .ok_or(MMMErrorCode::NumericOverflow)?;
     }
 
@@ -211,6 +282,15 @@ pub fn handler<'info>(
             system_program.to_account_info(),
             rent.to_account_info(),
         )?;
+        assert_valid_transfer_hook(
+            &payer_asset_account.to_account_info(),
+            &asset_mint.to_account_info(),
+            &sellside_escrow_token_account.to_account_info(),
+            &payer.to_account_info(),
+            remaining_account_without_m2,
+            args.asset_amount,
+            0,
+        )?;
         invoke_transfer_checked(
             token_program.key,
             payer_asset_account.to_account_info(),
@@ -225,7 +305,7 @@ pub fn handler<'info>(
 
         pool.sellside_asset_amount = pool
             .sellside_asset_amount
-            .checked_add(args.asset_amount)
+            .checked_add(received_asset_amount)
             .ok_or(MMMErrorCode::NumericOverflow)?;
         sell_state.pool = pool.key();
         sell_state.pool_owner = owner.key();
@@ -233,7 +313,7 @@ pub fn handler<'info>(
         sell_state.cosigner_annotation = pool.cosigner_annotation;
         sell_state.asset_amount = sell_state
             .asset_amount
-            .checked_add(args.asset_amount)
+            .checked_add(received_asset_amount)
             .ok_or(MMMErrorCode::NumericOverflow)?;
     } else {
         let owner_token_account = ctx.accounts.owner_token_account.to_account_info();
@@ -248,6 +328,15 @@ pub fn handler<'info>(
             rent.to_account_info(),
         )?;
 
+        assert_valid_transfer_hook(
+            &payer_asset_account.to_account_info(),
+            &asset_mint.to_account_info(),
+            &owner_token_account.to_account_info(),
+            &payer.to_account_info(),
+            remaining_account_without_m2,
+            args.asset_amount,
+            0,
+        )?;
         invoke_transfer_checked(
             token_program.key,
             payer_asset_account.to_account_info(),
@@ -263,6 +352,11 @@ pub fn handler<'info>(
 
     // we can close the payer_asset_account if no amount left
     if payer_asset_account.amount == args.asset_amount {
+        harvest_withheld_transfer_fee(
+            &token_program.to_account_info(),
+            &asset_mint.to_account_info(),
+            &payer_asset_account.to_account_info(),
+        )?;
         close_account(CpiContext::new(
             token_program.to_account_info(),
             anchor_spl::token_2022::CloseAccount {
@@ -273,22 +367,23 @@ pub fn handler<'info>(
         ))?;
     }
 
-    let royalty_paid: u64 = if let Ok(transfer_hook_program_id) =
-        get_transfer_hook_program_id(&asset_mint.to_account_info())
-    {
-        if transfer_hook_program_id == Some(LIBREPLEX_ROYALTY_ENFORCEMENT_PROGRAM_ID) {
-            pay_creator_fees_in_sol_ext(
-                seller_receives,
-                optional_creator_account,
-                buyside_sol_escrow_account.to_account_info(),
-                sfbp,
-                buyside_sol_escrow_account_seeds,
-            )?
-        } else {
-            0
-        }
+    let RoyaltyPayment {
+        total: royalty_paid,
+        accrued: royalty_accrued,
+    } = if let Some(ref ext_metadata) = ext_metadata {
+        pay_creator_fees_in_sol(
+            10_000,
+            seller_receives,
+            ext_metadata,
+            creator_accounts,
+            buyside_sol_escrow_account.to_account_info(),
+            sfbp,
+            buyside_sol_escrow_account_seeds,
+            system_program.to_account_info(),
+            &global_config,
+        )?
     } else {
-        0
+        RoyaltyPayment::default()
     };
 
     // prevent frontrun by pool config changes
@@ -298,6 +393,8 @@ pub fn handler<'info>(
         .checked_sub(taker_fee as u64)
         .ok_or(MMMErrorCode::NumericOverflow)?
         .checked_sub(royalty_paid)
+        .ok_or(MMMErrorCode::NumericOverflow)?
+        .checked_sub(protocol_fee)
         .ok_or(MMMErrorCode::NumericOverflow)?;
     if payment_amount < args.min_payment_amount {
         return Err(MMMErrorCode::InvalidRequestedPrice.into());
@@ -312,7 +409,8 @@ pub fn handler<'info>(
         buyside_sol_escrow_account_seeds,
     )?;
 
-    if lp_fee > 0 {
+    // compounded lp fees stay in the buyside escrow
+    if lp_fee > 0 && !pool.compound_lp_fee {
         invoke_signed(
             &system_instruction::transfer(buyside_sol_escrow_account.key, owner.key, lp_fee),
             &[
@@ -322,25 +420,36 @@ pub fn handler<'info>(
             buyside_sol_escrow_account_seeds,
         )?;
     }
-    if referral_fee > 0 {
-        invoke_signed(
-            &system_instruction::transfer(
-                buyside_sol_escrow_account.key,
-                referral.key,
-                referral_fee,
-            ),
-            &[
-                buyside_sol_escrow_account.to_account_info(),
-                referral.to_account_info(),
-            ],
-            buyside_sol_escrow_account_seeds,
-        )?;
-    }
+    let referral_registry = get_referral_registry(&ctx.accounts.referral_registry)?;
+    pay_referral_fee(
+        &buyside_sol_escrow_account.to_account_info(),
+        &referral.to_account_info(),
+        referral_registry.as_ref(),
+        ctx.remaining_accounts,
+        &system_program.to_account_info(),
+        buyside_sol_escrow_account_seeds,
+        referral_fee,
+    )?;
+
+    pay_protocol_fee(
+        &buyside_sol_escrow_account.to_account_info(),
+        &ctx.accounts.treasury,
+        &global_config,
+        &system_program.to_account_info(),
+        buyside_sol_escrow_account_seeds,
+        protocol_fee,
+    )?;
 
     pool.lp_fee_earned = pool
         .lp_fee_earned
         .checked_add(lp_fee)
         .ok_or(MMMErrorCode::NumericOverflow)?;
+    if pool.compound_lp_fee {
+        pool.lp_fee_compounded = pool
+            .lp_fee_compounded
+            .checked_add(lp_fee)
+            .ok_or(MMMErrorCode::NumericOverflow)?;
+    }
     pool.spot_price = next_price;
 
     try_close_escrow(
@@ -348,6 +457,7 @@ pub fn handler<'info>(
         pool,
         system_program,
         buyside_sol_escrow_account_seeds,
+        &global_config,
     )?;
     try_close_sell_state(sell_state, payer.to_account_info())?;
 
@@ -376,6 +486,7 @@ pub fn handler<'info>(
                 pool,
                 system_program,
                 buyside_sol_escrow_account_seeds,
+                &global_config,
             )?;
         }
     }
@@ -385,11 +496,28 @@ pub fn handler<'info>(
     try_close_pool(pool, owner.to_account_info())?;
 
     msg!(
-        "{{\"lp_fee\":{},\"total_price\":{},\"royalty_paid\":{}}}",
+        "{{\"lp_fee\":{},\"protocol_fee\":{},\"total_price\":{},\"royalty_paid\":{},\"transfer_fee_withheld\":{}}}",
         lp_fee,
+        protocol_fee,
         total_price,
         royalty_paid,
+        transfer_fee_withheld,
     );
 
-    Ok(())
+    FulfillResult {
+        total_price,
+        lp_fee,
+        maker_fee,
+        taker_fee,
+        protocol_fee,
+        royalty_paid,
+        royalty_accrued,
+        payment_amount,
+        next_price: pool.spot_price,
+        sellside_asset_amount: pool.sellside_asset_amount,
+        transfer_fee_withheld,
+        trade_in_total_price: 0,
+        trade_in_payment_received: 0,
+    }
+    .set_return_data()
 }
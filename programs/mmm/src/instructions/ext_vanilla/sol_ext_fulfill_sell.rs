@@ -12,12 +12,18 @@ use crate::{
     constants::*,
     errors::MMMErrorCode,
     instructions::{
-        get_sell_fulfill_pool_price_info, get_transfer_hook_program_id, log_pool,
-        pay_creator_fees_in_sol_ext, split_remaining_account_for_ext, try_close_pool,
-        try_close_sell_state, PoolPriceInfo,
+        get_sell_fulfill_pool_price_info, log_pool, pay_creator_fees_in_sol,
+        split_remaining_account_for_ext, try_close_pool, try_close_sell_state, PoolPriceInfo,
+    },
+    state::{Pool, SellState, PAUSE_FLAG_EXT},
+    util::{
+        assert_allowed_transfer_hook_program, assert_no_cpi_guard, assert_not_paused,
+        assert_valid_transfer_hook, check_allowlists_for_mint_ext, get_ext_group,
+        get_extension_policy, get_fee_schedule, get_global_config, get_metadata_royalty_bp,
+        get_protocol_fee, get_referral_registry, get_royalty_schedule,
+        get_transfer_fee_and_received_amount, harvest_withheld_transfer_fee, pay_protocol_fee,
+        pay_referral_fee, should_compound_lp_fee, FulfillResult, RoyaltyPayment,
     },
-    state::{Pool, SellState},
-    util::check_allowlists_for_mint_ext,
     verify_referral::verify_referral,
     SolFulfillSellArgs,
 };
@@ -39,10 +45,25 @@ pub struct ExtSolFulfillSell<'info> {
     pub cosigner: Signer<'info>,
     #[account(
         mut,
-        constraint = verify_referral(&pool, &referral) @ MMMErrorCode::InvalidReferral,
+        constraint = verify_referral(&pool, &referral, &global_config) @ MMMErrorCode::InvalidReferral,
     )]
     /// CHECK: use verify_referral to check the referral account
     pub referral: UncheckedAccount<'info>,
+    /// CHECK: it might not be initialized, loaded with get_global_config
+    #[account(seeds = [GLOBAL_CONFIG_PREFIX.as_bytes()], bump)]
+    pub global_config: UncheckedAccount<'info>,
+    /// CHECK: checked against the treasury of global_config in pay_protocol_fee
+    #[account(mut)]
+    pub treasury: UncheckedAccount<'info>,
+    /// CHECK: it might not be initialized, loaded with get_fee_schedule
+    #[account(seeds = [FEE_SCHEDULE_PREFIX.as_bytes(), pool.referral.as_ref()], bump)]
+    pub fee_schedule: UncheckedAccount<'info>,
+    /// CHECK: the royalty schedule pda of the collection, checked in
+    /// get_royalty_schedule, it might not be initialized
+    pub royalty_schedule: UncheckedAccount<'info>,
+    /// CHECK: it might not be initialized, loaded with get_referral_registry
+    #[account(seeds = [REFERRAL_REGISTRY_PREFIX.as_bytes(), pool.referral.as_ref()], bump)]
+    pub referral_registry: UncheckedAccount<'info>,
     #[account(
         mut,
         seeds = [POOL_PREFIX.as_bytes(), owner.key().as_ref(), pool.uuid.as_ref()],
@@ -63,6 +84,7 @@ pub struct ExtSolFulfillSell<'info> {
     pub buyside_sol_escrow_account: AccountInfo<'info>,
     /// CHECK: check_allowlists_for_mint_ext
     #[account(
+        mut,
         mint::token_program = token_program,
         constraint = asset_mint.supply == 1 && asset_mint.decimals == 0 @ MMMErrorCode::InvalidTokenMint,
     )]
@@ -92,6 +114,12 @@ pub struct ExtSolFulfillSell<'info> {
         bump
     )]
     pub sell_state: Account<'info, SellState>,
+    /// CHECK: the default extension policy is used if it's not initialized
+    #[account(
+        seeds = [EXTENSION_POLICY_PREFIX.as_bytes(), pool.key().as_ref()],
+        bump,
+    )]
+    pub extension_policy: UncheckedAccount<'info>,
     pub system_program: Program<'info, System>,
     pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
@@ -106,13 +134,17 @@ pub fn handler<'info>(
     let owner = &ctx.accounts.owner;
     let referral = &ctx.accounts.referral;
     let pool = &mut ctx.accounts.pool;
+    let global_config = get_global_config(&ctx.accounts.global_config)?;
+    assert_not_paused(&global_config, PAUSE_FLAG_EXT)?;
+    let fee_schedule =
+        get_fee_schedule(&pool.referral, &ctx.accounts.fee_schedule, &global_config)?;
     let sell_state = &mut ctx.accounts.sell_state;
 
     let payer = &ctx.accounts.payer;
     let payer_asset_account = &ctx.accounts.payer_asset_account;
     let asset_mint = &ctx.accounts.asset_mint;
     let remaining_accounts = ctx.remaining_accounts;
-    let (optional_creator_account, remaining_account_without_creator, sfbp) =
+    let (ext_metadata, creator_accounts, remaining_account_without_creator) =
         split_remaining_account_for_ext(remaining_accounts, &asset_mint.to_account_info(), false)?;
 
     let sellside_escrow_token_account = &ctx.accounts.sellside_escrow_token_account;
@@ -124,15 +156,28 @@ pub fn handler<'info>(
         &[ctx.bumps.pool],
     ]];
 
+    let extension_policy = get_extension_policy(
+        &pool.key(),
+        &ctx.accounts.extension_policy.to_account_info(),
+    )?;
     check_allowlists_for_mint_ext(
         &pool.allowlists,
         &asset_mint.to_account_info(),
         args.allowlist_aux,
+        &extension_policy,
+        ctx.remaining_accounts,
     )?;
+    assert_no_cpi_guard(&sellside_escrow_token_account.to_account_info())?;
+    assert_allowed_transfer_hook_program(pool, &global_config, &asset_mint.to_account_info())?;
+
+    // the full asset_amount leaves the pool, the buyer receives it minus the transfer fee
+    let (transfer_fee_withheld, _) =
+        get_transfer_fee_and_received_amount(&asset_mint.to_account_info(), args.asset_amount)?;
 
     let PoolPriceInfo {
         total_price,
         next_price,
+        spot_price_moved,
         lp_fee,
         maker_fee,
         taker_fee,
@@ -140,11 +185,14 @@ pub fn handler<'info>(
         transfer_sol_to,
     } = get_sell_fulfill_pool_price_info(
         pool,
+        sell_state,
         owner,
         buyside_sol_escrow_account,
         args.asset_amount,
         args.maker_fee_bp,
         args.taker_fee_bp,
+        &global_config,
+        &fee_schedule,
     )?;
 
     // TODO: make sure that the lp fee is paid with the correct amount
@@ -167,6 +215,15 @@ pub fn handler<'info>(
         ],
     )?;
 
+    assert_valid_transfer_hook(
+        &sellside_escrow_token_account.to_account_info(),
+        &asset_mint.to_account_info(),
+        &payer_asset_account.to_account_info(),
+        &pool.to_account_info(),
+        remaining_account_without_creator,
+        args.asset_amount,
+        0,
+    )?;
     invoke_transfer_checked(
         token_program.key,
         sellside_escrow_token_account.to_account_info(),
@@ -181,6 +238,11 @@ pub fn handler<'info>(
 
     // we can close the sellside_escrow_token_account if no amount left
     if sellside_escrow_token_account.amount == args.asset_amount {
+        harvest_withheld_transfer_fee(
+            &token_program.to_account_info(),
+            &asset_mint.to_account_info(),
+            &sellside_escrow_token_account.to_account_info(),
+        )?;
         close_account(CpiContext::new_with_signer(
             token_program.to_account_info(),
             anchor_spl::token_2022::CloseAccount {
@@ -192,27 +254,44 @@ pub fn handler<'info>(
         ))?;
     }
 
+    // compounded lp fees go to the buyside escrow instead of the owner
+    let compound_lp_fee = should_compound_lp_fee(pool, buyside_sol_escrow_account, lp_fee)?;
+    let lp_fee_to = if compound_lp_fee {
+        buyside_sol_escrow_account.to_account_info()
+    } else {
+        owner.to_account_info()
+    };
     if lp_fee > 0 {
         invoke(
-            &system_instruction::transfer(payer.key, owner.key, lp_fee),
+            &system_instruction::transfer(payer.key, lp_fee_to.key, lp_fee),
             &[
                 payer.to_account_info(),
-                owner.to_account_info(),
+                lp_fee_to,
                 system_program.to_account_info(),
             ],
         )?;
     }
 
-    if referral_fee > 0 {
-        invoke(
-            &system_instruction::transfer(payer.key, referral.key, referral_fee),
-            &[
-                payer.to_account_info(),
-                referral.to_account_info(),
-                system_program.to_account_info(),
-            ],
-        )?;
-    }
+    let referral_registry = get_referral_registry(&ctx.accounts.referral_registry)?;
+    pay_referral_fee(
+        &payer.to_account_info(),
+        &referral.to_account_info(),
+        referral_registry.as_ref(),
+        ctx.remaining_accounts,
+        &system_program.to_account_info(),
+        &[&[&[]]],
+        referral_fee,
+    )?;
+
+    let protocol_fee = get_protocol_fee(&global_config, pool, total_price)?;
+    pay_protocol_fee(
+        payer,
+        &ctx.accounts.treasury,
+        &global_config,
+        system_program,
+        &[&[&[]]],
+        protocol_fee,
+    )?;
 
     pool.spot_price = next_price;
     pool.sellside_asset_amount = pool
@@ -223,23 +302,36 @@ pub fn handler<'info>(
         .lp_fee_earned
         .checked_add(lp_fee)
         .ok_or(MMMErrorCode::NumericOverflow)?;
+    if compound_lp_fee {
+        pool.lp_fee_compounded = pool
+            .lp_fee_compounded
+            .checked_add(lp_fee)
+            .ok_or(MMMErrorCode::NumericOverflow)?;
+    }
 
-    let royalty_paid: u64 = if let Ok(transfer_hook_program_id) =
-        get_transfer_hook_program_id(&asset_mint.to_account_info())
-    {
-        if transfer_hook_program_id == Some(LIBREPLEX_ROYALTY_ENFORCEMENT_PROGRAM_ID) {
-            pay_creator_fees_in_sol_ext(
-                total_price,
-                optional_creator_account,
-                payer.to_account_info(),
-                sfbp,
-                &[&[&[]]],
-            )?
-        } else {
-            0
-        }
+    let RoyaltyPayment {
+        total: royalty_paid,
+        accrued: royalty_accrued,
+    } = if let Some(ref ext_metadata) = ext_metadata {
+        let royalty_schedule = get_royalty_schedule(
+            get_ext_group(&asset_mint.to_account_info()),
+            &ctx.accounts.royalty_schedule,
+        )?;
+        let royalty_bp =
+            get_metadata_royalty_bp(total_price, ext_metadata, None, royalty_schedule.as_ref());
+        pay_creator_fees_in_sol(
+            10_000,
+            total_price,
+            ext_metadata,
+            creator_accounts,
+            payer.to_account_info(),
+            royalty_bp,
+            &[&[&[]]],
+            system_program.to_account_info(),
+            &global_config,
+        )?
     } else {
-        0
+        RoyaltyPayment::default()
     };
 
     // prevent frontrun by pool config changes
@@ -249,6 +341,8 @@ pub fn handler<'info>(
         .checked_add(taker_fee as u64)
         .ok_or(MMMErrorCode::NumericOverflow)?
         .checked_add(royalty_paid)
+        .ok_or(MMMErrorCode::NumericOverflow)?
+        .checked_add(protocol_fee)
         .ok_or(MMMErrorCode::NumericOverflow)?;
     if payment_amount > args.max_payment_amount {
         return Err(MMMErrorCode::InvalidRequestedPrice.into());
@@ -265,11 +359,29 @@ pub fn handler<'info>(
     try_close_pool(pool, owner.to_account_info())?;
 
     msg!(
-        "{{\"lp_fee\":{},\"total_price\":{},\"royalty_paid\":{}}}",
+        "{{\"lp_fee\":{},\"protocol_fee\":{},\"total_price\":{},\"royalty_paid\":{},\"spot_price_moved\":{},\"transfer_fee_withheld\":{}}}",
         lp_fee,
+        protocol_fee,
         total_price,
         royalty_paid,
+        spot_price_moved,
+        transfer_fee_withheld,
     );
 
-    Ok(())
+    FulfillResult {
+        total_price,
+        lp_fee,
+        maker_fee,
+        taker_fee,
+        protocol_fee,
+        royalty_paid,
+        royalty_accrued,
+        payment_amount,
+        next_price: pool.spot_price,
+        sellside_asset_amount: pool.sellside_asset_amount,
+        transfer_fee_withheld,
+        trade_in_total_price: 0,
+        trade_in_payment_received: 0,
+    }
+    .set_return_data()
 }
@@ -0,0 +1,125 @@
+use anchor_lang::{prelude::*, solana_program::sysvar};
+use anchor_spl::token_interface::{Mint, TokenInterface};
+use mpl_token_metadata::{accounts::Metadata, instructions::RevokeTransferV1CpiBuilder};
+
+use crate::{
+    auth_rules::assert_valid_authorization_rules,
+    constants::*,
+    errors::MMMErrorCode,
+    state::{Pool, SellState},
+    util::{is_listing_available, log_pool, release_sell_state_listing, try_close_pool},
+};
+
+// Mip1DelistSell removes a non-custodial listing created by Mip1ListSell, the
+// transfer delegate is revoked if the owner still holds the asset.
+#[derive(Accounts)]
+pub struct Mip1DelistSell<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(constraint = owner.key() != cosigner.key() @ MMMErrorCode::InvalidCosigner)]
+    pub cosigner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [POOL_PREFIX.as_bytes(), owner.key().as_ref(), pool.uuid.as_ref()],
+        has_one = owner @ MMMErrorCode::InvalidOwner,
+        has_one = cosigner @ MMMErrorCode::InvalidCosigner,
+        bump
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+    pub asset_mint: InterfaceAccount<'info, Mint>,
+    /// CHECK: will be checked in cpi
+    pub asset_master_edition: UncheckedAccount<'info>,
+    /// CHECK: will be checked in cpi
+    #[account(mut,
+    seeds = [
+        "metadata".as_bytes(),
+        mpl_token_metadata::ID.as_ref(),
+        asset_mint.key().as_ref(),
+    ],
+    bump,
+    seeds::program = mpl_token_metadata::ID,
+    )]
+    pub asset_metadata: UncheckedAccount<'info>,
+    /// CHECK: checked against the listing in sell_state, it might be closed already
+    #[account(
+        mut,
+        address = sell_state.listing_token_account @ MMMErrorCode::InvalidAccountState,
+    )]
+    pub asset_token_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            SELL_STATE_PREFIX.as_bytes(),
+            pool.key().as_ref(),
+            asset_mint.key().as_ref(),
+        ],
+        bump
+    )]
+    pub sell_state: Account<'info, SellState>,
+    /// CHECK: will be checked in cpi
+    #[account(mut)]
+    pub owner_token_record: UncheckedAccount<'info>,
+    /// CHECK: will be checked in cpi
+    pub authorization_rules: UncheckedAccount<'info>,
+
+    /// CHECK: checked by address and in cpi
+    #[account(address = mpl_token_metadata::ID)]
+    pub token_metadata_program: UncheckedAccount<'info>,
+    /// CHECK: checked by address and in cpi
+    #[account(address = MPL_TOKEN_AUTH_RULES)]
+    pub authorization_rules_program: UncheckedAccount<'info>,
+    /// CHECK: checked by address and in cpi
+    #[account(address = sysvar::instructions::id())]
+    pub instructions: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler(ctx: Context<Mip1DelistSell>) -> Result<()> {
+    let owner = &ctx.accounts.owner;
+    let asset_token_account = &ctx.accounts.asset_token_account;
+    let token_program = &ctx.accounts.token_program;
+    let pool = &mut ctx.accounts.pool;
+    let sell_state = &mut ctx.accounts.sell_state;
+    let asset_mint = &ctx.accounts.asset_mint;
+    let asset_metadata = &ctx.accounts.asset_metadata;
+    let asset_master_edition = &ctx.accounts.asset_master_edition;
+    let owner_token_record = &ctx.accounts.owner_token_record;
+    let system_program = &ctx.accounts.system_program;
+    let instructions = &ctx.accounts.instructions;
+    let authorization_rules = &ctx.accounts.authorization_rules;
+    let authorization_rules_program = &ctx.accounts.authorization_rules_program;
+    let token_metadata_program_ai = &ctx.accounts.token_metadata_program.to_account_info();
+
+    if is_listing_available(
+        &asset_token_account.to_account_info(),
+        pool,
+        token_program.key,
+    ) {
+        assert_valid_authorization_rules(
+            &Metadata::safe_deserialize(&asset_metadata.data.borrow())?,
+            authorization_rules,
+        )?;
+        RevokeTransferV1CpiBuilder::new(token_metadata_program_ai)
+            .delegate(&pool.to_account_info())
+            .metadata(&asset_metadata.to_account_info())
+            .master_edition(Some(&asset_master_edition.to_account_info()))
+            .token_record(Some(&owner_token_record.to_account_info()))
+            .mint(&asset_mint.to_account_info())
+            .token(&asset_token_account.to_account_info())
+            .authority(&owner.to_account_info())
+            .payer(&owner.to_account_info())
+            .system_program(&system_program.to_account_info())
+            .sysvar_instructions(&instructions.to_account_info())
+            .spl_token_program(Some(&token_program.to_account_info()))
+            .authorization_rules(Some(&authorization_rules.to_account_info()))
+            .authorization_rules_program(Some(&authorization_rules_program.to_account_info()))
+            .invoke()?;
+    }
+
+    release_sell_state_listing(pool, sell_state, owner.to_account_info())?;
+    log_pool("post_mip1_delist_sell", pool)?;
+    try_close_pool(pool, owner.to_account_info())?;
+
+    Ok(())
+}
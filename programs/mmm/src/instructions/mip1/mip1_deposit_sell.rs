@@ -1,21 +1,20 @@
-use std::collections::HashMap;
-
 use anchor_lang::{prelude::*, solana_program::sysvar, AnchorDeserialize};
 use anchor_spl::{
     associated_token::AssociatedToken,
     token_interface::{Mint, TokenAccount, TokenInterface},
 };
-use mpl_token_metadata::{
-    instructions::TransferCpiBuilder,
-    types::{AuthorizationData, Payload, PayloadType, SeedsVec, TransferArgs},
-};
+use mpl_token_metadata::{instructions::TransferCpiBuilder, types::TransferArgs};
 
 use crate::{
+    auth_rules::{get_authorization_data, OPERATION_TRANSFER_OWNER},
     constants::*,
     errors::MMMErrorCode,
     instructions::vanilla::DepositSellArgs,
-    state::{Pool, SellState},
-    util::{assert_is_programmable, check_allowlists_for_mint, log_pool},
+    state::{Pool, SellState, PAUSE_FLAG_MIP1},
+    util::{
+        assert_is_programmable, assert_not_paused, check_allowlists_for_mint, get_global_config,
+        log_pool, set_sell_state_price_override,
+    },
 };
 
 #[derive(Accounts)]
@@ -99,6 +98,9 @@ pub struct Mip1DepositSell<'info> {
     /// CHECK: checked by address and in cpi
     #[account(address = sysvar::instructions::id())]
     pub instructions: UncheckedAccount<'info>,
+    /// CHECK: it might not be initialized, loaded with get_global_config
+    #[account(seeds = [GLOBAL_CONFIG_PREFIX.as_bytes()], bump)]
+    pub global_config: UncheckedAccount<'info>,
     pub system_program: Program<'info, System>,
     pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
@@ -113,6 +115,8 @@ pub fn handler(ctx: Context<Mip1DepositSell>, args: DepositSellArgs) -> Result<(
     let token_program = &ctx.accounts.token_program;
     let sellside_escrow_token_account = &ctx.accounts.sellside_escrow_token_account;
     let pool = &mut ctx.accounts.pool;
+    let global_config = get_global_config(&ctx.accounts.global_config)?;
+    assert_not_paused(&global_config, PAUSE_FLAG_MIP1)?;
     let asset_master_edition = &ctx.accounts.asset_master_edition;
     let owner_token_record = &ctx.accounts.owner_token_record;
     let destination_token_record = &ctx.accounts.destination_token_record;
@@ -127,6 +131,10 @@ pub fn handler(ctx: Context<Mip1DepositSell>, args: DepositSellArgs) -> Result<(
     if pool.using_shared_escrow() {
         return Err(MMMErrorCode::InvalidAccountState.into());
     }
+    // the asset can't be both deposited and listed
+    if sell_state.is_listing() {
+        return Err(MMMErrorCode::InvalidAccountState.into());
+    }
 
     let parsed_metadata = check_allowlists_for_mint(
         &pool.allowlists,
@@ -137,21 +145,22 @@ pub fn handler(ctx: Context<Mip1DepositSell>, args: DepositSellArgs) -> Result<(
     )?;
     assert_is_programmable(&parsed_metadata)?;
 
-    let payload = Payload {
-        map: HashMap::from([(
-            "DestinationSeeds".to_owned(),
-            PayloadType::Seeds(SeedsVec {
-                seeds: vec![
-                    POOL_PREFIX.as_bytes().to_vec(),
-                    owner.key().to_bytes().to_vec(),
-                    pool.uuid.to_bytes().to_vec(),
-                ],
-            }),
-        )]),
-    };
+    let authorization_data = get_authorization_data(
+        &parsed_metadata,
+        authorization_rules,
+        OPERATION_TRANSFER_OWNER,
+        &["DestinationSeeds"],
+        vec![
+            POOL_PREFIX.as_bytes().to_vec(),
+            owner.key().to_bytes().to_vec(),
+            pool.uuid.to_bytes().to_vec(),
+        ],
+        args.asset_amount,
+        args.authorization_data,
+    )?;
 
     let transfer_args = TransferArgs::V1 {
-        authorization_data: Some(AuthorizationData { payload }),
+        authorization_data,
         amount: args.asset_amount,
     };
 
@@ -201,6 +210,9 @@ pub fn handler(ctx: Context<Mip1DepositSell>, args: DepositSellArgs) -> Result<(
         .asset_amount
         .checked_add(args.asset_amount)
         .ok_or(MMMErrorCode::NumericOverflow)?;
+    if let Some(price_override) = args.price_override {
+        set_sell_state_price_override(sell_state, price_override)?;
+    }
     log_pool("post_mip1_deposit_sell", pool)?;
 
     Ok(())
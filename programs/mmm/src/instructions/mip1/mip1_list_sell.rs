@@ -0,0 +1,183 @@
+use anchor_lang::{prelude::*, solana_program::sysvar, AnchorDeserialize};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use mpl_token_metadata::instructions::DelegateTransferV1CpiBuilder;
+
+use crate::{
+    auth_rules::{get_authorization_data, OPERATION_DELEGATE_TRANSFER},
+    constants::*,
+    errors::MMMErrorCode,
+    instructions::vanilla::DepositSellArgs,
+    state::{Pool, SellState, PAUSE_FLAG_MIP1},
+    util::{
+        assert_is_programmable, assert_not_paused, check_allowlists_for_mint, get_global_config,
+        log_pool, set_sell_state_price_override,
+    },
+};
+
+// Mip1ListSell is the non-custodial version of Mip1DepositSell, the pNFT
+// stays in the owner's wallet and the pool becomes its transfer delegate, so
+// that sol_mip1_fulfill_sell can transfer it straight to the buyer.
+#[derive(Accounts)]
+#[instruction(args:DepositSellArgs)]
+pub struct Mip1ListSell<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(constraint = owner.key() != cosigner.key() @ MMMErrorCode::InvalidCosigner)]
+    pub cosigner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [POOL_PREFIX.as_bytes(), owner.key().as_ref(), pool.uuid.as_ref()],
+        has_one = owner @ MMMErrorCode::InvalidOwner,
+        has_one = cosigner @ MMMErrorCode::InvalidCosigner,
+        bump
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+    /// CHECK: we will check the metadata in check_allowlists_for_mint(), also checked in cpi
+    #[account(mut,
+    seeds = [
+        "metadata".as_bytes(),
+        mpl_token_metadata::ID.as_ref(),
+        asset_mint.key().as_ref(),
+    ],
+    bump,
+    seeds::program = mpl_token_metadata::ID,
+    )]
+    pub asset_metadata: UncheckedAccount<'info>,
+    #[account(
+        constraint = asset_mint.supply == 1 && asset_mint.decimals == 0 @ MMMErrorCode::InvalidMip1AssetParams,
+    )]
+    pub asset_mint: InterfaceAccount<'info, Mint>,
+    /// CHECK: will be checked in cpi
+    pub asset_master_edition: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        token::mint = asset_mint,
+        token::authority = owner,
+        constraint = asset_token_account.amount == 1 @ MMMErrorCode::InvalidMip1AssetParams,
+        constraint = args.asset_amount == 1 @ MMMErrorCode::InvalidMip1AssetParams,
+    )]
+    pub asset_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        seeds = [
+            SELL_STATE_PREFIX.as_bytes(),
+            pool.key().as_ref(),
+            asset_mint.key().as_ref(),
+        ],
+        space = SellState::LEN,
+        bump
+    )]
+    pub sell_state: Account<'info, SellState>,
+    /// CHECK: will be used for allowlist checks
+    pub allowlist_aux_account: UncheckedAccount<'info>,
+    /// CHECK: will be checked in cpi
+    #[account(mut)]
+    pub owner_token_record: UncheckedAccount<'info>,
+    /// CHECK: will be checked in cpi
+    pub authorization_rules: UncheckedAccount<'info>,
+
+    /// CHECK: checked by address and in cpi
+    #[account(address = mpl_token_metadata::ID)]
+    pub token_metadata_program: UncheckedAccount<'info>,
+    /// CHECK: checked by address and in cpi
+    #[account(address = MPL_TOKEN_AUTH_RULES)]
+    pub authorization_rules_program: UncheckedAccount<'info>,
+    /// CHECK: checked by address and in cpi
+    #[account(address = sysvar::instructions::id())]
+    pub instructions: UncheckedAccount<'info>,
+    /// CHECK: it might not be initialized, loaded with get_global_config
+    #[account(seeds = [GLOBAL_CONFIG_PREFIX.as_bytes()], bump)]
+    pub global_config: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler(ctx: Context<Mip1ListSell>, args: DepositSellArgs) -> Result<()> {
+    let owner = &ctx.accounts.owner;
+    let asset_token_account = &ctx.accounts.asset_token_account;
+    let asset_mint = &ctx.accounts.asset_mint;
+    let asset_metadata = &ctx.accounts.asset_metadata;
+    let token_program = &ctx.accounts.token_program;
+    let pool = &mut ctx.accounts.pool;
+    let global_config = get_global_config(&ctx.accounts.global_config)?;
+    assert_not_paused(&global_config, PAUSE_FLAG_MIP1)?;
+    let asset_master_edition = &ctx.accounts.asset_master_edition;
+    let owner_token_record = &ctx.accounts.owner_token_record;
+    let sell_state = &mut ctx.accounts.sell_state;
+    let system_program = &ctx.accounts.system_program;
+    let instructions = &ctx.accounts.instructions;
+    let authorization_rules = &ctx.accounts.authorization_rules;
+    let authorization_rules_program = &ctx.accounts.authorization_rules_program;
+    let token_metadata_program_ai = &ctx.accounts.token_metadata_program.to_account_info();
+
+    if pool.using_shared_escrow() {
+        return Err(MMMErrorCode::InvalidAccountState.into());
+    }
+    // the asset can't be both deposited and listed
+    if sell_state.asset_amount != 0 {
+        return Err(MMMErrorCode::InvalidAccountState.into());
+    }
+
+    let parsed_metadata = check_allowlists_for_mint(
+        &pool.allowlists,
+        asset_mint,
+        asset_metadata,
+        Some(asset_master_edition),
+        args.allowlist_aux,
+    )?;
+    assert_is_programmable(&parsed_metadata)?;
+
+    let authorization_data = get_authorization_data(
+        &parsed_metadata,
+        authorization_rules,
+        OPERATION_DELEGATE_TRANSFER,
+        &["DelegateSeeds"],
+        vec![
+            POOL_PREFIX.as_bytes().to_vec(),
+            owner.key().to_bytes().to_vec(),
+            pool.uuid.to_bytes().to_vec(),
+        ],
+        args.asset_amount,
+        args.authorization_data,
+    )?;
+
+    let mut delegate_cpi = DelegateTransferV1CpiBuilder::new(token_metadata_program_ai);
+    if let Some(authorization_data) = authorization_data {
+        delegate_cpi.authorization_data(authorization_data);
+    }
+    delegate_cpi
+        .delegate(&pool.to_account_info())
+        .metadata(&asset_metadata.to_account_info())
+        .master_edition(Some(&asset_master_edition.to_account_info()))
+        .token_record(Some(&owner_token_record.to_account_info()))
+        .mint(&asset_mint.to_account_info())
+        .token(&asset_token_account.to_account_info())
+        .authority(&owner.to_account_info())
+        .payer(&owner.to_account_info())
+        .system_program(&system_program.to_account_info())
+        .sysvar_instructions(&instructions.to_account_info())
+        .spl_token_program(Some(&token_program.to_account_info()))
+        .authorization_rules(Some(&authorization_rules.to_account_info()))
+        .authorization_rules_program(Some(&authorization_rules_program.to_account_info()))
+        .amount(args.asset_amount)
+        .invoke()?;
+
+    pool.sellside_asset_amount = pool
+        .sellside_asset_amount
+        .checked_add(args.asset_amount)
+        .ok_or(MMMErrorCode::NumericOverflow)?;
+
+    sell_state.pool = pool.key();
+    sell_state.pool_owner = owner.key();
+    sell_state.asset_mint = asset_mint.key();
+    sell_state.cosigner_annotation = pool.cosigner_annotation;
+    sell_state.asset_amount = args.asset_amount;
+    sell_state.listing_token_account = asset_token_account.key();
+    if let Some(price_override) = args.price_override {
+        set_sell_state_price_override(sell_state, price_override)?;
+    }
+    log_pool("post_mip1_list_sell", pool)?;
+
+    Ok(())
+}
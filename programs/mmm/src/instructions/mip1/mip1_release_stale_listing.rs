@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenInterface};
+
+use crate::{
+    constants::*,
+    errors::MMMErrorCode,
+    state::{Pool, SellState},
+    util::{is_listing_available, log_pool, release_sell_state_listing},
+};
+
+// Mip1ReleaseStaleListing lets anyone release a non-custodial listing whose asset
+// is no longer available, e.g. when the owner moved it out of the listed token
+// account. The sell_state rent goes back to the owner.
+#[derive(Accounts)]
+pub struct Mip1ReleaseStaleListing<'info> {
+    /// CHECK: has_one of the pool, receives the sell_state rent
+    #[account(mut)]
+    pub owner: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [POOL_PREFIX.as_bytes(), owner.key().as_ref(), pool.uuid.as_ref()],
+        has_one = owner @ MMMErrorCode::InvalidOwner,
+        bump
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+    pub asset_mint: InterfaceAccount<'info, Mint>,
+    /// CHECK: checked against the listing in sell_state, it might be closed already
+    #[account(address = sell_state.listing_token_account @ MMMErrorCode::InvalidAccountState)]
+    pub asset_token_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            SELL_STATE_PREFIX.as_bytes(),
+            pool.key().as_ref(),
+            asset_mint.key().as_ref(),
+        ],
+        bump
+    )]
+    pub sell_state: Account<'info, SellState>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler(ctx: Context<Mip1ReleaseStaleListing>) -> Result<()> {
+    let owner = &ctx.accounts.owner;
+    let asset_token_account = &ctx.accounts.asset_token_account;
+    let token_program = &ctx.accounts.token_program;
+    let pool = &mut ctx.accounts.pool;
+    let sell_state = &mut ctx.accounts.sell_state;
+
+    // available listings can only be removed by the owner with mip1_delist_sell
+    if is_listing_available(
+        &asset_token_account.to_account_info(),
+        pool,
+        token_program.key,
+    ) {
+        return Err(MMMErrorCode::InvalidAccountState.into());
+    }
+
+    release_sell_state_listing(pool, sell_state, owner.to_account_info())?;
+    log_pool("post_mip1_release_stale_listing", pool)?;
+
+    Ok(())
+}
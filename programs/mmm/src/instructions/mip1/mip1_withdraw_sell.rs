@@ -1,26 +1,29 @@
-use std::collections::HashMap;
-
-use anchor_lang::{prelude::*, solana_program::sysvar, AnchorDeserialize};
+use anchor_lang::{prelude::*, solana_program::sysvar, AnchorDeserialize, AnchorSerialize};
 use anchor_spl::{
     associated_token::AssociatedToken,
     token_interface::{Mint, TokenAccount, TokenInterface},
 };
 use mpl_token_metadata::{
-    accounts::Metadata,
-    instructions::TransferCpiBuilder,
-    types::{AuthorizationData, Payload, PayloadType, SeedsVec, TransferArgs},
+    accounts::Metadata, instructions::TransferCpiBuilder, types::TransferArgs,
 };
 
 use crate::{
+    auth_rules::{get_authorization_data, AuthorizationDataLocal, OPERATION_TRANSFER_OWNER},
     constants::*,
     errors::MMMErrorCode,
-    instructions::vanilla::WithdrawSellArgs,
     state::{Pool, SellState},
     util::{assert_is_programmable, log_pool, try_close_pool, try_close_sell_state},
 };
 
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct Mip1WithdrawSellArgs {
+    pub asset_amount: u64,
+    pub allowlist_aux: Option<String>, // TODO: use it for future allowlist_aux
+    pub authorization_data: Option<AuthorizationDataLocal>, // only used by pNFTs with a rule set
+}
+
 #[derive(Accounts)]
-#[instruction(args:WithdrawSellArgs)]
+#[instruction(args:Mip1WithdrawSellArgs)]
 pub struct Mip1WithdrawSell<'info> {
     #[account(mut)]
     pub owner: Signer<'info>,
@@ -111,7 +114,7 @@ pub struct Mip1WithdrawSell<'info> {
     pub rent: Sysvar<'info, Rent>,
 }
 
-pub fn handler(ctx: Context<Mip1WithdrawSell>, args: WithdrawSellArgs) -> Result<()> {
+pub fn handler(ctx: Context<Mip1WithdrawSell>, args: Mip1WithdrawSellArgs) -> Result<()> {
     let owner = &ctx.accounts.owner;
     let asset_token_account = &ctx.accounts.asset_token_account;
     let sellside_escrow_token_account = &ctx.accounts.sellside_escrow_token_account;
@@ -138,19 +141,21 @@ pub fn handler(ctx: Context<Mip1WithdrawSell>, args: WithdrawSellArgs) -> Result
         &[ctx.bumps.pool],
     ]];
 
-    assert_is_programmable(&Metadata::safe_deserialize(&asset_metadata.data.borrow())?)?;
+    let parsed_metadata = Metadata::safe_deserialize(&asset_metadata.data.borrow())?;
+    assert_is_programmable(&parsed_metadata)?;
 
-    let payload = Payload {
-        map: HashMap::from([(
-            "SourceSeeds".to_owned(),
-            PayloadType::Seeds(SeedsVec {
-                seeds: pool_seeds[0][0..3].iter().map(|v| v.to_vec()).collect(),
-            }),
-        )]),
-    };
+    let authorization_data = get_authorization_data(
+        &parsed_metadata,
+        authorization_rules,
+        OPERATION_TRANSFER_OWNER,
+        &["SourceSeeds", "AuthoritySeeds"],
+        pool_seeds[0][0..3].iter().map(|v| v.to_vec()).collect(),
+        args.asset_amount,
+        args.authorization_data,
+    )?;
 
     let transfer_args = TransferArgs::V1 {
-        authorization_data: Some(AuthorizationData { payload }),
+        authorization_data,
         amount: args.asset_amount,
     };
 
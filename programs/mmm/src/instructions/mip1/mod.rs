@@ -1,9 +1,15 @@
+pub mod mip1_delist_sell;
 pub mod mip1_deposit_sell;
+pub mod mip1_list_sell;
+pub mod mip1_release_stale_listing;
 pub mod mip1_withdraw_sell;
 pub mod sol_mip1_fulfill_buy;
 pub mod sol_mip1_fulfill_sell;
 
+pub use mip1_delist_sell::*;
 pub use mip1_deposit_sell::*;
+pub use mip1_list_sell::*;
+pub use mip1_release_stale_listing::*;
 pub use mip1_withdraw_sell::*;
 pub use sol_mip1_fulfill_buy::*;
 pub use sol_mip1_fulfill_sell::*;
@@ -3,26 +3,28 @@ use anchor_spl::{
     associated_token::AssociatedToken,
     token_interface::{Mint, TokenAccount, TokenInterface},
 };
-use mpl_token_metadata::{
-    instructions::TransferCpiBuilder,
-    types::{AuthorizationData, Payload, PayloadType, SeedsVec, TransferArgs},
-};
-use std::{collections::HashMap, convert::TryFrom};
+use mpl_token_metadata::{instructions::TransferCpiBuilder, types::TransferArgs};
+use std::convert::TryFrom;
 
 use crate::{
     ata::init_if_needed_ata,
+    auth_rules::{get_authorization_data, OPERATION_TRANSFER_OWNER},
     constants::*,
     errors::MMMErrorCode,
     index_ra,
     instructions::{
         check_remaining_accounts_for_m2, sol_fulfill_buy::SolFulfillBuyArgs, withdraw_m2,
     },
-    state::{Pool, SellState},
+    state::{Pool, SellState, PAUSE_FLAG_MIP1},
     util::{
-        assert_is_programmable, assert_valid_fees_bp, check_allowlists_for_mint,
-        get_buyside_seller_receives, get_lp_fee_bp, get_metadata_royalty_bp, get_sol_fee,
-        get_sol_lp_fee, get_sol_total_price_and_next_price, log_pool, pay_creator_fees_in_sol,
-        try_close_escrow, try_close_pool, try_close_sell_state,
+        apply_trait_premium, assert_is_programmable, assert_not_paused, assert_valid_fees_bp,
+        check_allowlists_for_mint, get_buyside_seller_receives, get_fee_schedule,
+        get_global_config, get_lp_fee_bp, get_metadata_royalty_bp,
+        get_print_edition_royalty_metadata, get_protocol_fee, get_referral_registry,
+        get_royalty_schedule, get_sol_fee, get_sol_lp_fee, get_sol_total_price_and_next_price,
+        get_verified_collection, log_pool, pay_creator_fees_in_sol, pay_protocol_fee,
+        pay_referral_fee, try_close_escrow, try_close_pool, try_close_sell_state, FulfillResult,
+        RoyaltyPayment,
     },
     verify_referral::verify_referral,
 };
@@ -43,10 +45,25 @@ pub struct SolMip1FulfillBuy<'info> {
     pub cosigner: Signer<'info>,
     #[account(
         mut,
-        constraint = verify_referral(&pool, &referral) @ MMMErrorCode::InvalidReferral,
+        constraint = verify_referral(&pool, &referral, &global_config) @ MMMErrorCode::InvalidReferral,
     )]
     /// CHECK: use verify_referral to check the referral account
     pub referral: UncheckedAccount<'info>,
+    /// CHECK: it might not be initialized, loaded with get_global_config
+    #[account(seeds = [GLOBAL_CONFIG_PREFIX.as_bytes()], bump)]
+    pub global_config: UncheckedAccount<'info>,
+    /// CHECK: checked against the treasury of global_config in pay_protocol_fee
+    #[account(mut)]
+    pub treasury: UncheckedAccount<'info>,
+    /// CHECK: it might not be initialized, loaded with get_fee_schedule
+    #[account(seeds = [FEE_SCHEDULE_PREFIX.as_bytes(), pool.referral.as_ref()], bump)]
+    pub fee_schedule: UncheckedAccount<'info>,
+    /// CHECK: the royalty schedule pda of the collection, checked in
+    /// get_royalty_schedule, it might not be initialized
+    pub royalty_schedule: UncheckedAccount<'info>,
+    /// CHECK: it might not be initialized, loaded with get_referral_registry
+    #[account(seeds = [REFERRAL_REGISTRY_PREFIX.as_bytes(), pool.referral.as_ref()], bump)]
+    pub referral_registry: UncheckedAccount<'info>,
     #[account(
         mut,
         seeds = [POOL_PREFIX.as_bytes(), owner.key().as_ref(), pool.uuid.as_ref()],
@@ -157,6 +174,10 @@ pub fn handler<'info>(
     let token_program = &ctx.accounts.token_program;
     let system_program = &ctx.accounts.system_program;
     let pool = &mut ctx.accounts.pool;
+    let global_config = get_global_config(&ctx.accounts.global_config)?;
+    assert_not_paused(&global_config, PAUSE_FLAG_MIP1)?;
+    let fee_schedule =
+        get_fee_schedule(&pool.referral, &ctx.accounts.fee_schedule, &global_config)?;
     let sell_state = &mut ctx.accounts.sell_state;
     let owner = &ctx.accounts.owner;
     let owner_key = &ctx.accounts.owner.key();
@@ -202,17 +223,43 @@ pub fn handler<'info>(
         args.allowlist_aux,
     )?;
     assert_is_programmable(&parsed_metadata)?;
+    let parsed_metadata = get_print_edition_royalty_metadata(
+        parsed_metadata,
+        asset_master_edition,
+        remaining_accounts,
+    )?;
 
     let (total_price, next_price) =
         get_sol_total_price_and_next_price(pool, args.asset_amount, true)?;
-    let metadata_royalty_bp = get_metadata_royalty_bp(total_price, &parsed_metadata, None);
+    let total_price =
+        apply_trait_premium(pool, &asset_mint.key(), total_price, &args.trait_premium)?;
+    let royalty_schedule = get_royalty_schedule(
+        get_verified_collection(&parsed_metadata),
+        &ctx.accounts.royalty_schedule,
+    )?;
+    let metadata_royalty_bp = get_metadata_royalty_bp(
+        total_price,
+        &parsed_metadata,
+        None,
+        royalty_schedule.as_ref(),
+    );
     let seller_receives = {
-        let lp_fee_bp = get_lp_fee_bp(pool, buyside_sol_escrow_account.lamports());
+        let lp_fee_bp = get_lp_fee_bp(pool, buyside_sol_escrow_account.lamports(), true)?;
         get_buyside_seller_receives(total_price, lp_fee_bp, metadata_royalty_bp, 10000)
     }?;
-    let lp_fee = get_sol_lp_fee(pool, buyside_sol_escrow_account.lamports(), seller_receives)?;
+    let lp_fee = get_sol_lp_fee(
+        pool,
+        buyside_sol_escrow_account.lamports(),
+        seller_receives,
+        true,
+    )?;
 
-    assert_valid_fees_bp(args.maker_fee_bp, args.taker_fee_bp)?;
+    assert_valid_fees_bp(
+        args.maker_fee_bp,
+        args.taker_fee_bp,
+        &global_config,
+        &fee_schedule,
+    )?;
     let maker_fee = get_sol_fee(seller_receives, args.maker_fee_bp)?;
     let taker_fee = get_sol_fee(seller_receives, args.taker_fee_bp)?;
     let referral_fee = u64::try_from(
@@ -221,12 +268,15 @@ pub fn handler<'info>(
             .ok_or(MMMErrorCode::NumericOverflow)?,
     )
     .map_err(|_| MMMErrorCode::NumericOverflow)?;
+    let protocol_fee = get_protocol_fee(&global_config, pool, seller_receives)?;
 
     // check creator_accounts and verify the remaining accounts
     let creator_accounts = if pool.using_shared_escrow() {
-        check_remaining_accounts_for_m2(remaining_accounts, &pool.owner.key())?;
+        check_remaining_accounts_for_m2(remaining_accounts, &pool.owner.key(), &global_config)?;
 
-        let amount: u64 = (total_price as i64 + maker_fee) as u64;
+        // compounded lp fees stay in the shared escrow
+        let compounded_lp_fee = if pool.compound_lp_fee { lp_fee } else { 0 };
+        let amount: u64 = (total_price as i64 + maker_fee - compounded_lp_fee as i64) as u64;
         withdraw_m2(
             pool,
             ctx.bumps.pool,
@@ -236,6 +286,7 @@ pub fn handler<'info>(
             index_ra!(remaining_accounts, 0),
             pool.owner,
             amount,
+            &global_config,
         )?;
         pool.shared_escrow_count = pool
             .shared_escrow_count
@@ -248,17 +299,18 @@ pub fn handler<'info>(
     };
 
     // transfer to token account owned by pool
-    let payload = Payload {
-        map: HashMap::from([(
-            "DestinationSeeds".to_owned(),
-            PayloadType::Seeds(SeedsVec {
-                seeds: pool_seeds[0][0..3].iter().map(|v| v.to_vec()).collect(),
-            }),
-        )]),
-    };
+    let authorization_data = get_authorization_data(
+        &parsed_metadata,
+        authorization_rules,
+        OPERATION_TRANSFER_OWNER,
+        &["DestinationSeeds"],
+        pool_seeds[0][0..3].iter().map(|v| v.to_vec()).collect(),
+        args.asset_amount,
+        args.authorization_data.clone(),
+    )?;
 
     let transfer_args = TransferArgs::V1 {
-        authorization_data: Some(AuthorizationData { payload }),
+        authorization_data,
         amount: args.asset_amount,
     };
 
@@ -314,17 +366,18 @@ pub fn handler<'info>(
             rent.to_account_info(),
         )?;
 
-        let payload = Payload {
-            map: HashMap::from([(
-                "SourceSeeds".to_owned(),
-                PayloadType::Seeds(SeedsVec {
-                    seeds: pool_seeds[0][0..3].iter().map(|v| v.to_vec()).collect(),
-                }),
-            )]),
-        };
+        let authorization_data = get_authorization_data(
+            &parsed_metadata,
+            authorization_rules,
+            OPERATION_TRANSFER_OWNER,
+            &["SourceSeeds", "AuthoritySeeds"],
+            pool_seeds[0][0..3].iter().map(|v| v.to_vec()).collect(),
+            args.asset_amount,
+            args.authorization_data,
+        )?;
 
         let transfer_args = TransferArgs::V1 {
-            authorization_data: Some(AuthorizationData { payload }),
+            authorization_data,
             amount: args.asset_amount,
         };
 
@@ -375,7 +428,10 @@ pub fn handler<'info>(
     }
 
     // pool owner as buyer is going to pay the royalties
-    let royalty_paid = pay_creator_fees_in_sol(
+    let RoyaltyPayment {
+        total: royalty_paid,
+        accrued: royalty_accrued,
+    } = pay_creator_fees_in_sol(
         10000,
         seller_receives,
         &parsed_metadata,
@@ -384,6 +440,7 @@ pub fn handler<'info>(
         metadata_royalty_bp,
         buyside_sol_escrow_account_seeds,
         system_program.to_account_info(),
+        &global_config,
     )?;
 
     // prevent frontrun by pool config changes
@@ -395,6 +452,8 @@ pub fn handler<'info>(
         .checked_sub(taker_fee as u64)
         .ok_or(MMMErrorCode::NumericOverflow)?
         .checked_sub(royalty_paid)
+        .ok_or(MMMErrorCode::NumericOverflow)?
+        .checked_sub(protocol_fee)
         .ok_or(MMMErrorCode::NumericOverflow)?;
     if payment_amount < args.min_payment_amount {
         return Err(MMMErrorCode::InvalidRequestedPrice.into());
@@ -414,7 +473,8 @@ pub fn handler<'info>(
         buyside_sol_escrow_account_seeds,
     )?;
 
-    if lp_fee > 0 {
+    // compounded lp fees stay in the buyside escrow
+    if lp_fee > 0 && !pool.compound_lp_fee {
         anchor_lang::solana_program::program::invoke_signed(
             &anchor_lang::solana_program::system_instruction::transfer(
                 buyside_sol_escrow_account.key,
@@ -430,26 +490,36 @@ pub fn handler<'info>(
             buyside_sol_escrow_account_seeds,
         )?;
     }
-    if referral_fee > 0 {
-        anchor_lang::solana_program::program::invoke_signed(
-            &anchor_lang::solana_program::system_instruction::transfer(
-                buyside_sol_escrow_account.key,
-                referral.key,
-                referral_fee,
-            ),
-            &[
-                buyside_sol_escrow_account.to_account_info(),
-                referral.to_account_info(),
-                system_program.to_account_info(),
-            ],
-            buyside_sol_escrow_account_seeds,
-        )?;
-    }
+    let referral_registry = get_referral_registry(&ctx.accounts.referral_registry)?;
+    pay_referral_fee(
+        &buyside_sol_escrow_account.to_account_info(),
+        &referral.to_account_info(),
+        referral_registry.as_ref(),
+        ctx.remaining_accounts,
+        &system_program.to_account_info(),
+        buyside_sol_escrow_account_seeds,
+        referral_fee,
+    )?;
+
+    pay_protocol_fee(
+        &buyside_sol_escrow_account.to_account_info(),
+        &ctx.accounts.treasury,
+        &global_config,
+        &system_program.to_account_info(),
+        buyside_sol_escrow_account_seeds,
+        protocol_fee,
+    )?;
 
     pool.lp_fee_earned = pool
         .lp_fee_earned
         .checked_add(lp_fee)
         .ok_or(MMMErrorCode::NumericOverflow)?;
+    if pool.compound_lp_fee {
+        pool.lp_fee_compounded = pool
+            .lp_fee_compounded
+            .checked_add(lp_fee)
+            .ok_or(MMMErrorCode::NumericOverflow)?;
+    }
     pool.spot_price = next_price;
 
     try_close_escrow(
@@ -457,6 +527,7 @@ pub fn handler<'info>(
         pool,
         system_program,
         buyside_sol_escrow_account_seeds,
+        &global_config,
     )?;
     try_close_sell_state(sell_state, payer.to_account_info())?;
 
@@ -486,6 +557,7 @@ pub fn handler<'info>(
                 pool,
                 system_program,
                 buyside_sol_escrow_account_seeds,
+                &global_config,
             )?;
         }
     }
@@ -495,11 +567,27 @@ pub fn handler<'info>(
     try_close_pool(pool, owner.to_account_info())?;
 
     msg!(
-        "{{\"lp_fee\":{},\"royalty_paid\":{},\"total_price\":{}}}",
+        "{{\"lp_fee\":{},\"protocol_fee\":{},\"royalty_paid\":{},\"total_price\":{}}}",
         lp_fee,
+        protocol_fee,
         royalty_paid,
         total_price,
     );
 
-    Ok(())
+    FulfillResult {
+        total_price,
+        lp_fee,
+        maker_fee,
+        taker_fee,
+        protocol_fee,
+        royalty_paid,
+        royalty_accrued,
+        payment_amount,
+        next_price: pool.spot_price,
+        sellside_asset_amount: pool.sellside_asset_amount,
+        transfer_fee_withheld: 0,
+        trade_in_total_price: 0,
+        trade_in_payment_received: 0,
+    }
+    .set_return_data()
 }
@@ -1,22 +1,27 @@
 use anchor_lang::{prelude::*, solana_program::sysvar, AnchorDeserialize, AnchorSerialize};
 use anchor_spl::{
-    associated_token::AssociatedToken,
+    associated_token::{get_associated_token_address_with_program_id, AssociatedToken},
     token_interface::{Mint, TokenAccount, TokenInterface},
 };
-use mpl_token_metadata::{
-    instructions::TransferCpiBuilder,
-    types::{AuthorizationData, Payload, PayloadType, SeedsVec, TransferArgs},
-};
-use std::{collections::HashMap, convert::TryFrom};
+use mpl_token_metadata::{instructions::TransferCpiBuilder, types::TransferArgs};
+use std::convert::TryFrom;
 
 use crate::{
+    auth_rules::{
+        get_authorization_data, AuthorizationDataLocal, OPERATION_TRANSFER_DELEGATE,
+        OPERATION_TRANSFER_OWNER,
+    },
     constants::*,
     errors::MMMErrorCode,
-    state::{Pool, SellState},
+    state::{Pool, SellState, PAUSE_FLAG_MIP1},
     util::{
-        assert_is_programmable, assert_valid_fees_bp, check_allowlists_for_mint,
-        get_metadata_royalty_bp, get_sol_fee, get_sol_lp_fee, get_sol_total_price_and_next_price,
-        log_pool, pay_creator_fees_in_sol, try_close_pool, try_close_sell_state,
+        assert_is_programmable, assert_not_paused, assert_valid_fees_bp, check_allowlists_for_mint,
+        get_fee_schedule, get_global_config, get_metadata_royalty_bp,
+        get_print_edition_royalty_metadata, get_protocol_fee, get_referral_registry,
+        get_royalty_schedule, get_sell_state_total_price_and_next_price, get_sol_fee,
+        get_sol_lp_fee, get_verified_collection, is_listing_available, log_pool,
+        pay_creator_fees_in_sol, pay_protocol_fee, pay_referral_fee, should_compound_lp_fee,
+        try_close_pool, try_close_sell_state, FulfillResult, RoyaltyPayment,
     },
     verify_referral::verify_referral,
 };
@@ -26,8 +31,9 @@ pub struct SolMip1FulfillSellArgs {
     pub asset_amount: u64,
     pub max_payment_amount: u64,
     pub allowlist_aux: Option<String>, // TODO: use it for future allowlist_aux
-    pub maker_fee_bp: i16,             // will be checked by cosigner
-    pub taker_fee_bp: i16,             // will be checked by cosigner
+    pub maker_fee_bp: i16,             // set by cosigner, capped by the FeeSchedule
+    pub taker_fee_bp: i16,             // set by cosigner, capped by the FeeSchedule
+    pub authorization_data: Option<AuthorizationDataLocal>,
 }
 
 // FulfillSell means a buyer wants to buy NFT/SFT from the pool
@@ -46,10 +52,25 @@ pub struct SolMip1FulfillSell<'info> {
     pub cosigner: Signer<'info>,
     #[account(
         mut,
-        constraint = verify_referral(&pool, &referral) @ MMMErrorCode::InvalidReferral,
+        constraint = verify_referral(&pool, &referral, &global_config) @ MMMErrorCode::InvalidReferral,
     )]
     /// CHECK: use verify_referral to check the referral account
     pub referral: UncheckedAccount<'info>,
+    /// CHECK: it might not be initialized, loaded with get_global_config
+    #[account(seeds = [GLOBAL_CONFIG_PREFIX.as_bytes()], bump)]
+    pub global_config: UncheckedAccount<'info>,
+    /// CHECK: checked against the treasury of global_config in pay_protocol_fee
+    #[account(mut)]
+    pub treasury: UncheckedAccount<'info>,
+    /// CHECK: it might not be initialized, loaded with get_fee_schedule
+    #[account(seeds = [FEE_SCHEDULE_PREFIX.as_bytes(), pool.referral.as_ref()], bump)]
+    pub fee_schedule: UncheckedAccount<'info>,
+    /// CHECK: the royalty schedule pda of the collection, checked in
+    /// get_royalty_schedule, it might not be initialized
+    pub royalty_schedule: UncheckedAccount<'info>,
+    /// CHECK: it might not be initialized, loaded with get_referral_registry
+    #[account(seeds = [REFERRAL_REGISTRY_PREFIX.as_bytes(), pool.referral.as_ref()], bump)]
+    pub referral_registry: UncheckedAccount<'info>,
     #[account(
         mut,
         seeds = [POOL_PREFIX.as_bytes(), owner.key().as_ref(), pool.uuid.as_ref()],
@@ -85,15 +106,13 @@ pub struct SolMip1FulfillSell<'info> {
     pub asset_mint: InterfaceAccount<'info, Mint>,
     /// CHECK: will be checked in cpi
     pub asset_master_edition: UncheckedAccount<'info>,
+    /// CHECK: the pool's escrow token account, or the owner's token account for
+    /// non-custodial listings, checked in the handler
     #[account(
         mut,
-        associated_token::mint = asset_mint,
-        associated_token::authority = pool,
-        associated_token::token_program = token_program,
-        constraint = sellside_escrow_token_account.amount == 1 @ MMMErrorCode::InvalidMip1AssetParams,
         constraint = args.asset_amount == 1 @ MMMErrorCode::InvalidMip1AssetParams,
     )]
-    pub sellside_escrow_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub sellside_escrow_token_account: UncheckedAccount<'info>,
     #[account(
         init_if_needed,
         associated_token::mint = asset_mint,
@@ -147,6 +166,10 @@ pub fn handler<'info>(
     let owner = &ctx.accounts.owner;
     let referral = &ctx.accounts.referral;
     let pool = &mut ctx.accounts.pool;
+    let global_config = get_global_config(&ctx.accounts.global_config)?;
+    assert_not_paused(&global_config, PAUSE_FLAG_MIP1)?;
+    let fee_schedule =
+        get_fee_schedule(&pool.referral, &ctx.accounts.fee_schedule, &global_config)?;
     let sell_state = &mut ctx.accounts.sell_state;
 
     let payer = &ctx.accounts.payer;
@@ -171,6 +194,39 @@ pub fn handler<'info>(
         &[ctx.bumps.pool],
     ]];
 
+    // for non-custodial listings, the pool transfers the asset from the owner's
+    // wallet as the transfer delegate
+    let is_listing = sell_state.is_listing();
+    if is_listing {
+        if sellside_escrow_token_account.key() != sell_state.listing_token_account {
+            return Err(MMMErrorCode::InvalidAccountState.into());
+        }
+        // the owner moved the asset out of the listed token account, the owner
+        // can release the listing with mip1_delist_sell
+        if !is_listing_available(
+            &sellside_escrow_token_account.to_account_info(),
+            pool,
+            token_program.key,
+        ) {
+            return Err(MMMErrorCode::ListingUnavailable.into());
+        }
+    } else if sellside_escrow_token_account.key()
+        != get_associated_token_address_with_program_id(
+            &pool.key(),
+            &asset_mint.key(),
+            token_program.key,
+        )
+        || sellside_escrow_token_account.owner != token_program.key
+    {
+        return Err(MMMErrorCode::InvalidMip1AssetParams.into());
+    }
+    let sellside_escrow_amount =
+        TokenAccount::try_deserialize(&mut &sellside_escrow_token_account.data.borrow()[..])?
+            .amount;
+    if sellside_escrow_amount != 1 {
+        return Err(MMMErrorCode::InvalidMip1AssetParams.into());
+    }
+
     let parsed_metadata = check_allowlists_for_mint(
         &pool.allowlists,
         asset_mint,
@@ -179,12 +235,28 @@ pub fn handler<'info>(
         args.allowlist_aux,
     )?;
     assert_is_programmable(&parsed_metadata)?;
+    let parsed_metadata = get_print_edition_royalty_metadata(
+        parsed_metadata,
+        asset_master_edition,
+        ctx.remaining_accounts,
+    )?;
 
     let (total_price, next_price) =
-        get_sol_total_price_and_next_price(pool, args.asset_amount, false)?;
-    let lp_fee = get_sol_lp_fee(pool, buyside_sol_escrow_account.lamports(), total_price)?;
+        get_sell_state_total_price_and_next_price(pool, sell_state, args.asset_amount)?;
+    let spot_price_moved = next_price != pool.spot_price;
+    let lp_fee = get_sol_lp_fee(
+        pool,
+        buyside_sol_escrow_account.lamports(),
+        total_price,
+        false,
+    )?;
 
-    assert_valid_fees_bp(args.maker_fee_bp, args.taker_fee_bp)?;
+    assert_valid_fees_bp(
+        args.maker_fee_bp,
+        args.taker_fee_bp,
+        &global_config,
+        &fee_schedule,
+    )?;
     let maker_fee = get_sol_fee(total_price, args.maker_fee_bp)?;
     let taker_fee = get_sol_fee(total_price, args.taker_fee_bp)?;
     let referral_fee = u64::try_from(
@@ -219,17 +291,24 @@ pub fn handler<'info>(
         ],
     )?;
 
-    let payload = Payload {
-        map: HashMap::from([(
-            "SourceSeeds".to_owned(),
-            PayloadType::Seeds(SeedsVec {
-                seeds: pool_seeds[0][0..3].iter().map(|v| v.to_vec()).collect(),
-            }),
-        )]),
+    // the pool is the token owner of the escrow, or the delegate of the listing
+    let (operation, pool_seeds_keys): (&str, &[&str]) = if is_listing {
+        (OPERATION_TRANSFER_DELEGATE, &["AuthoritySeeds"])
+    } else {
+        (OPERATION_TRANSFER_OWNER, &["SourceSeeds", "AuthoritySeeds"])
     };
+    let authorization_data = get_authorization_data(
+        &parsed_metadata,
+        authorization_rules,
+        operation,
+        pool_seeds_keys,
+        pool_seeds[0][0..3].iter().map(|v| v.to_vec()).collect(),
+        args.asset_amount,
+        args.authorization_data,
+    )?;
 
     let transfer_args = TransferArgs::V1 {
-        authorization_data: Some(AuthorizationData { payload }),
+        authorization_data,
         amount: args.asset_amount,
     };
 
@@ -237,7 +316,11 @@ pub fn handler<'info>(
 
     transfer_cpi
         .token(&sellside_escrow_token_account.to_account_info())
-        .token_owner(&pool.to_account_info())
+        .token_owner(&if is_listing {
+            owner.to_account_info()
+        } else {
+            pool.to_account_info()
+        })
         .destination_token(&payer_asset_account.to_account_info())
         .destination_owner(&payer.to_account_info())
         .mint(&asset_mint.to_account_info())
@@ -256,7 +339,7 @@ pub fn handler<'info>(
         .transfer_args(transfer_args)
         .invoke_signed(pool_seeds)?;
 
-    if sellside_escrow_token_account.amount == args.asset_amount {
+    if !is_listing && sellside_escrow_amount == args.asset_amount {
         anchor_spl::token_2022::close_account(CpiContext::new_with_signer(
             token_program.to_account_info(),
             anchor_spl::token_2022::CloseAccount {
@@ -268,34 +351,49 @@ pub fn handler<'info>(
         ))?;
     }
 
+    // compounded lp fees go to the buyside escrow instead of the owner
+    let compound_lp_fee = should_compound_lp_fee(pool, buyside_sol_escrow_account, lp_fee)?;
+    let lp_fee_to = if compound_lp_fee {
+        buyside_sol_escrow_account.to_account_info()
+    } else {
+        owner.to_account_info()
+    };
     if lp_fee > 0 {
-        anchor_lang::solana_program::program::invoke(
-            &anchor_lang::solana_program::system_instruction::transfer(
-                payer.key, owner.key, lp_fee,
-            ),
-            &[
-                payer.to_account_info(),
-                owner.to_account_info(),
-                system_program.to_account_info(),
-            ],
-        )?;
-    }
-
-    if referral_fee > 0 {
         anchor_lang::solana_program::program::invoke(
             &anchor_lang::solana_program::system_instruction::transfer(
                 payer.key,
-                referral.key,
-                referral_fee,
+                lp_fee_to.key,
+                lp_fee,
             ),
             &[
                 payer.to_account_info(),
-                referral.to_account_info(),
+                lp_fee_to,
                 system_program.to_account_info(),
             ],
         )?;
     }
 
+    let referral_registry = get_referral_registry(&ctx.accounts.referral_registry)?;
+    pay_referral_fee(
+        &payer.to_account_info(),
+        &referral.to_account_info(),
+        referral_registry.as_ref(),
+        ctx.remaining_accounts,
+        &system_program.to_account_info(),
+        &[&[&[]]],
+        referral_fee,
+    )?;
+
+    let protocol_fee = get_protocol_fee(&global_config, pool, total_price)?;
+    pay_protocol_fee(
+        payer,
+        &ctx.accounts.treasury,
+        &global_config,
+        system_program,
+        &[&[&[]]],
+        protocol_fee,
+    )?;
+
     pool.spot_price = next_price;
     pool.sellside_asset_amount = pool
         .sellside_asset_amount
@@ -305,9 +403,27 @@ pub fn handler<'info>(
         .lp_fee_earned
         .checked_add(lp_fee)
         .ok_or(MMMErrorCode::NumericOverflow)?;
+    if compound_lp_fee {
+        pool.lp_fee_compounded = pool
+            .lp_fee_compounded
+            .checked_add(lp_fee)
+            .ok_or(MMMErrorCode::NumericOverflow)?;
+    }
 
-    let metadata_royalty_bp = get_metadata_royalty_bp(total_price, &parsed_metadata, None);
-    let royalty_paid = pay_creator_fees_in_sol(
+    let royalty_schedule = get_royalty_schedule(
+        get_verified_collection(&parsed_metadata),
+        &ctx.accounts.royalty_schedule,
+    )?;
+    let metadata_royalty_bp = get_metadata_royalty_bp(
+        total_price,
+        &parsed_metadata,
+        None,
+        royalty_schedule.as_ref(),
+    );
+    let RoyaltyPayment {
+        total: royalty_paid,
+        accrued: royalty_accrued,
+    } = pay_creator_fees_in_sol(
         10000,
         total_price,
         &parsed_metadata,
@@ -316,6 +432,7 @@ pub fn handler<'info>(
         metadata_royalty_bp,
         &[&[&[]]],
         system_program.to_account_info(),
+        &global_config,
     )?;
 
     // prevent frontrun by pool config changes
@@ -325,6 +442,8 @@ pub fn handler<'info>(
         .checked_add(taker_fee as u64)
         .ok_or(MMMErrorCode::NumericOverflow)?
         .checked_add(royalty_paid)
+        .ok_or(MMMErrorCode::NumericOverflow)?
+        .checked_add(protocol_fee)
         .ok_or(MMMErrorCode::NumericOverflow)?;
     if payment_amount > args.max_payment_amount {
         return Err(MMMErrorCode::InvalidRequestedPrice.into());
@@ -341,11 +460,28 @@ pub fn handler<'info>(
     try_close_pool(pool, owner.to_account_info())?;
 
     msg!(
-        "{{\"lp_fee\":{},\"royalty_paid\":{},\"total_price\":{}}}",
+        "{{\"lp_fee\":{},\"protocol_fee\":{},\"royalty_paid\":{},\"total_price\":{},\"spot_price_moved\":{}}}",
         lp_fee,
+        protocol_fee,
         royalty_paid,
         total_price,
+        spot_price_moved,
     );
 
-    Ok(())
+    FulfillResult {
+        total_price,
+        lp_fee,
+        maker_fee,
+        taker_fee,
+        protocol_fee,
+        royalty_paid,
+        royalty_accrued,
+        payment_amount,
+        next_price: pool.spot_price,
+        sellside_asset_amount: pool.sellside_asset_amount,
+        transfer_fee_withheld: 0,
+        trade_in_total_price: 0,
+        trade_in_payment_received: 0,
+    }
+    .set_return_data()
 }
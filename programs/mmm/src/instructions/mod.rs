@@ -6,6 +6,8 @@ pub mod ext_vanilla;
 pub mod mip1;
 pub mod mpl_core_asset;
 pub mod ocp;
+pub mod referral;
+pub mod royalty;
 pub mod vanilla;
 
 pub use admin::*;
@@ -14,6 +16,8 @@ pub use ext_vanilla::*;
 pub use mip1::*;
 pub use mpl_core_asset::*;
 pub use ocp::*;
+pub use referral::*;
+pub use royalty::*;
 pub use vanilla::*;
 
 use anchor_lang::{prelude::*, AnchorDeserialize, AnchorSerialize};
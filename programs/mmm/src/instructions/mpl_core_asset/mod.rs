@@ -1,12 +1,16 @@
 #![allow(missing_docs)]
 
 pub mod mpl_core_deposit_sell;
+pub mod mpl_core_list_sell;
+pub mod mpl_core_release_stale_listing;
 pub mod mpl_core_withdraw_sell;
 pub mod mpl_core_wrap;
 pub mod sol_mpl_core_fulfill_buy;
 pub mod sol_mpl_core_fulfill_sell;
 
 pub use mpl_core_deposit_sell::*;
+pub use mpl_core_list_sell::*;
+pub use mpl_core_release_stale_listing::*;
 pub use mpl_core_withdraw_sell::*;
 pub use mpl_core_wrap::*;
 pub use sol_mpl_core_fulfill_buy::*;
@@ -7,8 +7,12 @@ use crate::{
     assert_valid_core_plugins,
     constants::*,
     errors::MMMErrorCode,
-    state::{Pool, SellState},
-    util::{check_allowlists_for_mpl_core, log_pool},
+    get_core_royalties,
+    state::{Pool, PriceOverride, SellState, PAUSE_FLAG_MPL_CORE},
+    util::{
+        assert_not_paused, check_allowlists_for_mpl_core, get_global_config, log_pool,
+        set_sell_state_price_override,
+    },
     AssetInterface, IndexableAsset,
 };
 
@@ -16,6 +20,7 @@ use crate::{
 pub struct MplCoreDepositSellArgs {
     pub allowlist_aux: Option<String>,
     pub compression_proof: Option<Vec<u8>>,
+    pub price_override: Option<PriceOverride>,
 }
 
 #[derive(Accounts)]
@@ -53,6 +58,9 @@ pub struct MplCoreDepositSell<'info> {
     /// CHECK: check collection later
     collection: UncheckedAccount<'info>,
 
+    /// CHECK: it might not be initialized, loaded with get_global_config
+    #[account(seeds = [GLOBAL_CONFIG_PREFIX.as_bytes()], bump)]
+    pub global_config: UncheckedAccount<'info>,
     pub system_program: Program<'info, System>,
     pub asset_program: Interface<'info, AssetInterface>,
 }
@@ -61,14 +69,21 @@ pub fn handler(ctx: Context<MplCoreDepositSell>, args: MplCoreDepositSellArgs) -
     let owner = &ctx.accounts.owner;
     let asset = &ctx.accounts.asset;
     let pool = &mut ctx.accounts.pool;
+    let global_config = get_global_config(&ctx.accounts.global_config)?;
+    assert_not_paused(&global_config, PAUSE_FLAG_MPL_CORE)?;
     let sell_state = &mut ctx.accounts.sell_state;
     let collection = &ctx.accounts.collection;
 
     if pool.using_shared_escrow() {
         return Err(MMMErrorCode::InvalidAccountState.into());
     }
+    // the asset can't be both deposited and listed
+    if sell_state.is_listing() {
+        return Err(MMMErrorCode::InvalidAccountState.into());
+    }
 
     assert_valid_core_plugins(asset)?;
+    get_core_royalties(asset, &ctx.accounts.collection)?;
     let _ = check_allowlists_for_mpl_core(&pool.allowlists, asset, args.allowlist_aux)?;
 
     let transfer_asset_builder = TransferV1Builder::new()
@@ -111,6 +126,9 @@ pub fn handler(ctx: Context<MplCoreDepositSell>, args: MplCoreDepositSellArgs) -
         .asset_amount
         .checked_add(1)
         .ok_or(MMMErrorCode::NumericOverflow)?;
+    if let Some(price_override) = args.price_override {
+        set_sell_state_price_override(sell_state, price_override)?;
+    }
     log_pool("post_mpl_core_deposit_sell", pool)?;
 
     Ok(())
@@ -0,0 +1,162 @@
+use anchor_lang::prelude::*;
+use mpl_core::{
+    instructions::{AddPluginV1CpiBuilder, ApprovePluginAuthorityV1CpiBuilder},
+    types::{FreezeDelegate, Plugin, PluginAuthority, PluginType, TransferDelegate},
+};
+
+use crate::{
+    assert_valid_core_plugins,
+    constants::*,
+    errors::MMMErrorCode,
+    get_core_collection_account, get_core_royalties,
+    instructions::MplCoreDepositSellArgs,
+    set_core_listing_frozen,
+    state::{Pool, SellState, PAUSE_FLAG_MPL_CORE},
+    util::{
+        assert_not_paused, check_allowlists_for_mpl_core, get_global_config, log_pool,
+        set_sell_state_price_override,
+    },
+    AssetInterface, IndexableAsset, CORE_LISTING_PLUGINS,
+};
+
+// MplCoreListSell is the non-custodial version of MplCoreDepositSell, the asset
+// stays in the owner's wallet frozen, and the pool becomes its transfer and
+// freeze delegate, so that sol_mpl_core_fulfill_sell can thaw and transfer it
+// straight to the buyer.
+#[derive(Accounts)]
+#[instruction(args:MplCoreDepositSellArgs)]
+pub struct MplCoreListSell<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(constraint = owner.key() != cosigner.key() @ MMMErrorCode::InvalidCosigner)]
+    pub cosigner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [POOL_PREFIX.as_bytes(), owner.key().as_ref(), pool.uuid.as_ref()],
+        has_one = owner @ MMMErrorCode::InvalidOwner,
+        has_one = cosigner @ MMMErrorCode::InvalidCosigner,
+        bump
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+    #[account(
+        mut,
+        constraint = asset.to_account_info().owner == asset_program.key,
+        constraint = asset.owner == Some(owner.key()) @ MMMErrorCode::InvalidOwner,
+    )]
+    pub asset: Box<Account<'info, IndexableAsset>>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        seeds = [
+            SELL_STATE_PREFIX.as_bytes(),
+            pool.key().as_ref(),
+            asset.key().as_ref(),
+        ],
+        space = SellState::LEN,
+        bump
+    )]
+    pub sell_state: Account<'info, SellState>,
+    /// CHECK: check collection later
+    collection: UncheckedAccount<'info>,
+
+    /// CHECK: it might not be initialized, loaded with get_global_config
+    #[account(seeds = [GLOBAL_CONFIG_PREFIX.as_bytes()], bump)]
+    pub global_config: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub asset_program: Interface<'info, AssetInterface>,
+}
+
+pub fn handler(ctx: Context<MplCoreListSell>, args: MplCoreDepositSellArgs) -> Result<()> {
+    let owner = &ctx.accounts.owner;
+    let asset = &ctx.accounts.asset;
+    let pool = &mut ctx.accounts.pool;
+    let global_config = get_global_config(&ctx.accounts.global_config)?;
+    assert_not_paused(&global_config, PAUSE_FLAG_MPL_CORE)?;
+    let sell_state = &mut ctx.accounts.sell_state;
+    let system_program = &ctx.accounts.system_program;
+    let asset_program = &ctx.accounts.asset_program.to_account_info();
+
+    if pool.using_shared_escrow() {
+        return Err(MMMErrorCode::InvalidAccountState.into());
+    }
+    // the asset can't be both deposited and listed
+    if sell_state.asset_amount != 0 {
+        return Err(MMMErrorCode::InvalidAccountState.into());
+    }
+
+    assert_valid_core_plugins(asset)?;
+    get_core_royalties(asset, &ctx.accounts.collection)?;
+    check_allowlists_for_mpl_core(&pool.allowlists, asset, args.allowlist_aux)?;
+    let collection = get_core_collection_account(asset, &ctx.accounts.collection)?;
+
+    let pool_seeds: &[&[&[u8]]] = &[&[
+        POOL_PREFIX.as_bytes(),
+        pool.owner.as_ref(),
+        pool.uuid.as_ref(),
+        &[ctx.bumps.pool],
+    ]];
+    let pool_authority = PluginAuthority::Address {
+        address: pool.key(),
+    };
+
+    // owner managed plugins that already exist are approved to the pool,
+    // otherwise they are added with the pool as the initial authority
+    for plugin_type in CORE_LISTING_PLUGINS.iter() {
+        if asset.plugins.contains_key(plugin_type) {
+            ApprovePluginAuthorityV1CpiBuilder::new(asset_program)
+                .asset(&asset.to_account_info())
+                .collection(collection.as_ref())
+                .payer(&owner.to_account_info())
+                .authority(Some(&owner.to_account_info()))
+                .system_program(&system_program.to_account_info())
+                .plugin_type(plugin_type.clone())
+                .new_authority(pool_authority.clone())
+                .invoke()?;
+            if plugin_type == &PluginType::FreezeDelegate {
+                set_core_listing_frozen(
+                    asset_program,
+                    &asset.to_account_info(),
+                    collection.as_ref(),
+                    &owner.to_account_info(),
+                    &pool.to_account_info(),
+                    &system_program.to_account_info(),
+                    pool_seeds,
+                    true,
+                )?;
+            }
+        } else {
+            let plugin = if plugin_type == &PluginType::FreezeDelegate {
+                Plugin::FreezeDelegate(FreezeDelegate { frozen: true })
+            } else {
+                Plugin::TransferDelegate(TransferDelegate {})
+            };
+            AddPluginV1CpiBuilder::new(asset_program)
+                .asset(&asset.to_account_info())
+                .collection(collection.as_ref())
+                .payer(&owner.to_account_info())
+                .authority(Some(&owner.to_account_info()))
+                .system_program(&system_program.to_account_info())
+                .plugin(plugin)
+                .init_authority(pool_authority.clone())
+                .invoke()?;
+        }
+    }
+
+    pool.sellside_asset_amount = pool
+        .sellside_asset_amount
+        .checked_add(1)
+        .ok_or(MMMErrorCode::NumericOverflow)?;
+
+    sell_state.pool = pool.key();
+    sell_state.pool_owner = owner.key();
+    sell_state.asset_mint = asset.key();
+    sell_state.cosigner_annotation = pool.cosigner_annotation;
+    sell_state.asset_amount = 1;
+    sell_state.listing_token_account = asset.key();
+    if let Some(price_override) = args.price_override {
+        set_sell_state_price_override(sell_state, price_override)?;
+    }
+    log_pool("post_mpl_core_list_sell", pool)?;
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::*,
+    errors::MMMErrorCode,
+    is_core_listing_available,
+    state::{Pool, SellState},
+    util::{log_pool, release_sell_state_listing},
+    AssetInterface, IndexableAsset,
+};
+
+// MplCoreReleaseStaleListing lets anyone release a non-custodial listing whose
+// asset is no longer available, e.g. when the owner transferred it or revoked the
+// delegates of the pool. The sell_state rent goes back to the owner.
+#[derive(Accounts)]
+pub struct MplCoreReleaseStaleListing<'info> {
+    /// CHECK: has_one of the pool, receives the sell_state rent
+    #[account(mut)]
+    pub owner: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [POOL_PREFIX.as_bytes(), owner.key().as_ref(), pool.uuid.as_ref()],
+        has_one = owner @ MMMErrorCode::InvalidOwner,
+        bump
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+    #[account(constraint = asset.to_account_info().owner == asset_program.key)]
+    pub asset: Box<Account<'info, IndexableAsset>>,
+    #[account(
+        mut,
+        seeds = [
+            SELL_STATE_PREFIX.as_bytes(),
+            pool.key().as_ref(),
+            asset.key().as_ref(),
+        ],
+        bump
+    )]
+    pub sell_state: Account<'info, SellState>,
+    pub asset_program: Interface<'info, AssetInterface>,
+}
+
+pub fn handler(ctx: Context<MplCoreReleaseStaleListing>) -> Result<()> {
+    let owner = &ctx.accounts.owner;
+    let asset = &ctx.accounts.asset;
+    let pool = &mut ctx.accounts.pool;
+    let sell_state = &mut ctx.accounts.sell_state;
+
+    // available listings can only be removed by the owner with mpl_core_withdraw_sell
+    if is_core_listing_available(asset, &pool.key(), &pool.owner) {
+        return Err(MMMErrorCode::InvalidAccountState.into());
+    }
+
+    release_sell_state_listing(pool, sell_state, owner.to_account_info())?;
+    log_pool("post_mpl_core_release_stale_listing", pool)?;
+
+    Ok(())
+}
@@ -1,13 +1,17 @@
 use anchor_lang::prelude::*;
-use mpl_core::{instructions::TransferV1Builder, types::UpdateAuthority};
+use mpl_core::{
+    instructions::{RevokePluginAuthorityV1CpiBuilder, TransferV1Builder},
+    types::UpdateAuthority,
+};
 use solana_program::program::invoke_signed;
 
 use crate::{
     constants::*,
     errors::MMMErrorCode,
+    get_core_collection_account, is_core_listing_available, set_core_listing_frozen,
     state::{Pool, SellState},
-    util::{log_pool, try_close_pool, try_close_sell_state},
-    AssetInterface, IndexableAsset,
+    util::{log_pool, release_sell_state_listing, try_close_pool, try_close_sell_state},
+    AssetInterface, IndexableAsset, CORE_LISTING_PLUGINS,
 };
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
@@ -64,6 +68,49 @@ pub fn handler(ctx: Context<MplCoreWithdrawSell>, args: MplCoreWithdrawSellArgs)
     let pool = &mut ctx.accounts.pool;
     let sell_state = &mut ctx.accounts.sell_state;
     let collection = &ctx.accounts.collection;
+    let system_program = &ctx.accounts.system_program;
+
+    let pool_seeds: &[&[&[u8]]] = &[&[
+        POOL_PREFIX.as_bytes(),
+        pool.owner.as_ref(),
+        pool.uuid.as_ref(),
+        &[ctx.bumps.pool],
+    ]];
+
+    // non-custodial listings are withdrawn by thawing the asset and revoking
+    // the delegates of the pool, the asset never left the owner's wallet
+    if sell_state.is_listing() {
+        if is_core_listing_available(asset, &pool.key(), &pool.owner) {
+            let asset_program = &ctx.accounts.asset_program.to_account_info();
+            let collection = get_core_collection_account(asset, collection)?;
+            set_core_listing_frozen(
+                asset_program,
+                &asset.to_account_info(),
+                collection.as_ref(),
+                &owner.to_account_info(),
+                &pool.to_account_info(),
+                &system_program.to_account_info(),
+                pool_seeds,
+                false,
+            )?;
+            for plugin_type in CORE_LISTING_PLUGINS.iter() {
+                RevokePluginAuthorityV1CpiBuilder::new(asset_program)
+                    .asset(&asset.to_account_info())
+                    .collection(collection.as_ref())
+                    .payer(&owner.to_account_info())
+                    .authority(Some(&owner.to_account_info()))
+                    .system_program(&system_program.to_account_info())
+                    .plugin_type(plugin_type.clone())
+                    .invoke()?;
+            }
+        }
+
+        release_sell_state_listing(pool, sell_state, owner.to_account_info())?;
+        pool.buyside_payment_amount = buyside_sol_escrow_account.lamports();
+        log_pool("post_mpl_core_withdraw_sell", pool)?;
+        try_close_pool(pool, owner.to_account_info())?;
+        return Ok(());
+    }
 
     let transfer_asset_builder = TransferV1Builder::new()
         .asset(asset.key())
@@ -91,13 +138,6 @@ pub fn handler(ctx: Context<MplCoreWithdrawSell>, args: MplCoreWithdrawSellArgs)
         account_infos.push(collection.to_account_info());
     }
 
-    let pool_seeds: &[&[&[u8]]] = &[&[
-        POOL_PREFIX.as_bytes(),
-        pool.owner.as_ref(),
-        pool.uuid.as_ref(),
-        &[ctx.bumps.pool],
-    ]];
-
     invoke_signed(
         &transfer_asset_builder,
         account_infos.as_slice(),
@@ -1,10 +1,15 @@
-use anchor_lang::{accounts::unchecked_account::UncheckedAccount, error::Error};
+use anchor_lang::{
+    accounts::unchecked_account::UncheckedAccount, error::Error, Key, ToAccountInfo,
+};
 use mpl_core::{
-    types::{Plugin, PluginAuthority, PluginType, Royalties},
+    instructions::UpdatePluginV1CpiBuilder,
+    types::{
+        FreezeDelegate, Plugin, PluginAuthority, PluginType, Royalties, RuleSet, UpdateAuthority,
+    },
     ID,
 };
 use mpl_token_metadata::types::Creator;
-use solana_program::pubkey::Pubkey;
+use solana_program::{account_info::AccountInfo, pubkey::Pubkey};
 use std::ops::Deref;
 
 use crate::errors::MMMErrorCode;
@@ -109,6 +114,37 @@ pub fn get_royalties_from_plugin(
     None
 }
 
+pub fn assert_royalty_rule_set(rule_set: &RuleSet, program_id: &Pubkey) -> Result<(), Error> {
+    let allowed = match rule_set {
+        RuleSet::None => true,
+        RuleSet::ProgramAllowList(allow_list) => allow_list.contains(program_id),
+        RuleSet::ProgramDenyList(deny_list) => !deny_list.contains(program_id),
+    };
+    if !allowed {
+        return Err(MMMErrorCode::ProgramNotAllowedByRuleSet.into());
+    }
+    Ok(())
+}
+
+// returns the royalties of the asset, the royalties plugin of the collection
+// applies when the asset doesn't have one, so the collection account must be
+// provided for assets in a collection. The rule set of the royalties is checked
+// against this program.
+pub fn get_core_royalties(
+    asset: &IndexableAsset,
+    collection: &UncheckedAccount,
+) -> Result<Option<Royalties>, Error> {
+    if collection.key() != get_core_collection(asset).unwrap_or_default() {
+        return Err(MMMErrorCode::InvalidAssetCollection.into());
+    }
+    let collection_asset = deserialize_collection_asset(collection)?;
+    let royalties = get_royalties_from_plugin(asset, collection_asset.as_ref());
+    if let Some(royalties) = &royalties {
+        assert_royalty_rule_set(&royalties.rule_set, &crate::ID)?;
+    }
+    Ok(royalties)
+}
+
 pub fn assert_valid_core_plugins(asset: &IndexableAsset) -> Result<(), Error> {
     for plugin in asset.plugins.keys() {
         if CORE_DENY_LIST.contains(plugin) {
@@ -130,3 +166,70 @@ pub fn assert_valid_core_plugins(asset: &IndexableAsset) -> Result<(), Error> {
     }
     Ok(())
 }
+
+// the plugins that the pool holds the authority of for non-custodial listings
+pub const CORE_LISTING_PLUGINS: [PluginType; 2] =
+    [PluginType::TransferDelegate, PluginType::FreezeDelegate];
+
+// a non-custodial listing is available as long as the pool owner still owns
+// the asset and the pool is still the transfer and freeze delegate
+pub fn is_core_listing_available(
+    asset: &IndexableAsset,
+    pool: &Pubkey,
+    pool_owner: &Pubkey,
+) -> bool {
+    let pool_authority = PluginAuthority::Address { address: *pool };
+    asset.owner == Some(*pool_owner)
+        && CORE_LISTING_PLUGINS.iter().all(|plugin_type| {
+            asset
+                .plugins
+                .get(plugin_type)
+                .map(|plugin| plugin.authority == pool_authority)
+                .unwrap_or(false)
+        })
+}
+
+pub fn get_core_collection(asset: &IndexableAsset) -> Option<Pubkey> {
+    match asset.update_authority {
+        UpdateAuthority::Collection(collection) => Some(collection),
+        _ => None,
+    }
+}
+
+// returns the collection account info to pass to mpl core, None if the
+// collection account is not provided
+pub fn get_core_collection_account<'info>(
+    asset: &IndexableAsset,
+    collection: &UncheckedAccount<'info>,
+) -> Result<Option<AccountInfo<'info>>, Error> {
+    if collection.key == &Pubkey::default() {
+        return Ok(None);
+    }
+    if UpdateAuthority::Collection(collection.key()) != asset.update_authority {
+        return Err(MMMErrorCode::InvalidAssetCollection.into());
+    }
+    Ok(Some(collection.to_account_info()))
+}
+
+// freezes or thaws a listed asset, signed by the pool as the freeze delegate
+#[allow(clippy::too_many_arguments)]
+pub fn set_core_listing_frozen<'info>(
+    asset_program: &AccountInfo<'info>,
+    asset: &AccountInfo<'info>,
+    collection: Option<&AccountInfo<'info>>,
+    payer: &AccountInfo<'info>,
+    pool: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    pool_seeds: &[&[&[u8]]],
+    frozen: bool,
+) -> Result<(), Error> {
+    UpdatePluginV1CpiBuilder::new(asset_program)
+        .asset(asset)
+        .collection(collection)
+        .payer(payer)
+        .authority(Some(pool))
+        .system_program(system_program)
+        .plugin(Plugin::FreezeDelegate(FreezeDelegate { frozen }))
+        .invoke_signed(pool_seeds)?;
+    Ok(())
+}
@@ -5,18 +5,20 @@ use std::convert::TryFrom;
 
 use crate::{
     constants::*,
-    deserialize_collection_asset,
     errors::MMMErrorCode,
-    get_royalties_from_plugin, index_ra,
+    get_core_collection, get_core_royalties, index_ra,
     instructions::{
         check_allowlists_for_mpl_core, check_remaining_accounts_for_m2, create_core_metadata_core,
         withdraw_m2,
     },
-    state::{Pool, SellState},
+    state::{Pool, SellState, TraitPremium, PAUSE_FLAG_MPL_CORE},
     util::{
-        assert_valid_fees_bp, get_buyside_seller_receives, get_lp_fee_bp, get_metadata_royalty_bp,
-        get_sol_fee, get_sol_lp_fee, get_sol_total_price_and_next_price, log_pool,
-        pay_creator_fees_in_sol, try_close_escrow, try_close_pool, try_close_sell_state,
+        apply_trait_premium, assert_not_paused, assert_valid_fees_bp, get_buyside_seller_receives,
+        get_fee_schedule, get_global_config, get_lp_fee_bp, get_metadata_royalty_bp,
+        get_protocol_fee, get_referral_registry, get_royalty_schedule, get_sol_fee, get_sol_lp_fee,
+        get_sol_total_price_and_next_price, log_pool, pay_creator_fees_in_sol, pay_protocol_fee,
+        pay_referral_fee, try_close_escrow, try_close_pool, try_close_sell_state, FulfillResult,
+        RoyaltyPayment,
     },
     verify_referral::verify_referral,
     AssetInterface, IndexableAsset,
@@ -29,6 +31,7 @@ pub struct SolMplCoreFulfillBuyArgs {
     pub maker_fee_bp: i16,
     pub taker_fee_bp: i16,
     pub compression_proof: Option<Vec<u8>>,
+    pub trait_premium: Option<TraitPremium>,
 }
 
 #[derive(Accounts)]
@@ -43,10 +46,25 @@ pub struct SolMplCoreFulfillBuy<'info> {
     pub cosigner: Signer<'info>,
     #[account(
         mut,
-        constraint = verify_referral(&pool, &referral) @ MMMErrorCode::InvalidReferral,
+        constraint = verify_referral(&pool, &referral, &global_config) @ MMMErrorCode::InvalidReferral,
     )]
     /// CHECK: use verify_referral to check the referral account
     pub referral: UncheckedAccount<'info>,
+    /// CHECK: it might not be initialized, loaded with get_global_config
+    #[account(seeds = [GLOBAL_CONFIG_PREFIX.as_bytes()], bump)]
+    pub global_config: UncheckedAccount<'info>,
+    /// CHECK: checked against the treasury of global_config in pay_protocol_fee
+    #[account(mut)]
+    pub treasury: UncheckedAccount<'info>,
+    /// CHECK: it might not be initialized, loaded with get_fee_schedule
+    #[account(seeds = [FEE_SCHEDULE_PREFIX.as_bytes(), pool.referral.as_ref()], bump)]
+    pub fee_schedule: UncheckedAccount<'info>,
+    /// CHECK: the royalty schedule pda of the collection, checked in
+    /// get_royalty_schedule, it might not be initialized
+    pub royalty_schedule: UncheckedAccount<'info>,
+    /// CHECK: it might not be initialized, loaded with get_referral_registry
+    #[account(seeds = [REFERRAL_REGISTRY_PREFIX.as_bytes(), pool.referral.as_ref()], bump)]
+    pub referral_registry: UncheckedAccount<'info>,
     #[account(
         mut,
         seeds = [POOL_PREFIX.as_bytes(), owner.key().as_ref(), pool.uuid.as_ref()],
@@ -98,6 +116,10 @@ pub fn handler<'info>(
 ) -> Result<()> {
     let system_program = &ctx.accounts.system_program;
     let pool = &mut ctx.accounts.pool;
+    let global_config = get_global_config(&ctx.accounts.global_config)?;
+    assert_not_paused(&global_config, PAUSE_FLAG_MPL_CORE)?;
+    let fee_schedule =
+        get_fee_schedule(&pool.referral, &ctx.accounts.fee_schedule, &global_config)?;
     let sell_state = &mut ctx.accounts.sell_state;
     let owner = &ctx.accounts.owner;
     let referral = &ctx.accounts.referral;
@@ -116,28 +138,39 @@ pub fn handler<'info>(
     let _ = check_allowlists_for_mpl_core(&pool.allowlists, asset, args.allowlist_aux)?;
 
     let (total_price, next_price) = get_sol_total_price_and_next_price(pool, 1, true)?;
+    let total_price = apply_trait_premium(pool, &asset.key(), total_price, &args.trait_premium)?;
 
-    let collection_asset = deserialize_collection_asset(collection)?;
-    let (royalty_bp, metadata) =
-        if let Some(royalties) = get_royalties_from_plugin(asset, collection_asset.as_ref()) {
-            let metadata = create_core_metadata_core(&royalties);
-            (
-                get_metadata_royalty_bp(total_price, &metadata, None),
-                Some(metadata),
-            )
-        } else {
-            (0, None)
-        };
+    let (royalty_bp, metadata) = if let Some(royalties) = get_core_royalties(asset, collection)? {
+        let metadata = create_core_metadata_core(&royalties);
+        let royalty_schedule =
+            get_royalty_schedule(get_core_collection(asset), &ctx.accounts.royalty_schedule)?;
+        (
+            get_metadata_royalty_bp(total_price, &metadata, None, royalty_schedule.as_ref()),
+            Some(metadata),
+        )
+    } else {
+        (0, None)
+    };
     // TODO: update lp_fee_bp when shared escrow for both side is enabled
     let seller_receives = {
-        let lp_fee_bp = get_lp_fee_bp(pool, buyside_sol_escrow_account.lamports());
+        let lp_fee_bp = get_lp_fee_bp(pool, buyside_sol_escrow_account.lamports(), true)?;
         get_buyside_seller_receives(total_price, lp_fee_bp, royalty_bp, 10000)
     }?;
 
     // TODO: update lp_fee when shared escrow for both side is enabled
-    let lp_fee = get_sol_lp_fee(pool, buyside_sol_escrow_account.lamports(), seller_receives)?;
+    let lp_fee = get_sol_lp_fee(
+        pool,
+        buyside_sol_escrow_account.lamports(),
+        seller_receives,
+        true,
+    )?;
 
-    assert_valid_fees_bp(args.maker_fee_bp, args.taker_fee_bp)?;
+    assert_valid_fees_bp(
+        args.maker_fee_bp,
+        args.taker_fee_bp,
+        &global_config,
+        &fee_schedule,
+    )?;
     let maker_fee = get_sol_fee(seller_receives, args.maker_fee_bp)?;
     let taker_fee = get_sol_fee(seller_receives, args.taker_fee_bp)?;
     let referral_fee = u64::try_from(
@@ -146,12 +179,15 @@ pub fn handler<'info>(
             .ok_or(MMMErrorCode::NumericOverflow)?,
     )
     .map_err(|_| MMMErrorCode::NumericOverflow)?;
+    let protocol_fee = get_protocol_fee(&global_config, pool, seller_receives)?;
 
     // check creator_accounts and verify the remaining accounts
     let creator_accounts = if pool.using_shared_escrow() {
-        check_remaining_accounts_for_m2(remaining_accounts, &pool.owner.key())?;
+        check_remaining_accounts_for_m2(remaining_accounts, &pool.owner.key(), &global_config)?;
 
-        let amount: u64 = (total_price as i64 + maker_fee) as u64;
+        // compounded lp fees stay in the shared escrow
+        let compounded_lp_fee = if pool.compound_lp_fee { lp_fee } else { 0 };
+        let amount: u64 = (total_price as i64 + maker_fee - compounded_lp_fee as i64) as u64;
         withdraw_m2(
             pool,
             ctx.bumps.pool,
@@ -161,6 +197,7 @@ pub fn handler<'info>(
             index_ra!(remaining_accounts, 0),
             pool.owner,
             amount,
+            &global_config,
         )?;
         pool.shared_escrow_count = pool
             .shared_escrow_count
@@ -246,7 +283,10 @@ pub fn handler<'info>(
     }
 
     // pool owner as buyer is going to pay the royalties
-    let royalty_paid = if let Some(metadata) = &metadata {
+    let RoyaltyPayment {
+        total: royalty_paid,
+        accrued: royalty_accrued,
+    } = if let Some(metadata) = &metadata {
         pay_creator_fees_in_sol(
             10000,
             seller_receives,
@@ -256,10 +296,11 @@ pub fn handler<'info>(
             royalty_bp,
             buyside_sol_escrow_account_seeds,
             system_program.to_account_info(),
+            &global_config,
         )?
     } else {
         // Handle the case when metadata is None
-        0
+        RoyaltyPayment::default()
     };
 
     // prevent frontrun by pool config changes
@@ -271,6 +312,8 @@ pub fn handler<'info>(
         .checked_sub(taker_fee as u64)
         .ok_or(MMMErrorCode::NumericOverflow)?
         .checked_sub(royalty_paid)
+        .ok_or(MMMErrorCode::NumericOverflow)?
+        .checked_sub(protocol_fee)
         .ok_or(MMMErrorCode::NumericOverflow)?;
     if payment_amount < args.min_payment_amount {
         return Err(MMMErrorCode::InvalidRequestedPrice.into());
@@ -289,7 +332,8 @@ pub fn handler<'info>(
         buyside_sol_escrow_account_seeds,
     )?;
 
-    if lp_fee > 0 {
+    // compounded lp fees stay in the buyside escrow
+    if lp_fee > 0 && !pool.compound_lp_fee {
         anchor_lang::solana_program::program::invoke_signed(
             &anchor_lang::solana_program::system_instruction::transfer(
                 buyside_sol_escrow_account.key,
@@ -303,25 +347,36 @@ pub fn handler<'info>(
             buyside_sol_escrow_account_seeds,
         )?;
     }
-    if referral_fee > 0 {
-        anchor_lang::solana_program::program::invoke_signed(
-            &anchor_lang::solana_program::system_instruction::transfer(
-                buyside_sol_escrow_account.key,
-                referral.key,
-                referral_fee,
-            ),
-            &[
-                buyside_sol_escrow_account.to_account_info(),
-                referral.to_account_info(),
-            ],
-            buyside_sol_escrow_account_seeds,
-        )?;
-    }
+    let referral_registry = get_referral_registry(&ctx.accounts.referral_registry)?;
+    pay_referral_fee(
+        &buyside_sol_escrow_account.to_account_info(),
+        &referral.to_account_info(),
+        referral_registry.as_ref(),
+        ctx.remaining_accounts,
+        &system_program.to_account_info(),
+        buyside_sol_escrow_account_seeds,
+        referral_fee,
+    )?;
+
+    pay_protocol_fee(
+        &buyside_sol_escrow_account.to_account_info(),
+        &ctx.accounts.treasury,
+        &global_config,
+        &system_program.to_account_info(),
+        buyside_sol_escrow_account_seeds,
+        protocol_fee,
+    )?;
 
     pool.lp_fee_earned = pool
         .lp_fee_earned
         .checked_add(lp_fee)
         .ok_or(MMMErrorCode::NumericOverflow)?;
+    if pool.compound_lp_fee {
+        pool.lp_fee_compounded = pool
+            .lp_fee_compounded
+            .checked_add(lp_fee)
+            .ok_or(MMMErrorCode::NumericOverflow)?;
+    }
     pool.spot_price = next_price;
 
     try_close_escrow(
@@ -329,6 +384,7 @@ pub fn handler<'info>(
         pool,
         system_program,
         buyside_sol_escrow_account_seeds,
+        &global_config,
     )?;
     try_close_sell_state(sell_state, payer.to_account_info())?;
 
@@ -357,6 +413,7 @@ pub fn handler<'info>(
                 pool,
                 system_program,
                 buyside_sol_escrow_account_seeds,
+                &global_config,
             )?;
         }
     }
@@ -366,11 +423,27 @@ pub fn handler<'info>(
     try_close_pool(pool, owner.to_account_info())?;
 
     msg!(
-        "{{\"lp_fee\":{},\"royalty_paid\":{},\"total_price\":{}}}",
+        "{{\"lp_fee\":{},\"protocol_fee\":{},\"royalty_paid\":{},\"total_price\":{}}}",
         lp_fee,
+        protocol_fee,
         royalty_paid,
         total_price,
     );
 
-    Ok(())
+    FulfillResult {
+        total_price,
+        lp_fee,
+        maker_fee,
+        taker_fee,
+        protocol_fee,
+        royalty_paid,
+        royalty_accrued,
+        payment_amount,
+        next_price: pool.spot_price,
+        sellside_asset_amount: pool.sellside_asset_amount,
+        transfer_fee_withheld: 0,
+        trade_in_total_price: 0,
+        trade_in_payment_received: 0,
+    }
+    .set_return_data()
 }
@@ -5,17 +5,19 @@ use std::convert::TryFrom;
 
 use crate::{
     constants::*,
-    deserialize_collection_asset,
     errors::MMMErrorCode,
-    get_royalties_from_plugin,
+    get_core_collection, get_core_collection_account, get_core_royalties,
     instructions::{
         check_allowlists_for_mpl_core, create_core_metadata_core, get_sell_fulfill_pool_price_info,
         PoolPriceInfo,
     },
-    state::{Pool, SellState},
+    is_core_listing_available, set_core_listing_frozen,
+    state::{Pool, SellState, PAUSE_FLAG_MPL_CORE},
     util::{
-        get_metadata_royalty_bp, log_pool, pay_creator_fees_in_sol, try_close_pool,
-        try_close_sell_state,
+        assert_not_paused, get_fee_schedule, get_global_config, get_metadata_royalty_bp,
+        get_protocol_fee, get_referral_registry, get_royalty_schedule, log_pool,
+        pay_creator_fees_in_sol, pay_protocol_fee, pay_referral_fee, should_compound_lp_fee,
+        try_close_pool, try_close_sell_state, FulfillResult, RoyaltyPayment,
     },
     verify_referral::verify_referral,
     AssetInterface, IndexableAsset,
@@ -43,10 +45,25 @@ pub struct SolMplCoreFulfillSell<'info> {
     pub cosigner: Signer<'info>,
     #[account(
         mut,
-        constraint = verify_referral(&pool, &referral) @ MMMErrorCode::InvalidReferral,
+        constraint = verify_referral(&pool, &referral, &global_config) @ MMMErrorCode::InvalidReferral,
     )]
     /// CHECK: use verify_referral to check the referral account
     pub referral: UncheckedAccount<'info>,
+    /// CHECK: it might not be initialized, loaded with get_global_config
+    #[account(seeds = [GLOBAL_CONFIG_PREFIX.as_bytes()], bump)]
+    pub global_config: UncheckedAccount<'info>,
+    /// CHECK: checked against the treasury of global_config in pay_protocol_fee
+    #[account(mut)]
+    pub treasury: UncheckedAccount<'info>,
+    /// CHECK: it might not be initialized, loaded with get_fee_schedule
+    #[account(seeds = [FEE_SCHEDULE_PREFIX.as_bytes(), pool.referral.as_ref()], bump)]
+    pub fee_schedule: UncheckedAccount<'info>,
+    /// CHECK: the royalty schedule pda of the collection, checked in
+    /// get_royalty_schedule, it might not be initialized
+    pub royalty_schedule: UncheckedAccount<'info>,
+    /// CHECK: it might not be initialized, loaded with get_referral_registry
+    #[account(seeds = [REFERRAL_REGISTRY_PREFIX.as_bytes(), pool.referral.as_ref()], bump)]
+    pub referral_registry: UncheckedAccount<'info>,
     #[account(
         mut,
         seeds = [POOL_PREFIX.as_bytes(), owner.key().as_ref(), pool.uuid.as_ref()],
@@ -95,6 +112,10 @@ pub fn handler<'info>(
     let owner: &UncheckedAccount<'info> = &ctx.accounts.owner;
     let referral = &ctx.accounts.referral;
     let pool = &mut ctx.accounts.pool;
+    let global_config = get_global_config(&ctx.accounts.global_config)?;
+    assert_not_paused(&global_config, PAUSE_FLAG_MPL_CORE)?;
+    let fee_schedule =
+        get_fee_schedule(&pool.referral, &ctx.accounts.fee_schedule, &global_config)?;
     let asset = &ctx.accounts.asset;
     let sell_state = &mut ctx.accounts.sell_state;
     let payer = &ctx.accounts.payer;
@@ -110,9 +131,19 @@ pub fn handler<'info>(
 
     let _ = check_allowlists_for_mpl_core(&pool.allowlists, asset, args.allowlist_aux)?;
 
+    // for non-custodial listings, the pool thaws the asset and transfers it
+    // from the owner's wallet as the transfer delegate
+    let is_listing = sell_state.is_listing();
+    if is_listing && !is_core_listing_available(asset, &pool.key(), &pool.owner) {
+        // the pool lost the delegates of the asset, the owner can release the
+        // listing with mpl_core_withdraw_sell
+        return Err(MMMErrorCode::ListingUnavailable.into());
+    }
+
     let PoolPriceInfo {
         total_price,
         next_price,
+        spot_price_moved,
         lp_fee,
         maker_fee,
         taker_fee,
@@ -120,11 +151,14 @@ pub fn handler<'info>(
         transfer_sol_to,
     } = get_sell_fulfill_pool_price_info(
         pool,
+        sell_state,
         owner,
         buyside_sol_escrow_account,
         1,
         args.maker_fee_bp,
         args.taker_fee_bp,
+        &global_config,
+        &fee_schedule,
     )?;
 
     // TODO: make sure that the lp fee is paid with the correct amount
@@ -147,6 +181,19 @@ pub fn handler<'info>(
         ],
     )?;
 
+    if is_listing {
+        set_core_listing_frozen(
+            &ctx.accounts.asset_program.to_account_info(),
+            &asset.to_account_info(),
+            get_core_collection_account(asset, collection)?.as_ref(),
+            &payer.to_account_info(),
+            &pool.to_account_info(),
+            &system_program.to_account_info(),
+            pool_seeds,
+            false,
+        )?;
+    }
+
     let transfer_asset_builder = TransferV1Builder::new()
         .asset(asset.key())
         .payer(payer.key())
@@ -179,26 +226,45 @@ pub fn handler<'info>(
         pool_seeds,
     )?;
 
+    // compounded lp fees go to the buyside escrow instead of the owner
+    let compound_lp_fee = should_compound_lp_fee(pool, buyside_sol_escrow_account, lp_fee)?;
+    let lp_fee_to = if compound_lp_fee {
+        buyside_sol_escrow_account.to_account_info()
+    } else {
+        owner.to_account_info()
+    };
     if lp_fee > 0 {
-        anchor_lang::solana_program::program::invoke(
-            &anchor_lang::solana_program::system_instruction::transfer(
-                payer.key, owner.key, lp_fee,
-            ),
-            &[payer.to_account_info(), owner.to_account_info()],
-        )?;
-    }
-
-    if referral_fee > 0 {
         anchor_lang::solana_program::program::invoke(
             &anchor_lang::solana_program::system_instruction::transfer(
                 payer.key,
-                referral.key,
-                referral_fee,
+                lp_fee_to.key,
+                lp_fee,
             ),
-            &[payer.to_account_info(), referral.to_account_info()],
+            &[payer.to_account_info(), lp_fee_to],
         )?;
     }
 
+    let referral_registry = get_referral_registry(&ctx.accounts.referral_registry)?;
+    pay_referral_fee(
+        &payer.to_account_info(),
+        &referral.to_account_info(),
+        referral_registry.as_ref(),
+        ctx.remaining_accounts,
+        &system_program.to_account_info(),
+        &[&[&[]]],
+        referral_fee,
+    )?;
+
+    let protocol_fee = get_protocol_fee(&global_config, pool, total_price)?;
+    pay_protocol_fee(
+        payer,
+        &ctx.accounts.treasury,
+        &global_config,
+        system_program,
+        &[&[&[]]],
+        protocol_fee,
+    )?;
+
     pool.spot_price = next_price;
     pool.sellside_asset_amount = pool
         .sellside_asset_amount
@@ -208,25 +274,36 @@ pub fn handler<'info>(
         .lp_fee_earned
         .checked_add(lp_fee)
         .ok_or(MMMErrorCode::NumericOverflow)?;
+    if compound_lp_fee {
+        pool.lp_fee_compounded = pool
+            .lp_fee_compounded
+            .checked_add(lp_fee)
+            .ok_or(MMMErrorCode::NumericOverflow)?;
+    }
 
-    let collection_asset = deserialize_collection_asset(collection)?;
-    let royalty_paid =
-        if let Some(royalties) = get_royalties_from_plugin(asset, collection_asset.as_ref()) {
-            let metadata = create_core_metadata_core(&royalties);
-            let royalty_bp = get_metadata_royalty_bp(total_price, &metadata, None);
-            pay_creator_fees_in_sol(
-                10000,
-                total_price,
-                &metadata,
-                ctx.remaining_accounts,
-                payer.to_account_info(),
-                royalty_bp,
-                &[&[&[]]],
-                system_program.to_account_info(),
-            )?
-        } else {
-            0
-        };
+    let RoyaltyPayment {
+        total: royalty_paid,
+        accrued: royalty_accrued,
+    } = if let Some(royalties) = get_core_royalties(asset, collection)? {
+        let metadata = create_core_metadata_core(&royalties);
+        let royalty_schedule =
+            get_royalty_schedule(get_core_collection(asset), &ctx.accounts.royalty_schedule)?;
+        let royalty_bp =
+            get_metadata_royalty_bp(total_price, &metadata, None, royalty_schedule.as_ref());
+        pay_creator_fees_in_sol(
+            10000,
+            total_price,
+            &metadata,
+            ctx.remaining_accounts,
+            payer.to_account_info(),
+            royalty_bp,
+            &[&[&[]]],
+            system_program.to_account_info(),
+            &global_config,
+        )?
+    } else {
+        RoyaltyPayment::default()
+    };
 
     // prevent frontrun by pool config changes
     let payment_amount = total_price
@@ -235,6 +312,8 @@ pub fn handler<'info>(
         .checked_add(taker_fee as u64)
         .ok_or(MMMErrorCode::NumericOverflow)?
         .checked_add(royalty_paid)
+        .ok_or(MMMErrorCode::NumericOverflow)?
+        .checked_add(protocol_fee)
         .ok_or(MMMErrorCode::NumericOverflow)?;
     if payment_amount > args.max_payment_amount {
         return Err(MMMErrorCode::InvalidRequestedPrice.into());
@@ -251,11 +330,28 @@ pub fn handler<'info>(
     try_close_pool(pool, owner.to_account_info())?;
 
     msg!(
-        "{{\"lp_fee\":{},\"royalty_paid\":{},\"total_price\":{}}}",
+        "{{\"lp_fee\":{},\"protocol_fee\":{},\"royalty_paid\":{},\"total_price\":{},\"spot_price_moved\":{}}}",
         lp_fee,
+        protocol_fee,
         royalty_paid,
         total_price,
+        spot_price_moved,
     );
 
-    Ok(())
+    FulfillResult {
+        total_price,
+        lp_fee,
+        maker_fee,
+        taker_fee,
+        protocol_fee,
+        royalty_paid,
+        royalty_accrued,
+        payment_amount,
+        next_price: pool.spot_price,
+        sellside_asset_amount: pool.sellside_asset_amount,
+        transfer_fee_withheld: 0,
+        trade_in_total_price: 0,
+        trade_in_payment_received: 0,
+    }
+    .set_return_data()
 }
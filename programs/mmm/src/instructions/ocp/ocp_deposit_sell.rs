@@ -10,8 +10,11 @@ use crate::{
     constants::*,
     errors::MMMErrorCode,
     instructions::vanilla::DepositSellArgs,
-    state::{Pool, SellState},
-    util::{check_allowlists_for_mint, log_pool},
+    state::{Pool, SellState, PAUSE_FLAG_OCP},
+    util::{
+        assert_not_paused, check_allowlists_for_mint, get_global_config, log_pool,
+        set_sell_state_price_override,
+    },
 };
 
 #[derive(Accounts)]
@@ -87,6 +90,9 @@ pub struct OcpDepositSell<'info> {
     #[account(address = sysvar::instructions::id())]
     pub instructions: UncheckedAccount<'info>,
 
+    /// CHECK: it might not be initialized, loaded with get_global_config
+    #[account(seeds = [GLOBAL_CONFIG_PREFIX.as_bytes()], bump)]
+    pub global_config: UncheckedAccount<'info>,
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
@@ -101,6 +107,8 @@ pub fn handler(ctx: Context<OcpDepositSell>, args: DepositSellArgs) -> Result<()
     let token_program = &ctx.accounts.token_program;
     let sellside_escrow_token_account = &ctx.accounts.sellside_escrow_token_account;
     let pool = &mut ctx.accounts.pool;
+    let global_config = get_global_config(&ctx.accounts.global_config)?;
+    assert_not_paused(&global_config, PAUSE_FLAG_OCP)?;
     let sell_state = &mut ctx.accounts.sell_state;
 
     if pool.using_shared_escrow() {
@@ -185,6 +193,9 @@ pub fn handler(ctx: Context<OcpDepositSell>, args: DepositSellArgs) -> Result<()
         .asset_amount
         .checked_add(args.asset_amount)
         .ok_or(MMMErrorCode::NumericOverflow)?;
+    if let Some(price_override) = args.price_override {
+        set_sell_state_price_override(sell_state, price_override)?;
+    }
     log_pool("post_ocp_deposit_sell", pool)?;
 
     Ok(())
@@ -11,11 +11,14 @@ use crate::{
     ata::init_if_needed_ocp_ata,
     constants::*,
     errors::MMMErrorCode,
-    state::{Pool, SellState},
+    state::{Pool, SellState, PAUSE_FLAG_OCP},
     util::{
-        assert_valid_fees_bp, check_allowlists_for_mint, get_metadata_royalty_bp, get_sol_fee,
-        get_sol_lp_fee, get_sol_total_price_and_next_price, log_pool, pay_creator_fees_in_sol,
-        try_close_pool, try_close_sell_state,
+        assert_not_paused, assert_valid_fees_bp, check_allowlists_for_mint, get_fee_schedule,
+        get_global_config, get_metadata_royalty_bp, get_protocol_fee, get_referral_registry,
+        get_royalty_schedule, get_sell_state_total_price_and_next_price, get_sol_fee,
+        get_sol_lp_fee, get_verified_collection, log_pool, pay_creator_fees_in_sol,
+        pay_protocol_fee, pay_referral_fee, should_compound_lp_fee, try_close_pool,
+        try_close_sell_state, FulfillResult, RoyaltyPayment,
     },
     verify_referral::verify_referral,
 };
@@ -45,10 +48,25 @@ pub struct SolOcpFulfillSell<'info> {
     pub cosigner: Signer<'info>,
     #[account(
         mut,
-        constraint = verify_referral(&pool, &referral) @ MMMErrorCode::InvalidReferral,
+        constraint = verify_referral(&pool, &referral, &global_config) @ MMMErrorCode::InvalidReferral,
     )]
     /// CHECK: use verify_referral to check the referral account
     pub referral: UncheckedAccount<'info>,
+    /// CHECK: it might not be initialized, loaded with get_global_config
+    #[account(seeds = [GLOBAL_CONFIG_PREFIX.as_bytes()], bump)]
+    pub global_config: UncheckedAccount<'info>,
+    /// CHECK: checked against the treasury of global_config in pay_protocol_fee
+    #[account(mut)]
+    pub treasury: UncheckedAccount<'info>,
+    /// CHECK: it might not be initialized, loaded with get_fee_schedule
+    #[account(seeds = [FEE_SCHEDULE_PREFIX.as_bytes(), pool.referral.as_ref()], bump)]
+    pub fee_schedule: UncheckedAccount<'info>,
+    /// CHECK: the royalty schedule pda of the collection, checked in
+    /// get_royalty_schedule, it might not be initialized
+    pub royalty_schedule: UncheckedAccount<'info>,
+    /// CHECK: it might not be initialized, loaded with get_referral_registry
+    #[account(seeds = [REFERRAL_REGISTRY_PREFIX.as_bytes(), pool.referral.as_ref()], bump)]
+    pub referral_registry: UncheckedAccount<'info>,
     #[account(
         mut,
         seeds = [POOL_PREFIX.as_bytes(), owner.key().as_ref(), pool.uuid.as_ref()],
@@ -137,6 +155,10 @@ pub fn handler<'info>(
     let owner = &ctx.accounts.owner;
     let referral = &ctx.accounts.referral;
     let pool = &mut ctx.accounts.pool;
+    let global_config = get_global_config(&ctx.accounts.global_config)?;
+    assert_not_paused(&global_config, PAUSE_FLAG_OCP)?;
+    let fee_schedule =
+        get_fee_schedule(&pool.referral, &ctx.accounts.fee_schedule, &global_config)?;
     let sell_state = &mut ctx.accounts.sell_state;
 
     let payer = &ctx.accounts.payer;
@@ -163,10 +185,21 @@ pub fn handler<'info>(
     )?;
 
     let (total_price, next_price) =
-        get_sol_total_price_and_next_price(pool, args.asset_amount, false)?;
-    let lp_fee = get_sol_lp_fee(pool, buyside_sol_escrow_account.lamports(), total_price)?;
+        get_sell_state_total_price_and_next_price(pool, sell_state, args.asset_amount)?;
+    let spot_price_moved = next_price != pool.spot_price;
+    let lp_fee = get_sol_lp_fee(
+        pool,
+        buyside_sol_escrow_account.lamports(),
+        total_price,
+        false,
+    )?;
 
-    assert_valid_fees_bp(args.maker_fee_bp, args.taker_fee_bp)?;
+    assert_valid_fees_bp(
+        args.maker_fee_bp,
+        args.taker_fee_bp,
+        &global_config,
+        &fee_schedule,
+    )?;
     let maker_fee = get_sol_fee(total_price, args.maker_fee_bp)?;
     let taker_fee = get_sol_fee(total_price, args.taker_fee_bp)?;
     let referral_fee = u64::try_from(
@@ -262,34 +295,49 @@ pub fn handler<'info>(
         ))?;
     }
 
+    // compounded lp fees go to the buyside escrow instead of the owner
+    let compound_lp_fee = should_compound_lp_fee(pool, buyside_sol_escrow_account, lp_fee)?;
+    let lp_fee_to = if compound_lp_fee {
+        buyside_sol_escrow_account.to_account_info()
+    } else {
+        owner.to_account_info()
+    };
     if lp_fee > 0 {
-        anchor_lang::solana_program::program::invoke(
-            &anchor_lang::solana_program::system_instruction::transfer(
-                payer.key, owner.key, lp_fee,
-            ),
-            &[
-                payer.to_account_info(),
-                owner.to_account_info(),
-                system_program.to_account_info(),
-            ],
-        )?;
-    }
-
-    if referral_fee > 0 {
         anchor_lang::solana_program::program::invoke(
             &anchor_lang::solana_program::system_instruction::transfer(
                 payer.key,
-                referral.key,
-                referral_fee,
+                lp_fee_to.key,
+                lp_fee,
             ),
             &[
                 payer.to_account_info(),
-                referral.to_account_info(),
+                lp_fee_to,
                 system_program.to_account_info(),
             ],
         )?;
     }
 
+    let referral_registry = get_referral_registry(&ctx.accounts.referral_registry)?;
+    pay_referral_fee(
+        &payer.to_account_info(),
+        &referral.to_account_info(),
+        referral_registry.as_ref(),
+        ctx.remaining_accounts,
+        &system_program.to_account_info(),
+        &[&[&[]]],
+        referral_fee,
+    )?;
+
+    let protocol_fee = get_protocol_fee(&global_config, pool, total_price)?;
+    pay_protocol_fee(
+        payer,
+        &ctx.accounts.treasury,
+        &global_config,
+        system_program,
+        &[&[&[]]],
+        protocol_fee,
+    )?;
+
     pool.spot_price = next_price;
     pool.sellside_asset_amount = pool
         .sellside_asset_amount
@@ -299,9 +347,27 @@ pub fn handler<'info>(
         .lp_fee_earned
         .checked_add(lp_fee)
         .ok_or(MMMErrorCode::NumericOverflow)?;
+    if compound_lp_fee {
+        pool.lp_fee_compounded = pool
+            .lp_fee_compounded
+            .checked_add(lp_fee)
+            .ok_or(MMMErrorCode::NumericOverflow)?;
+    }
 
-    let royalty_bp = get_metadata_royalty_bp(total_price, &parsed_metadata, Some(ocp_policy));
-    let royalty_paid = pay_creator_fees_in_sol(
+    let royalty_schedule = get_royalty_schedule(
+        get_verified_collection(&parsed_metadata),
+        &ctx.accounts.royalty_schedule,
+    )?;
+    let royalty_bp = get_metadata_royalty_bp(
+        total_price,
+        &parsed_metadata,
+        Some(ocp_policy),
+        royalty_schedule.as_ref(),
+    );
+    let RoyaltyPayment {
+        total: royalty_paid,
+        accrued: royalty_accrued,
+    } = pay_creator_fees_in_sol(
         10000,
         total_price,
         &parsed_metadata,
@@ -310,6 +376,7 @@ pub fn handler<'info>(
         royalty_bp,
         &[&[&[]]],
         system_program.to_account_info(),
+        &global_config,
     )?;
 
     // prevent frontrun by pool config changes
@@ -319,6 +386,8 @@ pub fn handler<'info>(
         .checked_add(taker_fee as u64)
         .ok_or(MMMErrorCode::NumericOverflow)?
         .checked_add(royalty_paid)
+        .ok_or(MMMErrorCode::NumericOverflow)?
+        .checked_add(protocol_fee)
         .ok_or(MMMErrorCode::NumericOverflow)?;
     if payment_amount > args.max_payment_amount {
         return Err(MMMErrorCode::InvalidRequestedPrice.into());
@@ -335,11 +404,28 @@ pub fn handler<'info>(
     try_close_pool(pool, owner.to_account_info())?;
 
     msg!(
-        "{{\"lp_fee\":{},\"royalty_paid\":{},\"total_price\":{}}}",
+        "{{\"lp_fee\":{},\"protocol_fee\":{},\"royalty_paid\":{},\"total_price\":{},\"spot_price_moved\":{}}}",
         lp_fee,
+        protocol_fee,
         royalty_paid,
         total_price,
+        spot_price_moved,
     );
 
-    Ok(())
+    FulfillResult {
+        total_price,
+        lp_fee,
+        maker_fee,
+        taker_fee,
+        protocol_fee,
+        royalty_paid,
+        royalty_accrued,
+        payment_amount,
+        next_price: pool.spot_price,
+        sellside_asset_amount: pool.sellside_asset_amount,
+        transfer_fee_withheld: 0,
+        trade_in_total_price: 0,
+        trade_in_payment_received: 0,
+    }
+    .set_return_data()
 }
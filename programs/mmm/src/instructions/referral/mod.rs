@@ -0,0 +1,3 @@
+pub mod set_referral_registry;
+
+pub use set_referral_registry::*;
@@ -0,0 +1,44 @@
+use anchor_lang::{prelude::*, AnchorDeserialize, AnchorSerialize};
+
+use crate::{
+    constants::REFERRAL_REGISTRY_PREFIX,
+    errors::MMMErrorCode,
+    state::{ReferralRecipient, ReferralRegistry},
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SetReferralRegistryArgs {
+    // an empty list disables the split, the referral account is paid again
+    pub recipients: Vec<ReferralRecipient>,
+}
+
+#[derive(Accounts)]
+#[instruction(args:SetReferralRegistryArgs)]
+pub struct SetReferralRegistry<'info> {
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        payer = referrer,
+        seeds = [REFERRAL_REGISTRY_PREFIX.as_bytes(), referrer.key().as_ref()],
+        space = ReferralRegistry::LEN,
+        bump
+    )]
+    pub referral_registry: Account<'info, ReferralRegistry>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SetReferralRegistry>, args: SetReferralRegistryArgs) -> Result<()> {
+    let referral_registry = &mut ctx.accounts.referral_registry;
+
+    if !ReferralRegistry::valid_recipients(&args.recipients) {
+        return Err(MMMErrorCode::InvalidReferralRegistry.into());
+    }
+
+    referral_registry.referrer = ctx.accounts.referrer.key();
+    referral_registry.recipient_count = args.recipients.len() as u8;
+    referral_registry.recipients = Default::default();
+    referral_registry.recipients[..args.recipients.len()].copy_from_slice(&args.recipients);
+
+    Ok(())
+}
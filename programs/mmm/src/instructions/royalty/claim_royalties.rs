@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::ROYALTY_VAULT_PREFIX, errors::MMMErrorCode, state::RoyaltyVault};
+
+// ClaimRoyalties is permissionless, the vault is created on the first call so
+// that fulfill instructions can credit it, and the claimable lamports are
+// always sent to the creator.
+#[derive(Accounts)]
+pub struct ClaimRoyalties<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: only receives the claimed lamports, it's the seed of the vault
+    #[account(mut)]
+    pub creator: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [ROYALTY_VAULT_PREFIX.as_bytes(), creator.key().as_ref()],
+        space = RoyaltyVault::LEN,
+        bump
+    )]
+    pub royalty_vault: Account<'info, RoyaltyVault>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ClaimRoyalties>) -> Result<()> {
+    let creator = &ctx.accounts.creator;
+    let royalty_vault = &mut ctx.accounts.royalty_vault;
+    royalty_vault.creator = creator.key();
+
+    let rent = Rent::get()?;
+    let amount = royalty_vault
+        .to_account_info()
+        .lamports()
+        .saturating_sub(rent.minimum_balance(RoyaltyVault::LEN));
+    if amount == 0 {
+        return Ok(());
+    }
+    // the creator account can't be left below the rent-exempt minimum, the
+    // royalties keep accruing until the claim covers it
+    let creator_lamports = creator
+        .lamports()
+        .checked_add(amount)
+        .ok_or(MMMErrorCode::NumericOverflow)?;
+    if creator_lamports < rent.minimum_balance(0) {
+        return Err(MMMErrorCode::NotEnoughBalance.into());
+    }
+
+    **royalty_vault.to_account_info().try_borrow_mut_lamports()? -= amount;
+    **creator.try_borrow_mut_lamports()? = creator_lamports;
+    royalty_vault.claimed = royalty_vault
+        .claimed
+        .checked_add(amount)
+        .ok_or(MMMErrorCode::NumericOverflow)?;
+    msg!(
+        "{{\"creator\":\"{}\",\"claimed\":{}}}",
+        creator.key(),
+        amount
+    );
+
+    Ok(())
+}
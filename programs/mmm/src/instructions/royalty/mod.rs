@@ -0,0 +1,5 @@
+pub mod claim_royalties;
+pub mod set_royalty_schedule;
+
+pub use claim_royalties::*;
+pub use set_royalty_schedule::*;
@@ -0,0 +1,93 @@
+use anchor_lang::{prelude::*, AnchorDeserialize, AnchorSerialize};
+use mpl_core::types::UpdateAuthority;
+use mpl_token_metadata::accounts::Metadata;
+
+use crate::{
+    constants::{GLOBAL_CONFIG_PREFIX, ROYALTY_SCHEDULE_PREFIX},
+    deserialize_collection_asset,
+    errors::MMMErrorCode,
+    state::{RoyaltyBand, RoyaltySchedule},
+    util::{get_global_config, get_token_group_update_authority},
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SetRoyaltyScheduleArgs {
+    // an empty list disables the schedule, the flat royalty applies again
+    pub bands: Vec<RoyaltyBand>,
+}
+
+#[derive(Accounts)]
+#[instruction(args:SetRoyaltyScheduleArgs)]
+pub struct SetRoyaltySchedule<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// CHECK: the collection mint, the mpl core collection or the token22 group
+    /// mint, checked in get_collection_update_authority
+    pub collection: UncheckedAccount<'info>,
+    /// CHECK: the metadata of the collection mint, only used for metaplex
+    /// collections, can be the default pubkey otherwise
+    pub collection_metadata: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [ROYALTY_SCHEDULE_PREFIX.as_bytes(), collection.key().as_ref()],
+        space = RoyaltySchedule::LEN,
+        bump
+    )]
+    pub royalty_schedule: Account<'info, RoyaltySchedule>,
+    /// CHECK: it might not be initialized, loaded with get_global_config
+    #[account(seeds = [GLOBAL_CONFIG_PREFIX.as_bytes()], bump)]
+    pub global_config: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+fn get_collection_update_authority(
+    collection: &UncheckedAccount,
+    collection_metadata: &UncheckedAccount,
+) -> Result<Pubkey> {
+    let (metadata_key, _) = Metadata::find_pda(collection.key);
+    if collection_metadata.key == &metadata_key
+        && collection_metadata.owner == &mpl_token_metadata::ID
+    {
+        return Ok(
+            Metadata::safe_deserialize(&collection_metadata.data.borrow())?.update_authority,
+        );
+    }
+    if collection.owner == &mpl_core::ID {
+        return match deserialize_collection_asset(collection)? {
+            Some(collection_asset) => match collection_asset.update_authority {
+                UpdateAuthority::Address(update_authority) => Ok(update_authority),
+                _ => Err(MMMErrorCode::InvalidAssetCollection.into()),
+            },
+            None => Err(MMMErrorCode::InvalidAssetCollection.into()),
+        };
+    }
+    if collection.owner == &spl_token_2022::ID {
+        return get_token_group_update_authority(collection);
+    }
+    Err(MMMErrorCode::InvalidAssetCollection.into())
+}
+
+pub fn handler(ctx: Context<SetRoyaltySchedule>, args: SetRoyaltyScheduleArgs) -> Result<()> {
+    let authority = &ctx.accounts.authority;
+    let collection = &ctx.accounts.collection;
+    let royalty_schedule = &mut ctx.accounts.royalty_schedule;
+
+    let update_authority =
+        get_collection_update_authority(collection, &ctx.accounts.collection_metadata)?;
+    if update_authority != authority.key() {
+        return Err(MMMErrorCode::InvalidOwner.into());
+    }
+    let global_config = get_global_config(&ctx.accounts.global_config)?;
+    if !RoyaltySchedule::valid_bands(&args.bands, global_config.max_metadata_creator_royalty_bp) {
+        return Err(MMMErrorCode::InvalidRoyaltySchedule.into());
+    }
+
+    royalty_schedule.collection = collection.key();
+    royalty_schedule.authority = authority.key();
+    royalty_schedule.band_count = args.bands.len() as u8;
+    royalty_schedule.bands = Default::default();
+    royalty_schedule.bands[..args.bands.len()].copy_from_slice(&args.bands);
+
+    Ok(())
+}
@@ -1,15 +1,15 @@
 use anchor_lang::prelude::*;
 
 use crate::{
-    constants::{BUYSIDE_SOL_ESCROW_ACCOUNT_PREFIX, CANCEL_AUTHORITY, POOL_PREFIX},
+    constants::{BUYSIDE_SOL_ESCROW_ACCOUNT_PREFIX, GLOBAL_CONFIG_PREFIX, POOL_PREFIX},
     errors::MMMErrorCode,
     state::Pool,
-    util::{try_close_escrow, try_close_pool},
+    util::{get_global_config, try_close_escrow, try_close_pool},
 };
 
 #[derive(Accounts)]
 pub struct CloseIfBalanceInvalid<'info> {
-    #[account(address = CANCEL_AUTHORITY)]
+    /// checked against the cancel_authority of the global config
     pub authority: Signer<'info>,
     /// CHECK: checked in pool owner constraint
     #[account(mut)]
@@ -29,10 +29,20 @@ pub struct CloseIfBalanceInvalid<'info> {
         bump,
     )]
     pub buyside_sol_escrow_account: UncheckedAccount<'info>,
+    /// CHECK: it might not be initialized, loaded with get_global_config
+    #[account(seeds = [GLOBAL_CONFIG_PREFIX.as_bytes()], bump)]
+    pub global_config: UncheckedAccount<'info>,
     pub system_program: Program<'info, System>,
 }
 
 pub fn handler(ctx: Context<CloseIfBalanceInvalid>) -> Result<()> {
+    let global_config = get_global_config(&ctx.accounts.global_config)?;
+    require_keys_eq!(
+        ctx.accounts.authority.key(),
+        global_config.cancel_authority,
+        ErrorCode::ConstraintAddress
+    );
+
     // automatically close pools that have low escrow balance and no way of increase escrow balance
     let pool_key = ctx.accounts.pool.key();
     let buyside_sol_escrow_account_seeds: &[&[&[u8]]] = &[&[
@@ -45,6 +55,7 @@ pub fn handler(ctx: Context<CloseIfBalanceInvalid>) -> Result<()> {
         &ctx.accounts.pool,
         &ctx.accounts.system_program,
         buyside_sol_escrow_account_seeds,
+        &global_config,
     )?;
     ctx.accounts.pool.buyside_payment_amount = ctx.accounts.buyside_sol_escrow_account.lamports();
     try_close_pool(&ctx.accounts.pool, ctx.accounts.owner.to_account_info())
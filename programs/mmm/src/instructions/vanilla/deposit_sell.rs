@@ -5,16 +5,22 @@ use anchor_spl::{
 };
 
 use crate::{
+    auth_rules::AuthorizationDataLocal,
     constants::*,
     errors::MMMErrorCode,
-    state::{Pool, SellState},
-    util::{check_allowlists_for_mint, log_pool},
+    state::{Pool, PriceOverride, SellState, PAUSE_FLAG_VANILLA},
+    util::{
+        assert_not_paused, check_allowlists_for_mint, get_global_config, log_pool,
+        set_sell_state_price_override,
+    },
 };
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct DepositSellArgs {
     pub asset_amount: u64,
     pub allowlist_aux: Option<String>, // TODO: use it for future allowlist_aux
+    pub price_override: Option<PriceOverride>,
+    pub authorization_data: Option<AuthorizationDataLocal>, // only used by pNFTs with a rule set
 }
 
 #[derive(Accounts)]
@@ -76,6 +82,9 @@ pub struct DepositSell<'info> {
     pub sell_state: Account<'info, SellState>,
     /// CHECK: will be used for allowlist checks
     pub allowlist_aux_account: UncheckedAccount<'info>,
+    /// CHECK: it might not be initialized, loaded with get_global_config
+    #[account(seeds = [GLOBAL_CONFIG_PREFIX.as_bytes()], bump)]
+    pub global_config: UncheckedAccount<'info>,
     pub system_program: Program<'info, System>,
     pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
@@ -91,6 +100,8 @@ pub fn handler(ctx: Context<DepositSell>, args: DepositSellArgs) -> Result<()> {
     let sellside_escrow_token_account = &ctx.accounts.sellside_escrow_token_account;
     let token_program = &ctx.accounts.token_program;
     let pool = &mut ctx.accounts.pool;
+    let global_config = get_global_config(&ctx.accounts.global_config)?;
+    assert_not_paused(&global_config, PAUSE_FLAG_VANILLA)?;
     let sell_state = &mut ctx.accounts.sell_state;
 
     if pool.using_shared_escrow() {
@@ -141,6 +152,9 @@ pub fn handler(ctx: Context<DepositSell>, args: DepositSellArgs) -> Result<()> {
         .asset_amount
         .checked_add(args.asset_amount)
         .ok_or(MMMErrorCode::NumericOverflow)?;
+    if let Some(price_override) = args.price_override {
+        set_sell_state_price_override(sell_state, price_override)?;
+    }
     log_pool("post_deposit_sell", pool)?;
 
     Ok(())
@@ -4,7 +4,9 @@ pub mod close_if_balance_invalid;
 pub mod deposit_sell;
 pub mod sol_deposit_buy;
 pub mod sol_fulfill_buy;
+pub mod sol_fulfill_route;
 pub mod sol_fulfill_sell;
+pub mod sol_fulfill_trade_in;
 pub mod sol_withdraw_buy;
 pub mod withdraw_sell;
 
@@ -12,6 +14,8 @@ pub use close_if_balance_invalid::*;
 pub use deposit_sell::*;
 pub use sol_deposit_buy::*;
 pub use sol_fulfill_buy::*;
+pub use sol_fulfill_route::*;
 pub use sol_fulfill_sell::*;
+pub use sol_fulfill_trade_in::*;
 pub use sol_withdraw_buy::*;
 pub use withdraw_sell::*;
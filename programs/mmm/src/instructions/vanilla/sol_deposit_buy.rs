@@ -1,6 +1,11 @@
 use anchor_lang::{prelude::*, AnchorDeserialize, AnchorSerialize};
 
-use crate::{constants::*, errors::MMMErrorCode, state::Pool, util::log_pool};
+use crate::{
+    constants::*,
+    errors::MMMErrorCode,
+    state::{Pool, PAUSE_FLAG_ALL},
+    util::{assert_not_paused, get_global_config, log_pool},
+};
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct SolDepositBuyArgs {
@@ -31,6 +36,9 @@ pub struct SolDepositBuy<'info> {
         bump,
     )]
     pub buyside_sol_escrow_account: UncheckedAccount<'info>,
+    /// CHECK: it might not be initialized, loaded with get_global_config
+    #[account(seeds = [GLOBAL_CONFIG_PREFIX.as_bytes()], bump)]
+    pub global_config: UncheckedAccount<'info>,
     pub system_program: Program<'info, System>,
 }
 
@@ -39,6 +47,8 @@ pub fn handler(ctx: Context<SolDepositBuy>, args: SolDepositBuyArgs) -> Result<(
     let buyside_sol_escrow_account = &ctx.accounts.buyside_sol_escrow_account;
     let system_program = &ctx.accounts.system_program;
     let pool = &mut ctx.accounts.pool;
+    let global_config = get_global_config(&ctx.accounts.global_config)?;
+    assert_not_paused(&global_config, PAUSE_FLAG_ALL)?;
 
     if pool.using_shared_escrow() {
         return Err(MMMErrorCode::InvalidAccountState.into());
@@ -0,0 +1,198 @@
+use anchor_lang::{prelude::*, AnchorDeserialize, AnchorSerialize};
+use std::collections::BTreeSet;
+
+use crate::{
+    errors::MMMErrorCode,
+    instructions::vanilla::{
+        sol_fulfill_buy::{fulfill_buy, SolFulfillBuy, SolFulfillBuyArgs, SolFulfillBuyBumps},
+        sol_fulfill_sell::{fulfill_sell, SolFulfillSell, SolFulfillSellArgs, SolFulfillSellBumps},
+    },
+    util::FulfillResult,
+};
+
+// RouteLeg is a single fulfillment against one pool. accounts_len is the
+// number of remaining accounts used by the leg, which are the accounts of
+// the leg's fulfill instruction followed by its own remaining accounts.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub enum RouteLeg {
+    FulfillBuy {
+        accounts_len: u8,
+        args: SolFulfillBuyArgs,
+    },
+    FulfillSell {
+        accounts_len: u8,
+        args: SolFulfillSellArgs,
+    },
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SolFulfillRouteArgs {
+    pub legs: Vec<RouteLeg>,
+    pub max_payment_amount: u64, // aggregate amount paid by the payer for all the FulfillSell legs
+    pub min_payment_amount: u64, // aggregate amount received by the payer for all the FulfillBuy legs
+    pub deadline: i64,           // 0 means no deadline
+}
+
+// FulfillRouteResult is set as the return data of sol_fulfill_route, the fees
+// and royalties are summed over all the legs of the route
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct FulfillRouteResult {
+    pub legs: u8,
+    pub total_paid: u64,     // paid by the payer for all the FulfillSell legs
+    pub total_received: u64, // received by the payer for all the FulfillBuy legs
+    pub lp_fee: u64,
+    pub maker_fee: i64,
+    pub taker_fee: i64,
+    pub royalty_paid: u64,
+    pub royalty_accrued: u64,
+    pub protocol_fee: u64,
+}
+
+impl FulfillRouteResult {
+    pub fn set_return_data(&self) -> Result<()> {
+        anchor_lang::solana_program::program::set_return_data(&self.try_to_vec()?);
+        Ok(())
+    }
+
+    fn add_leg(&mut self, leg: &FulfillResult) -> Result<()> {
+        self.legs = self
+            .legs
+            .checked_add(1)
+            .ok_or(MMMErrorCode::NumericOverflow)?;
+        self.lp_fee = self
+            .lp_fee
+            .checked_add(leg.lp_fee)
+            .ok_or(MMMErrorCode::NumericOverflow)?;
+        self.maker_fee = self
+            .maker_fee
+            .checked_add(leg.maker_fee)
+            .ok_or(MMMErrorCode::NumericOverflow)?;
+        self.taker_fee = self
+            .taker_fee
+            .checked_add(leg.taker_fee)
+            .ok_or(MMMErrorCode::NumericOverflow)?;
+        self.royalty_paid = self
+            .royalty_paid
+            .checked_add(leg.royalty_paid)
+            .ok_or(MMMErrorCode::NumericOverflow)?;
+        self.royalty_accrued = self
+            .royalty_accrued
+            .checked_add(leg.royalty_accrued)
+            .ok_or(MMMErrorCode::NumericOverflow)?;
+        self.protocol_fee = self
+            .protocol_fee
+            .checked_add(leg.protocol_fee)
+            .ok_or(MMMErrorCode::NumericOverflow)?;
+        Ok(())
+    }
+}
+
+// FulfillRoute fills an ordered list of pools in one transaction, each leg
+// goes through the same logic as sol_fulfill_buy or sol_fulfill_sell, and the
+// whole route is reverted if the aggregate payment bounds are violated.
+#[derive(Accounts)]
+#[instruction(args:SolFulfillRouteArgs)]
+pub struct SolFulfillRoute<'info> {
+    #[account(
+        constraint = args.deadline == 0 || args.deadline >= Clock::get().unwrap().unix_timestamp @ MMMErrorCode::Expired,
+    )]
+    pub payer: Signer<'info>,
+    // Remaining accounts
+    //   for each leg in args.legs, accounts_len accounts of the leg
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SolFulfillRoute<'info>>,
+    args: SolFulfillRouteArgs,
+) -> Result<()> {
+    let program_id = ctx.program_id;
+    let payer = &ctx.accounts.payer;
+    let mut remaining_accounts = ctx.remaining_accounts;
+
+    if args.legs.is_empty() {
+        return Err(MMMErrorCode::InvalidRemainingAccounts.into());
+    }
+
+    let mut route_result = FulfillRouteResult::default();
+    for leg in args.legs {
+        let accounts_len = match leg {
+            RouteLeg::FulfillBuy { accounts_len, .. } => accounts_len,
+            RouteLeg::FulfillSell { accounts_len, .. } => accounts_len,
+        } as usize;
+        if remaining_accounts.len() < accounts_len {
+            return Err(MMMErrorCode::InvalidRemainingAccounts.into());
+        }
+        let (mut leg_accounts, rest) = remaining_accounts.split_at(accounts_len);
+        remaining_accounts = rest;
+
+        match leg {
+            RouteLeg::FulfillBuy { args, .. } => {
+                let ix_data = args.try_to_vec()?;
+                let mut bumps = SolFulfillBuyBumps::default();
+                let mut accounts = SolFulfillBuy::try_accounts(
+                    program_id,
+                    &mut leg_accounts,
+                    &ix_data,
+                    &mut bumps,
+                    &mut BTreeSet::new(),
+                )?;
+                if accounts.payer.key() != payer.key() {
+                    return Err(MMMErrorCode::InvalidAccountState.into());
+                }
+                let result = fulfill_buy(
+                    Context::new(program_id, &mut accounts, leg_accounts, bumps),
+                    args,
+                )?;
+                accounts.exit(program_id)?;
+                route_result.add_leg(&result)?;
+                route_result.total_received = route_result
+                    .total_received
+                    .checked_add(result.payment_amount)
+                    .ok_or(MMMErrorCode::NumericOverflow)?;
+            }
+            RouteLeg::FulfillSell { args, .. } => {
+                let ix_data = args.try_to_vec()?;
+                let mut bumps = SolFulfillSellBumps::default();
+                let mut accounts = SolFulfillSell::try_accounts(
+                    program_id,
+                    &mut leg_accounts,
+                    &ix_data,
+                    &mut bumps,
+                    &mut BTreeSet::new(),
+                )?;
+                if accounts.payer.key() != payer.key() {
+                    return Err(MMMErrorCode::InvalidAccountState.into());
+                }
+                let result = fulfill_sell(
+                    Context::new(program_id, &mut accounts, leg_accounts, bumps),
+                    args,
+                )?;
+                accounts.exit(program_id)?;
+                route_result.add_leg(&result)?;
+                route_result.total_paid = route_result
+                    .total_paid
+                    .checked_add(result.payment_amount)
+                    .ok_or(MMMErrorCode::NumericOverflow)?;
+            }
+        }
+    }
+
+    if !remaining_accounts.is_empty() {
+        return Err(MMMErrorCode::InvalidRemainingAccounts.into());
+    }
+
+    // prevent partial fills from moving the aggregate price out of bounds
+    if route_result.total_paid > args.max_payment_amount
+        || route_result.total_received < args.min_payment_amount
+    {
+        return Err(MMMErrorCode::InvalidRequestedPrice.into());
+    }
+
+    msg!(
+        "{{\"total_paid\":{},\"total_received\":{}}}",
+        route_result.total_paid,
+        route_result.total_received,
+    );
+
+    route_result.set_return_data()
+}
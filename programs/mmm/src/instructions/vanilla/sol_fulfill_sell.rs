@@ -9,10 +9,13 @@ use crate::{
     constants::*,
     errors::MMMErrorCode,
     instructions::{get_sell_fulfill_pool_price_info, PoolPriceInfo},
-    state::{Pool, SellState},
+    state::{Pool, SellState, PAUSE_FLAG_VANILLA},
     util::{
-        check_allowlists_for_mint, get_metadata_royalty_bp, log_pool, pay_creator_fees_in_sol,
-        try_close_pool, try_close_sell_state,
+        assert_not_paused, check_allowlists_for_mint, get_fee_schedule, get_global_config,
+        get_metadata_royalty_bp, get_print_edition_royalty_metadata, get_protocol_fee,
+        get_referral_registry, get_royalty_schedule, get_verified_collection, log_pool,
+        pay_creator_fees_in_sol, pay_protocol_fee, pay_referral_fee, should_compound_lp_fee,
+        try_close_pool, try_close_sell_state, FulfillResult, RoyaltyPayment,
     },
     verify_referral::verify_referral,
 };
@@ -23,8 +26,8 @@ pub struct SolFulfillSellArgs {
     pub max_payment_amount: u64,
     pub buyside_creator_royalty_bp: u16,
     pub allowlist_aux: Option<String>, // TODO: use it for future allowlist_aux
-    pub maker_fee_bp: i16,             // will be checked by cosigner
-    pub taker_fee_bp: i16,             // will be checked by cosigner
+    pub maker_fee_bp: i16,             // set by cosigner, capped by the FeeSchedule
+    pub taker_fee_bp: i16,             // set by cosigner, capped by the FeeSchedule
 }
 
 // FulfillSell means a buyer wants to buy NFT/SFT from the pool
@@ -43,10 +46,25 @@ pub struct SolFulfillSell<'info> {
     pub cosigner: Signer<'info>,
     #[account(
         mut,
-        constraint = verify_referral(&pool, &referral) @ MMMErrorCode::InvalidReferral,
+        constraint = verify_referral(&pool, &referral, &global_config) @ MMMErrorCode::InvalidReferral,
     )]
     /// CHECK: use verify_referral to check the referral account
     pub referral: UncheckedAccount<'info>,
+    /// CHECK: it might not be initialized, loaded with get_global_config
+    #[account(seeds = [GLOBAL_CONFIG_PREFIX.as_bytes()], bump)]
+    pub global_config: UncheckedAccount<'info>,
+    /// CHECK: checked against the treasury of global_config in pay_protocol_fee
+    #[account(mut)]
+    pub treasury: UncheckedAccount<'info>,
+    /// CHECK: it might not be initialized, loaded with get_fee_schedule
+    #[account(seeds = [FEE_SCHEDULE_PREFIX.as_bytes(), pool.referral.as_ref()], bump)]
+    pub fee_schedule: UncheckedAccount<'info>,
+    /// CHECK: the royalty schedule pda of the collection, checked in
+    /// get_royalty_schedule, it might not be initialized
+    pub royalty_schedule: UncheckedAccount<'info>,
+    /// CHECK: it might not be initialized, loaded with get_referral_registry
+    #[account(seeds = [REFERRAL_REGISTRY_PREFIX.as_bytes(), pool.referral.as_ref()], bump)]
+    pub referral_registry: UncheckedAccount<'info>,
     #[account(
         mut,
         seeds = [POOL_PREFIX.as_bytes(), owner.key().as_ref(), pool.uuid.as_ref()],
@@ -117,11 +135,24 @@ pub fn handler<'info>(
     ctx: Context<'_, '_, '_, 'info, SolFulfillSell<'info>>,
     args: SolFulfillSellArgs,
 ) -> Result<()> {
+    fulfill_sell(ctx, args)?.set_return_data()
+}
+
+// returns the result of the fulfillment, it's shared with the
+// router so that the same logic is used for each leg of a route
+pub(crate) fn fulfill_sell<'info>(
+    ctx: Context<'_, '_, '_, 'info, SolFulfillSell<'info>>,
+    args: SolFulfillSellArgs,
+) -> Result<FulfillResult> {
     let token_program = &ctx.accounts.token_program;
     let system_program = &ctx.accounts.system_program;
     let owner = &ctx.accounts.owner;
     let referral = &ctx.accounts.referral;
     let pool = &mut ctx.accounts.pool;
+    let global_config = get_global_config(&ctx.accounts.global_config)?;
+    assert_not_paused(&global_config, PAUSE_FLAG_VANILLA)?;
+    let fee_schedule =
+        get_fee_schedule(&pool.referral, &ctx.accounts.fee_schedule, &global_config)?;
     let sell_state = &mut ctx.accounts.sell_state;
 
     let payer = &ctx.accounts.payer;
@@ -146,10 +177,16 @@ pub fn handler<'info>(
         Some(asset_master_edition),
         args.allowlist_aux,
     )?;
+    let parsed_metadata = get_print_edition_royalty_metadata(
+        parsed_metadata,
+        asset_master_edition,
+        ctx.remaining_accounts,
+    )?;
 
     let PoolPriceInfo {
         total_price,
         next_price,
+        spot_price_moved,
         lp_fee,
         maker_fee,
         taker_fee,
@@ -157,11 +194,14 @@ pub fn handler<'info>(
         transfer_sol_to,
     } = get_sell_fulfill_pool_price_info(
         pool,
+        sell_state,
         owner,
         buyside_sol_escrow_account,
         args.asset_amount,
         args.maker_fee_bp,
         args.taker_fee_bp,
+        &global_config,
+        &fee_schedule,
     )?;
 
     // TODO: make sure that the lp fee is paid with the correct amount
@@ -209,34 +249,49 @@ pub fn handler<'info>(
         ))?;
     }
 
+    // compounded lp fees go to the buyside escrow instead of the owner
+    let compound_lp_fee = should_compound_lp_fee(pool, buyside_sol_escrow_account, lp_fee)?;
+    let lp_fee_to = if compound_lp_fee {
+        buyside_sol_escrow_account.to_account_info()
+    } else {
+        owner.to_account_info()
+    };
     if lp_fee > 0 {
-        anchor_lang::solana_program::program::invoke(
-            &anchor_lang::solana_program::system_instruction::transfer(
-                payer.key, owner.key, lp_fee,
-            ),
-            &[
-                payer.to_account_info(),
-                owner.to_account_info(),
-                system_program.to_account_info(),
-            ],
-        )?;
-    }
-
-    if referral_fee > 0 {
         anchor_lang::solana_program::program::invoke(
             &anchor_lang::solana_program::system_instruction::transfer(
                 payer.key,
-                referral.key,
-                referral_fee,
+                lp_fee_to.key,
+                lp_fee,
             ),
             &[
                 payer.to_account_info(),
-                referral.to_account_info(),
+                lp_fee_to,
                 system_program.to_account_info(),
             ],
         )?;
     }
 
+    let referral_registry = get_referral_registry(&ctx.accounts.referral_registry)?;
+    pay_referral_fee(
+        &payer.to_account_info(),
+        &referral.to_account_info(),
+        referral_registry.as_ref(),
+        ctx.remaining_accounts,
+        &system_program.to_account_info(),
+        &[&[&[]]],
+        referral_fee,
+    )?;
+
+    let protocol_fee = get_protocol_fee(&global_config, pool, total_price)?;
+    pay_protocol_fee(
+        payer,
+        &ctx.accounts.treasury,
+        &global_config,
+        system_program,
+        &[&[&[]]],
+        protocol_fee,
+    )?;
+
     pool.spot_price = next_price;
     pool.sellside_asset_amount = pool
         .sellside_asset_amount
@@ -246,9 +301,27 @@ pub fn handler<'info>(
         .lp_fee_earned
         .checked_add(lp_fee)
         .ok_or(MMMErrorCode::NumericOverflow)?;
+    if compound_lp_fee {
+        pool.lp_fee_compounded = pool
+            .lp_fee_compounded
+            .checked_add(lp_fee)
+            .ok_or(MMMErrorCode::NumericOverflow)?;
+    }
 
-    let royalty_bp = get_metadata_royalty_bp(total_price, &parsed_metadata, None);
-    let royalty_paid = pay_creator_fees_in_sol(
+    let royalty_schedule = get_royalty_schedule(
+        get_verified_collection(&parsed_metadata),
+        &ctx.accounts.royalty_schedule,
+    )?;
+    let royalty_bp = get_metadata_royalty_bp(
+        total_price,
+        &parsed_metadata,
+        None,
+        royalty_schedule.as_ref(),
+    );
+    let RoyaltyPayment {
+        total: royalty_paid,
+        accrued: royalty_accrued,
+    } = pay_creator_fees_in_sol(
         args.buyside_creator_royalty_bp,
         total_price,
         &parsed_metadata,
@@ -257,6 +330,7 @@ pub fn handler<'info>(
         royalty_bp,
         &[&[&[]]],
         system_program.to_account_info(),
+        &global_config,
     )?;
 
     // prevent frontrun by pool config changes
@@ -266,6 +340,8 @@ pub fn handler<'info>(
         .checked_add(taker_fee as u64)
         .ok_or(MMMErrorCode::NumericOverflow)?
         .checked_add(royalty_paid)
+        .ok_or(MMMErrorCode::NumericOverflow)?
+        .checked_add(protocol_fee)
         .ok_or(MMMErrorCode::NumericOverflow)?;
     if payment_amount > args.max_payment_amount {
         return Err(MMMErrorCode::InvalidRequestedPrice.into());
@@ -282,11 +358,27 @@ pub fn handler<'info>(
     try_close_pool(pool, owner.to_account_info())?;
 
     msg!(
-        "{{\"lp_fee\":{},\"royalty_paid\":{},\"total_price\":{}}}",
+        "{{\"lp_fee\":{},\"protocol_fee\":{},\"royalty_paid\":{},\"total_price\":{},\"spot_price_moved\":{}}}",
         lp_fee,
+        protocol_fee,
         royalty_paid,
         total_price,
+        spot_price_moved,
     );
 
-    Ok(())
+    Ok(FulfillResult {
+        total_price,
+        lp_fee,
+        maker_fee,
+        taker_fee,
+        protocol_fee,
+        royalty_paid,
+        royalty_accrued,
+        payment_amount,
+        next_price: pool.spot_price,
+        sellside_asset_amount: pool.sellside_asset_amount,
+        transfer_fee_withheld: 0,
+        trade_in_total_price: 0,
+        trade_in_payment_received: 0,
+    })
 }
@@ -0,0 +1,710 @@
+use anchor_lang::{prelude::*, AnchorDeserialize, AnchorSerialize};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+use std::convert::TryFrom;
+
+use crate::{
+    ata::init_if_needed_ata,
+    constants::*,
+    errors::MMMErrorCode,
+    state::{Pool, SellState, PAUSE_FLAG_VANILLA},
+    util::{
+        assert_not_paused, assert_valid_fees_bp, check_allowlists_for_mint,
+        get_buyside_seller_receives, get_fee_schedule, get_global_config, get_lp_fee_bp,
+        get_metadata_royalty_bp, get_print_edition_royalty_metadata, get_protocol_fee,
+        get_referral_registry, get_royalty_schedule, get_sell_state_total_price_and_next_price,
+        get_sol_fee, get_sol_lp_fee, get_sol_total_price_and_next_price, get_verified_collection,
+        log_pool, pay_creator_fees_in_sol, pay_protocol_fee, pay_referral_fee,
+        should_compound_lp_fee, try_close_escrow, try_close_pool, try_close_sell_state,
+        FulfillResult,
+    },
+    verify_referral::verify_referral,
+};
+
+// trait premiums are not supported, the pool buys the in_asset at the plain
+// curve price. Use sol_fulfill_buy to sell an asset with a trait premium
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SolFulfillTradeInArgs {
+    // net amount the payer is willing to pay for the trade-in, negative
+    // means the payer expects to receive at least that amount back
+    pub max_net_payment_amount: i64,
+    pub buyside_creator_royalty_bp: u16,
+    pub allowlist_aux: Option<String>, // TODO: use it for future allowlist_aux
+    pub maker_fee_bp: i16,             // set by cosigner, capped by the FeeSchedule
+    pub taker_fee_bp: i16,             // set by cosigner, capped by the FeeSchedule
+}
+
+// FulfillTradeIn means a holder wants to swap one NFT (in_asset) for another
+// NFT (out_asset) held by the same two sided pool. It's a fulfill buy of the
+// in_asset followed by a fulfill sell of the out_asset, but only the difference
+// of the two legs is settled, plus the fees and royalties of both legs.
+#[derive(Accounts)]
+#[instruction(args:SolFulfillTradeInArgs)]
+pub struct SolFulfillTradeIn<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: we will check the owner field that matches the pool owner
+    #[account(mut)]
+    pub owner: UncheckedAccount<'info>,
+    #[account(constraint = owner.key() != cosigner.key() @ MMMErrorCode::InvalidCosigner)]
+    pub cosigner: Signer<'info>,
+    #[account(
+        mut,
+        constraint = verify_referral(&pool, &referral, &global_config) @ MMMErrorCode::InvalidReferral,
+    )]
+    /// CHECK: use verify_referral to check the referral account
+    pub referral: UncheckedAccount<'info>,
+    /// CHECK: it might not be initialized, loaded with get_global_config
+    #[account(seeds = [GLOBAL_CONFIG_PREFIX.as_bytes()], bump)]
+    pub global_config: UncheckedAccount<'info>,
+    /// CHECK: checked against the treasury of global_config in pay_protocol_fee
+    #[account(mut)]
+    pub treasury: UncheckedAccount<'info>,
+    /// CHECK: it might not be initialized, loaded with get_fee_schedule
+    #[account(seeds = [FEE_SCHEDULE_PREFIX.as_bytes(), pool.referral.as_ref()], bump)]
+    pub fee_schedule: UncheckedAccount<'info>,
+    /// CHECK: the royalty schedule pda of the in_asset collection, checked
+    /// in get_royalty_schedule, it might not be initialized
+    pub in_royalty_schedule: UncheckedAccount<'info>,
+    /// CHECK: the royalty schedule pda of the out_asset collection, checked
+    /// in get_royalty_schedule, it might not be initialized
+    pub out_royalty_schedule: UncheckedAccount<'info>,
+    /// CHECK: it might not be initialized, loaded with get_referral_registry
+    #[account(seeds = [REFERRAL_REGISTRY_PREFIX.as_bytes(), pool.referral.as_ref()], bump)]
+    pub referral_registry: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [POOL_PREFIX.as_bytes(), owner.key().as_ref(), pool.uuid.as_ref()],
+        has_one = owner @ MMMErrorCode::InvalidOwner,
+        has_one = cosigner @ MMMErrorCode::InvalidCosigner,
+        constraint = pool.payment_mint.eq(&Pubkey::default()) @ MMMErrorCode::InvalidPaymentMint,
+        constraint = pool.expiry == 0 || pool.expiry > Clock::get().unwrap().unix_timestamp @ MMMErrorCode::Expired,
+        constraint = !pool.using_shared_escrow() @ MMMErrorCode::InvalidAccountState,
+        constraint = args.buyside_creator_royalty_bp <= 10000 @ MMMErrorCode::InvalidBP,
+        bump
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+    /// CHECK: it's a pda, and the private key is owned by the seeds
+    #[account(
+        mut,
+        seeds = [BUYSIDE_SOL_ESCROW_ACCOUNT_PREFIX.as_bytes(), pool.key().as_ref()],
+        bump,
+    )]
+    pub buyside_sol_escrow_account: UncheckedAccount<'info>,
+
+    // the asset sold into the pool
+    /// CHECK: we will check the metadata in check_allowlists_for_mint()
+    #[account(
+    seeds = [
+        "metadata".as_bytes(),
+        mpl_token_metadata::ID.as_ref(),
+        in_asset_mint.key().as_ref(),
+    ],
+    bump,
+    seeds::program = mpl_token_metadata::ID,
+    )]
+    pub in_asset_metadata: UncheckedAccount<'info>,
+    /// CHECK: we will check the master_edtion in check_allowlists_for_mint()
+    pub in_asset_master_edition: UncheckedAccount<'info>,
+    /// CHECK: check_allowlists_for_mint
+    #[account(constraint = in_asset_mint.key() != out_asset_mint.key() @ MMMErrorCode::InvalidTokenMint)]
+    pub in_asset_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        token::mint = in_asset_mint,
+        token::authority = payer,
+        token::token_program = token_program,
+    )]
+    pub payer_in_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: check in init_if_needed_ata
+    #[account(mut)]
+    pub in_sellside_escrow_token_account: UncheckedAccount<'info>,
+    /// CHECK: check in init_if_needed_ata
+    #[account(mut)]
+    pub owner_in_token_account: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [
+            SELL_STATE_PREFIX.as_bytes(),
+            pool.key().as_ref(),
+            in_asset_mint.key().as_ref(),
+        ],
+        space = SellState::LEN,
+        bump
+    )]
+    pub in_sell_state: Box<Account<'info, SellState>>,
+
+    // the asset bought from the pool
+    /// CHECK: we will check the metadata in check_allowlists_for_mint()
+    #[account(
+    seeds = [
+        "metadata".as_bytes(),
+        mpl_token_metadata::ID.as_ref(),
+        out_asset_mint.key().as_ref(),
+    ],
+    bump,
+    seeds::program = mpl_token_metadata::ID,
+    )]
+    pub out_asset_metadata: UncheckedAccount<'info>,
+    /// CHECK: we will check the master_edtion in check_allowlists_for_mint()
+    pub out_asset_master_edition: UncheckedAccount<'info>,
+    /// CHECK: check_allowlists_for_mint
+    pub out_asset_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = out_asset_mint,
+        associated_token::authority = pool,
+        associated_token::token_program = token_program,
+    )]
+    pub out_sellside_escrow_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = out_asset_mint,
+        associated_token::authority = payer,
+        associated_token::token_program = token_program,
+    )]
+    pub payer_out_asset_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [
+            SELL_STATE_PREFIX.as_bytes(),
+            pool.key().as_ref(),
+            out_asset_mint.key().as_ref(),
+        ],
+        bump
+    )]
+    pub out_sell_state: Box<Account<'info, SellState>>,
+
+    /// CHECK: will be used for allowlist checks
+    pub allowlist_aux_account: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+    // Remaining accounts
+    //   0..N: creator accounts of the in_asset
+    //   N..: creator accounts of the out_asset
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, SolFulfillTradeIn<'info>>,
+    args: SolFulfillTradeInArgs,
+) -> Result<()> {
+    let token_program = &ctx.accounts.token_program;
+    let system_program = &ctx.accounts.system_program;
+    let associated_token_program = &ctx.accounts.associated_token_program;
+    let rent = &ctx.accounts.rent;
+    let owner = &ctx.accounts.owner;
+    let referral = &ctx.accounts.referral;
+    let payer = &ctx.accounts.payer;
+    let pool = &mut ctx.accounts.pool;
+    let global_config = get_global_config(&ctx.accounts.global_config)?;
+    assert_not_paused(&global_config, PAUSE_FLAG_VANILLA)?;
+    let fee_schedule =
+        get_fee_schedule(&pool.referral, &ctx.accounts.fee_schedule, &global_config)?;
+    let buyside_sol_escrow_account = &ctx.accounts.buyside_sol_escrow_account;
+
+    let in_asset_mint = &ctx.accounts.in_asset_mint;
+    let payer_in_asset_account = &ctx.accounts.payer_in_asset_account;
+    let in_sell_state = &mut ctx.accounts.in_sell_state;
+
+    let out_asset_mint = &ctx.accounts.out_asset_mint;
+    let out_sellside_escrow_token_account = &ctx.accounts.out_sellside_escrow_token_account;
+    let payer_out_asset_account = &ctx.accounts.payer_out_asset_account;
+    let out_sell_state = &mut ctx.accounts.out_sell_state;
+
+    let (pool_key, pool_owner, pool_uuid) = (pool.key(), pool.owner, pool.uuid);
+    let pool_seeds: &[&[&[u8]]] = &[&[
+        POOL_PREFIX.as_bytes(),
+        pool_owner.as_ref(),
+        pool_uuid.as_ref(),
+        &[ctx.bumps.pool],
+    ]];
+    let buyside_sol_escrow_account_seeds: &[&[&[u8]]] = &[&[
+        BUYSIDE_SOL_ESCROW_ACCOUNT_PREFIX.as_bytes(),
+        pool_key.as_ref(),
+        &[ctx.bumps.buyside_sol_escrow_account],
+    ]];
+    let remaining_accounts = ctx.remaining_accounts;
+
+    let in_metadata = check_allowlists_for_mint(
+        &pool.allowlists,
+        in_asset_mint,
+        &ctx.accounts.in_asset_metadata,
+        Some(&ctx.accounts.in_asset_master_edition),
+        args.allowlist_aux.clone(),
+    )?;
+    let out_metadata = check_allowlists_for_mint(
+        &pool.allowlists,
+        out_asset_mint,
+        &ctx.accounts.out_asset_metadata,
+        Some(&ctx.accounts.out_asset_master_edition),
+        args.allowlist_aux,
+    )?;
+    let in_metadata = get_print_edition_royalty_metadata(
+        in_metadata,
+        &ctx.accounts.in_asset_master_edition,
+        remaining_accounts,
+    )?;
+    let out_metadata = get_print_edition_royalty_metadata(
+        out_metadata,
+        &ctx.accounts.out_asset_master_edition,
+        remaining_accounts,
+    )?;
+    assert_valid_fees_bp(
+        args.maker_fee_bp,
+        args.taker_fee_bp,
+        &global_config,
+        &fee_schedule,
+    )?;
+
+    // both legs are priced against the escrow balance before the trade
+    let buyside_sol_escrow_balance = buyside_sol_escrow_account.lamports();
+
+    // leg 1: the pool buys the in_asset at the current spot price
+    let (buy_total_price, buy_next_price) = get_sol_total_price_and_next_price(pool, 1, true)?;
+    let in_royalty_schedule = get_royalty_schedule(
+        get_verified_collection(&in_metadata),
+        &ctx.accounts.in_royalty_schedule,
+    )?;
+    let in_royalty_bp = get_metadata_royalty_bp(
+        buy_total_price,
+        &in_metadata,
+        None,
+        in_royalty_schedule.as_ref(),
+    );
+    let seller_receives = get_buyside_seller_receives(
+        buy_total_price,
+        get_lp_fee_bp(pool, buyside_sol_escrow_balance, true)?,
+        in_royalty_bp,
+        pool.buyside_creator_royalty_bp,
+    )?;
+    let buy_lp_fee = get_sol_lp_fee(pool, buyside_sol_escrow_balance, seller_receives, true)?;
+    let buy_maker_fee = get_sol_fee(seller_receives, args.maker_fee_bp)?;
+    let buy_taker_fee = get_sol_fee(seller_receives, args.taker_fee_bp)?;
+
+    // leg 2: the pool sells the out_asset, priced from where leg 1 left the curve
+    let spot_price = pool.spot_price;
+    pool.spot_price = buy_next_price;
+    let (sell_total_price, sell_next_price) =
+        get_sell_state_total_price_and_next_price(pool, out_sell_state, 1)?;
+    pool.spot_price = spot_price;
+    let sell_lp_fee = get_sol_lp_fee(pool, buyside_sol_escrow_balance, sell_total_price, false)?;
+    let sell_maker_fee = get_sol_fee(sell_total_price, args.maker_fee_bp)?;
+    let sell_taker_fee = get_sol_fee(sell_total_price, args.taker_fee_bp)?;
+
+    let (buy_referral_fee, sell_referral_fee) = (
+        u64::try_from(
+            buy_maker_fee
+                .checked_add(buy_taker_fee)
+                .ok_or(MMMErrorCode::NumericOverflow)?,
+        )
+        .map_err(|_| MMMErrorCode::NumericOverflow)?,
+        u64::try_from(
+            sell_maker_fee
+                .checked_add(sell_taker_fee)
+                .ok_or(MMMErrorCode::NumericOverflow)?,
+        )
+        .map_err(|_| MMMErrorCode::NumericOverflow)?,
+    );
+    let buy_protocol_fee = get_protocol_fee(&global_config, pool, seller_receives)?;
+    let sell_protocol_fee = get_protocol_fee(&global_config, pool, sell_total_price)?;
+
+    // transfer the in_asset to the pool or the owner
+    if pool.reinvest_fulfill_buy {
+        let in_sellside_escrow_token_account = ctx
+            .accounts
+            .in_sellside_escrow_token_account
+            .to_account_info();
+        init_if_needed_ata(
+            in_sellside_escrow_token_account.to_account_info(),
+            payer.to_account_info(),
+            pool.to_account_info(),
+            in_asset_mint.to_account_info(),
+            associated_token_program.to_account_info(),
+            token_program.to_account_info(),
+            system_program.to_account_info(),
+            rent.to_account_info(),
+        )?;
+        anchor_spl::token_2022::transfer_checked(
+            CpiContext::new(
+                token_program.to_account_info(),
+                anchor_spl::token_2022::TransferChecked {
+                    from: payer_in_asset_account.to_account_info(),
+                    mint: in_asset_mint.to_account_info(),
+                    to: in_sellside_escrow_token_account,
+                    authority: payer.to_account_info(),
+                },
+            ),
+            1,
+            in_asset_mint.decimals,
+        )?;
+        pool.sellside_asset_amount = pool
+            .sellside_asset_amount
+            .checked_add(1)
+            .ok_or(MMMErrorCode::NumericOverflow)?;
+        in_sell_state.pool = pool.key();
+        in_sell_state.pool_owner = owner.key();
+        in_sell_state.asset_mint = in_asset_mint.key();
+        in_sell_state.cosigner_annotation = pool.cosigner_annotation;
+        in_sell_state.asset_amount = in_sell_state
+            .asset_amount
+            .checked_add(1)
+            .ok_or(MMMErrorCode::NumericOverflow)?;
+    } else {
+        let owner_in_token_account = ctx.accounts.owner_in_token_account.to_account_info();
+        init_if_needed_ata(
+            owner_in_token_account.to_account_info(),
+            payer.to_account_info(),
+            owner.to_account_info(),
+            in_asset_mint.to_account_info(),
+            associated_token_program.to_account_info(),
+            token_program.to_account_info(),
+            system_program.to_account_info(),
+            rent.to_account_info(),
+        )?;
+        anchor_spl::token_2022::transfer_checked(
+            CpiContext::new(
+                token_program.to_account_info(),
+                anchor_spl::token_2022::TransferChecked {
+                    from: payer_in_asset_account.to_account_info(),
+                    mint: in_asset_mint.to_account_info(),
+                    to: owner_in_token_account,
+                    authority: payer.to_account_info(),
+                },
+            ),
+            1,
+            in_asset_mint.decimals,
+        )?;
+    }
+    if payer_in_asset_account.amount == 1 {
+        anchor_spl::token_2022::close_account(CpiContext::new(
+            token_program.to_account_info(),
+            anchor_spl::token_2022::CloseAccount {
+                account: payer_in_asset_account.to_account_info(),
+                destination: payer.to_account_info(),
+                authority: payer.to_account_info(),
+            },
+        ))?;
+    }
+
+    // transfer the out_asset to the payer
+    anchor_spl::token_2022::transfer_checked(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            anchor_spl::token_2022::TransferChecked {
+                from: out_sellside_escrow_token_account.to_account_info(),
+                mint: out_asset_mint.to_account_info(),
+                to: payer_out_asset_account.to_account_info(),
+                authority: pool.to_account_info(),
+            },
+            pool_seeds,
+        ),
+        1,
+        out_asset_mint.decimals,
+    )?;
+    if out_sellside_escrow_token_account.amount == 1 {
+        anchor_spl::token_2022::close_account(CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            anchor_spl::token_2022::CloseAccount {
+                account: out_sellside_escrow_token_account.to_account_info(),
+                destination: owner.to_account_info(),
+                authority: pool.to_account_info(),
+            },
+            pool_seeds,
+        ))?;
+    }
+
+    // royalties: the pool pays for the in_asset, the payer pays for the out_asset
+    let in_creator_count = in_metadata.creators.as_ref().map_or(0, |c| c.len());
+    if remaining_accounts.len() < in_creator_count {
+        return Err(MMMErrorCode::InvalidRemainingAccounts.into());
+    }
+    let (in_creator_accounts, out_creator_accounts) = remaining_accounts.split_at(in_creator_count);
+    let in_royalty = pay_creator_fees_in_sol(
+        pool.buyside_creator_royalty_bp,
+        seller_receives,
+        &in_metadata,
+        in_creator_accounts,
+        buyside_sol_escrow_account.to_account_info(),
+        in_royalty_bp,
+        buyside_sol_escrow_account_seeds,
+        system_program.to_account_info(),
+        &global_config,
+    )?;
+    let out_royalty_schedule = get_royalty_schedule(
+        get_verified_collection(&out_metadata),
+        &ctx.accounts.out_royalty_schedule,
+    )?;
+    let out_royalty_bp = get_metadata_royalty_bp(
+        sell_total_price,
+        &out_metadata,
+        None,
+        out_royalty_schedule.as_ref(),
+    );
+    let out_royalty = pay_creator_fees_in_sol(
+        args.buyside_creator_royalty_bp,
+        sell_total_price,
+        &out_metadata,
+        out_creator_accounts,
+        payer.to_account_info(),
+        out_royalty_bp,
+        &[&[&[]]],
+        system_program.to_account_info(),
+        &global_config,
+    )?;
+
+    // settle the difference of the two legs, what the payer would receive for
+    // the in_asset is netted against what the payer would pay for the out_asset
+    let seller_payment = buy_total_price
+        .checked_sub(buy_lp_fee)
+        .ok_or(MMMErrorCode::NumericOverflow)?
+        .checked_sub(buy_taker_fee as u64)
+        .ok_or(MMMErrorCode::NumericOverflow)?
+        .checked_sub(in_royalty.total)
+        .ok_or(MMMErrorCode::NumericOverflow)?
+        .checked_sub(buy_protocol_fee)
+        .ok_or(MMMErrorCode::NumericOverflow)?;
+    let buyer_payment = u64::try_from(
+        i64::try_from(sell_total_price)
+            .map_err(|_| MMMErrorCode::NumericOverflow)?
+            .checked_sub(sell_maker_fee)
+            .ok_or(MMMErrorCode::NumericOverflow)?,
+    )
+    .map_err(|_| MMMErrorCode::NumericOverflow)?;
+
+    let escrow_info = buyside_sol_escrow_account.to_account_info();
+    let payer_info = payer.to_account_info();
+    let owner_info = owner.to_account_info();
+    let system_program_info = system_program.to_account_info();
+    let transfer_sol_to = if pool.reinvest_fulfill_sell {
+        escrow_info.clone()
+    } else {
+        owner_info.clone()
+    };
+    let netted_payment = seller_payment.min(buyer_payment);
+    if !pool.reinvest_fulfill_sell && netted_payment > 0 {
+        anchor_lang::solana_program::program::invoke_signed(
+            &anchor_lang::solana_program::system_instruction::transfer(
+                escrow_info.key,
+                owner_info.key,
+                netted_payment,
+            ),
+            &[
+                escrow_info.clone(),
+                owner_info.clone(),
+                system_program_info.clone(),
+            ],
+            buyside_sol_escrow_account_seeds,
+        )?;
+    }
+    if buyer_payment > seller_payment {
+        anchor_lang::solana_program::program::invoke(
+            &anchor_lang::solana_program::system_instruction::transfer(
+                payer_info.key,
+                transfer_sol_to.key,
+                buyer_payment - seller_payment,
+            ),
+            &[
+                payer_info.clone(),
+                transfer_sol_to.clone(),
+                system_program_info.clone(),
+            ],
+        )?;
+    } else if seller_payment > buyer_payment {
+        anchor_lang::solana_program::program::invoke_signed(
+            &anchor_lang::solana_program::system_instruction::transfer(
+                escrow_info.key,
+                payer_info.key,
+                seller_payment - buyer_payment,
+            ),
+            &[
+                escrow_info.clone(),
+                payer_info.clone(),
+                system_program_info.clone(),
+            ],
+            buyside_sol_escrow_account_seeds,
+        )?;
+    }
+
+    // lp fees and referral fees of both legs, compounded lp fees stay in or go
+    // to the buyside escrow instead of the owner
+    let referral_info = referral.to_account_info();
+    let compound_sell_lp_fee = should_compound_lp_fee(pool, &escrow_info, sell_lp_fee)?;
+    if buy_lp_fee > 0 && !pool.compound_lp_fee {
+        anchor_lang::solana_program::program::invoke_signed(
+            &anchor_lang::solana_program::system_instruction::transfer(
+                escrow_info.key,
+                owner_info.key,
+                buy_lp_fee,
+            ),
+            &[
+                escrow_info.clone(),
+                owner_info.clone(),
+                system_program_info.clone(),
+            ],
+            buyside_sol_escrow_account_seeds,
+        )?;
+    }
+    if sell_lp_fee > 0 {
+        let sell_lp_fee_to = if compound_sell_lp_fee {
+            &escrow_info
+        } else {
+            &owner_info
+        };
+        anchor_lang::solana_program::program::invoke(
+            &anchor_lang::solana_program::system_instruction::transfer(
+                payer_info.key,
+                sell_lp_fee_to.key,
+                sell_lp_fee,
+            ),
+            &[
+                payer_info.clone(),
+                sell_lp_fee_to.clone(),
+                system_program_info.clone(),
+            ],
+        )?;
+    }
+    let referral_registry = get_referral_registry(&ctx.accounts.referral_registry)?;
+    pay_referral_fee(
+        &escrow_info,
+        &referral_info,
+        referral_registry.as_ref(),
+        ctx.remaining_accounts,
+        &system_program_info,
+        buyside_sol_escrow_account_seeds,
+        buy_referral_fee,
+    )?;
+    pay_referral_fee(
+        &payer_info,
+        &referral_info,
+        referral_registry.as_ref(),
+        ctx.remaining_accounts,
+        &system_program_info,
+        &[&[&[]]],
+        sell_referral_fee,
+    )?;
+
+    // protocol fees of both legs
+    let treasury_info = ctx.accounts.treasury.to_account_info();
+    pay_protocol_fee(
+        &escrow_info,
+        &treasury_info,
+        &global_config,
+        &system_program_info,
+        buyside_sol_escrow_account_seeds,
+        buy_protocol_fee,
+    )?;
+    pay_protocol_fee(
+        &payer_info,
+        &treasury_info,
+        &global_config,
+        &system_program_info,
+        &[&[&[]]],
+        sell_protocol_fee,
+    )?;
+
+    // prevent frontrun by pool config changes
+    let net_payment_amount = i128::from(sell_total_price)
+        + i128::from(sell_lp_fee)
+        + i128::from(sell_taker_fee)
+        + i128::from(out_royalty.total)
+        + i128::from(sell_protocol_fee)
+        - i128::from(seller_payment);
+    if net_payment_amount > i128::from(args.max_net_payment_amount) {
+        return Err(MMMErrorCode::InvalidRequestedPrice.into());
+    }
+
+    pool.spot_price = sell_next_price;
+    pool.sellside_asset_amount = pool
+        .sellside_asset_amount
+        .checked_sub(1)
+        .ok_or(MMMErrorCode::NumericOverflow)?;
+    pool.lp_fee_earned = pool
+        .lp_fee_earned
+        .checked_add(buy_lp_fee)
+        .ok_or(MMMErrorCode::NumericOverflow)?
+        .checked_add(sell_lp_fee)
+        .ok_or(MMMErrorCode::NumericOverflow)?;
+    if pool.compound_lp_fee {
+        pool.lp_fee_compounded = pool
+            .lp_fee_compounded
+            .checked_add(buy_lp_fee)
+            .ok_or(MMMErrorCode::NumericOverflow)?;
+    }
+    if compound_sell_lp_fee {
+        pool.lp_fee_compounded = pool
+            .lp_fee_compounded
+            .checked_add(sell_lp_fee)
+            .ok_or(MMMErrorCode::NumericOverflow)?;
+    }
+
+    out_sell_state.asset_amount = out_sell_state
+        .asset_amount
+        .checked_sub(1)
+        .ok_or(MMMErrorCode::NumericOverflow)?;
+    try_close_sell_state(out_sell_state, owner.to_account_info())?;
+    try_close_sell_state(in_sell_state, payer.to_account_info())?;
+
+    try_close_escrow(
+        &escrow_info,
+        pool,
+        system_program,
+        buyside_sol_escrow_account_seeds,
+        &global_config,
+    )?;
+    pool.buyside_payment_amount = buyside_sol_escrow_account.lamports();
+    log_pool("post_sol_fulfill_trade_in", pool)?;
+    try_close_pool(pool, owner.to_account_info())?;
+
+    let lp_fee = buy_lp_fee
+        .checked_add(sell_lp_fee)
+        .ok_or(MMMErrorCode::NumericOverflow)?;
+    let protocol_fee = buy_protocol_fee
+        .checked_add(sell_protocol_fee)
+        .ok_or(MMMErrorCode::NumericOverflow)?;
+    let royalty_paid = in_royalty
+        .total
+        .checked_add(out_royalty.total)
+        .ok_or(MMMErrorCode::NumericOverflow)?;
+    msg!(
+        "{{\"lp_fee\":{},\"protocol_fee\":{},\"royalty_paid\":{},\"buy_total_price\":{},\"sell_total_price\":{},\"net_payment_amount\":{},\"spot_price_moved\":{}}}",
+        lp_fee,
+        protocol_fee,
+        royalty_paid,
+        buy_total_price,
+        sell_total_price,
+        net_payment_amount,
+        sell_next_price != spot_price,
+    );
+
+    FulfillResult {
+        total_price: sell_total_price,
+        lp_fee,
+        maker_fee: buy_maker_fee
+            .checked_add(sell_maker_fee)
+            .ok_or(MMMErrorCode::NumericOverflow)?,
+        taker_fee: buy_taker_fee
+            .checked_add(sell_taker_fee)
+            .ok_or(MMMErrorCode::NumericOverflow)?,
+        protocol_fee,
+        royalty_paid,
+        royalty_accrued: in_royalty
+            .accrued
+            .checked_add(out_royalty.accrued)
+            .ok_or(MMMErrorCode::NumericOverflow)?,
+        payment_amount: u64::try_from(net_payment_amount.max(0))
+            .map_err(|_| MMMErrorCode::NumericOverflow)?,
+        next_price: sell_next_price,
+        sellside_asset_amount: pool.sellside_asset_amount,
+        transfer_fee_withheld: 0,
+        trade_in_total_price: buy_total_price,
+        trade_in_payment_received: u64::try_from((-net_payment_amount).max(0))
+            .map_err(|_| MMMErrorCode::NumericOverflow)?,
+    }
+    .set_return_data()
+}
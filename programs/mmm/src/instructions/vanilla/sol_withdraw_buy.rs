@@ -2,7 +2,7 @@ use crate::{
     constants::*,
     errors::MMMErrorCode,
     state::Pool,
-    util::{log_pool, try_close_escrow, try_close_pool},
+    util::{get_global_config, log_pool, try_close_escrow, try_close_pool},
 };
 use anchor_lang::{prelude::*, AnchorDeserialize, AnchorSerialize};
 
@@ -34,6 +34,9 @@ pub struct SolWithdrawBuy<'info> {
         bump,
     )]
     pub buyside_sol_escrow_account: UncheckedAccount<'info>,
+    /// CHECK: it might not be initialized, loaded with get_global_config
+    #[account(seeds = [GLOBAL_CONFIG_PREFIX.as_bytes()], bump)]
+    pub global_config: UncheckedAccount<'info>,
     pub system_program: Program<'info, System>,
 }
 
@@ -42,6 +45,7 @@ pub fn handler(ctx: Context<SolWithdrawBuy>, args: SolWithdrawBuyArgs) -> Result
     let buyside_sol_escrow_account = &ctx.accounts.buyside_sol_escrow_account;
     let system_program = &ctx.accounts.system_program;
     let pool = &mut ctx.accounts.pool;
+    let global_config = get_global_config(&ctx.accounts.global_config)?;
     let pool_key = pool.key();
     let buyside_sol_escrow_account_seeds: &[&[&[u8]]] = &[&[
         BUYSIDE_SOL_ESCROW_ACCOUNT_PREFIX.as_bytes(),
@@ -72,6 +76,7 @@ pub fn handler(ctx: Context<SolWithdrawBuy>, args: SolWithdrawBuyArgs) -> Result
         pool,
         system_program,
         buyside_sol_escrow_account_seeds,
+        &global_config,
     )?;
 
     pool.buyside_payment_amount = buyside_sol_escrow_account.lamports();
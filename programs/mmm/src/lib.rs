@@ -6,9 +6,11 @@ use anchor_lang::{prelude::*, AnchorDeserialize, AnchorSerialize};
 declare_id!("mmm3XBJg5gk8XJxEKBvdgptZz6SgK4tXvn36sodowMc");
 
 mod ata;
+pub mod auth_rules;
 mod constants;
 mod errors;
 pub mod instructions;
+pub mod royalty_metadata;
 pub mod state;
 pub mod util;
 pub mod verify_referral;
@@ -60,6 +62,20 @@ pub mod mmm {
         instructions::sol_fulfill_sell::handler(ctx, args)
     }
 
+    pub fn sol_fulfill_route<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SolFulfillRoute<'info>>,
+        args: SolFulfillRouteArgs,
+    ) -> Result<()> {
+        instructions::sol_fulfill_route::handler(ctx, args)
+    }
+
+    pub fn sol_fulfill_trade_in<'info>(
+        ctx: Context<'_, '_, '_, 'info, SolFulfillTradeIn<'info>>,
+        args: SolFulfillTradeInArgs,
+    ) -> Result<()> {
+        instructions::sol_fulfill_trade_in::handler(ctx, args)
+    }
+
     pub fn withdraw_sell(ctx: Context<WithdrawSell>, args: WithdrawSellArgs) -> Result<()> {
         instructions::withdraw_sell::handler(ctx, args)
     }
@@ -96,7 +112,7 @@ pub mod mmm {
 
     pub fn mip1_withdraw_sell(
         ctx: Context<Mip1WithdrawSell>,
-        args: WithdrawSellArgs,
+        args: Mip1WithdrawSellArgs,
     ) -> Result<()> {
         instructions::mip1_withdraw_sell::handler(ctx, args)
     }
@@ -188,4 +204,92 @@ pub mod mmm {
     ) -> Result<()> {
         instructions::sol_cnft_fulfill_buy::handler(ctx, args)
     }
+
+    pub fn set_sell_state_price_override(
+        ctx: Context<SetSellStatePriceOverride>,
+        args: SetSellStatePriceOverrideArgs,
+    ) -> Result<()> {
+        instructions::set_sell_state_price_override::handler(ctx, args)
+    }
+
+    pub fn mip1_list_sell(ctx: Context<Mip1ListSell>, args: DepositSellArgs) -> Result<()> {
+        instructions::mip1_list_sell::handler(ctx, args)
+    }
+
+    pub fn mip1_delist_sell(ctx: Context<Mip1DelistSell>) -> Result<()> {
+        instructions::mip1_delist_sell::handler(ctx)
+    }
+
+    pub fn mip1_release_stale_listing(ctx: Context<Mip1ReleaseStaleListing>) -> Result<()> {
+        instructions::mip1_release_stale_listing::handler(ctx)
+    }
+
+    pub fn set_extension_policy(
+        ctx: Context<SetExtensionPolicy>,
+        args: SetExtensionPolicyArgs,
+    ) -> Result<()> {
+        instructions::set_extension_policy::handler(ctx, args)
+    }
+
+    pub fn mpl_core_list_sell(
+        ctx: Context<MplCoreListSell>,
+        args: MplCoreDepositSellArgs,
+    ) -> Result<()> {
+        instructions::mpl_core_list_sell::handler(ctx, args)
+    }
+
+    pub fn mpl_core_release_stale_listing(ctx: Context<MplCoreReleaseStaleListing>) -> Result<()> {
+        instructions::mpl_core_release_stale_listing::handler(ctx)
+    }
+
+    pub fn claim_royalties(ctx: Context<ClaimRoyalties>) -> Result<()> {
+        instructions::claim_royalties::handler(ctx)
+    }
+
+    pub fn set_royalty_schedule(
+        ctx: Context<SetRoyaltySchedule>,
+        args: SetRoyaltyScheduleArgs,
+    ) -> Result<()> {
+        instructions::set_royalty_schedule::handler(ctx, args)
+    }
+
+    pub fn init_global_config(
+        ctx: Context<InitGlobalConfig>,
+        args: InitGlobalConfigArgs,
+    ) -> Result<()> {
+        instructions::init_global_config::handler(ctx, args)
+    }
+
+    pub fn update_global_config(
+        ctx: Context<UpdateGlobalConfig>,
+        args: UpdateGlobalConfigArgs,
+    ) -> Result<()> {
+        instructions::update_global_config::handler(ctx, args)
+    }
+
+    pub fn set_protocol_fee_exemption(
+        ctx: Context<SetProtocolFeeExemption>,
+        args: SetProtocolFeeExemptionArgs,
+    ) -> Result<()> {
+        instructions::set_protocol_fee_exemption::handler(ctx, args)
+    }
+
+    pub fn accept_global_config_admin(ctx: Context<AcceptGlobalConfigAdmin>) -> Result<()> {
+        instructions::accept_global_config_admin::handler(ctx)
+    }
+
+    pub fn set_pause_flags(ctx: Context<SetPauseFlags>, args: SetPauseFlagsArgs) -> Result<()> {
+        instructions::set_pause_flags::handler(ctx, args)
+    }
+
+    pub fn set_referral_registry(
+        ctx: Context<SetReferralRegistry>,
+        args: SetReferralRegistryArgs,
+    ) -> Result<()> {
+        instructions::set_referral_registry::handler(ctx, args)
+    }
+
+    pub fn set_fee_schedule(ctx: Context<SetFeeSchedule>, args: SetFeeScheduleArgs) -> Result<()> {
+        instructions::set_fee_schedule::handler(ctx, args)
+    }
 }
@@ -0,0 +1,302 @@
+use std::str::FromStr;
+
+use anchor_lang::prelude::*;
+use mpl_token_metadata::types::Creator;
+
+use crate::{errors::MMMErrorCode, util::MetadataTrait};
+
+// Royalties of token22 assets are recorded in TokenMetadata.additional_metadata
+// with keys tagged by `_ro<version>_`, the supported versions are
+//   - `_ro_<creator>`: bp, a single creator with 100% share (current)
+//   - `_roa_<creator>`: share of each creator, and `_ros_`: bp (legacy)
+// Keys with any other version tag are from future encodings and are ignored,
+// so that new encodings don't brick the trading of existing assets.
+pub const ROYALTY_KEY_PREFIX: &str = "_ro";
+pub const ROYALTY_VERSION_CURRENT: &str = "";
+pub const ROYALTY_VERSION_LEGACY_CREATOR: &str = "a";
+pub const ROYALTY_VERSION_LEGACY_BP: &str = "s";
+pub const MAX_ROYALTY_CREATORS: usize = 5;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RoyaltyMetadata {
+    pub seller_fee_basis_points: u16,
+    pub creators: Vec<Creator>,
+}
+
+impl MetadataTrait for RoyaltyMetadata {
+    fn get_seller_fee_basis_points(&self) -> u16 {
+        self.seller_fee_basis_points
+    }
+
+    fn get_creators(&self) -> Option<Vec<Creator>> {
+        Some(self.creators.clone())
+    }
+}
+
+// splits `_ro<version>_<rest>` into (version, rest), version is alphanumeric
+fn split_royalty_key(key: &str) -> Option<(&str, &str)> {
+    let tagged = key.strip_prefix(ROYALTY_KEY_PREFIX)?;
+    let (version, rest) = tagged.split_once('_')?;
+    if !version.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return None;
+    }
+    Some((version, rest))
+}
+
+fn parse_bp(value: &str) -> Result<u16> {
+    let bp = value
+        .parse::<u16>()
+        .map_err(|_| MMMErrorCode::InvalidMetadataCreatorRoyalty)?;
+    if bp > 10_000 {
+        return Err(MMMErrorCode::InvalidBP.into());
+    }
+    Ok(bp)
+}
+
+fn parse_creator(value: &str) -> Result<Pubkey> {
+    Pubkey::from_str(value).map_err(|_| MMMErrorCode::InvalidCreatorAddress.into())
+}
+
+// returns None if there is no royalty recorded in the additional metadata, the
+// current encoding takes precedence over the legacy encoding if both are present
+pub fn parse_royalty_metadata(
+    additional_metadata: &[(String, String)],
+) -> Result<Option<RoyaltyMetadata>> {
+    let mut current: Option<RoyaltyMetadata> = None;
+    let mut legacy_bp: Option<u16> = None;
+    let mut legacy_creators: Vec<Creator> = vec![];
+
+    for (key, value) in additional_metadata.iter() {
+        let (version, rest) = match split_royalty_key(key) {
+            Some(v) => v,
+            None => continue,
+        };
+        match version {
+            ROYALTY_VERSION_CURRENT => {
+                let creator = parse_creator(rest)?;
+                let bp = parse_bp(value)?;
+                // use the first seen royalty
+                if current.is_none() {
+                    current = Some(RoyaltyMetadata {
+                        seller_fee_basis_points: bp,
+                        creators: vec![Creator {
+                            address: creator,
+                            verified: false,
+                            share: 100,
+                        }],
+                    });
+                }
+            }
+            ROYALTY_VERSION_LEGACY_CREATOR => {
+                let creator = parse_creator(rest)?;
+                let share = value
+                    .parse::<u8>()
+                    .map_err(|_| MMMErrorCode::InvalidMetadataCreatorRoyalty)?;
+                if legacy_creators.iter().any(|c| c.address == creator)
+                    || legacy_creators.len() >= MAX_ROYALTY_CREATORS
+                {
+                    return Err(MMMErrorCode::InvalidCreatorAddress.into());
+                }
+                legacy_creators.push(Creator {
+                    address: creator,
+                    verified: false,
+                    share,
+                });
+            }
+            ROYALTY_VERSION_LEGACY_BP => {
+                if !rest.is_empty() {
+                    continue;
+                }
+                let bp = parse_bp(value)?;
+                // use the first seen royalty
+                if legacy_bp.is_none() {
+                    legacy_bp = Some(bp);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if current.is_some() {
+        return Ok(current);
+    }
+    if legacy_creators.is_empty() && legacy_bp.is_none() {
+        return Ok(None);
+    }
+
+    let total_share = legacy_creators.iter().map(|c| c.share as u16).sum::<u16>();
+    match legacy_bp {
+        Some(bp) if !legacy_creators.is_empty() && total_share == 100 => {
+            Ok(Some(RoyaltyMetadata {
+                seller_fee_basis_points: bp,
+                creators: legacy_creators,
+            }))
+        }
+        _ => Err(MMMErrorCode::InvalidMetadataCreatorRoyalty.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // xorshift64, deterministic so that failures can be reproduced with the seed
+    struct XorShift(u64);
+
+    impl XorShift {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, n: u64) -> u64 {
+            self.next() % n
+        }
+
+        fn string(&mut self) -> String {
+            const CHARS: &[char] = &['_', 'r', 'o', 'a', 's', 'v', '1', '0', '-', ' ', 'é', '日'];
+            let len = self.below(12);
+            (0..len)
+                .map(|_| CHARS[self.below(CHARS.len() as u64) as usize])
+                .collect()
+        }
+
+        fn number(&mut self) -> String {
+            match self.below(4) {
+                0 => self.below(101).to_string(),
+                1 => self.below(20_000).to_string(),
+                2 => self.next().to_string(),
+                _ => format!("-{}", self.below(100)),
+            }
+        }
+
+        fn key(&mut self, creators: &[Pubkey]) -> String {
+            let creator = creators[self.below(creators.len() as u64) as usize];
+            match self.below(8) {
+                0 => format!("_ro_{}", creator),
+                1 => format!("_roa_{}", creator),
+                2 => "_ros_".to_string(),
+                3 => format!("_rov{}_{}", self.below(10), creator),
+                4 => format!("_ro_{}", self.string()),
+                5 => format!("_roa_{}", self.string()),
+                _ => self.string(),
+            }
+        }
+
+        fn value(&mut self) -> String {
+            if self.below(4) == 0 {
+                self.string()
+            } else {
+                self.number()
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_royalty_metadata() {
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let royalty = parse_royalty_metadata(&[
+            (format!("_roa_{}", a), "70".to_string()),
+            (format!("_roa_{}", b), "30".to_string()),
+            ("_ros_".to_string(), "500".to_string()),
+        ])
+        .unwrap()
+        .unwrap();
+        assert_eq!(royalty.seller_fee_basis_points, 500);
+        assert_eq!(royalty.creators.len(), 2);
+        assert_eq!(
+            (royalty.creators[0].address, royalty.creators[0].share),
+            (a, 70)
+        );
+        assert_eq!(
+            (royalty.creators[1].address, royalty.creators[1].share),
+            (b, 30)
+        );
+
+        let royalty = parse_royalty_metadata(&[
+            ("name".to_string(), "value".to_string()),
+            (format!("_rov2_{}", b), "garbage".to_string()),
+            (format!("_ro_{}", a), "300".to_string()),
+        ])
+        .unwrap()
+        .unwrap();
+        assert_eq!(royalty.seller_fee_basis_points, 300);
+        assert_eq!(royalty.creators[0].share, 100);
+
+        assert_eq!(
+            parse_royalty_metadata(&[("name".to_string(), "value".to_string())]).unwrap(),
+            None
+        );
+
+        // shares must add up to 100
+        assert!(parse_royalty_metadata(&[
+            (format!("_roa_{}", a), "70".to_string()),
+            ("_ros_".to_string(), "500".to_string()),
+        ])
+        .is_err());
+        assert!(parse_royalty_metadata(&[(format!("_ro_{}", a), "x".to_string())]).is_err());
+        assert!(parse_royalty_metadata(&[("_ro_x".to_string(), "100".to_string())]).is_err());
+        assert!(parse_royalty_metadata(&[(format!("_ro_{}", a), "10001".to_string())]).is_err());
+    }
+
+    #[test]
+    fn test_parse_royalty_metadata_fuzz() {
+        let creators: Vec<Pubkey> = (0..6).map(|_| Pubkey::new_unique()).collect();
+        let mut rng = XorShift(0x9e37_79b9_7f4a_7c15);
+        for _ in 0..20_000 {
+            let len = rng.below(8);
+            let additional_metadata: Vec<(String, String)> = (0..len)
+                .map(|_| (rng.key(&creators), rng.value()))
+                .collect();
+
+            // must never panic, and a parsed royalty must always be payable
+            if let Ok(Some(royalty)) = parse_royalty_metadata(&additional_metadata) {
+                assert!(royalty.seller_fee_basis_points <= 10_000);
+                assert!(!royalty.creators.is_empty());
+                assert!(royalty.creators.len() <= MAX_ROYALTY_CREATORS);
+                assert_eq!(
+                    royalty.creators.iter().map(|c| c.share as u16).sum::<u16>(),
+                    100
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_royalty_metadata_fuzz_valid_legacy() {
+        let mut rng = XorShift(0x2545_f491_4f6c_dd1d);
+        for _ in 0..2_000 {
+            let creators_len = 1 + rng.below(MAX_ROYALTY_CREATORS as u64) as usize;
+            let mut shares: Vec<u8> = vec![0; creators_len];
+            for _ in 0..100 {
+                shares[rng.below(creators_len as u64) as usize] += 1;
+            }
+            let bp = rng.below(10_001) as u16;
+
+            let mut additional_metadata: Vec<(String, String)> = vec![];
+            let mut expected: Vec<(Pubkey, u8)> = vec![];
+            for share in shares.iter() {
+                let creator = Pubkey::new_unique();
+                additional_metadata.push((format!("_roa_{}", creator), share.to_string()));
+                expected.push((creator, *share));
+            }
+            let bp_idx = rng.below(additional_metadata.len() as u64 + 1) as usize;
+            additional_metadata.insert(bp_idx, ("_ros_".to_string(), bp.to_string()));
+            let noise_idx = rng.below(additional_metadata.len() as u64 + 1) as usize;
+            additional_metadata.insert(noise_idx, (format!("x{}", rng.string()), rng.value()));
+
+            let royalty = parse_royalty_metadata(&additional_metadata)
+                .unwrap()
+                .unwrap();
+            assert_eq!(royalty.seller_fee_basis_points, bp);
+            let parsed: Vec<(Pubkey, u8)> = royalty
+                .creators
+                .iter()
+                .map(|c| (c.address, c.share))
+                .collect();
+            assert_eq!(parsed, expected);
+        }
+    }
+}
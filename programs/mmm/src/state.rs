@@ -2,12 +2,17 @@ use std::ops::Deref;
 
 use anchor_lang::{prelude::*, AnchorDeserialize, AnchorSerialize};
 use mpl_bubblegum::accounts::TreeConfig;
+use spl_token_2022::extension::ExtensionType;
 
 use crate::constants::*;
 
 pub const CURVE_KIND_LINEAR: u8 = 0;
 pub const CURVE_KIND_EXP: u8 = 1;
 
+pub const PRICE_OVERRIDE_KIND_NONE: u8 = 0;
+pub const PRICE_OVERRIDE_KIND_ABSOLUTE: u8 = 1;
+pub const PRICE_OVERRIDE_KIND_PREMIUM: u8 = 2;
+
 pub const ALLOWLIST_KIND_EMPTY: u8 = 0;
 pub const ALLOWLIST_KIND_FVCA: u8 = 1;
 pub const ALLOWLIST_KIND_MINT: u8 = 2;
@@ -15,9 +20,21 @@ pub const ALLOWLIST_KIND_MCC: u8 = 3;
 pub const ALLOWLIST_KIND_METADATA: u8 = 4;
 pub const ALLOWLIST_KIND_GROUP: u8 = 5;
 pub const ALLOWLIST_KIND_MPL_CORE_COLLECTION: u8 = 6;
+pub const ALLOWLIST_KIND_EXT_CREATOR: u8 = 7;
+pub const ALLOWLIST_KIND_EXT_COLLECTION: u8 = 8;
+pub const ALLOWLIST_KIND_MASTER_EDITION: u8 = 9;
 // ANY nft will pass the allowlist check, please make sure to use cosigner to check NFT validity
 pub const ALLOWLIST_KIND_ANY: u8 = u8::MAX;
 
+// bits of GlobalConfig.pause_flags, PAUSE_FLAG_ALL halts every asset family
+pub const PAUSE_FLAG_ALL: u8 = 1 << 0;
+pub const PAUSE_FLAG_VANILLA: u8 = 1 << 1;
+pub const PAUSE_FLAG_OCP: u8 = 1 << 2;
+pub const PAUSE_FLAG_MIP1: u8 = 1 << 3;
+pub const PAUSE_FLAG_EXT: u8 = 1 << 4;
+pub const PAUSE_FLAG_MPL_CORE: u8 = 1 << 5;
+pub const PAUSE_FLAG_CNFT: u8 = 1 << 6;
+
 #[derive(Default, Copy, Clone, AnchorSerialize, AnchorDeserialize)]
 pub struct Allowlist {
     pub kind: u8,
@@ -32,10 +49,12 @@ impl Allowlist {
     // kind == 4: metadata
     // kind == 5: group extension
     // kind == 6: upgrade authority
-    // kind == 7,8,... will be supported in the future
+    // kind == 7: token22 royalty creator, who is also the update authority of the group
+    // kind == 8: token22 group update authority
+    // kind == 9,10,... will be supported in the future
     // kind == 255: any
     pub fn valid(&self) -> bool {
-        if self.kind > ALLOWLIST_KIND_MPL_CORE_COLLECTION && self.kind != ALLOWLIST_KIND_ANY {
+        if self.kind > ALLOWLIST_KIND_MASTER_EDITION && self.kind != ALLOWLIST_KIND_ANY {
             return false;
         }
         if self.kind != 0 && self.kind != ALLOWLIST_KIND_ANY {
@@ -49,6 +68,44 @@ impl Allowlist {
     }
 }
 
+#[derive(Default, Copy, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct PriceOverride {
+    pub kind: u8,
+    pub value: u64,
+}
+
+impl PriceOverride {
+    // kind == 0: none, the asset trades at the curve price
+    // kind == 1: absolute price per unit in lamports, the curve is not moved
+    // kind == 2: premium in bp on top of the curve price, the curve moves as usual
+    pub fn valid(&self) -> bool {
+        match self.kind {
+            PRICE_OVERRIDE_KIND_NONE => self.value == 0,
+            PRICE_OVERRIDE_KIND_ABSOLUTE => self.value > 0 && self.value <= MAX_TOTAL_PRICE,
+            PRICE_OVERRIDE_KIND_PREMIUM => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_none(&self) -> bool {
+        self.kind == PRICE_OVERRIDE_KIND_NONE
+    }
+}
+
+// TraitPremium lets the cosigner pay more than the curve price for an asset
+// with specific traits when fulfilling buy. The cosigner signing the fulfill
+// tx attests the premium, and optionally a merkle proof can be supplied to
+// verify it against the root stored in pool.trait_premium_root, where
+// leaf = keccak(asset_id || trait_bucket || multiplier_bp (le) || expiry (le))
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct TraitPremium {
+    pub asset_id: Pubkey,
+    pub trait_bucket: [u8; 32],
+    pub multiplier_bp: u16,
+    pub expiry: i64,
+    pub proof: Option<Vec<[u8; 32]>>,
+}
+
 // seeds = [
 //    POOL_PREFIX.as_bytes(),
 //    owner.key().as_ref(),
@@ -66,7 +123,7 @@ pub struct Pool {
     pub expiry: i64,
     pub lp_fee_bp: u16,
     pub referral: Pubkey,
-    pub referral_bp: u16, // deprecated
+    pub reserved: [u8; 2], // was the deprecated referral_bp, splits are set in ReferralRegistry
     pub buyside_creator_royalty_bp: u16,
 
     // cosigner_annotation: it's set by the cosigner, could be the hash of the certain
@@ -88,6 +145,15 @@ pub struct Pool {
 
     pub shared_escrow_account: Pubkey, // this points to the shared escrow account PDA (usually M2)
     pub shared_escrow_count: u64, // this means that how many times (count) the shared escrow account can be fulfilled, and it can be mutable
+
+    pub max_trait_multiplier_bp: u16, // cap for TraitPremium.multiplier_bp, 0 means trait premium is disabled
+    pub transfer_hook_program_id: Pubkey, // extra T22 transfer hook program accepted by the pool besides the allow list of GlobalConfig
+    pub protocol_fee_exempt: bool, // set by the admin of GlobalConfig, the pool doesn't pay the protocol fee
+    pub compound_lp_fee: bool,     // lp fees go to the buyside escrow instead of the owner
+    pub lp_fee_compounded: u64, // the part of lp_fee_earned compounded, the rest is paid to the owner
+    pub dynamic_lp_fee_min_bp: u16, // the lp fee scales with the inventory imbalance between min and max,
+    pub dynamic_lp_fee_max_bp: u16, // disabled when max is 0, then lp_fee_bp is charged
+    pub trait_premium_root: [u8; 32], // merkle root of the TraitPremium leaves, set by the cosigner
 }
 
 impl Pool {
@@ -102,7 +168,14 @@ impl Pool {
         4 + (1 + 32) * ALLOWLIST_MAX_LEN + // Allowlist
         32 + // Pubkey
         8 + // u64
-        352; // padding
+        2 + // u16
+        32 + // Pubkey
+        1 + // bool
+        1 + // bool
+        8 + // u64
+        2 * 2 + // u16
+        32 + // [u8; 32]
+        272; // padding
 
     pub fn using_shared_escrow(&self) -> bool {
         self.shared_escrow_account != Pubkey::default()
@@ -128,14 +201,241 @@ pub struct SellState {
     pub asset_mint: Pubkey,
     pub asset_amount: u64,
     pub cosigner_annotation: [u8; 32],
+
+    // optional per-asset price set by the pool owner, overrides the curve
+    // price when fulfilling sell for this asset
+    pub price_override: PriceOverride,
+
+    // set for non-custodial listings, the asset stays in this token account of
+    // the pool owner and the pool is its transfer delegate, for mpl core assets
+    // it's the asset account itself
+    pub listing_token_account: Pubkey,
 }
 
 impl SellState {
     pub const LEN: usize = 8 +
         8 + // u64
-        32 * 3 + // Pubkey
+        32 * 4 + // Pubkey
         32 + // [u8; 32]
-        200; // padding
+        1 + 8 + // PriceOverride
+        159; // padding
+
+    pub fn is_listing(&self) -> bool {
+        self.listing_token_account != Pubkey::default()
+    }
+}
+
+// seeds = [
+//     EXTENSION_POLICY_PREFIX.as_bytes(),
+//     pool.key().as_ref(),
+// ]
+//
+// ExtensionPolicy is an optional companion account of the pool, when it's not
+// initialized, the pool uses T22_EXTENSION_ALLOW_LIST for token22 mints.
+#[account]
+#[derive(Default)]
+pub struct ExtensionPolicy {
+    pub pool: Pubkey,
+    // bit n is set when the mint extension with ExtensionType n is allowed, token
+    // account extensions like CpiGuard are checked on the token accounts instead
+    pub allowed_extensions: u64,
+    // permanent delegate accepted besides the pool itself, any other
+    // permanent delegate can move the asset out of the escrow
+    pub permanent_delegate: Pubkey,
+}
+
+impl ExtensionPolicy {
+    pub const LEN: usize = 8 +
+        32 * 2 + // Pubkey
+        8 + // u64
+        64; // padding
+
+    pub fn default_for(pool: Pubkey) -> Self {
+        Self {
+            pool,
+            allowed_extensions: Self::mask(&T22_EXTENSION_ALLOW_LIST),
+            permanent_delegate: Pubkey::default(),
+        }
+    }
+
+    pub fn mask(extensions: &[ExtensionType]) -> u64 {
+        extensions
+            .iter()
+            .filter(|&&ext| (ext as u16) < 64)
+            .fold(0, |mask, &ext| mask | (1 << ext as u16))
+    }
+
+    pub fn is_allowed(&self, ext: ExtensionType) -> bool {
+        (ext as u16) < 64 && self.allowed_extensions & (1 << ext as u16) != 0
+    }
+
+    // NonTransferable mints can never leave the escrow, so they can't be allowed
+    pub fn valid(&self) -> bool {
+        !self.is_allowed(ExtensionType::NonTransferable)
+    }
+}
+
+// seeds = [
+//     ROYALTY_VAULT_PREFIX.as_bytes(),
+//     creator.key().as_ref(),
+// ]
+//
+// RoyaltyVault accrues the royalties of a creator that can't be paid directly,
+// i.e. when the creator account would stay below the rent-exempt minimum, the
+// lamports above the rent of the vault can be claimed by the creator.
+#[account]
+#[derive(Default)]
+pub struct RoyaltyVault {
+    pub creator: Pubkey,
+    pub accrued: u64,
+    pub claimed: u64,
+}
+
+impl RoyaltyVault {
+    pub const LEN: usize = 8 +
+        32 + // Pubkey
+        8 * 2 + // u64
+        64; // padding
+}
+
+// seeds = [GLOBAL_CONFIG_PREFIX.as_bytes()]
+//
+// GlobalConfig holds the protocol wide settings managed by the admin, the
+// protocol fee is charged on every fulfill and sent to the treasury. The limits
+// used to be compiled in as constants, they are read from here instead. When
+// it's not initialized, the default built from the constants is used and no
+// protocol fee is charged.
+#[account]
+pub struct GlobalConfig {
+    pub version: u8,
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey, // set by the admin, becomes the admin once it accepts
+    pub treasury: Pubkey,
+    pub protocol_fee_bp: u16,
+    pub pause_flags: u8, // fulfills and deposits are halted, withdrawals and closes stay allowed
+
+    // limits, defaulting to the constants of the same name
+    pub cancel_authority: Pubkey,
+    pub max_lp_fee_bp: u16,
+    pub max_referral_fee_bp: i16,
+    pub max_metadata_creator_royalty_bp: u16,
+    pub min_sol_escrow_balance_bp: u16,
+    pub m2_auction_house: Pubkey,
+    pub payment_proxy_program_id: Pubkey,
+    pub payment_proxy_discriminator: [u8; 8],
+    pub payment_proxy_min_len: u32,
+    pub transfer_hook_program_allow_list: [Pubkey; MAX_TRANSFER_HOOK_PROGRAMS],
+
+    // fee schedule of referrals without their own FeeSchedule
+    pub default_maker_fee_bp: i16,
+    pub default_taker_fee_bp: i16,
+}
+
+impl Default for GlobalConfig {
+    fn default() -> Self {
+        Self {
+            version: GLOBAL_CONFIG_VERSION,
+            admin: Pubkey::default(),
+            pending_admin: Pubkey::default(),
+            treasury: Pubkey::default(),
+            protocol_fee_bp: 0,
+            pause_flags: 0,
+            cancel_authority: CANCEL_AUTHORITY,
+            max_lp_fee_bp: MAX_LP_FEE_BP,
+            max_referral_fee_bp: MAX_REFERRAL_FEE_BP,
+            max_metadata_creator_royalty_bp: MAX_METADATA_CREATOR_ROYALTY_BP,
+            min_sol_escrow_balance_bp: MIN_SOL_ESCROW_BALANCE_BP,
+            m2_auction_house: M2_AUCTION_HOUSE,
+            payment_proxy_program_id: PAYMENT_PROXY_PROGRAM_ID,
+            payment_proxy_discriminator: PAYMENT_PROXY_DISCRIMINATOR,
+            payment_proxy_min_len: PAYMENT_PROXY_MIN_LEN,
+            transfer_hook_program_allow_list: TRANSFER_HOOK_PROGRAM_ALLOW_LIST,
+            default_maker_fee_bp: MAX_REFERRAL_FEE_BP,
+            default_taker_fee_bp: MAX_REFERRAL_FEE_BP,
+        }
+    }
+}
+
+impl GlobalConfig {
+    pub const LEN: usize = 8 +
+        2 + // u8
+        32 * 6 + // Pubkey
+        2 * 7 + // u16, i16
+        8 + // [u8; 8]
+        4 + // u32
+        32 * MAX_TRANSFER_HOOK_PROGRAMS + // [Pubkey; MAX_TRANSFER_HOOK_PROGRAMS]
+        64; // padding
+
+    pub fn valid(&self) -> bool {
+        self.protocol_fee_bp <= MAX_PROTOCOL_FEE_BP
+            && self.max_lp_fee_bp <= 10000
+            && (0..=10000).contains(&self.max_referral_fee_bp)
+            && self.max_metadata_creator_royalty_bp <= 10000
+            && self.min_sol_escrow_balance_bp <= 10000
+            && self.default_fee_schedule(Pubkey::default()).valid(self)
+    }
+
+    pub fn is_transfer_hook_program_allowed(&self, program_id: &Pubkey) -> bool {
+        *program_id != Pubkey::default()
+            && self.transfer_hook_program_allow_list.contains(program_id)
+    }
+
+    pub fn default_fee_schedule(&self, referral: Pubkey) -> FeeSchedule {
+        FeeSchedule {
+            referral,
+            maker_fee_bp: self.default_maker_fee_bp,
+            taker_fee_bp: self.default_taker_fee_bp,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct RoyaltyBand {
+    pub min_price: u64,
+    pub royalty_bp: u16,
+}
+
+// RoyaltySchedule is the price-banded royalty of a collection, set by the
+// update authority of the collection, and consulted for all asset types
+// instead of the flat seller_fee_basis_points when it's initialized. Fulfill
+// instructions always take the royalty_schedule account. The collection is the collection mint for metaplex assets, the
+// collection asset for mpl core assets and the group mint for token22 assets.
+// seeds: [ROYALTY_SCHEDULE_PREFIX, collection]
+#[account]
+#[derive(Default)]
+pub struct RoyaltySchedule {
+    pub collection: Pubkey,
+    pub authority: Pubkey,
+    pub band_count: u8,
+    // sorted by min_price in strictly ascending order, only the first
+    // band_count bands are used
+    pub bands: [RoyaltyBand; ROYALTY_SCHEDULE_MAX_BANDS],
+}
+
+impl RoyaltySchedule {
+    pub const LEN: usize = 8 +
+        32 * 2 + // Pubkey
+        1 + // u8
+        (8 + 2) * ROYALTY_SCHEDULE_MAX_BANDS + // bands
+        64; // padding
+
+    pub fn valid_bands(bands: &[RoyaltyBand], max_royalty_bp: u16) -> bool {
+        bands.len() <= ROYALTY_SCHEDULE_MAX_BANDS
+            && bands.iter().all(|band| band.royalty_bp <= max_royalty_bp)
+            && bands
+                .windows(2)
+                .all(|pair| pair[0].min_price < pair[1].min_price)
+    }
+
+    // the royalty of the highest band at or below the price, the metadata
+    // royalty applies below the first band
+    pub fn get_royalty_bp(&self, total_price: u64, metadata_royalty_bp: u16) -> u16 {
+        self.bands[..usize::from(self.band_count).min(ROYALTY_SCHEDULE_MAX_BANDS)]
+            .iter()
+            .rev()
+            .find(|band| band.min_price <= total_price)
+            .map_or(metadata_royalty_bp, |band| band.royalty_bp)
+    }
 }
 
 // Wrapper structs to replace the Anchor program types until the Metaplex libs have
@@ -174,3 +474,80 @@ impl Deref for TreeConfigAnchor {
         &self.0
     }
 }
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct ReferralRecipient {
+    pub address: Pubkey,
+    pub share_bp: u16,
+}
+
+// seeds = [
+//     REFERRAL_REGISTRY_PREFIX.as_bytes(),
+//     referrer.key().as_ref(),
+// ]
+// ReferralRegistry splits the referral fee of the pools referred by the
+// referrer (pool.referral) between its recipients, for example an aggregator,
+// a front-end and the referrer itself. It's set by the referrer, fulfill
+// instructions take the registry account and the recipients are passed as
+// remaining accounts. Without a registry the whole referral fee goes to the
+// referral account.
+#[account]
+#[derive(Default)]
+pub struct ReferralRegistry {
+    pub referrer: Pubkey,
+    pub recipient_count: u8,
+    pub recipients: [ReferralRecipient; REFERRAL_REGISTRY_MAX_RECIPIENTS],
+}
+
+impl ReferralRegistry {
+    pub const LEN: usize = 8 +
+        32 + // Pubkey
+        1 + // u8
+        (32 + 2) * REFERRAL_REGISTRY_MAX_RECIPIENTS + // ReferralRecipient
+        64; // padding
+
+    // recipients are empty to disable the split, or their shares add up to 100%
+    pub fn valid_recipients(recipients: &[ReferralRecipient]) -> bool {
+        if recipients.is_empty() {
+            return true;
+        }
+        recipients.len() <= REFERRAL_REGISTRY_MAX_RECIPIENTS
+            && recipients.iter().all(|recipient| recipient.share_bp > 0)
+            && recipients
+                .iter()
+                .map(|recipient| u32::from(recipient.share_bp))
+                .sum::<u32>()
+                == 10000
+    }
+
+    pub fn recipients(&self) -> &[ReferralRecipient] {
+        &self.recipients[..self.recipient_count as usize]
+    }
+}
+
+// seeds = [
+//     FEE_SCHEDULE_PREFIX.as_bytes(),
+//     referral.key().as_ref(),
+// ]
+// FeeSchedule caps the maker and taker fees charged for the referral, it's set
+// by the admin of GlobalConfig. The cosigner can only lower the fees within the
+// schedule, referrals without a schedule use the default of GlobalConfig.
+#[account]
+#[derive(Default)]
+pub struct FeeSchedule {
+    pub referral: Pubkey,
+    pub maker_fee_bp: i16, // the highest maker fee, negative for a minimum maker rebate
+    pub taker_fee_bp: i16, // the highest taker fee
+}
+
+impl FeeSchedule {
+    pub const LEN: usize = 8 +
+        32 + // Pubkey
+        2 * 2 + // i16
+        64; // padding
+
+    pub fn valid(&self, global_config: &GlobalConfig) -> bool {
+        let bound = global_config.max_referral_fee_bp;
+        (0..=bound).contains(&self.taker_fee_bp) && (-bound..=bound).contains(&self.maker_fee_bp)
+    }
+}
@@ -1,37 +1,53 @@
 use crate::{
     constants::{
-        LIBREPLEX_ROYALTY_ENFORCEMENT_PROGRAM_ID, M2_AUCTION_HOUSE, M2_PREFIX, M2_PROGRAM,
-        MAX_METADATA_CREATOR_ROYALTY_BP, MAX_REFERRAL_FEE_BP, MAX_TOTAL_PRICE,
-        MIN_SOL_ESCROW_BALANCE_BP, POOL_PREFIX, T22_EXTENSION_ALLOW_LIST,
+        GLOBAL_CONFIG_VERSION, LIBREPLEX_ROYALTY_ENFORCEMENT_PROGRAM_ID, M2_PREFIX, M2_PROGRAM,
+        MAX_TOTAL_PRICE, MAX_TRAIT_MULTIPLIER_BP, POOL_PREFIX, ROYALTY_SCHEDULE_PREFIX,
+        ROYALTY_VAULT_PREFIX,
     },
     errors::MMMErrorCode,
     get_creators_from_royalties,
+    royalty_metadata::{parse_royalty_metadata, RoyaltyMetadata},
     state::*,
     Collection, IndexableAsset, MetadataArgs,
 };
 use anchor_lang::{prelude::*, solana_program::log::sol_log_data};
-use anchor_spl::token_interface::Mint;
+use anchor_spl::token_interface::{Mint, TokenAccount};
 use m2_interface::{
     withdraw_by_mmm_ix_with_program_id, WithdrawByMMMArgs, WithdrawByMmmIxArgs, WithdrawByMmmKeys,
 };
 use mpl_bubblegum::hash::hash_creators;
 use mpl_core::types::{Royalties, UpdateAuthority};
 use mpl_token_metadata::{
-    accounts::{MasterEdition, Metadata},
+    accounts::{Edition, MasterEdition, Metadata},
     types::{Creator, TokenStandard},
 };
 use open_creator_protocol::state::Policy;
-use solana_program::{keccak, program::invoke_signed};
+use solana_program::{
+    keccak,
+    program::{invoke, invoke_signed},
+};
+use spl_tlv_account_resolution::state::ExtraAccountMetaList;
 use spl_token_2022::{
     extension::{
-        group_member_pointer::GroupMemberPointer, metadata_pointer::MetadataPointer,
-        transfer_hook::TransferHook, BaseStateWithExtensions, StateWithExtensions,
+        cpi_guard::CpiGuard,
+        default_account_state::DefaultAccountState,
+        group_member_pointer::GroupMemberPointer,
+        metadata_pointer::MetadataPointer,
+        permanent_delegate::PermanentDelegate,
+        transfer_fee::{
+            instruction::harvest_withheld_tokens_to_mint, TransferFeeAmount, TransferFeeConfig,
+        },
+        transfer_hook::TransferHook,
+        BaseStateWithExtensions, ExtensionType, StateWithExtensions,
     },
-    state::Mint as Token22Mint,
+    state::{Account as Token22Account, AccountState, Mint as Token22Mint},
 };
-use spl_token_group_interface::state::TokenGroupMember;
+use spl_token_group_interface::state::{TokenGroup, TokenGroupMember};
 use spl_token_metadata_interface::state::TokenMetadata;
-use std::{convert::TryFrom, slice::Iter, str::FromStr};
+use spl_transfer_hook_interface::{
+    get_extra_account_metas_address, instruction::ExecuteInstruction,
+};
+use std::{convert::TryFrom, slice::Iter};
 
 #[macro_export]
 macro_rules! index_ra {
@@ -48,6 +64,24 @@ fn check_master_edition(master_edition_account_info: &AccountInfo) -> bool {
     version == 2 || version == 6
 }
 
+fn check_print_edition(edition_account_info: &AccountInfo) -> bool {
+    edition_account_info.data.borrow()[0] == 1
+}
+
+// returns the parent master edition if the edition account at the master
+// edition pda of the mint is a print edition
+pub fn get_print_edition_parent(edition: &AccountInfo) -> Result<Option<Pubkey>> {
+    if edition.data_is_empty()
+        || edition.owner.ne(&mpl_token_metadata::ID)
+        || !check_print_edition(edition)
+    {
+        return Ok(None);
+    }
+    let parsed_edition = Edition::from_bytes(&edition.data.borrow())
+        .map_err(|_| MMMErrorCode::InvalidMasterEdition)?;
+    Ok(Some(parsed_edition.parent))
+}
+
 pub fn check_allowlists(allowlists: &[Allowlist]) -> Result<()> {
     for allowlist in allowlists.iter() {
         if !allowlist.valid() {
@@ -71,7 +105,7 @@ pub fn check_allowlists_for_mint(
     // 2. make sure mint+metadata(e.g. first verified creator address) can match one of the allowlist
     // 3. note that the allowlist is unioned together, not intersection
     // 4. skip if the allowlist.is_empty()
-    // 5. verify that nft either does not have master edition or is master edition
+    // 5. verify that nft either does not have master edition or is master edition or print edition
 
     if *metadata.owner != mpl_token_metadata::ID {
         return Err(ErrorCode::AccountOwnedByWrongProgram.into());
@@ -80,6 +114,7 @@ pub fn check_allowlists_for_mint(
         return Err(ErrorCode::ConstraintSeeds.into());
     }
     let parsed_metadata = Metadata::safe_deserialize(&metadata.data.borrow())?;
+    let mut print_edition_parent: Option<Pubkey> = None;
     if let Some(master_edition) = master_edition {
         if MasterEdition::find_pda(&mint.key()).0 != master_edition.key() {
             return Err(ErrorCode::ConstraintSeeds.into());
@@ -88,9 +123,10 @@ pub fn check_allowlists_for_mint(
             if master_edition.owner.ne(&mpl_token_metadata::ID) {
                 return Err(ErrorCode::AccountOwnedByWrongProgram.into());
             }
-            if !check_master_edition(master_edition) {
+            if !check_master_edition(master_edition) && !check_print_edition(master_edition) {
                 return Err(MMMErrorCode::InvalidMasterEdition.into());
             }
+            print_edition_parent = get_print_edition_parent(master_edition)?;
         }
     }
 
@@ -112,6 +148,18 @@ pub fn check_allowlists_for_mint(
         }
     }
 
+    // print editions are only accepted by the master edition allowlists of
+    // their parent, every other kind rejects them
+    if let Some(parent) = print_edition_parent {
+        if allowlists.iter().any(|allowlist_val| {
+            allowlist_val.kind == ALLOWLIST_KIND_MASTER_EDITION
+                && parent == MasterEdition::find_pda(&allowlist_val.value).0
+        }) {
+            return Ok(parsed_metadata);
+        }
+        return Err(MMMErrorCode::InvalidMasterEdition.into());
+    }
+
     for allowlist_val in allowlists.iter() {
         match allowlist_val.kind {
             ALLOWLIST_KIND_EMPTY => {}
@@ -147,6 +195,10 @@ pub fn check_allowlists_for_mint(
                 // These checks are separate since allowlist values are unioned together.
                 continue;
             }
+            ALLOWLIST_KIND_MASTER_EDITION => {
+                // the value is the mint of the master edition, it only matches
+                // its prints which are checked above
+            }
             _ => {
                 return Err(MMMErrorCode::InvalidAllowLists.into());
             }
@@ -157,6 +209,48 @@ pub fn check_allowlists_for_mint(
     Err(MMMErrorCode::InvalidAllowLists.into())
 }
 
+// print editions copy the royalties of the master edition when printed, if the
+// print doesn't carry any creators, the royalties are taken from the parent
+// metadata, which is looked up by derivation in the remaining accounts
+pub fn get_print_edition_royalty_metadata(
+    parsed_metadata: Metadata,
+    edition: &AccountInfo,
+    remaining_accounts: &[AccountInfo],
+) -> Result<Metadata> {
+    let parent = match get_print_edition_parent(edition)? {
+        Some(parent) => parent,
+        None => return Ok(parsed_metadata),
+    };
+    if parsed_metadata
+        .creators
+        .as_ref()
+        .is_some_and(|creators| !creators.is_empty())
+    {
+        return Ok(parsed_metadata);
+    }
+
+    for account in remaining_accounts.iter() {
+        if account.owner.ne(&mpl_token_metadata::ID) {
+            continue;
+        }
+        let parent_metadata = match Metadata::safe_deserialize(&account.data.borrow()) {
+            Ok(parent_metadata) => parent_metadata,
+            Err(_) => continue,
+        };
+        if MasterEdition::find_pda(&parent_metadata.mint).0 == parent
+            && Metadata::find_pda(&parent_metadata.mint).0 == *account.key
+        {
+            return Ok(Metadata {
+                seller_fee_basis_points: parent_metadata.seller_fee_basis_points,
+                creators: parent_metadata.creators,
+                ..parsed_metadata
+            });
+        }
+    }
+    msg!("parent metadata of the print edition is missing");
+    Err(MMMErrorCode::InvalidRemainingAccounts.into())
+}
+
 pub fn check_allowlists_for_cnft(allowlists: &[Allowlist], collection: Collection) -> Result<()> {
     // Check mcc for cnft.
     for allowlist_val in allowlists.iter() {
@@ -219,24 +313,71 @@ pub fn get_buyside_seller_receives(
         .ok_or(MMMErrorCode::NumericOverflow.into())
 }
 
-pub fn get_lp_fee_bp(pool: &Pool, buyside_sol_escrow_balance: u64) -> u16 {
+// the lp fee is only charged when the pool has liquidity on both sides. With the
+// dynamic lp fee enabled, it scales between the min and max bp with the share of
+// the pool value on the side the trade adds to, so that rebalancing trades pay
+// less. A fulfill buy adds an asset to the pool, a fulfill sell adds sol.
+pub fn get_lp_fee_bp(
+    pool: &Pool,
+    buyside_sol_escrow_balance: u64,
+    fulfill_buy: bool,
+) -> Result<u16> {
     if pool.sellside_asset_amount < 1 {
-        return 0;
+        return Ok(0);
     }
 
     if buyside_sol_escrow_balance < pool.spot_price {
-        return 0;
+        return Ok(0);
+    }
+
+    if pool.dynamic_lp_fee_max_bp == 0 {
+        return Ok(pool.lp_fee_bp);
     }
 
-    pool.lp_fee_bp
+    let asset_value = u128::from(pool.sellside_asset_amount)
+        .checked_mul(u128::from(pool.spot_price))
+        .ok_or(MMMErrorCode::NumericOverflow)?;
+    let sol_value = u128::from(buyside_sol_escrow_balance);
+    let added_value = if fulfill_buy { asset_value } else { sol_value };
+    let min_bp = pool.dynamic_lp_fee_min_bp;
+    let bp_range = pool
+        .dynamic_lp_fee_max_bp
+        .checked_sub(min_bp)
+        .ok_or(MMMErrorCode::InvalidLPFee)?;
+    // the spot price is positive, so the total value is never zero here
+    let total_value = asset_value
+        .checked_add(sol_value)
+        .ok_or(MMMErrorCode::NumericOverflow)?;
+    let added_share_bp = added_value
+        .checked_mul(10000)
+        .and_then(|v| v.checked_div(total_value))
+        .ok_or(MMMErrorCode::NumericOverflow)?;
+    let dynamic_bp = u128::from(bp_range)
+        .checked_mul(added_share_bp)
+        .and_then(|v| v.checked_div(10000))
+        .and_then(|v| u16::try_from(v).ok())
+        .ok_or(MMMErrorCode::NumericOverflow)?;
+    min_bp
+        .checked_add(dynamic_bp)
+        .ok_or(MMMErrorCode::NumericOverflow.into())
+}
+
+pub fn check_dynamic_lp_fee(pool: &Pool, global_config: &GlobalConfig) -> Result<()> {
+    if pool.dynamic_lp_fee_min_bp > pool.dynamic_lp_fee_max_bp
+        || pool.dynamic_lp_fee_max_bp > global_config.max_lp_fee_bp
+    {
+        return Err(MMMErrorCode::InvalidLPFee.into());
+    }
+    Ok(())
 }
 
 pub fn get_sol_lp_fee(
     pool: &Pool,
     buyside_sol_escrow_balance: u64,
     total_sol_price: u64,
+    fulfill_buy: bool,
 ) -> Result<u64> {
-    let lp_fee_bp = get_lp_fee_bp(pool, buyside_sol_escrow_balance);
+    let lp_fee_bp = get_lp_fee_bp(pool, buyside_sol_escrow_balance, fulfill_buy)?;
 
     Ok(((total_sol_price as u128)
         .checked_mul(lp_fee_bp as u128)
@@ -378,6 +519,131 @@ pub fn get_sol_total_price_and_next_price(
     }
 }
 
+pub fn set_sell_state_price_override(
+    sell_state: &mut SellState,
+    price_override: PriceOverride,
+) -> Result<()> {
+    if !price_override.valid() {
+        return Err(MMMErrorCode::InvalidPriceOverride.into());
+    }
+    sell_state.price_override = price_override;
+    Ok(())
+}
+
+// returns (total_price, next_price) for fulfilling sell of n units of the asset
+// tracked by sell_state, honouring the per-asset price override if there's one.
+// an absolute override replaces the curve step and leaves the spot price where it is,
+// a premium override scales the curve price and moves the spot price as usual.
+pub fn get_sell_state_total_price_and_next_price(
+    pool: &Pool,
+    sell_state: &SellState,
+    n: u64,
+) -> Result<(u64, u64)> {
+    let price_override = &sell_state.price_override;
+    let (total_price, next_price) = match price_override.kind {
+        PRICE_OVERRIDE_KIND_NONE => return get_sol_total_price_and_next_price(pool, n, false),
+        PRICE_OVERRIDE_KIND_ABSOLUTE => (
+            price_override
+                .value
+                .checked_mul(n)
+                .ok_or(MMMErrorCode::NumericOverflow)?,
+            pool.spot_price,
+        ),
+        PRICE_OVERRIDE_KIND_PREMIUM => {
+            let (total_price, next_price) = get_sol_total_price_and_next_price(pool, n, false)?;
+            let total_price = u64::try_from(
+                (total_price as u128)
+                    .checked_mul(
+                        (price_override.value as u128)
+                            .checked_add(10000)
+                            .ok_or(MMMErrorCode::NumericOverflow)?,
+                    )
+                    .ok_or(MMMErrorCode::NumericOverflow)?
+                    .checked_div(10000)
+                    .ok_or(MMMErrorCode::NumericOverflow)?,
+            )
+            .map_err(|_| MMMErrorCode::NumericOverflow)?;
+            (total_price, next_price)
+        }
+        _ => return Err(MMMErrorCode::InvalidPriceOverride.into()),
+    };
+
+    if total_price == 0 || total_price > MAX_TOTAL_PRICE {
+        return Err(MMMErrorCode::NumericOverflow.into());
+    }
+    Ok((total_price, next_price))
+}
+
+fn verify_trait_premium_proof(
+    trait_premium: &TraitPremium,
+    proof: &[[u8; 32]],
+    root: &[u8; 32],
+) -> bool {
+    let leaf = keccak::hashv(&[
+        trait_premium.asset_id.as_ref(),
+        &trait_premium.trait_bucket,
+        &trait_premium.multiplier_bp.to_le_bytes(),
+        &trait_premium.expiry.to_le_bytes(),
+    ]);
+    let computed = proof.iter().fold(leaf.to_bytes(), |node, sibling| {
+        if node <= *sibling {
+            keccak::hashv(&[&node, sibling]).to_bytes()
+        } else {
+            keccak::hashv(&[sibling, &node]).to_bytes()
+        }
+    });
+    computed == *root
+}
+
+// scales the fulfill buy total_price by the trait premium multiplier if there's one,
+// the curve is not affected by the premium.
+pub fn apply_trait_premium(
+    pool: &Pool,
+    asset_id: &Pubkey,
+    total_price: u64,
+    trait_premium: &Option<TraitPremium>,
+) -> Result<u64> {
+    let trait_premium = match trait_premium {
+        Some(trait_premium) => trait_premium,
+        None => return Ok(total_price),
+    };
+
+    if trait_premium.asset_id != *asset_id
+        || trait_premium.multiplier_bp < 10000
+        || trait_premium.multiplier_bp > pool.max_trait_multiplier_bp
+    {
+        return Err(MMMErrorCode::InvalidTraitPremium.into());
+    }
+    if trait_premium.expiry != 0 && trait_premium.expiry <= Clock::get()?.unix_timestamp {
+        return Err(MMMErrorCode::Expired.into());
+    }
+    if let Some(ref proof) = trait_premium.proof {
+        if !verify_trait_premium_proof(trait_premium, proof, &pool.trait_premium_root) {
+            return Err(MMMErrorCode::InvalidTraitPremium.into());
+        }
+    }
+
+    let total_price = u64::try_from(
+        (total_price as u128)
+            .checked_mul(trait_premium.multiplier_bp as u128)
+            .ok_or(MMMErrorCode::NumericOverflow)?
+            .checked_div(10000)
+            .ok_or(MMMErrorCode::NumericOverflow)?,
+    )
+    .map_err(|_| MMMErrorCode::NumericOverflow)?;
+    if total_price > MAX_TOTAL_PRICE {
+        return Err(MMMErrorCode::NumericOverflow.into());
+    }
+    Ok(total_price)
+}
+
+pub fn check_max_trait_multiplier(max_trait_multiplier_bp: u16) -> Result<()> {
+    if max_trait_multiplier_bp > MAX_TRAIT_MULTIPLIER_BP {
+        return Err(MMMErrorCode::InvalidTraitPremium.into());
+    }
+    Ok(())
+}
+
 pub fn try_close_pool<'info>(pool: &Account<'info, Pool>, owner: AccountInfo<'info>) -> Result<()> {
     if pool.sellside_asset_amount != 0 {
         return Ok(());
@@ -407,6 +673,7 @@ pub fn try_close_escrow<'info>(
     pool: &Account<'info, Pool>,
     system_program: &Program<'info, System>,
     escrow_seeds: &[&[&[u8]]],
+    global_config: &GlobalConfig,
 ) -> Result<()> {
     // minimum rent needed to sustain a 0 data account
     let min_rent = Rent::get()?.minimum_balance(0);
@@ -417,7 +684,7 @@ pub fn try_close_escrow<'info>(
     } else {
         // pool balance cannot increase without manual deposit, so we calculate the actual value
         (u128::from(pool.spot_price))
-            .checked_mul(u128::from(MIN_SOL_ESCROW_BALANCE_BP))
+            .checked_mul(u128::from(global_config.min_sol_escrow_balance_bp))
             .and_then(|v| v.checked_div(10000))
             .and_then(|v| u64::try_from(v).ok())
             .ok_or(MMMErrorCode::NumericOverflow)?
@@ -463,71 +730,243 @@ pub fn try_close_sell_state<'info>(
     Ok(())
 }
 
+// a non-custodial listing is available if the listed token account still
+// holds the asset for the pool owner, with the pool as its delegate
+pub fn is_listing_available(
+    listing_token_account: &AccountInfo,
+    pool: &Account<Pool>,
+    token_program: &Pubkey,
+) -> bool {
+    if listing_token_account.owner != token_program {
+        return false;
+    }
+    match TokenAccount::try_deserialize(&mut &listing_token_account.data.borrow()[..]) {
+        Ok(token_account) => {
+            token_account.amount == 1
+                && token_account.owner == pool.owner
+                && Option::<Pubkey>::from(token_account.delegate) == Some(pool.key())
+        }
+        Err(_) => false,
+    }
+}
+
+// releases a non-custodial listing whose asset is no longer available in the
+// listed token account, e.g. when the owner moved the asset out of the wallet
+pub fn release_sell_state_listing<'info>(
+    pool: &mut Account<'info, Pool>,
+    sell_state: &mut Account<'info, SellState>,
+    owner: AccountInfo<'info>,
+) -> Result<()> {
+    if !sell_state.is_listing() {
+        return Err(MMMErrorCode::InvalidAccountState.into());
+    }
+    pool.sellside_asset_amount = pool
+        .sellside_asset_amount
+        .checked_sub(sell_state.asset_amount)
+        .ok_or(MMMErrorCode::NumericOverflow)?;
+    sell_state.asset_amount = 0;
+    try_close_sell_state(sell_state, owner)
+}
+
+// the dynamic royalty of the ocp policy takes precedence over the royalty
+// schedule of the collection, the flat seller_fee_basis_points applies when
+// neither is set
 pub fn get_metadata_royalty_bp(
     total_price: u64,
     parsed_metadata: &impl MetadataTrait,
     policy: Option<&Account<'_, Policy>>,
+    royalty_schedule: Option<&RoyaltySchedule>,
 ) -> u16 {
-    match policy {
-        None => parsed_metadata.get_seller_fee_basis_points(),
-        Some(p) => match &p.dynamic_royalty {
-            None => parsed_metadata.get_seller_fee_basis_points(),
-            Some(dynamic_royalty) => dynamic_royalty
-                .get_royalty_bp(total_price, parsed_metadata.get_seller_fee_basis_points()),
-        },
+    let seller_fee_basis_points = parsed_metadata.get_seller_fee_basis_points();
+    match (
+        policy.and_then(|p| p.dynamic_royalty.as_ref()),
+        royalty_schedule,
+    ) {
+        (Some(dynamic_royalty), _) => {
+            dynamic_royalty.get_royalty_bp(total_price, seller_fee_basis_points)
+        }
+        (None, Some(royalty_schedule)) => {
+            royalty_schedule.get_royalty_bp(total_price, seller_fee_basis_points)
+        }
+        (None, None) => seller_fee_basis_points,
     }
 }
 
-#[allow(clippy::too_many_arguments)]
-pub fn pay_creator_fees_in_sol_ext<'info>(
-    total_price: u64,
-    optional_creator_account: Option<&AccountInfo<'info>>,
-    payer: AccountInfo<'info>,
-    sfbp: u16,
-    payer_seeds: &[&[&[u8]]],
-) -> Result<u64> {
-    let royalty = ((total_price as u128)
-        .checked_mul(sfbp as u128)
-        .ok_or(MMMErrorCode::NumericOverflow)?
-        .checked_div(10000)
-        .ok_or(MMMErrorCode::NumericOverflow)?) as u64;
+pub fn get_verified_collection(metadata: &Metadata) -> Option<Pubkey> {
+    metadata
+        .collection
+        .as_ref()
+        .filter(|collection| collection.verified)
+        .map(|collection| collection.key)
+}
 
-    if royalty == 0 {
-        return Ok(0);
+// the royalty schedule account is required by the fulfill instructions at the
+// [ROYALTY_SCHEDULE_PREFIX, collection] pda, or at the pda of the default
+// pubkey for assets without a collection. It's optional to initialize, an
+// empty account means the collection has no royalty schedule.
+pub fn get_royalty_schedule(
+    collection: Option<Pubkey>,
+    royalty_schedule: &AccountInfo,
+) -> Result<Option<RoyaltySchedule>> {
+    let (royalty_schedule_key, _) = Pubkey::find_program_address(
+        &[
+            ROYALTY_SCHEDULE_PREFIX.as_bytes(),
+            collection.unwrap_or_default().as_ref(),
+        ],
+        &crate::ID,
+    );
+    if royalty_schedule.key != &royalty_schedule_key {
+        return Err(MMMErrorCode::InvalidRoyaltySchedule.into());
+    }
+    if collection.is_none()
+        || royalty_schedule.owner != &crate::ID
+        || royalty_schedule.data_is_empty()
+    {
+        return Ok(None);
     }
+    Ok(Some(RoyaltySchedule::try_deserialize(
+        &mut &royalty_schedule.data.borrow()[..],
+    )?))
+}
 
-    let creator_account = if let Some(creator_account) = optional_creator_account {
-        creator_account
-    } else {
-        return Ok(0);
-    };
+// royalty charged to the payer, accrued is the part of total that was credited
+// to royalty vaults instead of paid to the creators directly
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RoyaltyPayment {
+    pub total: u64,
+    pub accrued: u64,
+}
 
-    if payer.lamports() < royalty {
-        return Err(MMMErrorCode::NotEnoughBalance.into());
+pub fn find_royalty_vault<'a, 'info>(
+    creator: &Pubkey,
+    accounts: &'a [AccountInfo<'info>],
+) -> Option<&'a AccountInfo<'info>> {
+    let (royalty_vault, _) = Pubkey::find_program_address(
+        &[ROYALTY_VAULT_PREFIX.as_bytes(), creator.as_ref()],
+        &crate::ID,
+    );
+    accounts.iter().find(|account| {
+        account.key == &royalty_vault && account.owner == &crate::ID && account.is_writable
+    })
+}
+
+fn credit_royalty_vault(royalty_vault: &AccountInfo, amount: u64) -> Result<()> {
+    let mut vault = RoyaltyVault::try_deserialize(&mut &royalty_vault.data.borrow()[..])?;
+    vault.accrued = vault
+        .accrued
+        .checked_add(amount)
+        .ok_or(MMMErrorCode::NumericOverflow)?;
+    vault.try_serialize(&mut &mut royalty_vault.data.borrow_mut()[..])
+}
+
+// the creator fee is paid to the creator directly, unless the creator account
+// would stay below the rent-exempt minimum. The fee is then credited to the
+// royalty vault of the creator when it's passed after the creator accounts,
+// otherwise it's skipped and not charged, so the fulfill never depends on the
+// vault. Returns the destination and whether the fee is credited to a vault.
+fn get_creator_fee_destination<'a, 'info>(
+    creator_info: &'a AccountInfo<'info>,
+    creator_fee: u64,
+    creator_accounts: &'a [AccountInfo<'info>],
+    min_rent: u64,
+) -> Result<Option<(&'a AccountInfo<'info>, bool)>> {
+    let pay_directly = creator_info
+        .lamports()
+        .checked_add(creator_fee)
+        .ok_or(MMMErrorCode::NumericOverflow)?
+        > min_rent;
+    if pay_directly {
+        return Ok(Some((creator_info, false)));
     }
+    Ok(find_royalty_vault(creator_info.key, creator_accounts).map(|vault| (vault, true)))
+}
+
+fn pay_creator_fee<'info>(
+    creator_info: &AccountInfo<'info>,
+    creator_fee: u64,
+    creator_accounts: &[AccountInfo<'info>],
+    payer: &AccountInfo<'info>,
+    payer_seeds: &[&[&[u8]]],
+    system_program: &AccountInfo<'info>,
+    min_rent: u64,
+) -> Result<RoyaltyPayment> {
+    if creator_fee == 0 {
+        return Ok(RoyaltyPayment::default());
+    }
+    let (destination, to_vault) =
+        match get_creator_fee_destination(creator_info, creator_fee, creator_accounts, min_rent)? {
+            Some(destination) => destination,
+            None => return Ok(RoyaltyPayment::default()),
+        };
+
+    anchor_lang::solana_program::program::invoke_signed(
+        &anchor_lang::solana_program::system_instruction::transfer(
+            payer.key,
+            destination.key,
+            creator_fee,
+        ),
+        &[
+            payer.to_account_info(),
+            destination.to_account_info(),
+            system_program.to_account_info(),
+        ],
+        payer_seeds,
+    )?;
+    if !to_vault {
+        return Ok(RoyaltyPayment {
+            total: creator_fee,
+            accrued: 0,
+        });
+    }
+    credit_royalty_vault(destination, creator_fee)?;
+    Ok(RoyaltyPayment {
+        total: creator_fee,
+        accrued: creator_fee,
+    })
+}
 
-    if sfbp > MAX_METADATA_CREATOR_ROYALTY_BP {
-        return Err(MMMErrorCode::InvalidBP.into());
+// the creator accounts are passed in the order of the creators in the metadata
+pub fn check_creator_accounts(
+    creators: &[Creator],
+    creator_accounts: &[AccountInfo],
+) -> Result<()> {
+    if creator_accounts.len() < creators.len() {
+        return Err(ProgramError::NotEnoughAccountKeys.into());
     }
-    let min_rent = Rent::get()?.minimum_balance(0);
+    for (creator, creator_account) in creators.iter().zip(creator_accounts) {
+        if creator.address.ne(creator_account.key) {
+            return Err(MMMErrorCode::InvalidCreatorAddress.into());
+        }
+    }
+    Ok(())
+}
 
-    let creator_lamports = creator_account.lamports();
-    if creator_lamports
-        .checked_add(royalty)
-        .ok_or(MMMErrorCode::NumericOverflow)?
-        > min_rent
-    {
-        anchor_lang::solana_program::program::invoke_signed(
-            &anchor_lang::solana_program::system_instruction::transfer(
-                payer.key,
-                creator_account.key,
-                royalty,
-            ),
-            &[payer.to_account_info(), creator_account.to_account_info()],
-            payer_seeds,
-        )?;
+// splits the royalty by the share of each creator, the rounding remainder goes
+// to the last creator
+pub fn get_creator_fee_shares(creators: &[Creator], royalty: u64) -> Result<Vec<u64>> {
+    let mut shares = Vec::with_capacity(creators.len());
+    let mut total: u64 = 0;
+    for (index, creator) in creators.iter().enumerate() {
+        let share = if index == creators.len() - 1 {
+            royalty
+                .checked_sub(total)
+                .ok_or(MMMErrorCode::NumericOverflow)?
+        } else {
+            u64::try_from(
+                u128::from(royalty)
+                    .checked_mul(u128::from(creator.share))
+                    .ok_or(MMMErrorCode::NumericOverflow)?
+                    .checked_div(100)
+                    .ok_or(MMMErrorCode::NumericOverflow)?,
+            )
+            .map_err(|_| MMMErrorCode::NumericOverflow)?
+        };
+        total = total
+            .checked_add(share)
+            .ok_or(MMMErrorCode::NumericOverflow)?;
+        shares.push(share);
     }
-    Ok(royalty)
+    Ok(shares)
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -540,7 +979,8 @@ pub fn pay_creator_fees_in_sol<'info>(
     metadata_royalty_bp: u16,
     payer_seeds: &[&[&[u8]]],
     system_program: AccountInfo<'info>,
-) -> Result<u64> {
+    global_config: &GlobalConfig,
+) -> Result<RoyaltyPayment> {
     // total royalty paid by the buyer, it's one of the following
     //   - buyside_sol_escrow_account (when fulfill buy)
     //   - payer                      (when fulfill sell)
@@ -557,69 +997,51 @@ pub fn pay_creator_fees_in_sol<'info>(
         .ok_or(MMMErrorCode::NumericOverflow)?) as u64;
 
     if royalty == 0 {
-        return Ok(0);
+        return Ok(RoyaltyPayment::default());
     }
 
     let creators = if let Some(creators) = parsed_metadata.get_creators() {
         creators.clone()
     } else {
-        return Ok(0);
+        return Ok(RoyaltyPayment::default());
     };
 
     if payer.lamports() < royalty {
         return Err(MMMErrorCode::NotEnoughBalance.into());
     }
 
-    // hardcoded the max threshold for InvalidMetadataCreatorRoyalty
-    if parsed_metadata.get_seller_fee_basis_points() > MAX_METADATA_CREATOR_ROYALTY_BP {
+    // the max threshold for InvalidMetadataCreatorRoyalty is set in the global config
+    if parsed_metadata.get_seller_fee_basis_points() > global_config.max_metadata_creator_royalty_bp
+    {
         return Err(MMMErrorCode::InvalidMetadataCreatorRoyalty.into());
     }
+    check_creator_accounts(&creators, creator_accounts)?;
+    let creator_fees = get_creator_fee_shares(&creators, royalty)?;
     let min_rent = Rent::get()?.minimum_balance(0);
     let mut total_royalty: u64 = 0;
-
-    let creator_accounts_iter = &mut creator_accounts.iter();
-    for (index, creator) in creators.iter().enumerate() {
-        let creator_fee = if index == creators.len() - 1 {
-            royalty
-                .checked_sub(total_royalty)
-                .ok_or(MMMErrorCode::NumericOverflow)?
-        } else {
-            (royalty as u128)
-                .checked_mul(creator.share as u128)
-                .ok_or(MMMErrorCode::NumericOverflow)?
-                .checked_div(100)
-                .ok_or(MMMErrorCode::NumericOverflow)? as u64
-        };
-        let current_creator_info = next_account_info(creator_accounts_iter)?;
-        if creator.address.ne(current_creator_info.key) {
-            return Err(MMMErrorCode::InvalidCreatorAddress.into());
-        }
-        let current_creator_lamports = current_creator_info.lamports();
-        if creator_fee > 0
-            && current_creator_lamports
-                .checked_add(creator_fee)
-                .ok_or(MMMErrorCode::NumericOverflow)?
-                > min_rent
-        {
-            anchor_lang::solana_program::program::invoke_signed(
-                &anchor_lang::solana_program::system_instruction::transfer(
-                    payer.key,
-                    current_creator_info.key,
-                    creator_fee,
-                ),
-                &[
-                    payer.to_account_info(),
-                    current_creator_info.to_account_info(),
-                    system_program.to_account_info(),
-                ],
-                payer_seeds,
-            )?;
-            total_royalty = total_royalty
-                .checked_add(creator_fee)
-                .ok_or(MMMErrorCode::NumericOverflow)?;
-        }
-    }
-    Ok(total_royalty)
+    let mut total_accrued: u64 = 0;
+
+    for (current_creator_info, creator_fee) in creator_accounts.iter().zip(creator_fees) {
+        let payment = pay_creator_fee(
+            current_creator_info,
+            creator_fee,
+            creator_accounts,
+            &payer,
+            payer_seeds,
+            &system_program,
+            min_rent,
+        )?;
+        total_royalty = total_royalty
+            .checked_add(payment.total)
+            .ok_or(MMMErrorCode::NumericOverflow)?;
+        total_accrued = total_accrued
+            .checked_add(payment.accrued)
+            .ok_or(MMMErrorCode::NumericOverflow)?;
+    }
+    Ok(RoyaltyPayment {
+        total: total_royalty,
+        accrued: total_accrued,
+    })
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -629,12 +1051,13 @@ pub fn pay_creator_fees_in_sol_cnft<'info>(
     metadata_args: &MetadataArgs,
     creator_accounts: &[AccountInfo<'info>],
     payer: AccountInfo<'info>,
+    metadata_royalty_bp: u16,
     payer_seeds: &[&[&[u8]]],
     system_program: AccountInfo<'info>,
-) -> Result<u64> {
+) -> Result<RoyaltyPayment> {
     // Calculate the total royalty to be paid
     let royalty = ((total_price as u128)
-        .checked_mul(metadata_args.seller_fee_basis_points as u128)
+        .checked_mul(metadata_royalty_bp as u128)
         .ok_or(MMMErrorCode::NumericOverflow)?
         .checked_div(10000)
         .ok_or(MMMErrorCode::NumericOverflow)?
@@ -644,7 +1067,7 @@ pub fn pay_creator_fees_in_sol_cnft<'info>(
         .ok_or(MMMErrorCode::NumericOverflow)?) as u64;
 
     if royalty == 0 {
-        return Ok(0);
+        return Ok(RoyaltyPayment::default());
     }
 
     if payer.lamports() < royalty {
@@ -653,6 +1076,7 @@ pub fn pay_creator_fees_in_sol_cnft<'info>(
 
     let min_rent = Rent::get()?.minimum_balance(0);
     let mut total_royalty: u64 = 0;
+    let mut total_accrued: u64 = 0;
 
     let creator_accounts_iter = &mut creator_accounts.iter();
     for (index, creator) in metadata_args.creators.iter().enumerate() {
@@ -671,32 +1095,26 @@ pub fn pay_creator_fees_in_sol_cnft<'info>(
         if creator.address.ne(current_creator_info.key) {
             return Err(MMMErrorCode::InvalidCreatorAddress.into());
         }
-        let current_creator_lamports = current_creator_info.lamports();
-        if creator_fee > 0
-            && current_creator_lamports
-                .checked_add(creator_fee)
-                .ok_or(MMMErrorCode::NumericOverflow)?
-                > min_rent
-        {
-            anchor_lang::solana_program::program::invoke_signed(
-                &anchor_lang::solana_program::system_instruction::transfer(
-                    payer.key,
-                    current_creator_info.key,
-                    creator_fee,
-                ),
-                &[
-                    payer.to_account_info(),
-                    current_creator_info.to_account_info(),
-                    system_program.to_account_info(),
-                ],
-                payer_seeds,
-            )?;
-            total_royalty = total_royalty
-                .checked_add(creator_fee)
-                .ok_or(MMMErrorCode::NumericOverflow)?;
-        }
-    }
-    Ok(total_royalty)
+        let payment = pay_creator_fee(
+            current_creator_info,
+            creator_fee,
+            creator_accounts,
+            &payer,
+            payer_seeds,
+            &system_program,
+            min_rent,
+        )?;
+        total_royalty = total_royalty
+            .checked_add(payment.total)
+            .ok_or(MMMErrorCode::NumericOverflow)?;
+        total_accrued = total_accrued
+            .checked_add(payment.accrued)
+            .ok_or(MMMErrorCode::NumericOverflow)?;
+    }
+    Ok(RoyaltyPayment {
+        total: total_royalty,
+        accrued: total_accrued,
+    })
 }
 
 pub fn log_pool(prefix: &str, pool: &Pool) -> Result<()> {
@@ -713,8 +1131,13 @@ pub fn assert_is_programmable(parsed_metadata: &Metadata) -> Result<()> {
     }
 }
 
-pub fn assert_valid_fees_bp(maker_fee_bp: i16, taker_fee_bp: i16) -> Result<()> {
-    let bound = MAX_REFERRAL_FEE_BP;
+pub fn assert_valid_fees_bp(
+    maker_fee_bp: i16,
+    taker_fee_bp: i16,
+    global_config: &GlobalConfig,
+    fee_schedule: &FeeSchedule,
+) -> Result<()> {
+    let bound = global_config.max_referral_fee_bp;
     if !(0..=bound).contains(&taker_fee_bp) {
         return Err(MMMErrorCode::InvalidMakerOrTakerFeeBP.into());
     }
@@ -728,6 +1151,11 @@ pub fn assert_valid_fees_bp(maker_fee_bp: i16, taker_fee_bp: i16) -> Result<()>
         return Err(MMMErrorCode::InvalidMakerOrTakerFeeBP.into());
     }
 
+    // the cosigner can only lower the fees of the schedule
+    if maker_fee_bp > fee_schedule.maker_fee_bp || taker_fee_bp > fee_schedule.taker_fee_bp {
+        return Err(MMMErrorCode::InvalidMakerOrTakerFeeBP.into());
+    }
+
     Ok(())
 }
 
@@ -741,6 +1169,7 @@ pub fn withdraw_m2<'info>(
     m2_program: &AccountInfo<'info>,
     wallet: Pubkey,
     amount: u64,
+    global_config: &GlobalConfig,
 ) -> Result<()> {
     let pool_seeds: &[&[&[u8]]] = &[&[
         POOL_PREFIX.as_bytes(),
@@ -772,7 +1201,7 @@ pub fn withdraw_m2<'info>(
         WithdrawByMmmIxArgs {
             args: WithdrawByMMMArgs {
                 wallet,
-                auction_house: M2_AUCTION_HOUSE,
+                auction_house: global_config.m2_auction_house,
                 amount: withdraw_amount,
                 mmm_pool_uuid: pool.uuid,
             },
@@ -797,6 +1226,7 @@ pub fn withdraw_m2<'info>(
 pub fn check_remaining_accounts_for_m2(
     remaining_accounts: &[AccountInfo],
     pool_owner: &Pubkey,
+    global_config: &GlobalConfig,
 ) -> Result<()> {
     // check the remaining accounts at position 0 and 1
     // 0 has to be the m2_program
@@ -814,7 +1244,7 @@ pub fn check_remaining_accounts_for_m2(
     let (m2_shared_escrow_pda, _) = Pubkey::find_program_address(
         &[
             M2_PREFIX.as_bytes(),
-            M2_AUCTION_HOUSE.as_ref(),
+            global_config.m2_auction_house.as_ref(),
             pool_owner.as_ref(),
         ],
         &M2_PROGRAM,
@@ -826,42 +1256,322 @@ pub fn check_remaining_accounts_for_m2(
     Ok(())
 }
 
-pub fn assert_valid_extension(mint_deserialized: &StateWithExtensions<Token22Mint>) -> Result<()> {
-    let extension_types = mint_deserialized.get_extension_types()?;
-    for ext in extension_types.iter() {
-        if !T22_EXTENSION_ALLOW_LIST.contains(ext) {
-            return Err(MMMErrorCode::InvalidTokenExtension.into());
-        }
+// returns the extension policy of the pool, or the default policy built from
+// T22_EXTENSION_ALLOW_LIST if the account is not initialized. The address of
+// extension_policy must be checked by the caller with the pool seeds.
+pub fn get_extension_policy(
+    pool: &Pubkey,
+    extension_policy: &AccountInfo,
+) -> Result<ExtensionPolicy> {
+    if extension_policy.owner != &crate::ID || extension_policy.data_is_empty() {
+        return Ok(ExtensionPolicy::default_for(*pool));
+    }
+    let extension_policy =
+        ExtensionPolicy::try_deserialize(&mut &extension_policy.data.borrow()[..])?;
+    if extension_policy.pool != *pool {
+        return Err(MMMErrorCode::InvalidExtensionPolicy.into());
+    }
+    Ok(extension_policy)
+}
+
+// returns the global config, or the default config built from the constants
+// and without protocol fee if the account is not initialized. The address of global_config must be checked by
+// the caller with the seeds.
+pub fn get_global_config(global_config: &AccountInfo) -> Result<GlobalConfig> {
+    if global_config.owner != &crate::ID || global_config.data_is_empty() {
+        return Ok(GlobalConfig::default());
+    }
+    let parsed = GlobalConfig::try_deserialize(&mut &global_config.data.borrow()[..])?;
+    if parsed.version == 0 || parsed.version > GLOBAL_CONFIG_VERSION {
+        return Err(MMMErrorCode::InvalidGlobalConfig.into());
+    }
+    Ok(parsed)
+}
+
+// lp fees paid by the payer are compounded into the buyside escrow when the pool
+// opts in, unless the escrow can't be rent exempt with it, then the owner is paid
+pub fn should_compound_lp_fee(
+    pool: &Pool,
+    buyside_sol_escrow_account: &AccountInfo,
+    lp_fee: u64,
+) -> Result<bool> {
+    if !pool.compound_lp_fee || lp_fee == 0 {
+        return Ok(false);
     }
+    let min_rent = Rent::get()?.minimum_balance(0);
+    Ok(buyside_sol_escrow_account
+        .lamports()
+        .checked_add(lp_fee)
+        .ok_or(MMMErrorCode::NumericOverflow)?
+        >= min_rent)
+}
+
+// returns the fee schedule of the referral, or the default of the global config
+// if the account is not initialized. The address of fee_schedule must be
+// checked by the caller with the seeds.
+pub fn get_fee_schedule(
+    referral: &Pubkey,
+    fee_schedule: &AccountInfo,
+    global_config: &GlobalConfig,
+) -> Result<FeeSchedule> {
+    if fee_schedule.owner != &crate::ID || fee_schedule.data_is_empty() {
+        return Ok(global_config.default_fee_schedule(*referral));
+    }
+    FeeSchedule::try_deserialize(&mut &fee_schedule.data.borrow()[..])
+}
 
+// fulfills and deposits of an asset family are rejected when either the global
+// pause or the flag of the family is set
+pub fn assert_not_paused(global_config: &GlobalConfig, pause_flag: u8) -> Result<()> {
+    if global_config.pause_flags & (PAUSE_FLAG_ALL | pause_flag) != 0 {
+        return Err(MMMErrorCode::Paused.into());
+    }
     Ok(())
 }
 
-pub fn check_allowlists_for_mint_ext(
-    allowlists: &[Allowlist],
-    mint: &AccountInfo,
-    allowlist_aux: Option<String>,
-) -> Result<TokenMetadata> {
-    if mint.owner != &spl_token_2022::ID || mint.data_is_empty() {
-        return Err(MMMErrorCode::InvalidTokenMint.into());
+// the protocol fee is charged on the same amount as the maker and taker fees,
+// pools exempted by the admin don't pay it
+pub fn get_protocol_fee(global_config: &GlobalConfig, pool: &Pool, amount: u64) -> Result<u64> {
+    if pool.protocol_fee_exempt || global_config.protocol_fee_bp == 0 {
+        return Ok(0);
     }
+    u64::try_from(
+        (amount as u128)
+            .checked_mul(global_config.protocol_fee_bp as u128)
+            .ok_or(MMMErrorCode::NumericOverflow)?
+            .checked_div(10000)
+            .ok_or(MMMErrorCode::NumericOverflow)?,
+    )
+    .map_err(|_| MMMErrorCode::NumericOverflow.into())
+}
 
-    let borrowed_data = mint.data.borrow();
-    let mint_deserialized = StateWithExtensions::<Token22Mint>::unpack(&borrowed_data)?;
-    if !mint_deserialized.base.is_initialized {
-        return Err(MMMErrorCode::InvalidTokenMetadataExtension.into());
+// the address of referral_registry must be checked by the caller with the
+// seeds, an empty account means the referrer has no registry
+pub fn get_referral_registry(referral_registry: &AccountInfo) -> Result<Option<ReferralRegistry>> {
+    if referral_registry.owner != &crate::ID || referral_registry.data_is_empty() {
+        return Ok(None);
     }
-    assert_valid_extension(&mint_deserialized)?;
-    let parsed_metadata = assert_and_get_metadata_from_ext(mint)?;
+    Ok(Some(ReferralRegistry::try_deserialize(
+        &mut &referral_registry.data.borrow()[..],
+    )?))
+}
 
-    if allowlists
-        .iter()
-        .any(|&val| val.kind == ALLOWLIST_KIND_METADATA)
-    {
-        // If allowlist_aux is not passed in, do not validate URI.
-        if let Some(ref aux_key) = allowlist_aux {
-            // Handle URI padding.
-            if !parsed_metadata.uri.trim().starts_with(aux_key) {
+// splits the referral fee by the shares of the recipients, the rounding
+// remainder goes to the last recipient
+pub fn get_referral_fee_shares(
+    recipients: &[ReferralRecipient],
+    referral_fee: u64,
+) -> Result<Vec<u64>> {
+    let mut shares = Vec::with_capacity(recipients.len());
+    let mut total: u64 = 0;
+    for (index, recipient) in recipients.iter().enumerate() {
+        let share = if index == recipients.len() - 1 {
+            referral_fee
+                .checked_sub(total)
+                .ok_or(MMMErrorCode::NumericOverflow)?
+        } else {
+            u64::try_from(
+                u128::from(referral_fee)
+                    .checked_mul(u128::from(recipient.share_bp))
+                    .ok_or(MMMErrorCode::NumericOverflow)?
+                    .checked_div(10000)
+                    .ok_or(MMMErrorCode::NumericOverflow)?,
+            )
+            .map_err(|_| MMMErrorCode::NumericOverflow)?
+        };
+        total = total
+            .checked_add(share)
+            .ok_or(MMMErrorCode::NumericOverflow)?;
+        shares.push(share);
+    }
+    Ok(shares)
+}
+
+// pays the referral fee to the referral account, or splits it between the
+// recipients of the referral registry, which have to be passed as writable
+// remaining accounts unless they are the referral account
+pub fn pay_referral_fee<'info>(
+    from: &AccountInfo<'info>,
+    referral: &AccountInfo<'info>,
+    referral_registry: Option<&ReferralRegistry>,
+    remaining_accounts: &[AccountInfo<'info>],
+    system_program: &AccountInfo<'info>,
+    from_seeds: &[&[&[u8]]],
+    referral_fee: u64,
+) -> Result<()> {
+    if referral_fee == 0 {
+        return Ok(());
+    }
+    let recipients = referral_registry.map_or(&[][..], |registry| registry.recipients());
+    if recipients.is_empty() {
+        invoke_signed(
+            &anchor_lang::solana_program::system_instruction::transfer(
+                from.key,
+                referral.key,
+                referral_fee,
+            ),
+            &[from.clone(), referral.clone(), system_program.clone()],
+            from_seeds,
+        )?;
+        return Ok(());
+    }
+
+    let shares = get_referral_fee_shares(recipients, referral_fee)?;
+    for (recipient, share) in recipients.iter().zip(shares) {
+        if share == 0 {
+            continue;
+        }
+        let recipient_account = if recipient.address == referral.key() {
+            referral
+        } else {
+            remaining_accounts
+                .iter()
+                .find(|account| account.key == &recipient.address && account.is_writable)
+                .ok_or(MMMErrorCode::InvalidRemainingAccounts)?
+        };
+        invoke_signed(
+            &anchor_lang::solana_program::system_instruction::transfer(
+                from.key,
+                recipient_account.key,
+                share,
+            ),
+            &[
+                from.clone(),
+                recipient_account.clone(),
+                system_program.clone(),
+            ],
+            from_seeds,
+        )?;
+    }
+    Ok(())
+}
+
+pub fn pay_protocol_fee<'info>(
+    from: &AccountInfo<'info>,
+    treasury: &AccountInfo<'info>,
+    global_config: &GlobalConfig,
+    system_program: &AccountInfo<'info>,
+    from_seeds: &[&[&[u8]]],
+    protocol_fee: u64,
+) -> Result<()> {
+    if protocol_fee == 0 {
+        return Ok(());
+    }
+    if treasury.key != &global_config.treasury {
+        return Err(MMMErrorCode::InvalidTreasury.into());
+    }
+    invoke_signed(
+        &anchor_lang::solana_program::system_instruction::transfer(
+            from.key,
+            treasury.key,
+            protocol_fee,
+        ),
+        &[from.clone(), treasury.clone(), system_program.clone()],
+        from_seeds,
+    )?;
+    Ok(())
+}
+
+// the dangerous extensions are rejected with their own error code, the rest
+// with InvalidTokenExtension
+fn get_extension_error(ext: ExtensionType) -> MMMErrorCode {
+    match ext {
+        ExtensionType::NonTransferable => MMMErrorCode::NonTransferableMint,
+        ExtensionType::PermanentDelegate => MMMErrorCode::InvalidPermanentDelegate,
+        ExtensionType::DefaultAccountState => MMMErrorCode::InvalidDefaultAccountState,
+        ExtensionType::InterestBearingConfig => MMMErrorCode::InvalidInterestBearingConfig,
+        ExtensionType::TransferHook => MMMErrorCode::InvalidTransferHookProgram,
+        ExtensionType::TransferFeeConfig => MMMErrorCode::InvalidTransferFee,
+        _ => MMMErrorCode::InvalidTokenExtension,
+    }
+}
+
+pub fn assert_valid_extension(
+    mint_deserialized: &StateWithExtensions<Token22Mint>,
+    extension_policy: &ExtensionPolicy,
+) -> Result<()> {
+    let extension_types = mint_deserialized.get_extension_types()?;
+    for ext in extension_types.iter() {
+        if !extension_policy.is_allowed(*ext) {
+            msg!("token extension {:?} is not allowed by the pool", ext);
+            return Err(get_extension_error(*ext).into());
+        }
+        match ext {
+            ExtensionType::PermanentDelegate => {
+                let extension = mint_deserialized.get_extension::<PermanentDelegate>()?;
+                if let Some(delegate) = Option::<Pubkey>::from(extension.delegate) {
+                    if delegate != extension_policy.pool
+                        && delegate != extension_policy.permanent_delegate
+                    {
+                        msg!(
+                            "token extension PermanentDelegate |{}| is not allowed by the pool",
+                            delegate
+                        );
+                        return Err(MMMErrorCode::InvalidPermanentDelegate.into());
+                    }
+                }
+            }
+            ExtensionType::DefaultAccountState => {
+                // a frozen escrow token account can't receive or send the asset
+                let extension = mint_deserialized.get_extension::<DefaultAccountState>()?;
+                if extension.state == AccountState::Frozen as u8 {
+                    msg!("token extension DefaultAccountState is frozen");
+                    return Err(MMMErrorCode::InvalidDefaultAccountState.into());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+// CpiGuard is a token account extension, when it's enabled the owner can't
+// transfer or close the token account via CPI, so the fulfill would fail deep in
+// the token program. The escrow token accounts are owned by the pool and can't
+// enable it, but the accounts are checked all the same.
+pub fn assert_no_cpi_guard(token_account: &AccountInfo) -> Result<()> {
+    if token_account.owner != &spl_token_2022::ID || token_account.data_is_empty() {
+        return Ok(());
+    }
+    let borrowed_data = token_account.data.borrow();
+    let account_deserialized = StateWithExtensions::<Token22Account>::unpack(&borrowed_data)?;
+    if let Ok(extension) = account_deserialized.get_extension::<CpiGuard>() {
+        if bool::from(extension.lock_cpi) {
+            msg!("token account {} has CpiGuard enabled", token_account.key);
+            return Err(MMMErrorCode::CpiGuardEnabled.into());
+        }
+    }
+    Ok(())
+}
+
+pub fn check_allowlists_for_mint_ext(
+    allowlists: &[Allowlist],
+    mint: &AccountInfo,
+    allowlist_aux: Option<String>,
+    extension_policy: &ExtensionPolicy,
+    remaining_accounts: &[AccountInfo],
+) -> Result<TokenMetadata> {
+    if mint.owner != &spl_token_2022::ID || mint.data_is_empty() {
+        return Err(MMMErrorCode::InvalidTokenMint.into());
+    }
+
+    let borrowed_data = mint.data.borrow();
+    let mint_deserialized = StateWithExtensions::<Token22Mint>::unpack(&borrowed_data)?;
+    if !mint_deserialized.base.is_initialized {
+        return Err(MMMErrorCode::InvalidTokenMetadataExtension.into());
+    }
+    assert_valid_extension(&mint_deserialized, extension_policy)?;
+    let parsed_metadata = assert_and_get_metadata_from_ext(mint)?;
+
+    if allowlists
+        .iter()
+        .any(|&val| val.kind == ALLOWLIST_KIND_METADATA)
+    {
+        // If allowlist_aux is not passed in, do not validate URI.
+        if let Some(ref aux_key) = allowlist_aux {
+            // Handle URI padding.
+            if !parsed_metadata.uri.trim().starts_with(aux_key) {
                 msg!(
                     "Failed metadata validation. Expected URI: |{}| but got |{}|",
                     *aux_key,
@@ -900,6 +1610,25 @@ pub fn check_allowlists_for_mint_ext(
                 }
                 return Ok(parsed_metadata);
             }
+            ALLOWLIST_KIND_EXT_CREATOR => {
+                // the royalty creator and the metadata update authority can be set by
+                // anyone, the group update authority has to sign to add the member
+                if Option::<Pubkey>::from(parsed_metadata.update_authority)
+                    == Some(allowlist_val.value)
+                    && assert_creator_valid_for_ext(mint, &allowlist_val.value).is_ok()
+                    && get_ext_group_update_authority(mint, remaining_accounts).ok()
+                        == Some(allowlist_val.value)
+                {
+                    return Ok(parsed_metadata);
+                }
+            }
+            ALLOWLIST_KIND_EXT_COLLECTION => {
+                if get_ext_group_update_authority(mint, remaining_accounts).ok()
+                    == Some(allowlist_val.value)
+                {
+                    return Ok(parsed_metadata);
+                }
+            }
             ALLOWLIST_KIND_METADATA => {
                 // Do not validate URI here, as we already did it above.
                 // Group is validated in a separate function.
@@ -990,9 +1719,91 @@ pub fn assert_and_get_valid_group(mint: &AccountInfo) -> Result<Option<Pubkey>>
     Err(MMMErrorCode::InvalidTokenMemberExtension.into())
 }
 
+// the token22 group of the mint if it's a group member, members can only be
+// initialized by the update authority of the group
+pub fn get_ext_group(mint: &AccountInfo) -> Option<Pubkey> {
+    assert_and_get_valid_group(mint).ok().flatten()
+}
+
+// returns the update authority of the token22 group of the mint, the group
+// mint account is looked up by key in remaining_accounts
+pub fn get_ext_group_update_authority(
+    mint: &AccountInfo,
+    remaining_accounts: &[AccountInfo],
+) -> Result<Pubkey> {
+    let group_address =
+        assert_and_get_valid_group(mint)?.ok_or(MMMErrorCode::InvalidTokenMemberExtension)?;
+    let group_account = remaining_accounts
+        .iter()
+        .find(|account| account.key == &group_address)
+        .ok_or(MMMErrorCode::InvalidRemainingAccounts)?;
+    get_token_group_update_authority(group_account)
+}
+
+pub fn get_token_group_update_authority(group_account: &AccountInfo) -> Result<Pubkey> {
+    let group_address = group_account.key();
+    if group_account.owner != &spl_token_2022::ID {
+        return Err(MMMErrorCode::InvalidTokenMemberExtension.into());
+    }
+
+    let borrowed_data = group_account.data.borrow();
+    let group_deserialized = StateWithExtensions::<Token22Mint>::unpack(&borrowed_data)?;
+    let group = group_deserialized
+        .get_extension::<TokenGroup>()
+        .map_err(|_| MMMErrorCode::InvalidTokenMemberExtension)?;
+    // counter spoof check
+    if group.mint != group_address {
+        msg!("group mint does not match the group account");
+        return Err(MMMErrorCode::InvalidTokenMemberExtension.into());
+    }
+    Option::<Pubkey>::from(group.update_authority)
+        .ok_or(MMMErrorCode::InvalidTokenMemberExtension.into())
+}
+
+// FulfillResult is set as the return data of the fulfill instructions, so that
+// programs calling them via CPI can decode the outcome with get_return_data.
+// The layout is part of the CPI interface: fields are only appended, never
+// reordered or removed. Callers built against an older layout must decode the
+// prefix they know with AnchorDeserialize::deserialize, try_from_slice rejects
+// the trailing bytes of the newer fields.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct FulfillResult {
+    pub total_price: u64,
+    pub lp_fee: u64,
+    pub maker_fee: i64,
+    pub taker_fee: i64,
+    pub royalty_paid: u64,
+    // amount received by the seller for fulfill buy, or paid by the buyer for fulfill sell
+    pub payment_amount: u64,
+    pub next_price: u64,
+    pub sellside_asset_amount: u64,
+    // asset amount withheld by the mint's transfer fee, only for token22 mints
+    pub transfer_fee_withheld: u64,
+    // part of royalty_paid credited to royalty vaults instead of paid to the creators
+    pub royalty_accrued: u64,
+    // paid by the taker and sent to the treasury of GlobalConfig
+    pub protocol_fee: u64,
+    // sol_fulfill_trade_in sums the fields above over both legs, total_price is
+    // the price of the asset bought from the pool and payment_amount the net
+    // amount paid by the payer
+    //
+    // price of the asset traded in, only set by sol_fulfill_trade_in
+    pub trade_in_total_price: u64,
+    // net amount received by the payer when the asset traded in is worth more
+    pub trade_in_payment_received: u64,
+}
+
+impl FulfillResult {
+    pub fn set_return_data(&self) -> Result<()> {
+        solana_program::program::set_return_data(&self.try_to_vec()?);
+        Ok(())
+    }
+}
+
 pub struct PoolPriceInfo<'info> {
     pub total_price: u64,
     pub next_price: u64,
+    pub spot_price_moved: bool,
     pub lp_fee: u64,
     pub maker_fee: i64,
     pub taker_fee: i64,
@@ -1000,18 +1811,28 @@ pub struct PoolPriceInfo<'info> {
     pub transfer_sol_to: AccountInfo<'info>,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn get_sell_fulfill_pool_price_info<'info>(
     pool: &Pool,
+    sell_state: &SellState,
     owner: &UncheckedAccount<'info>,
     buyside_sol_escrow_account: &AccountInfo<'info>,
     asset_amount: u64,
     maker_fee_bp: i16,
     taker_fee_bp: i16,
+    global_config: &GlobalConfig,
+    fee_schedule: &FeeSchedule,
 ) -> Result<PoolPriceInfo<'info>> {
-    let (total_price, next_price) = get_sol_total_price_and_next_price(pool, asset_amount, false)?;
-    let lp_fee = get_sol_lp_fee(pool, buyside_sol_escrow_account.lamports(), total_price)?;
+    let (total_price, next_price) =
+        get_sell_state_total_price_and_next_price(pool, sell_state, asset_amount)?;
+    let lp_fee = get_sol_lp_fee(
+        pool,
+        buyside_sol_escrow_account.lamports(),
+        total_price,
+        false,
+    )?;
 
-    assert_valid_fees_bp(maker_fee_bp, taker_fee_bp)?;
+    assert_valid_fees_bp(maker_fee_bp, taker_fee_bp, global_config, fee_schedule)?;
     let maker_fee = get_sol_fee(total_price, maker_fee_bp)?;
     let taker_fee = get_sol_fee(total_price, taker_fee_bp)?;
     let referral_fee = u64::try_from(
@@ -1030,6 +1851,7 @@ pub fn get_sell_fulfill_pool_price_info<'info>(
     Ok(PoolPriceInfo {
         total_price,
         next_price,
+        spot_price_moved: next_price != pool.spot_price,
         lp_fee,
         maker_fee,
         taker_fee,
@@ -1038,33 +1860,116 @@ pub fn get_sell_fulfill_pool_price_info<'info>(
     })
 }
 
+// splits the creator accounts of the royalty enforced token22 asset from the
+// remaining accounts, the creator accounts are in the same order as the
+// creators in RoyaltyMetadata, and the rest are the transfer hook accounts
 pub fn split_remaining_account_for_ext<'a, 'info>(
     remaining_accounts: &'a [AccountInfo<'info>],
     token_mint: &AccountInfo,
     is_using_shared_escrow: bool,
 ) -> Result<(
-    Option<&'a AccountInfo<'info>>,
+    Option<RoyaltyMetadata>,
+    &'a [AccountInfo<'info>],
     &'a [AccountInfo<'info>],
-    u16,
 )> {
-    // for shared escrow before the transfer hook accounts
+    // for shared escrow before the creator accounts
     // we have m2_program + shared_escrow_account
     let split_idx = if is_using_shared_escrow { 2 } else { 0 };
     if let Ok(transfer_hook_program_id) = get_transfer_hook_program_id(token_mint) {
         if transfer_hook_program_id == Some(LIBREPLEX_ROYALTY_ENFORCEMENT_PROGRAM_ID) {
-            let creator_account = index_ra!(remaining_accounts, split_idx);
-            if let Ok(sfbp) =
-                assert_creator_valid_for_ext(&token_mint.to_account_info(), creator_account.key)
-            {
-                return Ok((
-                    Some(creator_account),
-                    &remaining_accounts[(split_idx + 1)..],
-                    sfbp,
-                ));
+            if let Ok(ext_metadata) = get_ext_metadata(token_mint) {
+                let creators_len = ext_metadata.creators.len();
+                if remaining_accounts.len() < split_idx + creators_len {
+                    return Err(MMMErrorCode::InvalidRemainingAccounts.into());
+                }
+                // the creator accounts are returned with the accounts after
+                // them, where the royalty vaults of the creators are looked up
+                let creator_accounts = &remaining_accounts[split_idx..];
+                let rest = &creator_accounts[creators_len..];
+                return Ok((Some(ext_metadata), creator_accounts, rest));
             }
         }
     }
-    Ok((None, remaining_accounts, 0))
+    Ok((None, &[], &remaining_accounts[split_idx..]))
+}
+
+// checks that the transfer hook program of the mint (if any) is accepted by the
+// pool, either via the allow list of GlobalConfig or via
+// pool.transfer_hook_program_id. It's not
+// checked on withdrawals, so that owners can always get their assets back.
+pub fn assert_allowed_transfer_hook_program(
+    pool: &Pool,
+    global_config: &GlobalConfig,
+    mint: &AccountInfo,
+) -> Result<()> {
+    if let Some(program_id) = get_transfer_hook_program_id(mint)? {
+        if !global_config.is_transfer_hook_program_allowed(&program_id)
+            && pool.transfer_hook_program_id != program_id
+        {
+            msg!("InvalidTransferHookProgram: {}", program_id);
+            return Err(MMMErrorCode::InvalidTransferHookProgram.into());
+        }
+    }
+    Ok(())
+}
+
+// validates the transfer hook accounts of the mint (if any) before transferring
+// with invoke_transfer_checked, so that missing hook accounts fail with a clear
+// error instead of inside the CPI.
+pub fn assert_valid_transfer_hook<'info>(
+    source: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    hook_accounts: &[AccountInfo<'info>],
+    amount: u64,
+    decimals: u8,
+) -> Result<()> {
+    let program_id = match get_transfer_hook_program_id(mint)? {
+        Some(program_id) => program_id,
+        None => return Ok(()),
+    };
+
+    let validation_pubkey = get_extra_account_metas_address(mint.key, &program_id);
+    let validation_info = hook_accounts
+        .iter()
+        .find(|account| *account.key == validation_pubkey)
+        .ok_or(MMMErrorCode::InvalidTransferHookAccounts)?;
+    if *validation_info.owner != program_id
+        || !hook_accounts
+            .iter()
+            .any(|account| *account.key == program_id)
+    {
+        return Err(MMMErrorCode::InvalidTransferHookAccounts.into());
+    }
+
+    // resolve the ExtraAccountMetaList the same way token-2022 does for the
+    // transfer, and make sure all the resolved accounts are passed in
+    let mut cpi_instruction = spl_token_2022::instruction::transfer_checked(
+        &spl_token_2022::ID,
+        source.key,
+        mint.key,
+        destination.key,
+        authority.key,
+        &[],
+        amount,
+        decimals,
+    )?;
+    let mut cpi_account_infos = vec![
+        source.clone(),
+        mint.clone(),
+        destination.clone(),
+        authority.clone(),
+    ];
+    ExtraAccountMetaList::add_to_cpi_instruction::<ExecuteInstruction>(
+        &mut cpi_instruction,
+        &mut cpi_account_infos,
+        &validation_info.try_borrow_data()?,
+        hook_accounts,
+    )
+    .map_err(|_| MMMErrorCode::InvalidTransferHookAccounts)?;
+
+    Ok(())
 }
 
 pub fn get_transfer_hook_program_id(mint: &AccountInfo) -> Result<Option<Pubkey>> {
@@ -1079,30 +1984,90 @@ pub fn get_transfer_hook_program_id(mint: &AccountInfo) -> Result<Option<Pubkey>
     Ok(None)
 }
 
+// returns the amount withheld by the TransferFeeConfig extension of the mint
+// (if any) when transferring amount in the current epoch, together with the
+// amount that actually arrives in the destination account.
+//
+// token22 rounds the fee up, so for the ext NFTs (supply 1, decimals 0) any
+// non-zero fee withholds the whole asset. Such mints are rejected with
+// InvalidTransferFee, only fee configs whose fee for the current epoch is 0
+// (0 bp or a 0 maximum_fee) can be traded.
+pub fn get_transfer_fee_and_received_amount(mint: &AccountInfo, amount: u64) -> Result<(u64, u64)> {
+    let borrowed_data = mint.data.borrow();
+    let mint_deserialized = StateWithExtensions::<Token22Mint>::unpack(&borrowed_data)?;
+    let transfer_fee = match mint_deserialized.get_extension::<TransferFeeConfig>() {
+        Ok(extension) => extension
+            .calculate_epoch_fee(Clock::get()?.epoch, amount)
+            .ok_or(MMMErrorCode::NumericOverflow)?,
+        Err(_) => 0,
+    };
+    let received_amount = amount
+        .checked_sub(transfer_fee)
+        .ok_or(MMMErrorCode::InvalidTransferFee)?;
+    if received_amount == 0 {
+        msg!(
+            "transfer fee {} withholds the whole amount {}",
+            transfer_fee,
+            amount
+        );
+        return Err(MMMErrorCode::InvalidTransferFee.into());
+    }
+    Ok((transfer_fee, received_amount))
+}
+
+// returns the transfer fee withheld in the token account, token22 refuses to
+// close a token account until its withheld fee is harvested
+pub fn get_withheld_transfer_fee(token_account: &AccountInfo) -> Result<u64> {
+    if token_account.owner != &spl_token_2022::ID {
+        return Ok(0);
+    }
+    let borrowed_data = token_account.data.borrow();
+    let account_deserialized = StateWithExtensions::<Token22Account>::unpack(&borrowed_data)?;
+    Ok(
+        match account_deserialized.get_extension::<TransferFeeAmount>() {
+            Ok(extension) => u64::from(extension.withheld_amount),
+            Err(_) => 0,
+        },
+    )
+}
+
+// moves the withheld transfer fee of the token account to the mint so that the
+// token account can be closed, harvesting is permissionless and the fee stays
+// claimable by the withdraw withheld authority of the mint
+pub fn harvest_withheld_transfer_fee<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    token_account: &AccountInfo<'info>,
+) -> Result<()> {
+    if get_withheld_transfer_fee(token_account)? == 0 {
+        return Ok(());
+    }
+    invoke(
+        &harvest_withheld_tokens_to_mint(token_program.key, mint.key, &[token_account.key])?,
+        &[mint.clone(), token_account.clone()],
+    )?;
+    Ok(())
+}
+
 pub fn assert_creator_valid_for_ext(mint: &AccountInfo, creator: &Pubkey) -> Result<u16> {
     if mint.data_is_empty() || mint.owner != &spl_token_2022::ID {
         return Err(MMMErrorCode::InvalidTokenStandard.into());
     }
 
-    if let Ok(token_metadata) = assert_and_get_metadata_from_ext(mint) {
-        if let Ok(sfbp) = get_royalty_enforcement_from_additional_metadata(
-            &token_metadata.additional_metadata,
-            creator,
-        ) {
-            return Ok(sfbp);
-        }
-        // fallback to legacy encoding standard, can be removed once creator updates to the
-        // latest encoding standard
-        if let Ok(sfbp) = get_royalty_enforcement_legacy_from_additional_metadata(
-            &token_metadata.additional_metadata,
-            creator,
-        ) {
-            return Ok(sfbp);
+    if let Ok(ext_metadata) = get_ext_metadata(mint) {
+        if ext_metadata.creators.iter().any(|c| c.address == *creator) {
+            return Ok(ext_metadata.seller_fee_basis_points);
         }
     }
     Err(MMMErrorCode::InvalidCreatorAddress.into())
 }
 
+pub fn get_ext_metadata(mint: &AccountInfo) -> Result<RoyaltyMetadata> {
+    let token_metadata = assert_and_get_metadata_from_ext(mint)?;
+    parse_royalty_metadata(&token_metadata.additional_metadata)?
+        .ok_or(MMMErrorCode::InvalidMetadataCreatorRoyalty.into())
+}
+
 pub fn assert_valid_group_member_pointer(
     mint_deserialized: &StateWithExtensions<Token22Mint>,
     mint_key: &Pubkey,
@@ -1142,51 +2107,12 @@ pub fn get_royalty_enforcement_from_additional_metadata(
     additional_metadata: &[(String, String)],
     creator: &Pubkey,
 ) -> Result<u16> {
-    for additional_meta in additional_metadata.iter() {
-        if additional_meta.0.starts_with("_ro_") {
-            let expected_creator = &additional_meta.0[4..];
-            let sfbp: u16 = additional_meta.1.parse::<u16>().unwrap();
-            if Pubkey::from_str(expected_creator).unwrap().eq(creator) {
-                if sfbp > 10_000 {
-                    return Err(MMMErrorCode::InvalidBP.into());
-                }
-                return Ok(sfbp);
-            }
+    match parse_royalty_metadata(additional_metadata)? {
+        Some(royalty) if royalty.creators.iter().any(|c| c.address == *creator) => {
+            Ok(royalty.seller_fee_basis_points)
         }
+        _ => Err(MMMErrorCode::InvalidMetadataCreatorRoyalty.into()),
     }
-    Err(MMMErrorCode::InvalidMetadataCreatorRoyalty.into())
-}
-
-pub fn get_royalty_enforcement_legacy_from_additional_metadata(
-    additional_metadata: &[(String, String)],
-    creator: &Pubkey,
-) -> Result<u16> {
-    let mut sfbp: u16 = 0;
-    let mut expected_creator: &str = "";
-    let mut is_first_seen_royalty: bool = true;
-    for additional_meta in additional_metadata.iter() {
-        if additional_meta.0.starts_with("_roa_") {
-            expected_creator = &additional_meta.0[5..];
-            if !Pubkey::from_str(expected_creator).unwrap().eq(creator) {
-                return Err(MMMErrorCode::InvalidCreatorAddress.into());
-            }
-            let share = additional_meta.1.parse::<u16>().unwrap();
-            if share != 100 {
-                return Err(MMMErrorCode::InvalidCreatorAddress.into());
-            }
-        } else if additional_meta.0 == "_ros_" && is_first_seen_royalty {
-            // return the first seen royalty
-            is_first_seen_royalty = false;
-            sfbp = additional_meta.1.parse::<u16>().unwrap();
-            if sfbp > 10_000 {
-                return Err(MMMErrorCode::InvalidBP.into());
-            }
-        }
-    }
-    if sfbp > 0 && expected_creator != "" {
-        return Ok(sfbp);
-    }
-    Err(MMMErrorCode::InvalidCreatorAddress.into())
 }
 
 // mpl core
@@ -1468,4 +2394,976 @@ mod tests {
             Err(e) => panic!("{:?}", e),
         }
     }
+
+    #[test]
+    fn test_get_sell_state_total_price_and_next_price() {
+        let pool = Pool {
+            spot_price: 1_000_000_000,
+            curve_type: CURVE_KIND_LINEAR,
+            curve_delta: 100_000_000,
+            ..Default::default()
+        };
+        let mut sell_state = SellState::default();
+        assert_eq!(
+            get_sell_state_total_price_and_next_price(&pool, &sell_state, 1).unwrap(),
+            (1_100_000_000, 1_100_000_000)
+        );
+
+        sell_state.price_override = PriceOverride {
+            kind: PRICE_OVERRIDE_KIND_ABSOLUTE,
+            value: 5_000_000_000,
+        };
+        assert_eq!(
+            get_sell_state_total_price_and_next_price(&pool, &sell_state, 2).unwrap(),
+            (10_000_000_000, 1_000_000_000)
+        );
+
+        sell_state.price_override = PriceOverride {
+            kind: PRICE_OVERRIDE_KIND_PREMIUM,
+            value: 5_000,
+        };
+        assert_eq!(
+            get_sell_state_total_price_and_next_price(&pool, &sell_state, 1).unwrap(),
+            (1_650_000_000, 1_100_000_000)
+        );
+
+        sell_state.price_override = PriceOverride { kind: 3, value: 1 };
+        assert!(get_sell_state_total_price_and_next_price(&pool, &sell_state, 1).is_err());
+        assert!(!sell_state.price_override.valid());
+    }
+
+    #[test]
+    fn test_verify_trait_premium_proof() {
+        let premium = |asset_id: Pubkey| TraitPremium {
+            asset_id,
+            trait_bucket: [7; 32],
+            multiplier_bp: 15000,
+            expiry: 0,
+            proof: None,
+        };
+        let leaf = |p: &TraitPremium| {
+            keccak::hashv(&[
+                p.asset_id.as_ref(),
+                &p.trait_bucket,
+                &p.multiplier_bp.to_le_bytes(),
+                &p.expiry.to_le_bytes(),
+            ])
+            .to_bytes()
+        };
+        let a = premium(Pubkey::new_unique());
+        let b = premium(Pubkey::new_unique());
+        let (la, lb) = (leaf(&a), leaf(&b));
+        let root = if la <= lb {
+            keccak::hashv(&[&la, &lb]).to_bytes()
+        } else {
+            keccak::hashv(&[&lb, &la]).to_bytes()
+        };
+
+        assert!(verify_trait_premium_proof(&a, &[lb], &root));
+        assert!(verify_trait_premium_proof(&b, &[la], &root));
+        assert!(!verify_trait_premium_proof(&a, &[la], &root));
+
+        let mut tampered = a.clone();
+        tampered.multiplier_bp = 20000;
+        assert!(!verify_trait_premium_proof(&tampered, &[lb], &root));
+
+        // the root is read from pool.trait_premium_root, not cosigner_annotation
+        let mut pool = Pool {
+            max_trait_multiplier_bp: 20000,
+            cosigner_annotation: root,
+            ..Default::default()
+        };
+        let mut with_proof = a.clone();
+        with_proof.proof = Some(vec![lb]);
+        assert!(apply_trait_premium(&pool, &a.asset_id, 100, &Some(with_proof.clone())).is_err());
+        pool.trait_premium_root = root;
+        assert_eq!(
+            apply_trait_premium(&pool, &a.asset_id, 100, &Some(with_proof)).unwrap(),
+            150
+        );
+
+        assert!(check_max_trait_multiplier(0).is_ok());
+        assert!(check_max_trait_multiplier(MAX_TRAIT_MULTIPLIER_BP).is_ok());
+        assert!(check_max_trait_multiplier(MAX_TRAIT_MULTIPLIER_BP + 1).is_err());
+    }
+
+    #[test]
+    fn test_assert_valid_extension() {
+        let account_data = decode_hex(T22_LIBREPLEX_ROYALTY_ENFORCEMENT_ACCOUNT_DATA_STR);
+        let mint_deserialized = StateWithExtensions::<Token22Mint>::unpack(&account_data).unwrap();
+        let pool = Pubkey::new_unique();
+
+        let default_policy = ExtensionPolicy::default_for(pool);
+        assert!(default_policy.valid());
+        assert!(assert_valid_extension(&mint_deserialized, &default_policy).is_ok());
+
+        let mut policy = ExtensionPolicy::default_for(pool);
+        policy.allowed_extensions &= !ExtensionPolicy::mask(&[ExtensionType::TransferHook]);
+        assert_eq!(
+            assert_valid_extension(&mint_deserialized, &policy).unwrap_err(),
+            MMMErrorCode::InvalidTransferHookProgram.into()
+        );
+        policy.allowed_extensions &= !ExtensionPolicy::mask(&[ExtensionType::MetadataPointer]);
+        assert_eq!(
+            assert_valid_extension(&mint_deserialized, &policy).unwrap_err(),
+            MMMErrorCode::InvalidTokenExtension.into()
+        );
+        assert_eq!(
+            get_extension_error(ExtensionType::NonTransferable) as u32,
+            MMMErrorCode::NonTransferableMint as u32
+        );
+        assert_eq!(
+            get_extension_error(ExtensionType::PermanentDelegate) as u32,
+            MMMErrorCode::InvalidPermanentDelegate as u32
+        );
+        assert_eq!(
+            get_extension_error(ExtensionType::DefaultAccountState) as u32,
+            MMMErrorCode::InvalidDefaultAccountState as u32
+        );
+        assert_eq!(
+            get_extension_error(ExtensionType::InterestBearingConfig) as u32,
+            MMMErrorCode::InvalidInterestBearingConfig as u32
+        );
+
+        policy.allowed_extensions |= ExtensionPolicy::mask(&[ExtensionType::NonTransferable]);
+        assert!(!policy.valid());
+    }
+
+    #[test]
+    fn test_get_print_edition_royalty_metadata() {
+        let master_mint = Pubkey::new_unique();
+        let (master_edition, _) = MasterEdition::find_pda(&master_mint);
+        let (parent_metadata_key, _) = Metadata::find_pda(&master_mint);
+        let creator = Creator {
+            address: Pubkey::new_unique(),
+            verified: true,
+            share: 100,
+        };
+        let metadata = |mint: Pubkey, creators: Option<Vec<Creator>>, bp: u16| Metadata {
+            key: mpl_token_metadata::types::Key::MetadataV1,
+            update_authority: Pubkey::default(),
+            mint,
+            name: String::new(),
+            symbol: String::new(),
+            uri: String::new(),
+            seller_fee_basis_points: bp,
+            creators,
+            primary_sale_happened: true,
+            is_mutable: true,
+            edition_nonce: None,
+            token_standard: Some(TokenStandard::NonFungibleEdition),
+            collection: None,
+            uses: None,
+            collection_details: None,
+            programmable_config: None,
+        };
+
+        let edition_key = Pubkey::new_unique();
+        let mut edition_lamports = 1;
+        let mut edition_data = Edition {
+            key: mpl_token_metadata::types::Key::EditionV1,
+            parent: master_edition,
+            edition: 7,
+        }
+        .try_to_vec()
+        .unwrap();
+        let edition = AccountInfo::new(
+            &edition_key,
+            false,
+            false,
+            &mut edition_lamports,
+            &mut edition_data,
+            &mpl_token_metadata::ID,
+            false,
+            0,
+        );
+        assert_eq!(
+            get_print_edition_parent(&edition).unwrap(),
+            Some(master_edition)
+        );
+
+        let mut parent_lamports = 1;
+        let mut parent_data = metadata(master_mint, Some(vec![creator.clone()]), 500)
+            .try_to_vec()
+            .unwrap();
+        let parent = AccountInfo::new(
+            &parent_metadata_key,
+            false,
+            false,
+            &mut parent_lamports,
+            &mut parent_data,
+            &mpl_token_metadata::ID,
+            false,
+            0,
+        );
+
+        // the print carries its own creators
+        let print_mint = Pubkey::new_unique();
+        let resolved = get_print_edition_royalty_metadata(
+            metadata(print_mint, Some(vec![creator.clone()]), 300),
+            &edition,
+            &[],
+        )
+        .unwrap();
+        assert_eq!(resolved.seller_fee_basis_points, 300);
+
+        // the print doesn't carry creators, take them from the parent
+        let resolved = get_print_edition_royalty_metadata(
+            metadata(print_mint, None, 0),
+            &edition,
+            std::slice::from_ref(&parent),
+        )
+        .unwrap();
+        assert_eq!(resolved.mint, print_mint);
+        assert_eq!(resolved.seller_fee_basis_points, 500);
+        assert_eq!(resolved.creators, Some(vec![creator]));
+
+        // the parent metadata is required
+        assert!(
+            get_print_edition_royalty_metadata(metadata(print_mint, None, 0), &edition, &[])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_check_allowlists_for_mint_print_edition() {
+        use solana_program::program_pack::Pack;
+
+        let mint_key = Pubkey::new_unique();
+        let mut mint_lamports = 1;
+        let mut mint_data = vec![0; spl_token::state::Mint::LEN];
+        spl_token::state::Mint {
+            supply: 1,
+            is_initialized: true,
+            ..Default::default()
+        }
+        .pack_into_slice(&mut mint_data);
+        let mint_ai = AccountInfo::new(
+            &mint_key,
+            false,
+            false,
+            &mut mint_lamports,
+            &mut mint_data,
+            &spl_token::ID,
+            false,
+            0,
+        );
+        let mint = InterfaceAccount::<Mint>::try_from(&mint_ai).unwrap();
+
+        let creator = Pubkey::new_unique();
+        let metadata_key = Metadata::find_pda(&mint_key).0;
+        let mut metadata_lamports = 1;
+        let mut metadata_data = Metadata {
+            key: mpl_token_metadata::types::Key::MetadataV1,
+            update_authority: Pubkey::default(),
+            mint: mint_key,
+            name: String::new(),
+            symbol: String::new(),
+            uri: String::new(),
+            seller_fee_basis_points: 500,
+            creators: Some(vec![Creator {
+                address: creator,
+                verified: true,
+                share: 100,
+            }]),
+            primary_sale_happened: true,
+            is_mutable: true,
+            edition_nonce: None,
+            token_standard: Some(TokenStandard::NonFungibleEdition),
+            collection: None,
+            uses: None,
+            collection_details: None,
+            programmable_config: None,
+        }
+        .try_to_vec()
+        .unwrap();
+        let metadata = AccountInfo::new(
+            &metadata_key,
+            false,
+            false,
+            &mut metadata_lamports,
+            &mut metadata_data,
+            &mpl_token_metadata::ID,
+            false,
+            0,
+        );
+
+        let master_mint = Pubkey::new_unique();
+        let edition_key = MasterEdition::find_pda(&mint_key).0;
+        let mut edition_lamports = 1;
+        let mut edition_data = Edition {
+            key: mpl_token_metadata::types::Key::EditionV1,
+            parent: MasterEdition::find_pda(&master_mint).0,
+            edition: 7,
+        }
+        .try_to_vec()
+        .unwrap();
+        let edition = AccountInfo::new(
+            &edition_key,
+            false,
+            false,
+            &mut edition_lamports,
+            &mut edition_data,
+            &mpl_token_metadata::ID,
+            false,
+            0,
+        );
+        let mut master_edition_lamports = 1;
+        let mut master_edition_data = MasterEdition {
+            key: mpl_token_metadata::types::Key::MasterEditionV2,
+            supply: 0,
+            max_supply: Some(0),
+        }
+        .try_to_vec()
+        .unwrap();
+        let master_edition = AccountInfo::new(
+            &edition_key,
+            false,
+            false,
+            &mut master_edition_lamports,
+            &mut master_edition_data,
+            &mpl_token_metadata::ID,
+            false,
+            0,
+        );
+
+        let check = |kind: u8, value: Pubkey, edition: &AccountInfo| {
+            check_allowlists_for_mint(
+                &[Allowlist { kind, value }],
+                &mint,
+                &metadata,
+                Some(edition),
+                None,
+            )
+        };
+
+        // the verified creator matches, but FVCA pools don't take prints
+        assert!(check(ALLOWLIST_KIND_FVCA, creator, &master_edition).is_ok());
+        assert_eq!(
+            check(ALLOWLIST_KIND_FVCA, creator, &edition).unwrap_err(),
+            MMMErrorCode::InvalidMasterEdition.into()
+        );
+        assert_eq!(
+            check(ALLOWLIST_KIND_ANY, Pubkey::default(), &edition).unwrap_err(),
+            MMMErrorCode::InvalidMasterEdition.into()
+        );
+
+        // only the master edition allowlist of the parent takes the print
+        assert!(check(ALLOWLIST_KIND_MASTER_EDITION, master_mint, &edition).is_ok());
+        assert_eq!(
+            check(
+                ALLOWLIST_KIND_MASTER_EDITION,
+                Pubkey::new_unique(),
+                &edition
+            )
+            .unwrap_err(),
+            MMMErrorCode::InvalidMasterEdition.into()
+        );
+        assert_eq!(
+            check(ALLOWLIST_KIND_MASTER_EDITION, master_mint, &master_edition).unwrap_err(),
+            MMMErrorCode::InvalidAllowLists.into()
+        );
+    }
+
+    #[test]
+    fn test_find_royalty_vault() {
+        let creator = Pubkey::new_unique();
+        let (vault_key, _) = Pubkey::find_program_address(
+            &[ROYALTY_VAULT_PREFIX.as_bytes(), creator.as_ref()],
+            &crate::ID,
+        );
+        let other_key = Pubkey::new_unique();
+        let system_program = anchor_lang::system_program::ID;
+        let mut lamports = [1; 3];
+        let mut data = [vec![], vec![], vec![]];
+        let [l0, l1, l2] = &mut lamports;
+        let [d0, d1, d2] = &mut data;
+        let creator_ai = AccountInfo::new(&creator, false, true, l0, d0, &system_program, false, 0);
+        let readonly_vault_ai =
+            AccountInfo::new(&vault_key, false, false, l1, d1, &crate::ID, false, 0);
+        let other_ai = AccountInfo::new(&other_key, false, true, l2, d2, &crate::ID, false, 0);
+
+        // the vault must be passed as writable
+        let accounts = [creator_ai.clone(), readonly_vault_ai.clone(), other_ai];
+        assert!(find_royalty_vault(&creator, &accounts).is_none());
+
+        let mut vault_ai = readonly_vault_ai;
+        vault_ai.is_writable = true;
+        let accounts = [creator_ai.clone(), vault_ai];
+        assert_eq!(
+            find_royalty_vault(&creator, &accounts).map(|account| account.key),
+            Some(&vault_key)
+        );
+
+        // the creator is paid directly when it ends up above the minimum
+        let (destination, to_vault) =
+            get_creator_fee_destination(&accounts[0], 100, &accounts, 100)
+                .unwrap()
+                .unwrap();
+        assert_eq!((destination.key, to_vault), (&creator, false));
+        // otherwise the fee goes to the vault, and is skipped without one
+        let (destination, to_vault) = get_creator_fee_destination(&accounts[0], 99, &accounts, 100)
+            .unwrap()
+            .unwrap();
+        assert_eq!((destination.key, to_vault), (&vault_key, true));
+        assert!(get_creator_fee_destination(
+            &creator_ai,
+            99,
+            std::slice::from_ref(&creator_ai),
+            100
+        )
+        .unwrap()
+        .is_none());
+    }
+
+    #[test]
+    fn test_get_metadata_royalty_bp_with_royalty_schedule() {
+        let metadata = MplCoreMetadata {
+            seller_fee_basis_points: 500,
+            creators: None,
+        };
+        let bands = [
+            RoyaltyBand {
+                min_price: 1_000,
+                royalty_bp: 300,
+            },
+            RoyaltyBand {
+                min_price: 10_000,
+                royalty_bp: 100,
+            },
+        ];
+        let max_bp = GlobalConfig::default().max_metadata_creator_royalty_bp;
+        assert!(RoyaltySchedule::valid_bands(&bands, max_bp));
+        assert!(!RoyaltySchedule::valid_bands(&[bands[1], bands[0]], max_bp));
+        assert!(!RoyaltySchedule::valid_bands(
+            &[RoyaltyBand {
+                min_price: 0,
+                royalty_bp: max_bp + 1,
+            }],
+            max_bp
+        ));
+        assert!(!RoyaltySchedule::valid_bands(
+            &[RoyaltyBand::default(); crate::constants::ROYALTY_SCHEDULE_MAX_BANDS + 1],
+            max_bp
+        ));
+
+        let mut royalty_schedule = RoyaltySchedule {
+            band_count: 2,
+            ..Default::default()
+        };
+        royalty_schedule.bands[..2].copy_from_slice(&bands);
+        let get_bp =
+            |price: u64| get_metadata_royalty_bp(price, &metadata, None, Some(&royalty_schedule));
+        assert_eq!(get_bp(999), 500);
+        assert_eq!(get_bp(1_000), 300);
+        assert_eq!(get_bp(9_999), 300);
+        assert_eq!(get_bp(10_000), 100);
+        assert_eq!(get_metadata_royalty_bp(10_000, &metadata, None, None), 500);
+
+        // bands after band_count are ignored
+        royalty_schedule.band_count = 1;
+        assert_eq!(
+            get_metadata_royalty_bp(10_000, &metadata, None, Some(&royalty_schedule)),
+            300
+        );
+    }
+
+    #[test]
+    fn test_get_royalty_schedule() {
+        let collection = Pubkey::new_unique();
+        let royalty_schedule_key = |collection: &Pubkey| {
+            Pubkey::find_program_address(
+                &[ROYALTY_SCHEDULE_PREFIX.as_bytes(), collection.as_ref()],
+                &crate::ID,
+            )
+            .0
+        };
+        let key = royalty_schedule_key(&collection);
+        let mut data = vec![];
+        RoyaltySchedule {
+            collection,
+            band_count: 1,
+            ..Default::default()
+        }
+        .try_serialize(&mut data)
+        .unwrap();
+        let mut lamports = 1;
+        let royalty_schedule = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &crate::ID,
+            false,
+            0,
+        );
+        assert_eq!(
+            get_royalty_schedule(Some(collection), &royalty_schedule)
+                .unwrap()
+                .map(|royalty_schedule| royalty_schedule.band_count),
+            Some(1)
+        );
+        // the account must be the pda of the collection
+        assert_eq!(
+            get_royalty_schedule(Some(Pubkey::new_unique()), &royalty_schedule).err(),
+            Some(MMMErrorCode::InvalidRoyaltySchedule.into())
+        );
+        assert_eq!(
+            get_royalty_schedule(None, &royalty_schedule).err(),
+            Some(MMMErrorCode::InvalidRoyaltySchedule.into())
+        );
+
+        // empty accounts mean no schedule, assets without a collection pass the
+        // pda of the default pubkey
+        let mut empty_data = vec![];
+        let mut empty_lamports = 0;
+        let empty = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut empty_lamports,
+            &mut empty_data,
+            &anchor_lang::system_program::ID,
+            false,
+            0,
+        );
+        assert!(get_royalty_schedule(Some(collection), &empty)
+            .unwrap()
+            .is_none());
+        let default_key = royalty_schedule_key(&Pubkey::default());
+        let mut no_collection = empty.clone();
+        no_collection.key = &default_key;
+        assert!(get_royalty_schedule(None, &no_collection)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_assert_royalty_rule_set() {
+        use crate::instructions::assert_royalty_rule_set;
+        use mpl_core::types::RuleSet;
+
+        let other_program = Pubkey::new_unique();
+        assert!(assert_royalty_rule_set(&RuleSet::None, &crate::ID).is_ok());
+        assert!(
+            assert_royalty_rule_set(&RuleSet::ProgramAllowList(vec![crate::ID]), &crate::ID)
+                .is_ok()
+        );
+        assert!(assert_royalty_rule_set(
+            &RuleSet::ProgramAllowList(vec![other_program]),
+            &crate::ID
+        )
+        .is_err());
+        assert!(
+            assert_royalty_rule_set(&RuleSet::ProgramDenyList(vec![crate::ID]), &crate::ID)
+                .is_err()
+        );
+        assert!(assert_royalty_rule_set(
+            &RuleSet::ProgramDenyList(vec![other_program]),
+            &crate::ID
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_get_protocol_fee() {
+        let mut pool = Pool::default();
+        let mut global_config = GlobalConfig::default();
+        assert_eq!(
+            get_protocol_fee(&global_config, &pool, 1_000_000).unwrap(),
+            0
+        );
+
+        global_config.protocol_fee_bp = 50;
+        assert_eq!(
+            get_protocol_fee(&global_config, &pool, 1_000_000).unwrap(),
+            5_000
+        );
+        assert_eq!(get_protocol_fee(&global_config, &pool, 199).unwrap(), 0);
+        assert_eq!(
+            get_protocol_fee(&global_config, &pool, u64::MAX).unwrap(),
+            (u64::MAX as u128 * 50 / 10000) as u64
+        );
+
+        // pools in the fee-free tier
+        pool.protocol_fee_exempt = true;
+        assert_eq!(
+            get_protocol_fee(&global_config, &pool, 1_000_000).unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_global_config_limits() {
+        let mut global_config = GlobalConfig::default();
+        assert!(global_config.valid());
+        assert_eq!(
+            global_config.cancel_authority,
+            crate::constants::CANCEL_AUTHORITY
+        );
+        assert_eq!(
+            global_config.max_referral_fee_bp,
+            crate::constants::MAX_REFERRAL_FEE_BP
+        );
+        assert_eq!(
+            global_config.m2_auction_house,
+            crate::constants::M2_AUCTION_HOUSE
+        );
+        assert!(global_config.is_transfer_hook_program_allowed(
+            &crate::constants::LIBREPLEX_ROYALTY_ENFORCEMENT_PROGRAM_ID
+        ));
+        // empty slots don't allow anything
+        assert!(!global_config.is_transfer_hook_program_allowed(&Pubkey::default()));
+        let transfer_hook_program_id = Pubkey::new_unique();
+        assert!(!global_config.is_transfer_hook_program_allowed(&transfer_hook_program_id));
+        global_config.transfer_hook_program_allow_list[1] = transfer_hook_program_id;
+        assert!(global_config.is_transfer_hook_program_allowed(&transfer_hook_program_id));
+
+        let fee_schedule = global_config.default_fee_schedule(Pubkey::default());
+        assert!(assert_valid_fees_bp(0, 500, &global_config, &fee_schedule).is_ok());
+        assert!(assert_valid_fees_bp(-500, 500, &global_config, &fee_schedule).is_ok());
+        assert!(assert_valid_fees_bp(0, 501, &global_config, &fee_schedule).is_err());
+
+        global_config.max_referral_fee_bp = 1_000;
+        global_config.default_taker_fee_bp = 1_000;
+        let fee_schedule = global_config.default_fee_schedule(Pubkey::default());
+        assert!(assert_valid_fees_bp(0, 501, &global_config, &fee_schedule).is_ok());
+        assert!(assert_valid_fees_bp(600, 500, &global_config, &fee_schedule).is_err());
+
+        // the cosigner can only lower the fees of the schedule
+        let fee_schedule = FeeSchedule {
+            maker_fee_bp: -50,
+            taker_fee_bp: 100,
+            ..Default::default()
+        };
+        assert!(fee_schedule.valid(&global_config));
+        assert!(assert_valid_fees_bp(-50, 100, &global_config, &fee_schedule).is_ok());
+        assert!(assert_valid_fees_bp(-100, 100, &global_config, &fee_schedule).is_ok());
+        assert!(assert_valid_fees_bp(0, 100, &global_config, &fee_schedule).is_err());
+        assert!(assert_valid_fees_bp(-50, 150, &global_config, &fee_schedule).is_err());
+
+        global_config.default_taker_fee_bp = 500;
+        global_config.max_referral_fee_bp = -1;
+        assert!(!global_config.valid());
+        global_config.max_referral_fee_bp = 500;
+        global_config.min_sol_escrow_balance_bp = 10_001;
+        assert!(!global_config.valid());
+    }
+
+    #[test]
+    fn test_assert_not_paused() {
+        let mut global_config = GlobalConfig::default();
+        assert!(assert_not_paused(&global_config, PAUSE_FLAG_VANILLA).is_ok());
+        assert!(assert_not_paused(&global_config, PAUSE_FLAG_ALL).is_ok());
+
+        global_config.pause_flags = PAUSE_FLAG_EXT;
+        assert!(assert_not_paused(&global_config, PAUSE_FLAG_VANILLA).is_ok());
+        assert!(assert_not_paused(&global_config, PAUSE_FLAG_ALL).is_ok());
+        assert!(assert_not_paused(&global_config, PAUSE_FLAG_EXT).is_err());
+
+        global_config.pause_flags = PAUSE_FLAG_ALL;
+        assert!(assert_not_paused(&global_config, PAUSE_FLAG_VANILLA).is_err());
+        assert!(assert_not_paused(&global_config, PAUSE_FLAG_CNFT).is_err());
+    }
+
+    #[test]
+    fn test_get_lp_fee_bp() {
+        let mut pool = Pool {
+            spot_price: 1_000_000_000,
+            sellside_asset_amount: 1,
+            lp_fee_bp: 200,
+            ..Default::default()
+        };
+        // one sided pools don't charge lp fees
+        assert_eq!(get_lp_fee_bp(&pool, 999_999_999, true).unwrap(), 0);
+        assert_eq!(get_lp_fee_bp(&pool, 3_000_000_000, true).unwrap(), 200);
+        assert_eq!(get_lp_fee_bp(&pool, 3_000_000_000, false).unwrap(), 200);
+
+        pool.dynamic_lp_fee_min_bp = 100;
+        pool.dynamic_lp_fee_max_bp = 500;
+        // 1 asset and 3 SOL, buying more assets is charged less than selling them
+        assert_eq!(get_lp_fee_bp(&pool, 3_000_000_000, true).unwrap(), 200);
+        assert_eq!(get_lp_fee_bp(&pool, 3_000_000_000, false).unwrap(), 400);
+        // balanced inventory is charged the middle of the range
+        assert_eq!(get_lp_fee_bp(&pool, 1_000_000_000, true).unwrap(), 300);
+        assert_eq!(get_lp_fee_bp(&pool, 1_000_000_000, false).unwrap(), 300);
+        assert_eq!(
+            get_sol_lp_fee(&pool, 3_000_000_000, 1_000_000_000, false).unwrap(),
+            40_000_000
+        );
+
+        let global_config = GlobalConfig::default();
+        assert!(check_dynamic_lp_fee(&pool, &global_config).is_ok());
+        pool.dynamic_lp_fee_min_bp = 600;
+        assert!(check_dynamic_lp_fee(&pool, &global_config).is_err());
+        pool.dynamic_lp_fee_min_bp = 0;
+        pool.dynamic_lp_fee_max_bp = global_config.max_lp_fee_bp + 1;
+        assert!(check_dynamic_lp_fee(&pool, &global_config).is_err());
+    }
+
+    #[test]
+    fn test_get_referral_fee_shares() {
+        let recipient = |share_bp| ReferralRecipient {
+            address: Pubkey::new_unique(),
+            share_bp,
+        };
+        let recipients = [recipient(5000), recipient(3000), recipient(2000)];
+        assert!(ReferralRegistry::valid_recipients(&recipients));
+        assert!(ReferralRegistry::valid_recipients(&[]));
+        assert!(!ReferralRegistry::valid_recipients(&recipients[..2]));
+        assert!(!ReferralRegistry::valid_recipients(&[
+            recipient(10000),
+            recipient(0)
+        ]));
+        assert!(!ReferralRegistry::valid_recipients(
+            &[recipient(2000); crate::constants::REFERRAL_REGISTRY_MAX_RECIPIENTS + 1]
+        ));
+
+        assert_eq!(
+            get_referral_fee_shares(&recipients, 1_000_000).unwrap(),
+            vec![500_000, 300_000, 200_000]
+        );
+        // the rounding remainder goes to the last recipient
+        assert_eq!(
+            get_referral_fee_shares(&recipients, 7).unwrap(),
+            vec![3, 2, 2]
+        );
+    }
+
+    #[test]
+    fn test_get_referral_registry() {
+        let key = Pubkey::new_unique();
+        let referrer = Pubkey::new_unique();
+        let mut data = vec![];
+        ReferralRegistry {
+            referrer,
+            recipient_count: 1,
+            ..Default::default()
+        }
+        .try_serialize(&mut data)
+        .unwrap();
+        let mut lamports = 1;
+        let referral_registry = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &crate::ID,
+            false,
+            0,
+        );
+        assert_eq!(
+            get_referral_registry(&referral_registry)
+                .unwrap()
+                .map(|registry| registry.referrer),
+            Some(referrer)
+        );
+
+        // an empty account means no registry
+        let mut empty_data = vec![];
+        let mut empty_lamports = 0;
+        let empty = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut empty_lamports,
+            &mut empty_data,
+            &anchor_lang::system_program::ID,
+            false,
+            0,
+        );
+        assert!(get_referral_registry(&empty).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_fulfill_result_layout() {
+        let result = FulfillResult {
+            total_price: 1,
+            lp_fee: 2,
+            maker_fee: 3,
+            taker_fee: 4,
+            royalty_paid: 5,
+            payment_amount: 6,
+            next_price: 7,
+            sellside_asset_amount: 8,
+            transfer_fee_withheld: 9,
+            royalty_accrued: 10,
+            protocol_fee: 11,
+            trade_in_total_price: 12,
+            trade_in_payment_received: 13,
+        };
+        let data = result.try_to_vec().unwrap();
+        // fields are never reordered, each one keeps its offset
+        let words: Vec<u64> = data
+            .chunks(8)
+            .map(|c| u64::from_le_bytes(c.try_into().unwrap()))
+            .collect();
+        assert_eq!(words, (1..=13).collect::<Vec<u64>>());
+        assert_eq!(FulfillResult::try_from_slice(&data).unwrap(), result);
+    }
+
+    #[test]
+    fn test_get_withheld_transfer_fee() {
+        use spl_token_2022::extension::StateWithExtensionsMut;
+
+        let len = ExtensionType::try_calculate_account_len::<Token22Account>(&[
+            ExtensionType::TransferFeeAmount,
+        ])
+        .unwrap();
+        let mut data = vec![0u8; len];
+        let mut state =
+            StateWithExtensionsMut::<Token22Account>::unpack_uninitialized(&mut data).unwrap();
+        state.base = Token22Account {
+            mint: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            amount: 1,
+            state: AccountState::Initialized,
+            ..Default::default()
+        };
+        state.pack_base();
+        state.init_account_type().unwrap();
+        state
+            .init_extension::<TransferFeeAmount>(true)
+            .unwrap()
+            .withheld_amount = 3.into();
+
+        let key = Pubkey::new_unique();
+        let mut lamports = 1;
+        let account = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &spl_token_2022::ID,
+            false,
+            0,
+        );
+        // the escrow holding withheld fees must be harvested before it's closed
+        assert_eq!(get_withheld_transfer_fee(&account).unwrap(), 3);
+
+        let mut legacy_data = vec![0u8; 165];
+        let mut legacy_lamports = 1;
+        let legacy_account = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut legacy_lamports,
+            &mut legacy_data,
+            &anchor_spl::token::ID,
+            false,
+            0,
+        );
+        assert_eq!(get_withheld_transfer_fee(&legacy_account).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_assert_no_cpi_guard() {
+        use spl_token_2022::extension::StateWithExtensionsMut;
+
+        let len =
+            ExtensionType::try_calculate_account_len::<Token22Account>(&[ExtensionType::CpiGuard])
+                .unwrap();
+        let mut data = vec![0u8; len];
+        let mut state =
+            StateWithExtensionsMut::<Token22Account>::unpack_uninitialized(&mut data).unwrap();
+        state.base = Token22Account {
+            mint: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            amount: 1,
+            state: AccountState::Initialized,
+            ..Default::default()
+        };
+        state.pack_base();
+        state.init_account_type().unwrap();
+        state.init_extension::<CpiGuard>(true).unwrap().lock_cpi = true.into();
+
+        let key = Pubkey::new_unique();
+        let mut lamports = 1;
+        let account = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &spl_token_2022::ID,
+            false,
+            0,
+        );
+        assert_eq!(
+            assert_no_cpi_guard(&account).unwrap_err(),
+            MMMErrorCode::CpiGuardEnabled.into()
+        );
+
+        // uninitialized escrow token accounts are created later in the fulfill
+        let mut empty_data = vec![];
+        let mut empty_lamports = 0;
+        let empty_account = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut empty_lamports,
+            &mut empty_data,
+            &anchor_lang::system_program::ID,
+            false,
+            0,
+        );
+        assert!(assert_no_cpi_guard(&empty_account).is_ok());
+    }
+
+    #[test]
+    fn test_ext_creator_fee_split() {
+        let creators = [
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ];
+        let ext_metadata = parse_royalty_metadata(&[
+            (format!("_roa_{}", creators[0]), "50".to_string()),
+            (format!("_roa_{}", creators[1]), "30".to_string()),
+            (format!("_roa_{}", creators[2]), "20".to_string()),
+            ("_ros_".to_string(), "500".to_string()),
+        ])
+        .unwrap()
+        .unwrap();
+        let metadata_creators = ext_metadata.get_creators().unwrap();
+        assert_eq!(ext_metadata.get_seller_fee_basis_points(), 500);
+
+        assert_eq!(
+            get_creator_fee_shares(&metadata_creators, 1_000_000).unwrap(),
+            vec![500_000, 300_000, 200_000]
+        );
+        // the rounding remainder goes to the last creator
+        assert_eq!(
+            get_creator_fee_shares(&metadata_creators, 7).unwrap(),
+            vec![3, 2, 2]
+        );
+
+        let mut lamports = [0; 3];
+        let mut data = [vec![], vec![], vec![]];
+        let [l0, l1, l2] = &mut lamports;
+        let [d0, d1, d2] = &mut data;
+        let system_program = anchor_lang::system_program::ID;
+        let a0 = AccountInfo::new(&creators[0], false, true, l0, d0, &system_program, false, 0);
+        let a1 = AccountInfo::new(&creators[1], false, true, l1, d1, &system_program, false, 0);
+        let a2 = AccountInfo::new(&creators[2], false, true, l2, d2, &system_program, false, 0);
+
+        let accounts = [a0.clone(), a1.clone(), a2.clone()];
+        assert!(check_creator_accounts(&metadata_creators, &accounts).is_ok());
+        // creators passed in the wrong order, or a different account, are rejected
+        let accounts = [a0.clone(), a2.clone(), a1.clone()];
+        assert_eq!(
+            check_creator_accounts(&metadata_creators, &accounts).unwrap_err(),
+            MMMErrorCode::InvalidCreatorAddress.into()
+        );
+        let accounts = [a0.clone(), a1.clone(), a0.clone()];
+        assert_eq!(
+            check_creator_accounts(&metadata_creators, &accounts).unwrap_err(),
+            MMMErrorCode::InvalidCreatorAddress.into()
+        );
+        let accounts = [a0, a1];
+        assert!(check_creator_accounts(&metadata_creators, &accounts).is_err());
+    }
 }
@@ -4,6 +4,12 @@ export const PREFIXES = {
   POOL: 'mmm_pool',
   BUYSIDE_SOL_ESCROW: 'mmm_buyside_sol_escrow_account',
   SELL_STATE: 'mmm_sell_state',
+  EXTENSION_POLICY: 'mmm_extension_policy',
+  ROYALTY_VAULT: 'mmm_royalty_vault',
+  ROYALTY_SCHEDULE: 'mmm_royalty_schedule',
+  GLOBAL_CONFIG: 'mmm_global_config',
+  REFERRAL_REGISTRY: 'mmm_referral_registry',
+  FEE_SCHEDULE: 'mmm_fee_schedule',
 };
 
 export enum CurveKind {
@@ -19,6 +25,9 @@ export enum AllowlistKind {
   metadata = 4,
   group = 5,
   mpl_core_collection = 6,
+  ext_creator = 7,
+  ext_collection = 8,
+  master_edition = 9,
   any = 255,
 }
 
@@ -20,6 +20,11 @@ export type Mmm = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "systemProgram",
           "isMut": false,
@@ -52,6 +57,11 @@ export type Mmm = {
           "name": "pool",
           "isMut": true,
           "isSigner": false
+        },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": [
@@ -145,6 +155,11 @@ export type Mmm = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "systemProgram",
           "isMut": false,
@@ -183,6 +198,11 @@ export type Mmm = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "systemProgram",
           "isMut": false,
@@ -221,6 +241,34 @@ export type Mmm = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "treasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "feeSchedule",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "royaltySchedule",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "get_royalty_schedule, it might not be initialized"
+          ]
+        },
+        {
+          "name": "referralRegistry",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "pool",
           "isMut": true,
@@ -324,6 +372,34 @@ export type Mmm = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "treasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "feeSchedule",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "royaltySchedule",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "get_royalty_schedule, it might not be initialized"
+          ]
+        },
+        {
+          "name": "referralRegistry",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "pool",
           "isMut": true,
@@ -399,6 +475,193 @@ export type Mmm = {
         }
       ]
     },
+    {
+      "name": "solFulfillRoute",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "SolFulfillRouteArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "solFulfillTradeIn",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "cosigner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "referral",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "treasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "feeSchedule",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "inRoyaltySchedule",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "in get_royalty_schedule, it might not be initialized"
+          ]
+        },
+        {
+          "name": "outRoyaltySchedule",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "in get_royalty_schedule, it might not be initialized"
+          ]
+        },
+        {
+          "name": "referralRegistry",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buysideSolEscrowAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "inAssetMetadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "inAssetMasterEdition",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "inAssetMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "payerInAssetAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "inSellsideEscrowTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "ownerInTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "inSellState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "outAssetMetadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "outAssetMasterEdition",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "outAssetMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "outSellsideEscrowTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payerOutAssetAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "outSellState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "allowlistAuxAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "SolFulfillTradeInArgs"
+          }
+        }
+      ]
+    },
     {
       "name": "withdrawSell",
       "accounts": [
@@ -530,6 +793,11 @@ export type Mmm = {
           "isMut": false,
           "isSigner": false
         },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "systemProgram",
           "isMut": false,
@@ -638,6 +906,11 @@ export type Mmm = {
           "isMut": false,
           "isSigner": false
         },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "systemProgram",
           "isMut": false,
@@ -691,6 +964,34 @@ export type Mmm = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "treasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "feeSchedule",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "royaltySchedule",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "get_royalty_schedule, it might not be initialized"
+          ]
+        },
+        {
+          "name": "referralRegistry",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "pool",
           "isMut": true,
@@ -820,12 +1121,40 @@ export type Mmm = {
           "isSigner": false
         },
         {
-          "name": "pool",
-          "isMut": true,
+          "name": "globalConfig",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "buysideSolEscrowAccount",
+          "name": "treasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "feeSchedule",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "royaltySchedule",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "get_royalty_schedule, it might not be initialized"
+          ]
+        },
+        {
+          "name": "referralRegistry",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buysideSolEscrowAccount",
           "isMut": true,
           "isSigner": false
         },
@@ -1115,6 +1444,11 @@ export type Mmm = {
           "isMut": false,
           "isSigner": false
         },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "systemProgram",
           "isMut": false,
@@ -1258,7 +1592,7 @@ export type Mmm = {
         {
           "name": "args",
           "type": {
-            "defined": "WithdrawSellArgs"
+            "defined": "Mip1WithdrawSellArgs"
           }
         }
       ]
@@ -1286,6 +1620,34 @@ export type Mmm = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "treasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "feeSchedule",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "royaltySchedule",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "get_royalty_schedule, it might not be initialized"
+          ]
+        },
+        {
+          "name": "referralRegistry",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "pool",
           "isMut": true,
@@ -1314,7 +1676,10 @@ export type Mmm = {
         {
           "name": "sellsideEscrowTokenAccount",
           "isMut": true,
-          "isSigner": false
+          "isSigner": false,
+          "docs": [
+            "non-custodial listings, checked in the handler"
+          ]
         },
         {
           "name": "payerAssetAccount",
@@ -1414,6 +1779,34 @@ export type Mmm = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "treasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "feeSchedule",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "royaltySchedule",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "get_royalty_schedule, it might not be initialized"
+          ]
+        },
+        {
+          "name": "referralRegistry",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "pool",
           "isMut": true,
@@ -1544,7 +1937,10 @@ export type Mmm = {
         {
           "name": "authority",
           "isMut": false,
-          "isSigner": true
+          "isSigner": true,
+          "docs": [
+            "checked against the cancel_authority of the global config"
+          ]
         },
         {
           "name": "owner",
@@ -1561,6 +1957,11 @@ export type Mmm = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "systemProgram",
           "isMut": false,
@@ -1591,6 +1992,11 @@ export type Mmm = {
           "name": "sharedEscrowAccount",
           "isMut": false,
           "isSigner": false
+        },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": [
@@ -1622,7 +2028,7 @@ export type Mmm = {
         },
         {
           "name": "assetMint",
-          "isMut": false,
+          "isMut": true,
           "isSigner": false
         },
         {
@@ -1640,6 +2046,16 @@ export type Mmm = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "extensionPolicy",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "systemProgram",
           "isMut": false,
@@ -1688,6 +2104,34 @@ export type Mmm = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "treasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "feeSchedule",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "royaltySchedule",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "get_royalty_schedule, it might not be initialized"
+          ]
+        },
+        {
+          "name": "referralRegistry",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "pool",
           "isMut": true,
@@ -1700,7 +2144,7 @@ export type Mmm = {
         },
         {
           "name": "assetMint",
-          "isMut": false,
+          "isMut": true,
           "isSigner": false
         },
         {
@@ -1718,6 +2162,11 @@ export type Mmm = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "extensionPolicy",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "systemProgram",
           "isMut": false,
@@ -1766,6 +2215,34 @@ export type Mmm = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "treasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "feeSchedule",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "royaltySchedule",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "get_royalty_schedule, it might not be initialized"
+          ]
+        },
+        {
+          "name": "referralRegistry",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "pool",
           "isMut": true,
@@ -1778,7 +2255,7 @@ export type Mmm = {
         },
         {
           "name": "assetMint",
-          "isMut": false,
+          "isMut": true,
           "isSigner": false
         },
         {
@@ -1806,6 +2283,11 @@ export type Mmm = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "extensionPolicy",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "systemProgram",
           "isMut": false,
@@ -1856,7 +2338,7 @@ export type Mmm = {
         },
         {
           "name": "assetMint",
-          "isMut": false,
+          "isMut": true,
           "isSigner": false
         },
         {
@@ -1942,6 +2424,11 @@ export type Mmm = {
           "isMut": false,
           "isSigner": false
         },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "systemProgram",
           "isMut": false,
@@ -2043,6 +2530,34 @@ export type Mmm = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "treasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "feeSchedule",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "royaltySchedule",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "get_royalty_schedule, it might not be initialized"
+          ]
+        },
+        {
+          "name": "referralRegistry",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "pool",
           "isMut": true,
@@ -2111,6 +2626,34 @@ export type Mmm = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "treasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "feeSchedule",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "royaltySchedule",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "get_royalty_schedule, it might not be initialized"
+          ]
+        },
+        {
+          "name": "referralRegistry",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "pool",
           "isMut": true,
@@ -2179,6 +2722,34 @@ export type Mmm = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "treasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "feeSchedule",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "royaltySchedule",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "get_royalty_schedule, it might not be initialized"
+          ]
+        },
+        {
+          "name": "referralRegistry",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "pool",
           "isMut": true,
@@ -2233,454 +2804,695 @@ export type Mmm = {
           }
         }
       ]
-    }
-  ],
-  "accounts": [
+    },
     {
-      "name": "pool",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "spotPrice",
-            "type": "u64"
-          },
-          {
-            "name": "curveType",
-            "type": "u8"
-          },
-          {
-            "name": "curveDelta",
-            "type": "u64"
-          },
-          {
-            "name": "reinvestFulfillBuy",
-            "type": "bool"
-          },
-          {
-            "name": "reinvestFulfillSell",
-            "type": "bool"
-          },
-          {
-            "name": "expiry",
-            "type": "i64"
-          },
-          {
-            "name": "lpFeeBp",
-            "type": "u16"
-          },
-          {
-            "name": "referral",
-            "type": "publicKey"
-          },
-          {
-            "name": "referralBp",
-            "type": "u16"
-          },
-          {
-            "name": "buysideCreatorRoyaltyBp",
-            "type": "u16"
-          },
-          {
-            "name": "cosignerAnnotation",
-            "type": {
-              "array": [
-                "u8",
-                32
-              ]
-            }
-          },
-          {
-            "name": "sellsideAssetAmount",
-            "type": "u64"
-          },
-          {
-            "name": "lpFeeEarned",
-            "type": "u64"
-          },
-          {
-            "name": "owner",
-            "type": "publicKey"
-          },
-          {
-            "name": "cosigner",
-            "type": "publicKey"
-          },
-          {
-            "name": "uuid",
-            "type": "publicKey"
-          },
-          {
-            "name": "paymentMint",
-            "type": "publicKey"
-          },
-          {
-            "name": "allowlists",
-            "type": {
-              "array": [
-                {
-                  "defined": "Allowlist"
-                },
-                6
-              ]
-            }
-          },
-          {
-            "name": "buysidePaymentAmount",
-            "type": "u64"
-          },
-          {
-            "name": "sharedEscrowAccount",
-            "type": "publicKey"
-          },
-          {
-            "name": "sharedEscrowCount",
-            "type": "u64"
+      "name": "setSellStatePriceOverride",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "cosigner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "pool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sellState",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "SetSellStatePriceOverrideArgs"
           }
-        ]
-      }
+        }
+      ]
     },
     {
-      "name": "sellState",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "pool",
-            "type": "publicKey"
-          },
-          {
-            "name": "poolOwner",
-            "type": "publicKey"
-          },
-          {
-            "name": "assetMint",
-            "type": "publicKey"
-          },
-          {
-            "name": "assetAmount",
-            "type": "u64"
-          },
-          {
-            "name": "cosignerAnnotation",
-            "type": {
-              "array": [
-                "u8",
-                32
-              ]
-            }
+      "name": "mip1ListSell",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "cosigner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "assetMetadata",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "assetMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetMasterEdition",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "allowlistAuxAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "ownerTokenRecord",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authorizationRules",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMetadataProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authorizationRulesProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "DepositSellArgs"
           }
-        ]
-      }
-    }
-  ],
-  "types": [
+        }
+      ]
+    },
     {
-      "name": "CreatePoolArgs",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "spotPrice",
-            "type": "u64"
-          },
-          {
-            "name": "curveType",
-            "type": "u8"
-          },
-          {
-            "name": "curveDelta",
-            "type": "u64"
-          },
-          {
-            "name": "reinvestFulfillBuy",
-            "type": "bool"
-          },
-          {
-            "name": "reinvestFulfillSell",
-            "type": "bool"
-          },
-          {
-            "name": "expiry",
-            "type": "i64"
-          },
-          {
-            "name": "lpFeeBp",
-            "type": "u16"
-          },
-          {
-            "name": "referral",
-            "type": "publicKey"
-          },
-          {
-            "name": "cosignerAnnotation",
-            "type": {
-              "array": [
-                "u8",
-                32
-              ]
-            }
-          },
-          {
-            "name": "buysideCreatorRoyaltyBp",
-            "type": "u16"
-          },
-          {
-            "name": "uuid",
-            "type": "publicKey"
-          },
-          {
-            "name": "paymentMint",
-            "type": "publicKey"
-          },
-          {
-            "name": "allowlists",
-            "type": {
-              "array": [
-                {
-                  "defined": "Allowlist"
-                },
-                6
-              ]
-            }
-          }
-        ]
-      }
+      "name": "mip1DelistSell",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "cosigner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "assetMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetMasterEdition",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetMetadata",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "assetTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "ownerTokenRecord",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authorizationRules",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMetadataProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authorizationRulesProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
     },
     {
-      "name": "SetSharedEscrowArgs",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "sharedEscrowCount",
-            "type": "u64"
-          }
-        ]
-      }
+      "name": "mip1ReleaseStaleListing",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "assetMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetTokenAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sellState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
     },
     {
-      "name": "UpdateAllowlistsArgs",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "allowlists",
-            "type": {
-              "array": [
-                {
-                  "defined": "Allowlist"
-                },
-                6
-              ]
-            }
+      "name": "setExtensionPolicy",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "cosigner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "pool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "extensionPolicy",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "SetExtensionPolicyArgs"
           }
-        ]
-      }
+        }
+      ]
     },
     {
-      "name": "UpdatePoolArgs",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "spotPrice",
-            "type": "u64"
-          },
-          {
-            "name": "curveType",
-            "type": "u8"
-          },
-          {
-            "name": "curveDelta",
-            "type": "u64"
-          },
-          {
-            "name": "reinvestFulfillBuy",
-            "type": "bool"
-          },
-          {
-            "name": "reinvestFulfillSell",
-            "type": "bool"
-          },
-          {
-            "name": "expiry",
-            "type": "i64"
-          },
-          {
-            "name": "lpFeeBp",
-            "type": "u16"
-          },
-          {
-            "name": "referral",
-            "type": "publicKey"
-          },
-          {
-            "name": "cosignerAnnotation",
-            "type": {
-              "array": [
-                "u8",
-                32
-              ]
-            }
-          },
-          {
-            "name": "buysideCreatorRoyaltyBp",
-            "type": "u16"
+      "name": "mplCoreListSell",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "cosigner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "asset",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collection",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "MplCoreDepositSellArgs"
           }
-        ]
-      }
+        }
+      ]
     },
     {
-      "name": "Collection",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "verified",
-            "type": "bool"
-          },
-          {
-            "name": "key",
-            "type": "publicKey"
+      "name": "mplCoreReleaseStaleListing",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "asset",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sellState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "assetProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "claimRoyalties",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "creator",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "royaltyVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "setRoyaltySchedule",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "collection",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "mint, checked in get_collection_update_authority"
+          ]
+        },
+        {
+          "name": "collectionMetadata",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "collections, can be the default pubkey otherwise"
+          ]
+        },
+        {
+          "name": "royaltySchedule",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "SetRoyaltyScheduleArgs"
           }
-        ]
-      }
+        }
+      ]
     },
     {
-      "name": "Uses",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "useMethod",
-            "type": {
-              "defined": "UseMethod"
-            }
-          },
-          {
-            "name": "remaining",
-            "type": "u64"
-          },
-          {
-            "name": "total",
-            "type": "u64"
+      "name": "initGlobalConfig",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalConfig",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "InitGlobalConfigArgs"
           }
-        ]
-      }
+        }
+      ]
     },
     {
-      "name": "Creator",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "address",
-            "type": "publicKey"
-          },
-          {
-            "name": "verified",
-            "type": "bool"
-          },
-          {
-            "name": "share",
-            "docs": [
-              "The percentage share.",
-              "",
-              "The value is a percentage, not basis points."
-            ],
-            "type": "u8"
+      "name": "updateGlobalConfig",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "globalConfig",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "UpdateGlobalConfigArgs"
           }
-        ]
-      }
+        }
+      ]
     },
     {
-      "name": "MetadataArgs",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "name",
-            "type": "string"
-          },
-          {
-            "name": "symbol",
-            "type": "string"
-          },
-          {
-            "name": "uri",
-            "type": "string"
+      "name": "setProtocolFeeExemption",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "SetProtocolFeeExemptionArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "acceptGlobalConfigAdmin",
+      "accounts": [
+        {
+          "name": "pendingAdmin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "globalConfig",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "setPauseFlags",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "globalConfig",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "SetPauseFlagsArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setReferralRegistry",
+      "accounts": [
+        {
+          "name": "referrer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "referralRegistry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "SetReferralRegistryArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setFeeSchedule",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "referral",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "feeSchedule",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "SetFeeScheduleArgs"
+          }
+        }
+      ]
+    }
+  ],
+  "accounts": [
+    {
+      "name": "pool",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "spotPrice",
+            "type": "u64"
           },
           {
-            "name": "sellerFeeBasisPoints",
-            "type": "u16"
+            "name": "curveType",
+            "type": "u8"
           },
           {
-            "name": "primarySaleHappened",
-            "type": "bool"
+            "name": "curveDelta",
+            "type": "u64"
           },
           {
-            "name": "isMutable",
+            "name": "reinvestFulfillBuy",
             "type": "bool"
           },
           {
-            "name": "editionNonce",
-            "type": {
-              "option": "u8"
-            }
+            "name": "reinvestFulfillSell",
+            "type": "bool"
           },
           {
-            "name": "tokenStandard",
-            "type": {
-              "option": {
-                "defined": "TokenStandard"
-              }
-            }
+            "name": "expiry",
+            "type": "i64"
           },
           {
-            "name": "collection",
-            "type": {
-              "option": {
-                "defined": "Collection"
-              }
-            }
+            "name": "lpFeeBp",
+            "type": "u16"
           },
           {
-            "name": "uses",
-            "type": {
-              "option": {
-                "defined": "Uses"
-              }
-            }
+            "name": "referral",
+            "type": "publicKey"
           },
           {
-            "name": "tokenProgramVersion",
+            "name": "reserved",
             "type": {
-              "defined": "TokenProgramVersion"
+              "array": [
+                "u8",
+                2
+              ]
             }
           },
           {
-            "name": "creators",
-            "type": {
-              "vec": {
-                "defined": "Creator"
-              }
-            }
-          }
-        ]
-      }
-    },
-    {
-      "name": "SolCnftFulfillBuyArgs",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "assetId",
-            "type": "publicKey"
+            "name": "buysideCreatorRoyaltyBp",
+            "type": "u16"
           },
           {
-            "name": "root",
+            "name": "cosignerAnnotation",
             "type": {
               "array": [
                 "u8",
@@ -2689,278 +3501,331 @@ export type Mmm = {
             }
           },
           {
-            "name": "nonce",
+            "name": "sellsideAssetAmount",
             "type": "u64"
           },
           {
-            "name": "index",
-            "type": "u32"
+            "name": "lpFeeEarned",
+            "type": "u64"
           },
           {
-            "name": "minPaymentAmount",
-            "type": "u64"
+            "name": "owner",
+            "type": "publicKey"
           },
           {
-            "name": "makerFeeBp",
-            "type": "i16"
+            "name": "cosigner",
+            "type": "publicKey"
           },
           {
-            "name": "takerFeeBp",
-            "type": "i16"
+            "name": "uuid",
+            "type": "publicKey"
           },
           {
-            "name": "metadataArgs",
+            "name": "paymentMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "allowlists",
             "type": {
-              "defined": "MetadataArgs"
+              "array": [
+                {
+                  "defined": "Allowlist"
+                },
+                6
+              ]
             }
-          }
-        ]
-      }
-    },
-    {
-      "name": "SolMip1FulfillSellArgs",
-      "type": {
-        "kind": "struct",
-        "fields": [
+          },
           {
-            "name": "assetAmount",
+            "name": "buysidePaymentAmount",
             "type": "u64"
           },
           {
-            "name": "maxPaymentAmount",
+            "name": "sharedEscrowAccount",
+            "type": "publicKey"
+          },
+          {
+            "name": "sharedEscrowCount",
             "type": "u64"
           },
           {
-            "name": "allowlistAux",
-            "type": {
-              "option": "string"
-            }
+            "name": "maxTraitMultiplierBp",
+            "type": "u16"
           },
           {
-            "name": "makerFeeBp",
-            "type": "i16"
+            "name": "transferHookProgramId",
+            "type": "publicKey"
           },
           {
-            "name": "takerFeeBp",
-            "type": "i16"
-          }
-        ]
-      }
-    },
-    {
-      "name": "MplCoreDepositSellArgs",
-      "type": {
-        "kind": "struct",
-        "fields": [
+            "name": "protocolFeeExempt",
+            "type": "bool"
+          },
           {
-            "name": "allowlistAux",
-            "type": {
-              "option": "string"
-            }
+            "name": "compoundLpFee",
+            "type": "bool"
           },
           {
-            "name": "compressionProof",
+            "name": "lpFeeCompounded",
+            "type": "u64"
+          },
+          {
+            "name": "dynamicLpFeeMinBp",
+            "type": "u16"
+          },
+          {
+            "name": "dynamicLpFeeMaxBp",
+            "type": "u16"
+          },
+          {
+            "name": "traitPremiumRoot",
             "type": {
-              "option": "bytes"
+              "array": [
+                "u8",
+                32
+              ]
             }
           }
         ]
       }
     },
     {
-      "name": "MplCoreWithdrawSellArgs",
+      "name": "sellState",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "compressionProof",
+            "name": "pool",
+            "type": "publicKey"
+          },
+          {
+            "name": "poolOwner",
+            "type": "publicKey"
+          },
+          {
+            "name": "assetMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "assetAmount",
+            "type": "u64"
+          },
+          {
+            "name": "cosignerAnnotation",
             "type": {
-              "option": "bytes"
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "priceOverride",
+            "type": {
+              "defined": "PriceOverride"
             }
+          },
+          {
+            "name": "listingTokenAccount",
+            "type": "publicKey"
           }
         ]
       }
     },
     {
-      "name": "SolMplCoreFulfillBuyArgs",
+      "name": "extensionPolicy",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "minPaymentAmount",
-            "type": "u64"
+            "name": "pool",
+            "type": "publicKey"
           },
           {
-            "name": "allowlistAux",
-            "type": {
-              "option": "string"
-            }
+            "name": "allowedExtensions",
+            "type": "u64"
           },
           {
-            "name": "makerFeeBp",
-            "type": "i16"
+            "name": "permanentDelegate",
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "royaltyVault",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "creator",
+            "type": "publicKey"
           },
           {
-            "name": "takerFeeBp",
-            "type": "i16"
+            "name": "accrued",
+            "type": "u64"
           },
           {
-            "name": "compressionProof",
-            "type": {
-              "option": "bytes"
-            }
+            "name": "claimed",
+            "type": "u64"
           }
         ]
       }
     },
     {
-      "name": "SolMplCoreFulfillSellArgs",
+      "name": "globalConfig",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "maxPaymentAmount",
-            "type": "u64"
+            "name": "version",
+            "type": "u8"
           },
           {
-            "name": "buysideCreatorRoyaltyBp",
+            "name": "admin",
+            "type": "publicKey"
+          },
+          {
+            "name": "pendingAdmin",
+            "type": "publicKey"
+          },
+          {
+            "name": "treasury",
+            "type": "publicKey"
+          },
+          {
+            "name": "protocolFeeBp",
             "type": "u16"
           },
           {
-            "name": "allowlistAux",
-            "type": {
-              "option": "string"
-            }
+            "name": "pauseFlags",
+            "type": "u8"
           },
           {
-            "name": "makerFeeBp",
-            "type": "i16"
+            "name": "cancelAuthority",
+            "type": "publicKey"
           },
           {
-            "name": "takerFeeBp",
+            "name": "maxLpFeeBp",
+            "type": "u16"
+          },
+          {
+            "name": "maxReferralFeeBp",
             "type": "i16"
           },
           {
-            "name": "compressionProof",
+            "name": "maxMetadataCreatorRoyaltyBp",
+            "type": "u16"
+          },
+          {
+            "name": "minSolEscrowBalanceBp",
+            "type": "u16"
+          },
+          {
+            "name": "m2AuctionHouse",
+            "type": "publicKey"
+          },
+          {
+            "name": "paymentProxyProgramId",
+            "type": "publicKey"
+          },
+          {
+            "name": "paymentProxyDiscriminator",
             "type": {
-              "option": "bytes"
+              "array": [
+                "u8",
+                8
+              ]
             }
-          }
-        ]
-      }
-    },
-    {
-      "name": "SolOcpFulfillSellArgs",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "assetAmount",
-            "type": "u64"
           },
           {
-            "name": "maxPaymentAmount",
-            "type": "u64"
+            "name": "paymentProxyMinLen",
+            "type": "u32"
           },
           {
-            "name": "allowlistAux",
+            "name": "transferHookProgramAllowList",
             "type": {
-              "option": "string"
+              "array": [
+                "publicKey",
+                3
+              ]
             }
           },
           {
-            "name": "makerFeeBp",
+            "name": "defaultMakerFeeBp",
             "type": "i16"
           },
           {
-            "name": "takerFeeBp",
+            "name": "defaultTakerFeeBp",
             "type": "i16"
           }
         ]
       }
     },
     {
-      "name": "DepositSellArgs",
+      "name": "royaltySchedule",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "assetAmount",
-            "type": "u64"
+            "name": "collection",
+            "type": "publicKey"
           },
           {
-            "name": "allowlistAux",
+            "name": "authority",
+            "type": "publicKey"
+          },
+          {
+            "name": "bandCount",
+            "type": "u8"
+          },
+          {
+            "name": "bands",
             "type": {
-              "option": "string"
+              "array": [
+                {
+                  "defined": "RoyaltyBand"
+                },
+                8
+              ]
             }
           }
         ]
       }
     },
     {
-      "name": "SolDepositBuyArgs",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "paymentAmount",
-            "type": "u64"
-          }
-        ]
-      }
-    },
-    {
-      "name": "SolFulfillBuyArgs",
+      "name": "referralRegistry",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "assetAmount",
-            "type": "u64"
+            "name": "referrer",
+            "type": "publicKey"
           },
           {
-            "name": "minPaymentAmount",
-            "type": "u64"
+            "name": "recipientCount",
+            "type": "u8"
           },
           {
-            "name": "allowlistAux",
+            "name": "recipients",
             "type": {
-              "option": "string"
+              "array": [
+                {
+                  "defined": "ReferralRecipient"
+                },
+                4
+              ]
             }
-          },
-          {
-            "name": "makerFeeBp",
-            "type": "i16"
-          },
-          {
-            "name": "takerFeeBp",
-            "type": "i16"
           }
         ]
       }
     },
     {
-      "name": "SolFulfillSellArgs",
+      "name": "feeSchedule",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "assetAmount",
-            "type": "u64"
-          },
-          {
-            "name": "maxPaymentAmount",
-            "type": "u64"
-          },
-          {
-            "name": "buysideCreatorRoyaltyBp",
-            "type": "u16"
-          },
-          {
-            "name": "allowlistAux",
-            "type": {
-              "option": "string"
-            }
+            "name": "referral",
+            "type": "publicKey"
           },
           {
             "name": "makerFeeBp",
@@ -2972,1674 +3837,5217 @@ export type Mmm = {
           }
         ]
       }
-    },
+    }
+  ],
+  "types": [
     {
-      "name": "SolWithdrawBuyArgs",
+      "name": "TaggedPayload",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "paymentAmount",
-            "type": "u64"
+            "name": "name",
+            "type": "string"
+          },
+          {
+            "name": "payload",
+            "type": {
+              "defined": "PayloadTypeLocal"
+            }
           }
         ]
       }
     },
     {
-      "name": "WithdrawSellArgs",
+      "name": "AuthorizationDataLocal",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "assetAmount",
-            "type": "u64"
-          },
-          {
-            "name": "allowlistAux",
+            "name": "payload",
             "type": {
-              "option": "string"
+              "vec": {
+                "defined": "TaggedPayload"
+              }
             }
           }
         ]
       }
     },
     {
-      "name": "Allowlist",
+      "name": "CreatePoolArgs",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "kind",
+            "name": "spotPrice",
+            "type": "u64"
+          },
+          {
+            "name": "curveType",
             "type": "u8"
           },
           {
-            "name": "value",
-            "type": "publicKey"
-          }
-        ]
-      }
-    },
-    {
-      "name": "TokenStandard",
-      "type": {
-        "kind": "enum",
-        "variants": [
+            "name": "curveDelta",
+            "type": "u64"
+          },
           {
-            "name": "NonFungible"
+            "name": "reinvestFulfillBuy",
+            "type": "bool"
           },
           {
-            "name": "FungibleAsset"
+            "name": "reinvestFulfillSell",
+            "type": "bool"
           },
           {
-            "name": "Fungible"
+            "name": "expiry",
+            "type": "i64"
           },
           {
-            "name": "NonFungibleEdition"
+            "name": "lpFeeBp",
+            "type": "u16"
+          },
+          {
+            "name": "referral",
+            "type": "publicKey"
+          },
+          {
+            "name": "cosignerAnnotation",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "buysideCreatorRoyaltyBp",
+            "type": "u16"
+          },
+          {
+            "name": "uuid",
+            "type": "publicKey"
+          },
+          {
+            "name": "paymentMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "allowlists",
+            "type": {
+              "array": [
+                {
+                  "defined": "Allowlist"
+                },
+                6
+              ]
+            }
+          },
+          {
+            "name": "maxTraitMultiplierBp",
+            "type": {
+              "option": "u16"
+            }
+          },
+          {
+            "name": "traitPremiumRoot",
+            "type": {
+              "option": {
+                "array": [
+                  "u8",
+                  32
+                ]
+              }
+            }
+          },
+          {
+            "name": "transferHookProgramId",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "compoundLpFee",
+            "type": {
+              "option": "bool"
+            }
+          },
+          {
+            "name": "dynamicLpFeeMinBp",
+            "type": {
+              "option": "u16"
+            }
+          },
+          {
+            "name": "dynamicLpFeeMaxBp",
+            "type": {
+              "option": "u16"
+            }
           }
         ]
       }
     },
     {
-      "name": "UseMethod",
+      "name": "InitGlobalConfigArgs",
       "type": {
-        "kind": "enum",
-        "variants": [
+        "kind": "struct",
+        "fields": [
           {
-            "name": "Burn"
+            "name": "admin",
+            "type": "publicKey"
           },
           {
-            "name": "Multiple"
+            "name": "treasury",
+            "type": "publicKey"
           },
           {
-            "name": "Single"
+            "name": "protocolFeeBp",
+            "type": "u16"
           }
         ]
       }
     },
     {
-      "name": "TokenProgramVersion",
+      "name": "SetExtensionPolicyArgs",
       "type": {
-        "kind": "enum",
-        "variants": [
+        "kind": "struct",
+        "fields": [
           {
-            "name": "Original"
+            "name": "allowedExtensions",
+            "type": "u64"
           },
           {
-            "name": "Token2022"
+            "name": "permanentDelegate",
+            "type": "publicKey"
           }
         ]
       }
-    }
-  ],
-  "errors": [
-    {
-      "code": 6000,
-      "name": "InvalidLPFee",
-      "msg": "lp fee bp must be between 0 and 2000"
-    },
-    {
-      "code": 6001,
-      "name": "InvalidAllowLists",
-      "msg": "invalid allowlists"
-    },
-    {
-      "code": 6002,
-      "name": "InvalidBP",
-      "msg": "invalid bp"
-    },
-    {
-      "code": 6003,
-      "name": "InvalidCurveType",
-      "msg": "invalid curve type"
-    },
-    {
-      "code": 6004,
-      "name": "InvalidCurveDelta",
-      "msg": "invalid curve delta"
-    },
-    {
-      "code": 6005,
-      "name": "InvalidCosigner",
-      "msg": "invalid cosigner"
-    },
-    {
-      "code": 6006,
-      "name": "InvalidPaymentMint",
-      "msg": "invalid payment mint"
-    },
-    {
-      "code": 6007,
-      "name": "InvalidOwner",
-      "msg": "invalid owner"
-    },
-    {
-      "code": 6008,
-      "name": "NumericOverflow",
-      "msg": "numeric overflow"
-    },
-    {
-      "code": 6009,
-      "name": "InvalidRequestedPrice",
-      "msg": "invalid requested price"
-    },
-    {
-      "code": 6010,
-      "name": "NotEmptyEscrowAccount",
-      "msg": "not empty escrow account"
     },
     {
-      "code": 6011,
-      "name": "NotEmptySellsideAssetAmount",
-      "msg": "not empty sell side asset amount"
-    },
-    {
-      "code": 6012,
-      "name": "InvalidReferral",
-      "msg": "invalid referral"
-    },
-    {
-      "code": 6013,
-      "name": "InvalidMasterEdition",
-      "msg": "invalid master edition"
+      "name": "SetFeeScheduleArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "makerFeeBp",
+            "type": "i16"
+          },
+          {
+            "name": "takerFeeBp",
+            "type": "i16"
+          }
+        ]
+      }
     },
     {
-      "code": 6014,
-      "name": "Expired",
-      "msg": "expired"
+      "name": "SetPauseFlagsArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "pauseFlags",
+            "type": "u8"
+          }
+        ]
+      }
     },
     {
-      "code": 6015,
-      "name": "InvalidCreatorAddress",
-      "msg": "invalid creator address"
+      "name": "SetProtocolFeeExemptionArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "exempt",
+            "type": "bool"
+          }
+        ]
+      }
     },
     {
-      "code": 6016,
-      "name": "NotEnoughBalance",
-      "msg": "not enough balance"
+      "name": "SetSellStatePriceOverrideArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "priceOverride",
+            "type": {
+              "defined": "PriceOverride"
+            }
+          }
+        ]
+      }
     },
     {
-      "code": 6017,
-      "name": "InvalidTokenOwner",
-      "msg": "invalid token owner"
+      "name": "SetSharedEscrowArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "sharedEscrowCount",
+            "type": "u64"
+          }
+        ]
+      }
     },
     {
-      "code": 6018,
-      "name": "PubkeyMismatch",
-      "msg": "pubkey mismatch"
+      "name": "UpdateAllowlistsArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "allowlists",
+            "type": {
+              "array": [
+                {
+                  "defined": "Allowlist"
+                },
+                6
+              ]
+            }
+          }
+        ]
+      }
     },
     {
-      "code": 6019,
-      "name": "UninitializedAccount",
-      "msg": "uninitialized account"
-    },
-    {
-      "code": 6020,
-      "name": "InvalidSpotPrice",
-      "msg": "invalid spot_price"
-    },
-    {
-      "code": 6021,
-      "name": "InvalidMetadataCreatorRoyalty",
-      "msg": "invalid metadata creator royalty"
-    },
-    {
-      "code": 6022,
-      "name": "InvalidMakerOrTakerFeeBP",
-      "msg": "invalid maker or taker bp"
-    },
-    {
-      "code": 6023,
-      "name": "InvalidOcpAssetParams",
-      "msg": "Invalid OCP mint supply constraints"
-    },
-    {
-      "code": 6024,
-      "name": "InvalidAccountState",
-      "msg": "Invalid account state"
-    },
-    {
-      "code": 6025,
-      "name": "InvalidTokenMint",
-      "msg": "Invalid token mint"
-    },
-    {
-      "code": 6026,
-      "name": "InvalidMip1AssetParams",
-      "msg": "Invalid MIP1 mint supply constraints"
-    },
-    {
-      "code": 6027,
-      "name": "InvalidTokenStandard",
-      "msg": "Invalid token standard"
+      "name": "UpdateGlobalConfigArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "treasury",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "protocolFeeBp",
+            "type": {
+              "option": "u16"
+            }
+          },
+          {
+            "name": "pendingAdmin",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "cancelAuthority",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "maxLpFeeBp",
+            "type": {
+              "option": "u16"
+            }
+          },
+          {
+            "name": "maxReferralFeeBp",
+            "type": {
+              "option": "i16"
+            }
+          },
+          {
+            "name": "maxMetadataCreatorRoyaltyBp",
+            "type": {
+              "option": "u16"
+            }
+          },
+          {
+            "name": "minSolEscrowBalanceBp",
+            "type": {
+              "option": "u16"
+            }
+          },
+          {
+            "name": "m2AuctionHouse",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "paymentProxyProgramId",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "paymentProxyDiscriminator",
+            "type": {
+              "option": {
+                "array": [
+                  "u8",
+                  8
+                ]
+              }
+            }
+          },
+          {
+            "name": "paymentProxyMinLen",
+            "type": {
+              "option": "u32"
+            }
+          },
+          {
+            "name": "transferHookProgramAllowList",
+            "type": {
+              "option": {
+                "array": [
+                  "publicKey",
+                  3
+                ]
+              }
+            }
+          },
+          {
+            "name": "defaultMakerFeeBp",
+            "type": {
+              "option": "i16"
+            }
+          },
+          {
+            "name": "defaultTakerFeeBp",
+            "type": {
+              "option": "i16"
+            }
+          }
+        ]
+      }
     },
     {
-      "code": 6028,
-      "name": "UnexpectedMetadataUri",
-      "msg": "Unexpected metadata uri"
+      "name": "UpdatePoolArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "spotPrice",
+            "type": "u64"
+          },
+          {
+            "name": "curveType",
+            "type": "u8"
+          },
+          {
+            "name": "curveDelta",
+            "type": "u64"
+          },
+          {
+            "name": "reinvestFulfillBuy",
+            "type": "bool"
+          },
+          {
+            "name": "reinvestFulfillSell",
+            "type": "bool"
+          },
+          {
+            "name": "expiry",
+            "type": "i64"
+          },
+          {
+            "name": "lpFeeBp",
+            "type": "u16"
+          },
+          {
+            "name": "referral",
+            "type": "publicKey"
+          },
+          {
+            "name": "cosignerAnnotation",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "buysideCreatorRoyaltyBp",
+            "type": "u16"
+          },
+          {
+            "name": "maxTraitMultiplierBp",
+            "type": {
+              "option": "u16"
+            }
+          },
+          {
+            "name": "traitPremiumRoot",
+            "type": {
+              "option": {
+                "array": [
+                  "u8",
+                  32
+                ]
+              }
+            }
+          },
+          {
+            "name": "transferHookProgramId",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "compoundLpFee",
+            "type": {
+              "option": "bool"
+            }
+          },
+          {
+            "name": "dynamicLpFeeMinBp",
+            "type": {
+              "option": "u16"
+            }
+          },
+          {
+            "name": "dynamicLpFeeMaxBp",
+            "type": {
+              "option": "u16"
+            }
+          }
+        ]
+      }
     },
     {
-      "code": 6029,
-      "name": "InvalidRemainingAccounts",
-      "msg": "Invalid remaining accounts"
+      "name": "Collection",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "verified",
+            "type": "bool"
+          },
+          {
+            "name": "key",
+            "type": "publicKey"
+          }
+        ]
+      }
     },
     {
-      "code": 6030,
-      "name": "InvalidTokenMetadataExtension",
-      "msg": "Invalid token metadata extensions"
+      "name": "Uses",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "useMethod",
+            "type": {
+              "defined": "UseMethod"
+            }
+          },
+          {
+            "name": "remaining",
+            "type": "u64"
+          },
+          {
+            "name": "total",
+            "type": "u64"
+          }
+        ]
+      }
     },
     {
-      "code": 6031,
-      "name": "InvalidTokenMemberExtension",
-      "msg": "Invalid token member extensions"
+      "name": "Creator",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "address",
+            "type": "publicKey"
+          },
+          {
+            "name": "verified",
+            "type": "bool"
+          },
+          {
+            "name": "share",
+            "docs": [
+              "The percentage share.",
+              "",
+              "The value is a percentage, not basis points."
+            ],
+            "type": "u8"
+          }
+        ]
+      }
     },
     {
-      "code": 6032,
-      "name": "InvalidAssetCollection",
-      "msg": "Invalid asset collection"
+      "name": "MetadataArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "name",
+            "type": "string"
+          },
+          {
+            "name": "symbol",
+            "type": "string"
+          },
+          {
+            "name": "uri",
+            "type": "string"
+          },
+          {
+            "name": "sellerFeeBasisPoints",
+            "type": "u16"
+          },
+          {
+            "name": "primarySaleHappened",
+            "type": "bool"
+          },
+          {
+            "name": "isMutable",
+            "type": "bool"
+          },
+          {
+            "name": "editionNonce",
+            "type": {
+              "option": "u8"
+            }
+          },
+          {
+            "name": "tokenStandard",
+            "type": {
+              "option": {
+                "defined": "TokenStandard"
+              }
+            }
+          },
+          {
+            "name": "collection",
+            "type": {
+              "option": {
+                "defined": "Collection"
+              }
+            }
+          },
+          {
+            "name": "uses",
+            "type": {
+              "option": {
+                "defined": "Uses"
+              }
+            }
+          },
+          {
+            "name": "tokenProgramVersion",
+            "type": {
+              "defined": "TokenProgramVersion"
+            }
+          },
+          {
+            "name": "creators",
+            "type": {
+              "vec": {
+                "defined": "Creator"
+              }
+            }
+          }
+        ]
+      }
     },
     {
-      "code": 6033,
-      "name": "InvalidTokenExtension",
-      "msg": "Invalid token extension"
+      "name": "SolCnftFulfillBuyArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "assetId",
+            "type": "publicKey"
+          },
+          {
+            "name": "root",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "nonce",
+            "type": "u64"
+          },
+          {
+            "name": "index",
+            "type": "u32"
+          },
+          {
+            "name": "minPaymentAmount",
+            "type": "u64"
+          },
+          {
+            "name": "makerFeeBp",
+            "type": "i16"
+          },
+          {
+            "name": "takerFeeBp",
+            "type": "i16"
+          },
+          {
+            "name": "metadataArgs",
+            "type": {
+              "defined": "MetadataArgs"
+            }
+          },
+          {
+            "name": "traitPremium",
+            "type": {
+              "option": {
+                "defined": "TraitPremium"
+              }
+            }
+          },
+          {
+            "name": "royaltyVaultCount",
+            "type": "u8"
+          },
+          {
+            "name": "referralRecipientCount",
+            "type": "u8"
+          }
+        ]
+      }
     },
     {
-      "code": 6034,
-      "name": "UnsupportedAssetPlugin",
-      "msg": "Unsupported asset plugin"
+      "name": "SolMip1FulfillSellArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "assetAmount",
+            "type": "u64"
+          },
+          {
+            "name": "maxPaymentAmount",
+            "type": "u64"
+          },
+          {
+            "name": "allowlistAux",
+            "type": {
+              "option": "string"
+            }
+          },
+          {
+            "name": "makerFeeBp",
+            "type": "i16"
+          },
+          {
+            "name": "takerFeeBp",
+            "type": "i16"
+          },
+          {
+            "name": "authorizationData",
+            "type": {
+              "option": {
+                "defined": "AuthorizationDataLocal"
+              }
+            }
+          }
+        ]
+      }
     },
     {
-      "code": 6035,
-      "name": "InvalidCnftMetadata",
-      "msg": "Invalid cnft metadata"
+      "name": "MplCoreDepositSellArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "allowlistAux",
+            "type": {
+              "option": "string"
+            }
+          },
+          {
+            "name": "compressionProof",
+            "type": {
+              "option": "bytes"
+            }
+          },
+          {
+            "name": "priceOverride",
+            "type": {
+              "option": {
+                "defined": "PriceOverride"
+              }
+            }
+          }
+        ]
+      }
     },
     {
-      "code": 6036,
-      "name": "InvalidCnftMetadataArgs",
-      "msg": "Invalid cnft metadata args"
-    }
-  ]
-};
-
-export const IDL: Mmm = {
-  "version": "0.2.0",
-  "name": "mmm",
-  "instructions": [
-    {
-      "name": "createPool",
-      "accounts": [
-        {
-          "name": "owner",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "cosigner",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "pool",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "args",
-          "type": {
-            "defined": "CreatePoolArgs"
+      "name": "MplCoreWithdrawSellArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "compressionProof",
+            "type": {
+              "option": "bytes"
+            }
           }
-        }
-      ]
+        ]
+      }
     },
     {
-      "name": "updatePool",
-      "accounts": [
-        {
-          "name": "owner",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "cosigner",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "pool",
-          "isMut": true,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "args",
-          "type": {
-            "defined": "UpdatePoolArgs"
+      "name": "SolMplCoreFulfillBuyArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "minPaymentAmount",
+            "type": "u64"
+          },
+          {
+            "name": "allowlistAux",
+            "type": {
+              "option": "string"
+            }
+          },
+          {
+            "name": "makerFeeBp",
+            "type": "i16"
+          },
+          {
+            "name": "takerFeeBp",
+            "type": "i16"
+          },
+          {
+            "name": "compressionProof",
+            "type": {
+              "option": "bytes"
+            }
+          },
+          {
+            "name": "traitPremium",
+            "type": {
+              "option": {
+                "defined": "TraitPremium"
+              }
+            }
           }
-        }
-      ]
+        ]
+      }
     },
     {
-      "name": "updateAllowlists",
-      "accounts": [
-        {
-          "name": "cosigner",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "owner",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "pool",
-          "isMut": true,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "args",
-          "type": {
-            "defined": "UpdateAllowlistsArgs"
+      "name": "SolMplCoreFulfillSellArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "maxPaymentAmount",
+            "type": "u64"
+          },
+          {
+            "name": "buysideCreatorRoyaltyBp",
+            "type": "u16"
+          },
+          {
+            "name": "allowlistAux",
+            "type": {
+              "option": "string"
+            }
+          },
+          {
+            "name": "makerFeeBp",
+            "type": "i16"
+          },
+          {
+            "name": "takerFeeBp",
+            "type": "i16"
+          },
+          {
+            "name": "compressionProof",
+            "type": {
+              "option": "bytes"
+            }
           }
-        }
-      ]
-    },
-    {
-      "name": "solClosePool",
-      "accounts": [
-        {
-          "name": "owner",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "cosigner",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "pool",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "buysideSolEscrowAccount",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": []
+        ]
+      }
     },
     {
-      "name": "solDepositBuy",
-      "accounts": [
-        {
-          "name": "owner",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "cosigner",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "pool",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "buysideSolEscrowAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "args",
-          "type": {
-            "defined": "SolDepositBuyArgs"
+      "name": "SolOcpFulfillSellArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "assetAmount",
+            "type": "u64"
+          },
+          {
+            "name": "maxPaymentAmount",
+            "type": "u64"
+          },
+          {
+            "name": "allowlistAux",
+            "type": {
+              "option": "string"
+            }
+          },
+          {
+            "name": "makerFeeBp",
+            "type": "i16"
+          },
+          {
+            "name": "takerFeeBp",
+            "type": "i16"
           }
-        }
-      ]
+        ]
+      }
     },
     {
-      "name": "solWithdrawBuy",
-      "accounts": [
-        {
-          "name": "owner",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "cosigner",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "pool",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "buysideSolEscrowAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "args",
-          "type": {
-            "defined": "SolWithdrawBuyArgs"
+      "name": "SetReferralRegistryArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "recipients",
+            "type": {
+              "vec": {
+                "defined": "ReferralRecipient"
+              }
+            }
           }
-        }
-      ]
+        ]
+      }
     },
     {
-      "name": "solFulfillBuy",
-      "accounts": [
-        {
-          "name": "payer",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "owner",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "cosigner",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "referral",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "pool",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "buysideSolEscrowAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "assetMetadata",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "assetMasterEdition",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "assetMint",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "payerAssetAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "sellsideEscrowTokenAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "ownerTokenAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "allowlistAuxAccount",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "sellState",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "associatedTokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "rent",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "args",
-          "type": {
-            "defined": "SolFulfillBuyArgs"
+      "name": "SetRoyaltyScheduleArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "bands",
+            "type": {
+              "vec": {
+                "defined": "RoyaltyBand"
+              }
+            }
           }
-        }
-      ]
+        ]
+      }
     },
     {
-      "name": "solFulfillSell",
-      "accounts": [
-        {
-          "name": "payer",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "owner",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "cosigner",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "referral",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "pool",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "buysideSolEscrowAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "assetMetadata",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "assetMasterEdition",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "assetMint",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "sellsideEscrowTokenAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "payerAssetAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "allowlistAuxAccount",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "sellState",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "associatedTokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "rent",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "args",
-          "type": {
-            "defined": "SolFulfillSellArgs"
+      "name": "DepositSellArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "assetAmount",
+            "type": "u64"
+          },
+          {
+            "name": "allowlistAux",
+            "type": {
+              "option": "string"
+            }
+          },
+          {
+            "name": "priceOverride",
+            "type": {
+              "option": {
+                "defined": "PriceOverride"
+              }
+            }
+          },
+          {
+            "name": "authorizationData",
+            "type": {
+              "option": {
+                "defined": "AuthorizationDataLocal"
+              }
+            }
           }
-        }
-      ]
+        ]
+      }
     },
     {
-      "name": "withdrawSell",
-      "accounts": [
-        {
-          "name": "owner",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "cosigner",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "pool",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "assetMint",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "assetTokenAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "sellsideEscrowTokenAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "buysideSolEscrowAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "allowlistAuxAccount",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "sellState",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "associatedTokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "rent",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "args",
-          "type": {
-            "defined": "WithdrawSellArgs"
+      "name": "SolDepositBuyArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "paymentAmount",
+            "type": "u64"
           }
-        }
-      ]
+        ]
+      }
     },
     {
-      "name": "depositSell",
-      "accounts": [
-        {
-          "name": "owner",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "cosigner",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "pool",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "assetMetadata",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "assetMasterEdition",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "assetMint",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "assetTokenAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "sellsideEscrowTokenAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "sellState",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "allowlistAuxAccount",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "associatedTokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "rent",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "args",
-          "type": {
-            "defined": "DepositSellArgs"
+      "name": "SolFulfillBuyArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "assetAmount",
+            "type": "u64"
+          },
+          {
+            "name": "minPaymentAmount",
+            "type": "u64"
+          },
+          {
+            "name": "allowlistAux",
+            "type": {
+              "option": "string"
+            }
+          },
+          {
+            "name": "makerFeeBp",
+            "type": "i16"
+          },
+          {
+            "name": "takerFeeBp",
+            "type": "i16"
+          },
+          {
+            "name": "traitPremium",
+            "type": {
+              "option": {
+                "defined": "TraitPremium"
+              }
+            }
+          },
+          {
+            "name": "authorizationData",
+            "type": {
+              "option": {
+                "defined": "AuthorizationDataLocal"
+              }
+            }
           }
-        }
-      ]
+        ]
+      }
     },
     {
-      "name": "ocpDepositSell",
-      "accounts": [
-        {
-          "name": "owner",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "cosigner",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "pool",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "assetMetadata",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "assetMint",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "assetTokenAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "sellsideEscrowTokenAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "sellState",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "allowlistAuxAccount",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "ocpMintState",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "ocpPolicy",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "ocpFreezeAuthority",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "ocpProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "cmtProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "instructions",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "associatedTokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "rent",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "args",
-          "type": {
-            "defined": "DepositSellArgs"
+      "name": "SolFulfillRouteArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "legs",
+            "type": {
+              "vec": {
+                "defined": "RouteLeg"
+              }
+            }
+          },
+          {
+            "name": "maxPaymentAmount",
+            "type": "u64"
+          },
+          {
+            "name": "minPaymentAmount",
+            "type": "u64"
+          },
+          {
+            "name": "deadline",
+            "type": "i64"
           }
-        }
-      ]
+        ]
+      }
     },
     {
-      "name": "solOcpFulfillBuy",
-      "accounts": [
-        {
-          "name": "payer",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "owner",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "cosigner",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "referral",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "pool",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "buysideSolEscrowAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "assetMetadata",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "assetMint",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "payerAssetAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "sellsideEscrowTokenAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "ownerTokenAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "allowlistAuxAccount",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "sellState",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "ocpMintState",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "ocpPolicy",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "ocpFreezeAuthority",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "ocpProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "cmtProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "instructions",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "associatedTokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "rent",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "args",
-          "type": {
-            "defined": "SolFulfillBuyArgs"
+      "name": "FulfillRouteResult",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "legs",
+            "type": "u8"
+          },
+          {
+            "name": "totalPaid",
+            "type": "u64"
+          },
+          {
+            "name": "totalReceived",
+            "type": "u64"
+          },
+          {
+            "name": "lpFee",
+            "type": "u64"
+          },
+          {
+            "name": "makerFee",
+            "type": "i64"
+          },
+          {
+            "name": "takerFee",
+            "type": "i64"
+          },
+          {
+            "name": "royaltyPaid",
+            "type": "u64"
+          },
+          {
+            "name": "royaltyAccrued",
+            "type": "u64"
+          },
+          {
+            "name": "protocolFee",
+            "type": "u64"
           }
-        }
-      ]
+        ]
+      }
     },
     {
-      "name": "solOcpFulfillSell",
-      "accounts": [
-        {
-          "name": "payer",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "owner",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "cosigner",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "referral",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "pool",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "buysideSolEscrowAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "assetMetadata",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "assetMint",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "sellsideEscrowTokenAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "payerAssetAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "allowlistAuxAccount",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "sellState",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "ocpMintState",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "ocpPolicy",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "ocpFreezeAuthority",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "ocpProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "cmtProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "instructions",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "associatedTokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "rent",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "args",
-          "type": {
-            "defined": "SolOcpFulfillSellArgs"
+      "name": "SolFulfillSellArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "assetAmount",
+            "type": "u64"
+          },
+          {
+            "name": "maxPaymentAmount",
+            "type": "u64"
+          },
+          {
+            "name": "buysideCreatorRoyaltyBp",
+            "type": "u16"
+          },
+          {
+            "name": "allowlistAux",
+            "type": {
+              "option": "string"
+            }
+          },
+          {
+            "name": "makerFeeBp",
+            "type": "i16"
+          },
+          {
+            "name": "takerFeeBp",
+            "type": "i16"
           }
-        }
-      ]
+        ]
+      }
     },
     {
-      "name": "ocpWithdrawSell",
-      "accounts": [
-        {
-          "name": "owner",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "cosigner",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "pool",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "assetMint",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "assetMetadata",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "assetTokenAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "sellsideEscrowTokenAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "buysideSolEscrowAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "allowlistAuxAccount",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "sellState",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "ocpMintState",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "ocpPolicy",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "ocpFreezeAuthority",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "ocpProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "cmtProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "instructions",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "associatedTokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "rent",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "args",
-          "type": {
-            "defined": "WithdrawSellArgs"
+      "name": "SolFulfillTradeInArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "maxNetPaymentAmount",
+            "type": "i64"
+          },
+          {
+            "name": "buysideCreatorRoyaltyBp",
+            "type": "u16"
+          },
+          {
+            "name": "allowlistAux",
+            "type": {
+              "option": "string"
+            }
+          },
+          {
+            "name": "makerFeeBp",
+            "type": "i16"
+          },
+          {
+            "name": "takerFeeBp",
+            "type": "i16"
           }
-        }
-      ]
+        ]
+      }
     },
     {
-      "name": "mip1DepositSell",
-      "accounts": [
-        {
-          "name": "owner",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "cosigner",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "pool",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "assetMetadata",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "assetMint",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "assetMasterEdition",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "assetTokenAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "sellsideEscrowTokenAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "sellState",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "allowlistAuxAccount",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "ownerTokenRecord",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "destinationTokenRecord",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "authorizationRules",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "tokenMetadataProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "authorizationRulesProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "instructions",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "associatedTokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "rent",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "args",
-          "type": {
-            "defined": "DepositSellArgs"
+      "name": "SolWithdrawBuyArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "paymentAmount",
+            "type": "u64"
           }
-        }
-      ]
+        ]
+      }
     },
     {
-      "name": "mip1WithdrawSell",
-      "accounts": [
-        {
-          "name": "owner",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "cosigner",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "pool",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "assetMint",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "assetMasterEdition",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "assetMetadata",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "assetTokenAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "sellsideEscrowTokenAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "buysideSolEscrowAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "allowlistAuxAccount",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "sellState",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "ownerTokenRecord",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "destinationTokenRecord",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "authorizationRules",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "tokenMetadataProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "authorizationRulesProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "instructions",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "associatedTokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "rent",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "args",
-          "type": {
-            "defined": "WithdrawSellArgs"
+      "name": "WithdrawSellArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "assetAmount",
+            "type": "u64"
+          },
+          {
+            "name": "allowlistAux",
+            "type": {
+              "option": "string"
+            }
           }
-        }
-      ]
+        ]
+      }
     },
     {
-      "name": "solMip1FulfillSell",
-      "accounts": [
-        {
-          "name": "payer",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "owner",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "cosigner",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "referral",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "pool",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "buysideSolEscrowAccount",
-          "isMut": true,
-          "isSigner": false
+      "name": "Mip1WithdrawSellArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "assetAmount",
+            "type": "u64"
+          },
+          {
+            "name": "allowlistAux",
+            "type": {
+              "option": "string"
+            }
+          },
+          {
+            "name": "authorizationData",
+            "type": {
+              "option": {
+                "defined": "AuthorizationDataLocal"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "Allowlist",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "kind",
+            "type": "u8"
+          },
+          {
+            "name": "value",
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "PriceOverride",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "kind",
+            "type": "u8"
+          },
+          {
+            "name": "value",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "TraitPremium",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "assetId",
+            "type": "publicKey"
+          },
+          {
+            "name": "traitBucket",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "multiplierBp",
+            "type": "u16"
+          },
+          {
+            "name": "expiry",
+            "type": "i64"
+          },
+          {
+            "name": "proof",
+            "type": {
+              "option": {
+                "vec": {
+                  "array": [
+                    "u8",
+                    32
+                  ]
+                }
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "RoyaltyBand",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "minPrice",
+            "type": "u64"
+          },
+          {
+            "name": "royaltyBp",
+            "type": "u16"
+          }
+        ]
+      }
+    },
+    {
+      "name": "ReferralRecipient",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "address",
+            "type": "publicKey"
+          },
+          {
+            "name": "shareBp",
+            "type": "u16"
+          }
+        ]
+      }
+    },
+    {
+      "name": "FulfillResult",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "totalPrice",
+            "type": "u64"
+          },
+          {
+            "name": "lpFee",
+            "type": "u64"
+          },
+          {
+            "name": "makerFee",
+            "type": "i64"
+          },
+          {
+            "name": "takerFee",
+            "type": "i64"
+          },
+          {
+            "name": "royaltyPaid",
+            "type": "u64"
+          },
+          {
+            "name": "paymentAmount",
+            "type": "u64"
+          },
+          {
+            "name": "nextPrice",
+            "type": "u64"
+          },
+          {
+            "name": "sellsideAssetAmount",
+            "type": "u64"
+          },
+          {
+            "name": "transferFeeWithheld",
+            "type": "u64"
+          },
+          {
+            "name": "royaltyAccrued",
+            "type": "u64"
+          },
+          {
+            "name": "protocolFee",
+            "type": "u64"
+          },
+          {
+            "name": "tradeInTotalPrice",
+            "type": "u64"
+          },
+          {
+            "name": "tradeInPaymentReceived",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "PayloadTypeLocal",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Pubkey",
+            "fields": [
+              "publicKey"
+            ]
+          },
+          {
+            "name": "Seeds",
+            "fields": [
+              {
+                "vec": "bytes"
+              }
+            ]
+          },
+          {
+            "name": "MerkleProof",
+            "fields": [
+              {
+                "vec": {
+                  "array": [
+                    "u8",
+                    32
+                  ]
+                }
+              }
+            ]
+          },
+          {
+            "name": "Number",
+            "fields": [
+              "u64"
+            ]
+          }
+        ]
+      }
+    },
+    {
+      "name": "TokenStandard",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "NonFungible"
+          },
+          {
+            "name": "FungibleAsset"
+          },
+          {
+            "name": "Fungible"
+          },
+          {
+            "name": "NonFungibleEdition"
+          }
+        ]
+      }
+    },
+    {
+      "name": "UseMethod",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Burn"
+          },
+          {
+            "name": "Multiple"
+          },
+          {
+            "name": "Single"
+          }
+        ]
+      }
+    },
+    {
+      "name": "TokenProgramVersion",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Original"
+          },
+          {
+            "name": "Token2022"
+          }
+        ]
+      }
+    },
+    {
+      "name": "RouteLeg",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "FulfillBuy",
+            "fields": [
+              {
+                "name": "accountsLen",
+                "type": "u8"
+              },
+              {
+                "name": "args",
+                "type": {
+                  "defined": "SolFulfillBuyArgs"
+                }
+              }
+            ]
+          },
+          {
+            "name": "FulfillSell",
+            "fields": [
+              {
+                "name": "accountsLen",
+                "type": "u8"
+              },
+              {
+                "name": "args",
+                "type": {
+                  "defined": "SolFulfillSellArgs"
+                }
+              }
+            ]
+          }
+        ]
+      }
+    }
+  ],
+  "errors": [
+    {
+      "code": 6000,
+      "name": "InvalidLPFee",
+      "msg": "lp fee bp must be between 0 and 2000"
+    },
+    {
+      "code": 6001,
+      "name": "InvalidAllowLists",
+      "msg": "invalid allowlists"
+    },
+    {
+      "code": 6002,
+      "name": "InvalidBP",
+      "msg": "invalid bp"
+    },
+    {
+      "code": 6003,
+      "name": "InvalidCurveType",
+      "msg": "invalid curve type"
+    },
+    {
+      "code": 6004,
+      "name": "InvalidCurveDelta",
+      "msg": "invalid curve delta"
+    },
+    {
+      "code": 6005,
+      "name": "InvalidCosigner",
+      "msg": "invalid cosigner"
+    },
+    {
+      "code": 6006,
+      "name": "InvalidPaymentMint",
+      "msg": "invalid payment mint"
+    },
+    {
+      "code": 6007,
+      "name": "InvalidOwner",
+      "msg": "invalid owner"
+    },
+    {
+      "code": 6008,
+      "name": "NumericOverflow",
+      "msg": "numeric overflow"
+    },
+    {
+      "code": 6009,
+      "name": "InvalidRequestedPrice",
+      "msg": "invalid requested price"
+    },
+    {
+      "code": 6010,
+      "name": "NotEmptyEscrowAccount",
+      "msg": "not empty escrow account"
+    },
+    {
+      "code": 6011,
+      "name": "NotEmptySellsideAssetAmount",
+      "msg": "not empty sell side asset amount"
+    },
+    {
+      "code": 6012,
+      "name": "InvalidReferral",
+      "msg": "invalid referral"
+    },
+    {
+      "code": 6013,
+      "name": "InvalidMasterEdition",
+      "msg": "invalid master edition"
+    },
+    {
+      "code": 6014,
+      "name": "Expired",
+      "msg": "expired"
+    },
+    {
+      "code": 6015,
+      "name": "InvalidCreatorAddress",
+      "msg": "invalid creator address"
+    },
+    {
+      "code": 6016,
+      "name": "NotEnoughBalance",
+      "msg": "not enough balance"
+    },
+    {
+      "code": 6017,
+      "name": "InvalidTokenOwner",
+      "msg": "invalid token owner"
+    },
+    {
+      "code": 6018,
+      "name": "PubkeyMismatch",
+      "msg": "pubkey mismatch"
+    },
+    {
+      "code": 6019,
+      "name": "UninitializedAccount",
+      "msg": "uninitialized account"
+    },
+    {
+      "code": 6020,
+      "name": "InvalidSpotPrice",
+      "msg": "invalid spot_price"
+    },
+    {
+      "code": 6021,
+      "name": "InvalidMetadataCreatorRoyalty",
+      "msg": "invalid metadata creator royalty"
+    },
+    {
+      "code": 6022,
+      "name": "InvalidMakerOrTakerFeeBP",
+      "msg": "invalid maker or taker bp"
+    },
+    {
+      "code": 6023,
+      "name": "InvalidOcpAssetParams",
+      "msg": "Invalid OCP mint supply constraints"
+    },
+    {
+      "code": 6024,
+      "name": "InvalidAccountState",
+      "msg": "Invalid account state"
+    },
+    {
+      "code": 6025,
+      "name": "InvalidTokenMint",
+      "msg": "Invalid token mint"
+    },
+    {
+      "code": 6026,
+      "name": "InvalidMip1AssetParams",
+      "msg": "Invalid MIP1 mint supply constraints"
+    },
+    {
+      "code": 6027,
+      "name": "InvalidTokenStandard",
+      "msg": "Invalid token standard"
+    },
+    {
+      "code": 6028,
+      "name": "UnexpectedMetadataUri",
+      "msg": "Unexpected metadata uri"
+    },
+    {
+      "code": 6029,
+      "name": "InvalidRemainingAccounts",
+      "msg": "Invalid remaining accounts"
+    },
+    {
+      "code": 6030,
+      "name": "InvalidTokenMetadataExtension",
+      "msg": "Invalid token metadata extensions"
+    },
+    {
+      "code": 6031,
+      "name": "InvalidTokenMemberExtension",
+      "msg": "Invalid token member extensions"
+    },
+    {
+      "code": 6032,
+      "name": "InvalidAssetCollection",
+      "msg": "Invalid asset collection"
+    },
+    {
+      "code": 6033,
+      "name": "InvalidTokenExtension",
+      "msg": "Invalid token extension"
+    },
+    {
+      "code": 6034,
+      "name": "UnsupportedAssetPlugin",
+      "msg": "Unsupported asset plugin"
+    },
+    {
+      "code": 6035,
+      "name": "InvalidCnftMetadata",
+      "msg": "Invalid cnft metadata"
+    },
+    {
+      "code": 6036,
+      "name": "InvalidCnftMetadataArgs",
+      "msg": "Invalid cnft metadata args"
+    },
+    {
+      "code": 6037,
+      "name": "InvalidPriceOverride",
+      "msg": "Invalid price override"
+    },
+    {
+      "code": 6038,
+      "name": "InvalidTraitPremium",
+      "msg": "Invalid trait premium"
+    },
+    {
+      "code": 6039,
+      "name": "InvalidTransferHookProgram",
+      "msg": "Transfer hook program is not allowed"
+    },
+    {
+      "code": 6040,
+      "name": "InvalidTransferHookAccounts",
+      "msg": "Invalid transfer hook accounts"
+    },
+    {
+      "code": 6041,
+      "name": "InvalidTransferFee",
+      "msg": "Transfer fee exceeds transfer amount"
+    },
+    {
+      "code": 6042,
+      "name": "InvalidPermanentDelegate",
+      "msg": "Permanent delegate is not allowed"
+    },
+    {
+      "code": 6043,
+      "name": "InvalidExtensionPolicy",
+      "msg": "Invalid extension policy"
+    },
+    {
+      "code": 6044,
+      "name": "InvalidAuthorizationRules",
+      "msg": "Authorization rules don't match the rule set of the asset"
+    },
+    {
+      "code": 6045,
+      "name": "InvalidAuthorizationData",
+      "msg": "Invalid authorization payload"
+    },
+    {
+      "code": 6046,
+      "name": "InvalidRoyaltySchedule",
+      "msg": "Invalid royalty schedule"
+    },
+    {
+      "code": 6047,
+      "name": "ProgramNotAllowedByRuleSet",
+      "msg": "Program is not allowed by the royalty rule set of the asset"
+    },
+    {
+      "code": 6048,
+      "name": "InvalidTreasury",
+      "msg": "Invalid treasury"
+    },
+    {
+      "code": 6049,
+      "name": "InvalidAdmin",
+      "msg": "Invalid admin"
+    },
+    {
+      "code": 6050,
+      "name": "InvalidGlobalConfig",
+      "msg": "Invalid global config"
+    },
+    {
+      "code": 6051,
+      "name": "Paused",
+      "msg": "Trading is paused"
+    },
+    {
+      "code": 6052,
+      "name": "InvalidReferralRegistry",
+      "msg": "Invalid referral registry"
+    },
+    {
+      "code": 6053,
+      "name": "NonTransferableMint",
+      "msg": "Non-transferable mint is not allowed"
+    },
+    {
+      "code": 6054,
+      "name": "InvalidDefaultAccountState",
+      "msg": "Default account state is not allowed"
+    },
+    {
+      "code": 6055,
+      "name": "InvalidInterestBearingConfig",
+      "msg": "Interest bearing mint is not allowed"
+    },
+    {
+      "code": 6056,
+      "name": "CpiGuardEnabled",
+      "msg": "CPI guard is enabled on the token account"
+    },
+    {
+      "code": 6057,
+      "name": "ListingUnavailable",
+      "msg": "Listing is no longer available"
+    },
+    {
+      "code": 6058,
+      "name": "InvalidRuleSet",
+      "msg": "Invalid rule set"
+    },
+    {
+      "code": 6059,
+      "name": "RuleSetOperationNotFound",
+      "msg": "Operation is not in the rule set"
+    },
+    {
+      "code": 6060,
+      "name": "MissingAuthorizationPayload",
+      "msg": "Authorization payload doesn't satisfy the rule set"
+    }
+  ]
+};
+
+export const IDL: Mmm = {
+  "version": "0.2.0",
+  "name": "mmm",
+  "instructions": [
+    {
+      "name": "createPool",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "cosigner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "CreatePoolArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "updatePool",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "cosigner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "UpdatePoolArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "updateAllowlists",
+      "accounts": [
+        {
+          "name": "cosigner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "UpdateAllowlistsArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "solClosePool",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "cosigner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buysideSolEscrowAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "solDepositBuy",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "cosigner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buysideSolEscrowAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "SolDepositBuyArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "solWithdrawBuy",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "cosigner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buysideSolEscrowAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "SolWithdrawBuyArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "solFulfillBuy",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "cosigner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "referral",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "treasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "feeSchedule",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "royaltySchedule",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "get_royalty_schedule, it might not be initialized"
+          ]
+        },
+        {
+          "name": "referralRegistry",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buysideSolEscrowAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "assetMetadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetMasterEdition",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "payerAssetAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellsideEscrowTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "ownerTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "allowlistAuxAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sellState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "SolFulfillBuyArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "solFulfillSell",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "cosigner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "referral",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "treasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "feeSchedule",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "royaltySchedule",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "get_royalty_schedule, it might not be initialized"
+          ]
+        },
+        {
+          "name": "referralRegistry",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buysideSolEscrowAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "assetMetadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetMasterEdition",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sellsideEscrowTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payerAssetAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "allowlistAuxAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sellState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "SolFulfillSellArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "solFulfillRoute",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "SolFulfillRouteArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "solFulfillTradeIn",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "cosigner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "referral",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "treasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "feeSchedule",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "inRoyaltySchedule",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "in get_royalty_schedule, it might not be initialized"
+          ]
+        },
+        {
+          "name": "outRoyaltySchedule",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "in get_royalty_schedule, it might not be initialized"
+          ]
+        },
+        {
+          "name": "referralRegistry",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buysideSolEscrowAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "inAssetMetadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "inAssetMasterEdition",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "inAssetMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "payerInAssetAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "inSellsideEscrowTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "ownerInTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "inSellState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "outAssetMetadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "outAssetMasterEdition",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "outAssetMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "outSellsideEscrowTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payerOutAssetAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "outSellState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "allowlistAuxAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "SolFulfillTradeInArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "withdrawSell",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "cosigner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "assetMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellsideEscrowTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buysideSolEscrowAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "allowlistAuxAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sellState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "WithdrawSellArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "depositSell",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "cosigner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "assetMetadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetMasterEdition",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellsideEscrowTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "allowlistAuxAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "DepositSellArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "ocpDepositSell",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "cosigner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "assetMetadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellsideEscrowTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "allowlistAuxAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "ocpMintState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "ocpPolicy",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "ocpFreezeAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "ocpProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "cmtProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "DepositSellArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "solOcpFulfillBuy",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "cosigner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "referral",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "treasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "feeSchedule",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "royaltySchedule",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "get_royalty_schedule, it might not be initialized"
+          ]
+        },
+        {
+          "name": "referralRegistry",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buysideSolEscrowAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "assetMetadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "payerAssetAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellsideEscrowTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "ownerTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "allowlistAuxAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sellState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "ocpMintState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "ocpPolicy",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "ocpFreezeAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "ocpProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "cmtProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "SolFulfillBuyArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "solOcpFulfillSell",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "cosigner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "referral",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "treasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "feeSchedule",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "royaltySchedule",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "get_royalty_schedule, it might not be initialized"
+          ]
+        },
+        {
+          "name": "referralRegistry",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buysideSolEscrowAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "assetMetadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sellsideEscrowTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payerAssetAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "allowlistAuxAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sellState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "ocpMintState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "ocpPolicy",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "ocpFreezeAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "ocpProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "cmtProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "SolOcpFulfillSellArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "ocpWithdrawSell",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "cosigner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "assetMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetMetadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellsideEscrowTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buysideSolEscrowAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "allowlistAuxAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sellState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "ocpMintState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "ocpPolicy",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "ocpFreezeAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "ocpProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "cmtProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "WithdrawSellArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "mip1DepositSell",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "cosigner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "assetMetadata",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "assetMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetMasterEdition",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellsideEscrowTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "allowlistAuxAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "ownerTokenRecord",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "destinationTokenRecord",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authorizationRules",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMetadataProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authorizationRulesProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "DepositSellArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "mip1WithdrawSell",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "cosigner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "assetMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetMasterEdition",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetMetadata",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "assetTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellsideEscrowTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buysideSolEscrowAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "allowlistAuxAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sellState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "ownerTokenRecord",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "destinationTokenRecord",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authorizationRules",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMetadataProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authorizationRulesProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "Mip1WithdrawSellArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "solMip1FulfillSell",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "cosigner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "referral",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "treasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "feeSchedule",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "royaltySchedule",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "get_royalty_schedule, it might not be initialized"
+          ]
+        },
+        {
+          "name": "referralRegistry",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buysideSolEscrowAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "assetMetadata",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "assetMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetMasterEdition",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sellsideEscrowTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "non-custodial listings, checked in the handler"
+          ]
+        },
+        {
+          "name": "payerAssetAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "allowlistAuxAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sellState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "ownerTokenRecord",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "destinationTokenRecord",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authorizationRules",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMetadataProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authorizationRulesProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "SolMip1FulfillSellArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "solMip1FulfillBuy",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "cosigner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "referral",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "treasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "feeSchedule",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "royaltySchedule",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "get_royalty_schedule, it might not be initialized"
+          ]
+        },
+        {
+          "name": "referralRegistry",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buysideSolEscrowAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "assetMetadata",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "assetMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetMasterEdition",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "payerAssetAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellsideEscrowTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "ownerTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "allowlistAuxAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sellState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenOwnerTokenRecord",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "This is the token record for the seller"
+          ]
+        },
+        {
+          "name": "poolTokenRecord",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "This is the token record for the pool - will always be required"
+          ]
+        },
+        {
+          "name": "poolOwnerTokenRecord",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "This is the token record for the pool owner - will be required if reinvest = true"
+          ]
+        },
+        {
+          "name": "tokenMetadataProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authorizationRulesProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authorizationRules",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "SolFulfillBuyArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "closeIfBalanceInvalid",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "checked against the cancel_authority of the global config"
+          ]
+        },
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buysideSolEscrowAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "setSharedEscrow",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "cosigner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sharedEscrowAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "SetSharedEscrowArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "extDepositSell",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "cosigner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "assetMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "assetTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellsideEscrowTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "extensionPolicy",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "DepositSellArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "solExtFulfillSell",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "cosigner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "referral",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "treasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "feeSchedule",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "royaltySchedule",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "get_royalty_schedule, it might not be initialized"
+          ]
+        },
+        {
+          "name": "referralRegistry",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buysideSolEscrowAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "assetMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellsideEscrowTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payerAssetAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "extensionPolicy",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "SolFulfillSellArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "solExtFulfillBuy",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "cosigner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "referral",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "treasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "feeSchedule",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "royaltySchedule",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "get_royalty_schedule, it might not be initialized"
+          ]
+        },
+        {
+          "name": "referralRegistry",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buysideSolEscrowAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "assetMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payerAssetAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellsideEscrowTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "ownerTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "allowlistAuxAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sellState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "extensionPolicy",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "SolFulfillBuyArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "extWithdrawSell",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "cosigner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "assetMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "assetTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellsideEscrowTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buysideSolEscrowAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "allowlistAuxAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sellState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "WithdrawSellArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "mplCoreDepositSell",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "cosigner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "asset",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collection",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "MplCoreDepositSellArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "mplCoreWithdrawSell",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "cosigner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "asset",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buysideSolEscrowAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collection",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "MplCoreWithdrawSellArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "solMplCoreFulfillSell",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "cosigner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "referral",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "treasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "feeSchedule",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "royaltySchedule",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "get_royalty_schedule, it might not be initialized"
+          ]
+        },
+        {
+          "name": "referralRegistry",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buysideSolEscrowAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "asset",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collection",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "SolMplCoreFulfillSellArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "solMplCoreFulfillBuy",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "cosigner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "referral",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "treasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "feeSchedule",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "royaltySchedule",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "get_royalty_schedule, it might not be initialized"
+          ]
+        },
+        {
+          "name": "referralRegistry",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buysideSolEscrowAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "asset",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collection",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "SolMplCoreFulfillBuyArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "cnftFulfillBuy",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "cosigner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "referral",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "treasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "feeSchedule",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "royaltySchedule",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "get_royalty_schedule, it might not be initialized"
+          ]
+        },
+        {
+          "name": "referralRegistry",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buysideSolEscrowAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "treeAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "merkleTree",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "logWrapper",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "bubblegumProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "compressionProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sellState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "SolCnftFulfillBuyArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setSellStatePriceOverride",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "cosigner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "pool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sellState",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "SetSellStatePriceOverrideArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "mip1ListSell",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "cosigner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "assetMetadata",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "assetMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetMasterEdition",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "allowlistAuxAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "ownerTokenRecord",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authorizationRules",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMetadataProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authorizationRulesProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "DepositSellArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "mip1DelistSell",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "cosigner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "assetMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetMasterEdition",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetMetadata",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "assetTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "ownerTokenRecord",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authorizationRules",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMetadataProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authorizationRulesProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "mip1ReleaseStaleListing",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "assetMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "assetTokenAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sellState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "setExtensionPolicy",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "cosigner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "pool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "extensionPolicy",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "SetExtensionPolicyArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "mplCoreListSell",
+      "accounts": [
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "cosigner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "asset",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "collection",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "globalConfig",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
         },
         {
-          "name": "assetMetadata",
+          "name": "assetProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "MplCoreDepositSellArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "mplCoreReleaseStaleListing",
+      "accounts": [
+        {
+          "name": "owner",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "assetMint",
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "asset",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "assetMasterEdition",
+          "name": "sellState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "assetProgram",
           "isMut": false,
           "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "claimRoyalties",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
         },
         {
-          "name": "sellsideEscrowTokenAccount",
+          "name": "creator",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "payerAssetAccount",
+          "name": "royaltyVault",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "allowlistAuxAccount",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "setRoyaltySchedule",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
         },
         {
-          "name": "sellState",
+          "name": "collection",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "mint, checked in get_collection_update_authority"
+          ]
+        },
+        {
+          "name": "collectionMetadata",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "collections, can be the default pubkey otherwise"
+          ]
+        },
+        {
+          "name": "royaltySchedule",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "ownerTokenRecord",
-          "isMut": true,
+          "name": "globalConfig",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "destinationTokenRecord",
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "SetRoyaltyScheduleArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "initGlobalConfig",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "globalConfig",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "authorizationRules",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
-        },
+        }
+      ],
+      "args": [
         {
-          "name": "tokenMetadataProgram",
+          "name": "args",
+          "type": {
+            "defined": "InitGlobalConfigArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "updateGlobalConfig",
+      "accounts": [
+        {
+          "name": "admin",
           "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "globalConfig",
+          "isMut": true,
           "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "UpdateGlobalConfigArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setProtocolFeeExemption",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
         },
         {
-          "name": "authorizationRulesProgram",
+          "name": "globalConfig",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "instructions",
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "SetProtocolFeeExemptionArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "acceptGlobalConfigAdmin",
+      "accounts": [
+        {
+          "name": "pendingAdmin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "globalConfig",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "setPauseFlags",
+      "accounts": [
+        {
+          "name": "admin",
           "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "globalConfig",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "SetPauseFlagsArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setReferralRegistry",
+      "accounts": [
+        {
+          "name": "referrer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "referralRegistry",
+          "isMut": true,
           "isSigner": false
         },
         {
           "name": "systemProgram",
           "isMut": false,
           "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "SetReferralRegistryArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setFeeSchedule",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
         },
         {
-          "name": "tokenProgram",
+          "name": "globalConfig",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "associatedTokenProgram",
+          "name": "referral",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "rent",
+          "name": "feeSchedule",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
         }
@@ -4648,903 +9056,1032 @@ export const IDL: Mmm = {
         {
           "name": "args",
           "type": {
-            "defined": "SolMip1FulfillSellArgs"
+            "defined": "SetFeeScheduleArgs"
+          }
+        }
+      ]
+    }
+  ],
+  "accounts": [
+    {
+      "name": "pool",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "spotPrice",
+            "type": "u64"
+          },
+          {
+            "name": "curveType",
+            "type": "u8"
+          },
+          {
+            "name": "curveDelta",
+            "type": "u64"
+          },
+          {
+            "name": "reinvestFulfillBuy",
+            "type": "bool"
+          },
+          {
+            "name": "reinvestFulfillSell",
+            "type": "bool"
+          },
+          {
+            "name": "expiry",
+            "type": "i64"
+          },
+          {
+            "name": "lpFeeBp",
+            "type": "u16"
+          },
+          {
+            "name": "referral",
+            "type": "publicKey"
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u8",
+                2
+              ]
+            }
+          },
+          {
+            "name": "buysideCreatorRoyaltyBp",
+            "type": "u16"
+          },
+          {
+            "name": "cosignerAnnotation",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "sellsideAssetAmount",
+            "type": "u64"
+          },
+          {
+            "name": "lpFeeEarned",
+            "type": "u64"
+          },
+          {
+            "name": "owner",
+            "type": "publicKey"
+          },
+          {
+            "name": "cosigner",
+            "type": "publicKey"
+          },
+          {
+            "name": "uuid",
+            "type": "publicKey"
+          },
+          {
+            "name": "paymentMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "allowlists",
+            "type": {
+              "array": [
+                {
+                  "defined": "Allowlist"
+                },
+                6
+              ]
+            }
+          },
+          {
+            "name": "buysidePaymentAmount",
+            "type": "u64"
+          },
+          {
+            "name": "sharedEscrowAccount",
+            "type": "publicKey"
+          },
+          {
+            "name": "sharedEscrowCount",
+            "type": "u64"
+          },
+          {
+            "name": "maxTraitMultiplierBp",
+            "type": "u16"
+          },
+          {
+            "name": "transferHookProgramId",
+            "type": "publicKey"
+          },
+          {
+            "name": "protocolFeeExempt",
+            "type": "bool"
+          },
+          {
+            "name": "compoundLpFee",
+            "type": "bool"
+          },
+          {
+            "name": "lpFeeCompounded",
+            "type": "u64"
+          },
+          {
+            "name": "dynamicLpFeeMinBp",
+            "type": "u16"
+          },
+          {
+            "name": "dynamicLpFeeMaxBp",
+            "type": "u16"
+          },
+          {
+            "name": "traitPremiumRoot",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "sellState",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "pool",
+            "type": "publicKey"
+          },
+          {
+            "name": "poolOwner",
+            "type": "publicKey"
+          },
+          {
+            "name": "assetMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "assetAmount",
+            "type": "u64"
+          },
+          {
+            "name": "cosignerAnnotation",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "priceOverride",
+            "type": {
+              "defined": "PriceOverride"
+            }
+          },
+          {
+            "name": "listingTokenAccount",
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "extensionPolicy",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "pool",
+            "type": "publicKey"
+          },
+          {
+            "name": "allowedExtensions",
+            "type": "u64"
+          },
+          {
+            "name": "permanentDelegate",
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "royaltyVault",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "creator",
+            "type": "publicKey"
+          },
+          {
+            "name": "accrued",
+            "type": "u64"
+          },
+          {
+            "name": "claimed",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "globalConfig",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "admin",
+            "type": "publicKey"
+          },
+          {
+            "name": "pendingAdmin",
+            "type": "publicKey"
+          },
+          {
+            "name": "treasury",
+            "type": "publicKey"
+          },
+          {
+            "name": "protocolFeeBp",
+            "type": "u16"
+          },
+          {
+            "name": "pauseFlags",
+            "type": "u8"
+          },
+          {
+            "name": "cancelAuthority",
+            "type": "publicKey"
+          },
+          {
+            "name": "maxLpFeeBp",
+            "type": "u16"
+          },
+          {
+            "name": "maxReferralFeeBp",
+            "type": "i16"
+          },
+          {
+            "name": "maxMetadataCreatorRoyaltyBp",
+            "type": "u16"
+          },
+          {
+            "name": "minSolEscrowBalanceBp",
+            "type": "u16"
+          },
+          {
+            "name": "m2AuctionHouse",
+            "type": "publicKey"
+          },
+          {
+            "name": "paymentProxyProgramId",
+            "type": "publicKey"
+          },
+          {
+            "name": "paymentProxyDiscriminator",
+            "type": {
+              "array": [
+                "u8",
+                8
+              ]
+            }
+          },
+          {
+            "name": "paymentProxyMinLen",
+            "type": "u32"
+          },
+          {
+            "name": "transferHookProgramAllowList",
+            "type": {
+              "array": [
+                "publicKey",
+                3
+              ]
+            }
+          },
+          {
+            "name": "defaultMakerFeeBp",
+            "type": "i16"
+          },
+          {
+            "name": "defaultTakerFeeBp",
+            "type": "i16"
+          }
+        ]
+      }
+    },
+    {
+      "name": "royaltySchedule",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "collection",
+            "type": "publicKey"
+          },
+          {
+            "name": "authority",
+            "type": "publicKey"
+          },
+          {
+            "name": "bandCount",
+            "type": "u8"
+          },
+          {
+            "name": "bands",
+            "type": {
+              "array": [
+                {
+                  "defined": "RoyaltyBand"
+                },
+                8
+              ]
+            }
           }
-        }
-      ]
+        ]
+      }
     },
     {
-      "name": "solMip1FulfillBuy",
-      "accounts": [
-        {
-          "name": "payer",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "owner",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "cosigner",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "referral",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "pool",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "buysideSolEscrowAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "assetMetadata",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "assetMint",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "assetMasterEdition",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "payerAssetAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "sellsideEscrowTokenAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "ownerTokenAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "allowlistAuxAccount",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "sellState",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "tokenOwnerTokenRecord",
-          "isMut": true,
-          "isSigner": false,
-          "docs": [
-            "This is the token record for the seller"
-          ]
-        },
-        {
-          "name": "poolTokenRecord",
-          "isMut": true,
-          "isSigner": false,
-          "docs": [
-            "This is the token record for the pool - will always be required"
-          ]
-        },
-        {
-          "name": "poolOwnerTokenRecord",
-          "isMut": true,
-          "isSigner": false,
-          "docs": [
-            "This is the token record for the pool owner - will be required if reinvest = true"
-          ]
-        },
-        {
-          "name": "tokenMetadataProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "authorizationRulesProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "authorizationRules",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "instructions",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "associatedTokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "rent",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "args",
-          "type": {
-            "defined": "SolFulfillBuyArgs"
+      "name": "referralRegistry",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "referrer",
+            "type": "publicKey"
+          },
+          {
+            "name": "recipientCount",
+            "type": "u8"
+          },
+          {
+            "name": "recipients",
+            "type": {
+              "array": [
+                {
+                  "defined": "ReferralRecipient"
+                },
+                4
+              ]
+            }
           }
-        }
-      ]
+        ]
+      }
     },
     {
-      "name": "closeIfBalanceInvalid",
-      "accounts": [
-        {
-          "name": "authority",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "owner",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "pool",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "buysideSolEscrowAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": []
-    },
+      "name": "feeSchedule",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "referral",
+            "type": "publicKey"
+          },
+          {
+            "name": "makerFeeBp",
+            "type": "i16"
+          },
+          {
+            "name": "takerFeeBp",
+            "type": "i16"
+          }
+        ]
+      }
+    }
+  ],
+  "types": [
     {
-      "name": "setSharedEscrow",
-      "accounts": [
-        {
-          "name": "owner",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "cosigner",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "pool",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "sharedEscrowAccount",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "args",
-          "type": {
-            "defined": "SetSharedEscrowArgs"
+      "name": "TaggedPayload",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "name",
+            "type": "string"
+          },
+          {
+            "name": "payload",
+            "type": {
+              "defined": "PayloadTypeLocal"
+            }
           }
-        }
-      ]
+        ]
+      }
     },
     {
-      "name": "extDepositSell",
-      "accounts": [
-        {
-          "name": "owner",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "cosigner",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "pool",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "assetMint",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "assetTokenAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "sellsideEscrowTokenAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "sellState",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "associatedTokenProgram",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "args",
-          "type": {
-            "defined": "DepositSellArgs"
+      "name": "AuthorizationDataLocal",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "payload",
+            "type": {
+              "vec": {
+                "defined": "TaggedPayload"
+              }
+            }
           }
-        }
-      ]
+        ]
+      }
     },
     {
-      "name": "solExtFulfillSell",
-      "accounts": [
-        {
-          "name": "payer",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "owner",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "cosigner",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "referral",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "pool",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "buysideSolEscrowAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "assetMint",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "sellsideEscrowTokenAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "payerAssetAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "sellState",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "associatedTokenProgram",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "args",
-          "type": {
-            "defined": "SolFulfillSellArgs"
+      "name": "CreatePoolArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "spotPrice",
+            "type": "u64"
+          },
+          {
+            "name": "curveType",
+            "type": "u8"
+          },
+          {
+            "name": "curveDelta",
+            "type": "u64"
+          },
+          {
+            "name": "reinvestFulfillBuy",
+            "type": "bool"
+          },
+          {
+            "name": "reinvestFulfillSell",
+            "type": "bool"
+          },
+          {
+            "name": "expiry",
+            "type": "i64"
+          },
+          {
+            "name": "lpFeeBp",
+            "type": "u16"
+          },
+          {
+            "name": "referral",
+            "type": "publicKey"
+          },
+          {
+            "name": "cosignerAnnotation",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "buysideCreatorRoyaltyBp",
+            "type": "u16"
+          },
+          {
+            "name": "uuid",
+            "type": "publicKey"
+          },
+          {
+            "name": "paymentMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "allowlists",
+            "type": {
+              "array": [
+                {
+                  "defined": "Allowlist"
+                },
+                6
+              ]
+            }
+          },
+          {
+            "name": "maxTraitMultiplierBp",
+            "type": {
+              "option": "u16"
+            }
+          },
+          {
+            "name": "traitPremiumRoot",
+            "type": {
+              "option": {
+                "array": [
+                  "u8",
+                  32
+                ]
+              }
+            }
+          },
+          {
+            "name": "transferHookProgramId",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "compoundLpFee",
+            "type": {
+              "option": "bool"
+            }
+          },
+          {
+            "name": "dynamicLpFeeMinBp",
+            "type": {
+              "option": "u16"
+            }
+          },
+          {
+            "name": "dynamicLpFeeMaxBp",
+            "type": {
+              "option": "u16"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "InitGlobalConfigArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "admin",
+            "type": "publicKey"
+          },
+          {
+            "name": "treasury",
+            "type": "publicKey"
+          },
+          {
+            "name": "protocolFeeBp",
+            "type": "u16"
           }
-        }
-      ]
+        ]
+      }
     },
     {
-      "name": "solExtFulfillBuy",
-      "accounts": [
-        {
-          "name": "payer",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "owner",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "cosigner",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "referral",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "pool",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "buysideSolEscrowAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "assetMint",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "payerAssetAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "sellsideEscrowTokenAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "ownerTokenAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "allowlistAuxAccount",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "sellState",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "associatedTokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "rent",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "args",
-          "type": {
-            "defined": "SolFulfillBuyArgs"
+      "name": "SetExtensionPolicyArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "allowedExtensions",
+            "type": "u64"
+          },
+          {
+            "name": "permanentDelegate",
+            "type": "publicKey"
           }
-        }
-      ]
+        ]
+      }
     },
     {
-      "name": "extWithdrawSell",
-      "accounts": [
-        {
-          "name": "owner",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "cosigner",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "pool",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "assetMint",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "assetTokenAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "sellsideEscrowTokenAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "buysideSolEscrowAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "allowlistAuxAccount",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "sellState",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "associatedTokenProgram",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "args",
-          "type": {
-            "defined": "WithdrawSellArgs"
+      "name": "SetFeeScheduleArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "makerFeeBp",
+            "type": "i16"
+          },
+          {
+            "name": "takerFeeBp",
+            "type": "i16"
           }
-        }
-      ]
+        ]
+      }
     },
     {
-      "name": "mplCoreDepositSell",
-      "accounts": [
-        {
-          "name": "owner",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "cosigner",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "pool",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "asset",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "sellState",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "collection",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "assetProgram",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "args",
-          "type": {
-            "defined": "MplCoreDepositSellArgs"
+      "name": "SetPauseFlagsArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "pauseFlags",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "SetProtocolFeeExemptionArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "exempt",
+            "type": "bool"
+          }
+        ]
+      }
+    },
+    {
+      "name": "SetSellStatePriceOverrideArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "priceOverride",
+            "type": {
+              "defined": "PriceOverride"
+            }
           }
-        }
-      ]
+        ]
+      }
     },
     {
-      "name": "mplCoreWithdrawSell",
-      "accounts": [
-        {
-          "name": "owner",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "cosigner",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "pool",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "asset",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "buysideSolEscrowAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "sellState",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "collection",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "assetProgram",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "args",
-          "type": {
-            "defined": "MplCoreWithdrawSellArgs"
+      "name": "SetSharedEscrowArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "sharedEscrowCount",
+            "type": "u64"
           }
-        }
-      ]
+        ]
+      }
     },
     {
-      "name": "solMplCoreFulfillSell",
-      "accounts": [
-        {
-          "name": "payer",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "owner",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "cosigner",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "referral",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "pool",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "buysideSolEscrowAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "asset",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "sellState",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "collection",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "assetProgram",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "args",
-          "type": {
-            "defined": "SolMplCoreFulfillSellArgs"
+      "name": "UpdateAllowlistsArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "allowlists",
+            "type": {
+              "array": [
+                {
+                  "defined": "Allowlist"
+                },
+                6
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "UpdateGlobalConfigArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "treasury",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "protocolFeeBp",
+            "type": {
+              "option": "u16"
+            }
+          },
+          {
+            "name": "pendingAdmin",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "cancelAuthority",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "maxLpFeeBp",
+            "type": {
+              "option": "u16"
+            }
+          },
+          {
+            "name": "maxReferralFeeBp",
+            "type": {
+              "option": "i16"
+            }
+          },
+          {
+            "name": "maxMetadataCreatorRoyaltyBp",
+            "type": {
+              "option": "u16"
+            }
+          },
+          {
+            "name": "minSolEscrowBalanceBp",
+            "type": {
+              "option": "u16"
+            }
+          },
+          {
+            "name": "m2AuctionHouse",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "paymentProxyProgramId",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "paymentProxyDiscriminator",
+            "type": {
+              "option": {
+                "array": [
+                  "u8",
+                  8
+                ]
+              }
+            }
+          },
+          {
+            "name": "paymentProxyMinLen",
+            "type": {
+              "option": "u32"
+            }
+          },
+          {
+            "name": "transferHookProgramAllowList",
+            "type": {
+              "option": {
+                "array": [
+                  "publicKey",
+                  3
+                ]
+              }
+            }
+          },
+          {
+            "name": "defaultMakerFeeBp",
+            "type": {
+              "option": "i16"
+            }
+          },
+          {
+            "name": "defaultTakerFeeBp",
+            "type": {
+              "option": "i16"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "UpdatePoolArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "spotPrice",
+            "type": "u64"
+          },
+          {
+            "name": "curveType",
+            "type": "u8"
+          },
+          {
+            "name": "curveDelta",
+            "type": "u64"
+          },
+          {
+            "name": "reinvestFulfillBuy",
+            "type": "bool"
+          },
+          {
+            "name": "reinvestFulfillSell",
+            "type": "bool"
+          },
+          {
+            "name": "expiry",
+            "type": "i64"
+          },
+          {
+            "name": "lpFeeBp",
+            "type": "u16"
+          },
+          {
+            "name": "referral",
+            "type": "publicKey"
+          },
+          {
+            "name": "cosignerAnnotation",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "buysideCreatorRoyaltyBp",
+            "type": "u16"
+          },
+          {
+            "name": "maxTraitMultiplierBp",
+            "type": {
+              "option": "u16"
+            }
+          },
+          {
+            "name": "traitPremiumRoot",
+            "type": {
+              "option": {
+                "array": [
+                  "u8",
+                  32
+                ]
+              }
+            }
+          },
+          {
+            "name": "transferHookProgramId",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "compoundLpFee",
+            "type": {
+              "option": "bool"
+            }
+          },
+          {
+            "name": "dynamicLpFeeMinBp",
+            "type": {
+              "option": "u16"
+            }
+          },
+          {
+            "name": "dynamicLpFeeMaxBp",
+            "type": {
+              "option": "u16"
+            }
           }
-        }
-      ]
+        ]
+      }
     },
     {
-      "name": "solMplCoreFulfillBuy",
-      "accounts": [
-        {
-          "name": "payer",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "owner",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "cosigner",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "referral",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "pool",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "buysideSolEscrowAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "asset",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "sellState",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "collection",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "assetProgram",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "args",
-          "type": {
-            "defined": "SolMplCoreFulfillBuyArgs"
+      "name": "Collection",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "verified",
+            "type": "bool"
+          },
+          {
+            "name": "key",
+            "type": "publicKey"
           }
-        }
-      ]
+        ]
+      }
     },
     {
-      "name": "cnftFulfillBuy",
-      "accounts": [
-        {
-          "name": "payer",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "owner",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "cosigner",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "referral",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "pool",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "buysideSolEscrowAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "treeAuthority",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "merkleTree",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "logWrapper",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "bubblegumProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "compressionProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "sellState",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "args",
-          "type": {
-            "defined": "SolCnftFulfillBuyArgs"
+      "name": "Uses",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "useMethod",
+            "type": {
+              "defined": "UseMethod"
+            }
+          },
+          {
+            "name": "remaining",
+            "type": "u64"
+          },
+          {
+            "name": "total",
+            "type": "u64"
           }
-        }
-      ]
-    }
-  ],
-  "accounts": [
+        ]
+      }
+    },
     {
-      "name": "pool",
+      "name": "Creator",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "address",
+            "type": "publicKey"
+          },
+          {
+            "name": "verified",
+            "type": "bool"
+          },
+          {
+            "name": "share",
+            "docs": [
+              "The percentage share.",
+              "",
+              "The value is a percentage, not basis points."
+            ],
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "MetadataArgs",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "spotPrice",
-            "type": "u64"
+            "name": "name",
+            "type": "string"
+          },
+          {
+            "name": "symbol",
+            "type": "string"
           },
           {
-            "name": "curveType",
-            "type": "u8"
+            "name": "uri",
+            "type": "string"
           },
           {
-            "name": "curveDelta",
-            "type": "u64"
+            "name": "sellerFeeBasisPoints",
+            "type": "u16"
           },
           {
-            "name": "reinvestFulfillBuy",
+            "name": "primarySaleHappened",
             "type": "bool"
           },
           {
-            "name": "reinvestFulfillSell",
+            "name": "isMutable",
             "type": "bool"
           },
           {
-            "name": "expiry",
-            "type": "i64"
+            "name": "editionNonce",
+            "type": {
+              "option": "u8"
+            }
           },
           {
-            "name": "lpFeeBp",
-            "type": "u16"
+            "name": "tokenStandard",
+            "type": {
+              "option": {
+                "defined": "TokenStandard"
+              }
+            }
           },
           {
-            "name": "referral",
-            "type": "publicKey"
+            "name": "collection",
+            "type": {
+              "option": {
+                "defined": "Collection"
+              }
+            }
           },
           {
-            "name": "referralBp",
-            "type": "u16"
+            "name": "uses",
+            "type": {
+              "option": {
+                "defined": "Uses"
+              }
+            }
           },
           {
-            "name": "buysideCreatorRoyaltyBp",
-            "type": "u16"
+            "name": "tokenProgramVersion",
+            "type": {
+              "defined": "TokenProgramVersion"
+            }
           },
           {
-            "name": "cosignerAnnotation",
+            "name": "creators",
+            "type": {
+              "vec": {
+                "defined": "Creator"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "SolCnftFulfillBuyArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "assetId",
+            "type": "publicKey"
+          },
+          {
+            "name": "root",
             "type": {
               "array": [
                 "u8",
@@ -5553,379 +10090,296 @@ export const IDL: Mmm = {
             }
           },
           {
-            "name": "sellsideAssetAmount",
-            "type": "u64"
-          },
-          {
-            "name": "lpFeeEarned",
+            "name": "nonce",
             "type": "u64"
           },
           {
-            "name": "owner",
-            "type": "publicKey"
+            "name": "index",
+            "type": "u32"
           },
           {
-            "name": "cosigner",
-            "type": "publicKey"
+            "name": "minPaymentAmount",
+            "type": "u64"
           },
           {
-            "name": "uuid",
-            "type": "publicKey"
+            "name": "makerFeeBp",
+            "type": "i16"
           },
           {
-            "name": "paymentMint",
-            "type": "publicKey"
+            "name": "takerFeeBp",
+            "type": "i16"
           },
           {
-            "name": "allowlists",
+            "name": "metadataArgs",
             "type": {
-              "array": [
-                {
-                  "defined": "Allowlist"
-                },
-                6
-              ]
+              "defined": "MetadataArgs"
             }
           },
           {
-            "name": "buysidePaymentAmount",
-            "type": "u64"
+            "name": "traitPremium",
+            "type": {
+              "option": {
+                "defined": "TraitPremium"
+              }
+            }
           },
           {
-            "name": "sharedEscrowAccount",
-            "type": "publicKey"
+            "name": "royaltyVaultCount",
+            "type": "u8"
           },
           {
-            "name": "sharedEscrowCount",
-            "type": "u64"
+            "name": "referralRecipientCount",
+            "type": "u8"
           }
         ]
       }
     },
     {
-      "name": "sellState",
+      "name": "SolMip1FulfillSellArgs",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "pool",
-            "type": "publicKey"
+            "name": "assetAmount",
+            "type": "u64"
           },
           {
-            "name": "poolOwner",
-            "type": "publicKey"
+            "name": "maxPaymentAmount",
+            "type": "u64"
           },
           {
-            "name": "assetMint",
-            "type": "publicKey"
+            "name": "allowlistAux",
+            "type": {
+              "option": "string"
+            }
           },
           {
-            "name": "assetAmount",
-            "type": "u64"
+            "name": "makerFeeBp",
+            "type": "i16"
           },
           {
-            "name": "cosignerAnnotation",
+            "name": "takerFeeBp",
+            "type": "i16"
+          },
+          {
+            "name": "authorizationData",
             "type": {
-              "array": [
-                "u8",
-                32
-              ]
+              "option": {
+                "defined": "AuthorizationDataLocal"
+              }
             }
           }
         ]
       }
-    }
-  ],
-  "types": [
+    },
     {
-      "name": "CreatePoolArgs",
+      "name": "MplCoreDepositSellArgs",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "spotPrice",
-            "type": "u64"
-          },
-          {
-            "name": "curveType",
-            "type": "u8"
-          },
-          {
-            "name": "curveDelta",
-            "type": "u64"
-          },
-          {
-            "name": "reinvestFulfillBuy",
-            "type": "bool"
-          },
-          {
-            "name": "reinvestFulfillSell",
-            "type": "bool"
-          },
-          {
-            "name": "expiry",
-            "type": "i64"
-          },
-          {
-            "name": "lpFeeBp",
-            "type": "u16"
-          },
-          {
-            "name": "referral",
-            "type": "publicKey"
-          },
-          {
-            "name": "cosignerAnnotation",
+            "name": "allowlistAux",
             "type": {
-              "array": [
-                "u8",
-                32
-              ]
+              "option": "string"
             }
           },
           {
-            "name": "buysideCreatorRoyaltyBp",
-            "type": "u16"
-          },
-          {
-            "name": "uuid",
-            "type": "publicKey"
-          },
-          {
-            "name": "paymentMint",
-            "type": "publicKey"
+            "name": "compressionProof",
+            "type": {
+              "option": "bytes"
+            }
           },
           {
-            "name": "allowlists",
+            "name": "priceOverride",
             "type": {
-              "array": [
-                {
-                  "defined": "Allowlist"
-                },
-                6
-              ]
+              "option": {
+                "defined": "PriceOverride"
+              }
             }
           }
         ]
       }
     },
     {
-      "name": "SetSharedEscrowArgs",
+      "name": "MplCoreWithdrawSellArgs",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "sharedEscrowCount",
-            "type": "u64"
+            "name": "compressionProof",
+            "type": {
+              "option": "bytes"
+            }
           }
         ]
       }
     },
     {
-      "name": "UpdateAllowlistsArgs",
+      "name": "SolMplCoreFulfillBuyArgs",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "allowlists",
+            "name": "minPaymentAmount",
+            "type": "u64"
+          },
+          {
+            "name": "allowlistAux",
             "type": {
-              "array": [
-                {
-                  "defined": "Allowlist"
-                },
-                6
-              ]
+              "option": "string"
+            }
+          },
+          {
+            "name": "makerFeeBp",
+            "type": "i16"
+          },
+          {
+            "name": "takerFeeBp",
+            "type": "i16"
+          },
+          {
+            "name": "compressionProof",
+            "type": {
+              "option": "bytes"
+            }
+          },
+          {
+            "name": "traitPremium",
+            "type": {
+              "option": {
+                "defined": "TraitPremium"
+              }
             }
           }
         ]
       }
     },
     {
-      "name": "UpdatePoolArgs",
+      "name": "SolMplCoreFulfillSellArgs",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "spotPrice",
-            "type": "u64"
-          },
-          {
-            "name": "curveType",
-            "type": "u8"
-          },
-          {
-            "name": "curveDelta",
+            "name": "maxPaymentAmount",
             "type": "u64"
           },
           {
-            "name": "reinvestFulfillBuy",
-            "type": "bool"
-          },
-          {
-            "name": "reinvestFulfillSell",
-            "type": "bool"
+            "name": "buysideCreatorRoyaltyBp",
+            "type": "u16"
           },
           {
-            "name": "expiry",
-            "type": "i64"
+            "name": "allowlistAux",
+            "type": {
+              "option": "string"
+            }
           },
           {
-            "name": "lpFeeBp",
-            "type": "u16"
+            "name": "makerFeeBp",
+            "type": "i16"
           },
           {
-            "name": "referral",
-            "type": "publicKey"
+            "name": "takerFeeBp",
+            "type": "i16"
           },
           {
-            "name": "cosignerAnnotation",
+            "name": "compressionProof",
             "type": {
-              "array": [
-                "u8",
-                32
-              ]
+              "option": "bytes"
             }
-          },
-          {
-            "name": "buysideCreatorRoyaltyBp",
-            "type": "u16"
           }
         ]
       }
     },
     {
-      "name": "Collection",
+      "name": "SolOcpFulfillSellArgs",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "verified",
-            "type": "bool"
+            "name": "assetAmount",
+            "type": "u64"
           },
           {
-            "name": "key",
-            "type": "publicKey"
+            "name": "maxPaymentAmount",
+            "type": "u64"
+          },
+          {
+            "name": "allowlistAux",
+            "type": {
+              "option": "string"
+            }
+          },
+          {
+            "name": "makerFeeBp",
+            "type": "i16"
+          },
+          {
+            "name": "takerFeeBp",
+            "type": "i16"
           }
         ]
       }
     },
     {
-      "name": "Uses",
+      "name": "SetReferralRegistryArgs",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "useMethod",
+            "name": "recipients",
             "type": {
-              "defined": "UseMethod"
+              "vec": {
+                "defined": "ReferralRecipient"
+              }
             }
-          },
-          {
-            "name": "remaining",
-            "type": "u64"
-          },
-          {
-            "name": "total",
-            "type": "u64"
           }
         ]
       }
     },
     {
-      "name": "Creator",
+      "name": "SetRoyaltyScheduleArgs",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "address",
-            "type": "publicKey"
-          },
-          {
-            "name": "verified",
-            "type": "bool"
-          },
-          {
-            "name": "share",
-            "docs": [
-              "The percentage share.",
-              "",
-              "The value is a percentage, not basis points."
-            ],
-            "type": "u8"
+            "name": "bands",
+            "type": {
+              "vec": {
+                "defined": "RoyaltyBand"
+              }
+            }
           }
         ]
       }
     },
     {
-      "name": "MetadataArgs",
+      "name": "DepositSellArgs",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "name",
-            "type": "string"
-          },
-          {
-            "name": "symbol",
-            "type": "string"
-          },
-          {
-            "name": "uri",
-            "type": "string"
-          },
-          {
-            "name": "sellerFeeBasisPoints",
-            "type": "u16"
-          },
-          {
-            "name": "primarySaleHappened",
-            "type": "bool"
-          },
-          {
-            "name": "isMutable",
-            "type": "bool"
-          },
-          {
-            "name": "editionNonce",
-            "type": {
-              "option": "u8"
-            }
+            "name": "assetAmount",
+            "type": "u64"
           },
           {
-            "name": "tokenStandard",
+            "name": "allowlistAux",
             "type": {
-              "option": {
-                "defined": "TokenStandard"
-              }
+              "option": "string"
             }
           },
           {
-            "name": "collection",
+            "name": "priceOverride",
             "type": {
               "option": {
-                "defined": "Collection"
+                "defined": "PriceOverride"
               }
             }
           },
           {
-            "name": "uses",
+            "name": "authorizationData",
             "type": {
               "option": {
-                "defined": "Uses"
-              }
-            }
-          },
-          {
-            "name": "tokenProgramVersion",
-            "type": {
-              "defined": "TokenProgramVersion"
-            }
-          },
-          {
-            "name": "creators",
-            "type": {
-              "vec": {
-                "defined": "Creator"
+                "defined": "AuthorizationDataLocal"
               }
             }
           }
@@ -5933,54 +10387,19 @@ export const IDL: Mmm = {
       }
     },
     {
-      "name": "SolCnftFulfillBuyArgs",
+      "name": "SolDepositBuyArgs",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "assetId",
-            "type": "publicKey"
-          },
-          {
-            "name": "root",
-            "type": {
-              "array": [
-                "u8",
-                32
-              ]
-            }
-          },
-          {
-            "name": "nonce",
-            "type": "u64"
-          },
-          {
-            "name": "index",
-            "type": "u32"
-          },
-          {
-            "name": "minPaymentAmount",
+            "name": "paymentAmount",
             "type": "u64"
-          },
-          {
-            "name": "makerFeeBp",
-            "type": "i16"
-          },
-          {
-            "name": "takerFeeBp",
-            "type": "i16"
-          },
-          {
-            "name": "metadataArgs",
-            "type": {
-              "defined": "MetadataArgs"
-            }
           }
         ]
       }
     },
     {
-      "name": "SolMip1FulfillSellArgs",
+      "name": "SolFulfillBuyArgs",
       "type": {
         "kind": "struct",
         "fields": [
@@ -5989,7 +10408,7 @@ export const IDL: Mmm = {
             "type": "u64"
           },
           {
-            "name": "maxPaymentAmount",
+            "name": "minPaymentAmount",
             "type": "u64"
           },
           {
@@ -6005,53 +10424,115 @@ export const IDL: Mmm = {
           {
             "name": "takerFeeBp",
             "type": "i16"
+          },
+          {
+            "name": "traitPremium",
+            "type": {
+              "option": {
+                "defined": "TraitPremium"
+              }
+            }
+          },
+          {
+            "name": "authorizationData",
+            "type": {
+              "option": {
+                "defined": "AuthorizationDataLocal"
+              }
+            }
           }
         ]
       }
     },
     {
-      "name": "MplCoreDepositSellArgs",
+      "name": "SolFulfillRouteArgs",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "allowlistAux",
+            "name": "legs",
             "type": {
-              "option": "string"
+              "vec": {
+                "defined": "RouteLeg"
+              }
             }
           },
           {
-            "name": "compressionProof",
-            "type": {
-              "option": "bytes"
-            }
+            "name": "maxPaymentAmount",
+            "type": "u64"
+          },
+          {
+            "name": "minPaymentAmount",
+            "type": "u64"
+          },
+          {
+            "name": "deadline",
+            "type": "i64"
           }
         ]
       }
     },
     {
-      "name": "MplCoreWithdrawSellArgs",
+      "name": "FulfillRouteResult",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "compressionProof",
-            "type": {
-              "option": "bytes"
-            }
+            "name": "legs",
+            "type": "u8"
+          },
+          {
+            "name": "totalPaid",
+            "type": "u64"
+          },
+          {
+            "name": "totalReceived",
+            "type": "u64"
+          },
+          {
+            "name": "lpFee",
+            "type": "u64"
+          },
+          {
+            "name": "makerFee",
+            "type": "i64"
+          },
+          {
+            "name": "takerFee",
+            "type": "i64"
+          },
+          {
+            "name": "royaltyPaid",
+            "type": "u64"
+          },
+          {
+            "name": "royaltyAccrued",
+            "type": "u64"
+          },
+          {
+            "name": "protocolFee",
+            "type": "u64"
           }
         ]
       }
     },
     {
-      "name": "SolMplCoreFulfillBuyArgs",
+      "name": "SolFulfillSellArgs",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "minPaymentAmount",
+            "name": "assetAmount",
+            "type": "u64"
+          },
+          {
+            "name": "maxPaymentAmount",
             "type": "u64"
           },
+          {
+            "name": "buysideCreatorRoyaltyBp",
+            "type": "u16"
+          },
           {
             "name": "allowlistAux",
             "type": {
@@ -6065,24 +10546,18 @@ export const IDL: Mmm = {
           {
             "name": "takerFeeBp",
             "type": "i16"
-          },
-          {
-            "name": "compressionProof",
-            "type": {
-              "option": "bytes"
-            }
           }
         ]
       }
     },
     {
-      "name": "SolMplCoreFulfillSellArgs",
+      "name": "SolFulfillTradeInArgs",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "maxPaymentAmount",
-            "type": "u64"
+            "name": "maxNetPaymentAmount",
+            "type": "i64"
           },
           {
             "name": "buysideCreatorRoyaltyBp",
@@ -6101,27 +10576,29 @@ export const IDL: Mmm = {
           {
             "name": "takerFeeBp",
             "type": "i16"
-          },
-          {
-            "name": "compressionProof",
-            "type": {
-              "option": "bytes"
-            }
           }
         ]
       }
     },
     {
-      "name": "SolOcpFulfillSellArgs",
+      "name": "SolWithdrawBuyArgs",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "assetAmount",
+            "name": "paymentAmount",
             "type": "u64"
-          },
+          }
+        ]
+      }
+    },
+    {
+      "name": "WithdrawSellArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
           {
-            "name": "maxPaymentAmount",
+            "name": "assetAmount",
             "type": "u64"
           },
           {
@@ -6129,20 +10606,12 @@ export const IDL: Mmm = {
             "type": {
               "option": "string"
             }
-          },
-          {
-            "name": "makerFeeBp",
-            "type": "i16"
-          },
-          {
-            "name": "takerFeeBp",
-            "type": "i16"
           }
         ]
       }
     },
     {
-      "name": "DepositSellArgs",
+      "name": "Mip1WithdrawSellArgs",
       "type": {
         "kind": "struct",
         "fields": [
@@ -6155,128 +10624,221 @@ export const IDL: Mmm = {
             "type": {
               "option": "string"
             }
+          },
+          {
+            "name": "authorizationData",
+            "type": {
+              "option": {
+                "defined": "AuthorizationDataLocal"
+              }
+            }
           }
         ]
       }
     },
     {
-      "name": "SolDepositBuyArgs",
+      "name": "Allowlist",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "paymentAmount",
-            "type": "u64"
+            "name": "kind",
+            "type": "u8"
+          },
+          {
+            "name": "value",
+            "type": "publicKey"
           }
         ]
       }
     },
     {
-      "name": "SolFulfillBuyArgs",
+      "name": "PriceOverride",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "assetAmount",
-            "type": "u64"
+            "name": "kind",
+            "type": "u8"
           },
           {
-            "name": "minPaymentAmount",
+            "name": "value",
             "type": "u64"
-          },
-          {
-            "name": "allowlistAux",
-            "type": {
-              "option": "string"
-            }
-          },
-          {
-            "name": "makerFeeBp",
-            "type": "i16"
-          },
-          {
-            "name": "takerFeeBp",
-            "type": "i16"
           }
         ]
       }
     },
     {
-      "name": "SolFulfillSellArgs",
+      "name": "TraitPremium",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "assetAmount",
-            "type": "u64"
+            "name": "assetId",
+            "type": "publicKey"
           },
           {
-            "name": "maxPaymentAmount",
-            "type": "u64"
+            "name": "traitBucket",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
           },
           {
-            "name": "buysideCreatorRoyaltyBp",
+            "name": "multiplierBp",
             "type": "u16"
           },
           {
-            "name": "allowlistAux",
+            "name": "expiry",
+            "type": "i64"
+          },
+          {
+            "name": "proof",
             "type": {
-              "option": "string"
+              "option": {
+                "vec": {
+                  "array": [
+                    "u8",
+                    32
+                  ]
+                }
+              }
             }
-          },
+          }
+        ]
+      }
+    },
+    {
+      "name": "RoyaltyBand",
+      "type": {
+        "kind": "struct",
+        "fields": [
           {
-            "name": "makerFeeBp",
-            "type": "i16"
+            "name": "minPrice",
+            "type": "u64"
           },
           {
-            "name": "takerFeeBp",
-            "type": "i16"
+            "name": "royaltyBp",
+            "type": "u16"
           }
         ]
       }
     },
     {
-      "name": "SolWithdrawBuyArgs",
+      "name": "ReferralRecipient",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "paymentAmount",
-            "type": "u64"
+            "name": "address",
+            "type": "publicKey"
+          },
+          {
+            "name": "shareBp",
+            "type": "u16"
           }
         ]
       }
     },
     {
-      "name": "WithdrawSellArgs",
+      "name": "FulfillResult",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "assetAmount",
+            "name": "totalPrice",
             "type": "u64"
           },
           {
-            "name": "allowlistAux",
-            "type": {
-              "option": "string"
-            }
+            "name": "lpFee",
+            "type": "u64"
+          },
+          {
+            "name": "makerFee",
+            "type": "i64"
+          },
+          {
+            "name": "takerFee",
+            "type": "i64"
+          },
+          {
+            "name": "royaltyPaid",
+            "type": "u64"
+          },
+          {
+            "name": "paymentAmount",
+            "type": "u64"
+          },
+          {
+            "name": "nextPrice",
+            "type": "u64"
+          },
+          {
+            "name": "sellsideAssetAmount",
+            "type": "u64"
+          },
+          {
+            "name": "transferFeeWithheld",
+            "type": "u64"
+          },
+          {
+            "name": "royaltyAccrued",
+            "type": "u64"
+          },
+          {
+            "name": "protocolFee",
+            "type": "u64"
+          },
+          {
+            "name": "tradeInTotalPrice",
+            "type": "u64"
+          },
+          {
+            "name": "tradeInPaymentReceived",
+            "type": "u64"
           }
         ]
       }
     },
     {
-      "name": "Allowlist",
+      "name": "PayloadTypeLocal",
       "type": {
-        "kind": "struct",
-        "fields": [
+        "kind": "enum",
+        "variants": [
           {
-            "name": "kind",
-            "type": "u8"
+            "name": "Pubkey",
+            "fields": [
+              "publicKey"
+            ]
           },
           {
-            "name": "value",
-            "type": "publicKey"
+            "name": "Seeds",
+            "fields": [
+              {
+                "vec": "bytes"
+              }
+            ]
+          },
+          {
+            "name": "MerkleProof",
+            "fields": [
+              {
+                "vec": {
+                  "array": [
+                    "u8",
+                    32
+                  ]
+                }
+              }
+            ]
+          },
+          {
+            "name": "Number",
+            "fields": [
+              "u64"
+            ]
           }
         ]
       }
@@ -6331,6 +10893,44 @@ export const IDL: Mmm = {
           }
         ]
       }
+    },
+    {
+      "name": "RouteLeg",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "FulfillBuy",
+            "fields": [
+              {
+                "name": "accountsLen",
+                "type": "u8"
+              },
+              {
+                "name": "args",
+                "type": {
+                  "defined": "SolFulfillBuyArgs"
+                }
+              }
+            ]
+          },
+          {
+            "name": "FulfillSell",
+            "fields": [
+              {
+                "name": "accountsLen",
+                "type": "u8"
+              },
+              {
+                "name": "args",
+                "type": {
+                  "defined": "SolFulfillSellArgs"
+                }
+              }
+            ]
+          }
+        ]
+      }
     }
   ],
   "errors": [
@@ -6518,6 +11118,126 @@ export const IDL: Mmm = {
       "code": 6036,
       "name": "InvalidCnftMetadataArgs",
       "msg": "Invalid cnft metadata args"
+    },
+    {
+      "code": 6037,
+      "name": "InvalidPriceOverride",
+      "msg": "Invalid price override"
+    },
+    {
+      "code": 6038,
+      "name": "InvalidTraitPremium",
+      "msg": "Invalid trait premium"
+    },
+    {
+      "code": 6039,
+      "name": "InvalidTransferHookProgram",
+      "msg": "Transfer hook program is not allowed"
+    },
+    {
+      "code": 6040,
+      "name": "InvalidTransferHookAccounts",
+      "msg": "Invalid transfer hook accounts"
+    },
+    {
+      "code": 6041,
+      "name": "InvalidTransferFee",
+      "msg": "Transfer fee exceeds transfer amount"
+    },
+    {
+      "code": 6042,
+      "name": "InvalidPermanentDelegate",
+      "msg": "Permanent delegate is not allowed"
+    },
+    {
+      "code": 6043,
+      "name": "InvalidExtensionPolicy",
+      "msg": "Invalid extension policy"
+    },
+    {
+      "code": 6044,
+      "name": "InvalidAuthorizationRules",
+      "msg": "Authorization rules don't match the rule set of the asset"
+    },
+    {
+      "code": 6045,
+      "name": "InvalidAuthorizationData",
+      "msg": "Invalid authorization payload"
+    },
+    {
+      "code": 6046,
+      "name": "InvalidRoyaltySchedule",
+      "msg": "Invalid royalty schedule"
+    },
+    {
+      "code": 6047,
+      "name": "ProgramNotAllowedByRuleSet",
+      "msg": "Program is not allowed by the royalty rule set of the asset"
+    },
+    {
+      "code": 6048,
+      "name": "InvalidTreasury",
+      "msg": "Invalid treasury"
+    },
+    {
+      "code": 6049,
+      "name": "InvalidAdmin",
+      "msg": "Invalid admin"
+    },
+    {
+      "code": 6050,
+      "name": "InvalidGlobalConfig",
+      "msg": "Invalid global config"
+    },
+    {
+      "code": 6051,
+      "name": "Paused",
+      "msg": "Trading is paused"
+    },
+    {
+      "code": 6052,
+      "name": "InvalidReferralRegistry",
+      "msg": "Invalid referral registry"
+    },
+    {
+      "code": 6053,
+      "name": "NonTransferableMint",
+      "msg": "Non-transferable mint is not allowed"
+    },
+    {
+      "code": 6054,
+      "name": "InvalidDefaultAccountState",
+      "msg": "Default account state is not allowed"
+    },
+    {
+      "code": 6055,
+      "name": "InvalidInterestBearingConfig",
+      "msg": "Interest bearing mint is not allowed"
+    },
+    {
+      "code": 6056,
+      "name": "CpiGuardEnabled",
+      "msg": "CPI guard is enabled on the token account"
+    },
+    {
+      "code": 6057,
+      "name": "ListingUnavailable",
+      "msg": "Listing is no longer available"
+    },
+    {
+      "code": 6058,
+      "name": "InvalidRuleSet",
+      "msg": "Invalid rule set"
+    },
+    {
+      "code": 6059,
+      "name": "RuleSetOperationNotFound",
+      "msg": "Operation is not in the rule set"
+    },
+    {
+      "code": 6060,
+      "name": "MissingAuthorizationPayload",
+      "msg": "Authorization payload doesn't satisfy the rule set"
     }
   ]
 };
@@ -9,6 +9,7 @@ import {
   ExtensionType,
   getExtensionData,
   getMetadataPointerState,
+  getTokenGroupMemberState,
   Mint,
   TOKEN_2022_PROGRAM_ID,
   unpackMint,
@@ -40,6 +41,7 @@ export interface MetadataProvider {
   get sellerFeeBasisPoints(): number;
   get splTokenMetadata(): TokenMetadata | undefined;
   get mintAccount(): Mint & { tokenProgramId: PublicKey };
+  get collection(): PublicKey | undefined;
 }
 
 export class RpcMetadataProvider implements MetadataProvider {
@@ -153,6 +155,20 @@ export class RpcMetadataProvider implements MetadataProvider {
     }
     return unpack(data);
   }
+
+  // the verified collection of the metadata, or the token22 group of the mint,
+  // used to derive the royalty schedule of the asset
+  get collection(): PublicKey | undefined {
+    const collection = this.metadataAccount?.collection;
+    if (collection?.verified) {
+      return collection.key;
+    }
+    const groupMember = getTokenGroupMemberState(this.mintAccount);
+    if (groupMember?.mint.equals(this.mint)) {
+      return groupMember.group;
+    }
+    return undefined;
+  }
 }
 
 function parseMintState(
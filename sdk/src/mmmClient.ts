@@ -30,7 +30,12 @@ import { IDL, Mmm } from './idl/mmm';
 import {
   getM2BuyerSharedEscrow,
   getMMMBuysideSolEscrowPDA,
+  getMMMExtensionPolicyPDA,
+  getMMMFeeSchedulePDA,
+  getMMMGlobalConfigPDA,
   getMMMPoolPDA,
+  getMMMReferralRegistryPDA,
+  getMMMRoyaltySchedulePDA,
   getMMMSellStatePDA,
   getTokenRecordPDA,
   M2_PROGRAM,
@@ -198,6 +203,33 @@ export class MMMClient {
     };
   }
 
+  private async getFulfillFeeAccounts(
+    payer: PublicKey,
+    collection: PublicKey | undefined,
+  ) {
+    if (!this.poolData) throw MMMClient.ErrPoolDataEmpty;
+    const { key: globalConfig } = getMMMGlobalConfigPDA(MMMProgramID);
+    const globalConfigData =
+      await this.program.account.globalConfig.fetchNullable(globalConfig);
+    // the treasury is only checked when a protocol fee is charged, the payer
+    // stands in for it while the global config doesn't set one
+    const treasury =
+      globalConfigData && !globalConfigData.treasury.equals(PublicKey.default)
+        ? globalConfigData.treasury
+        : payer;
+    return {
+      globalConfig,
+      treasury,
+      feeSchedule: getMMMFeeSchedulePDA(MMMProgramID, this.poolData.referral)
+        .key,
+      royaltySchedule: getMMMRoyaltySchedulePDA(MMMProgramID, collection).key,
+      referralRegistry: getMMMReferralRegistryPDA(
+        MMMProgramID,
+        this.poolData.referral,
+      ).key,
+    };
+  }
+
   async getInsCreatePool(
     args: anchor.IdlTypes<Mmm>['CreatePoolArgs'],
     owner: PublicKey,
@@ -207,6 +239,7 @@ export class MMMClient {
     let builder = this.program.methods.createPool(args).accountsStrict({
       pool: poolKey,
       owner,
+      globalConfig: getMMMGlobalConfigPDA(MMMProgramID).key,
       cosigner,
       systemProgram: SystemProgram.programId,
     });
@@ -236,6 +269,7 @@ export class MMMClient {
     let builder = this.program.methods.updatePool(args).accountsStrict({
       pool: this.poolData.pool,
       owner: this.poolData.owner,
+      globalConfig: getMMMGlobalConfigPDA(MMMProgramID).key,
       cosigner: this.poolData.cosigner,
     });
     return await builder.instruction();
@@ -248,6 +282,7 @@ export class MMMClient {
     let builder = this.program.methods.setSharedEscrow(args).accountsStrict({
       pool: this.poolData.pool,
       owner: this.poolData.owner,
+      globalConfig: getMMMGlobalConfigPDA(MMMProgramID).key,
       cosigner: this.poolData.cosigner,
       sharedEscrowAccount: getM2BuyerSharedEscrow(this.poolData.owner).key,
     });
@@ -265,6 +300,7 @@ export class MMMClient {
     let builder = this.program.methods.solDepositBuy(args).accountsStrict({
       pool: this.poolData.pool,
       owner: this.poolData.owner,
+      globalConfig: getMMMGlobalConfigPDA(MMMProgramID).key,
       cosigner: this.poolData.cosigner,
       buysideSolEscrowAccount: buysideSolEscrowAccount,
       systemProgram: SystemProgram.programId,
@@ -283,6 +319,7 @@ export class MMMClient {
     let builder = this.program.methods.solWithdrawBuy(args).accountsStrict({
       pool: this.poolData.pool,
       owner: this.poolData.owner,
+      globalConfig: getMMMGlobalConfigPDA(MMMProgramID).key,
       cosigner: this.poolData.cosigner,
       buysideSolEscrowAccount: buysideSolEscrowAccount,
       systemProgram: SystemProgram.programId,
@@ -323,11 +360,13 @@ export class MMMClient {
         convertAccountInfoToRpcAccount(assetMint, mintOrCoreAsset),
       );
       const creators = asset.royalties?.creators ?? [];
+      const collection = collectionAddress(asset);
       const mplCoreArgs = {
         minPaymentAmount: args.minPaymentAmount,
         allowlistAux: args.allowlistAux,
         takerFeeBp: args.takerFeeBp,
         makerFeeBp: args.makerFeeBp,
+        traitPremium: args.traitPremium,
       } as anchor.IdlTypes<Mmm>['SolMplCoreFulfillBuyArgs'];
       builder = this.program.methods
         .solMplCoreFulfillBuy(mplCoreArgs)
@@ -340,9 +379,13 @@ export class MMMClient {
           buysideSolEscrowAccount,
           asset: asset.publicKey,
           sellState,
-          collection: collectionAddress(asset) || PublicKey.default,
+          collection: collection || PublicKey.default,
           systemProgram: SystemProgram.programId,
           assetProgram: MPL_CORE_PROGRAM_ID,
+          ...(await this.getFulfillFeeAccounts(
+            payer,
+            collection ? new PublicKey(collection) : undefined,
+          )),
         });
 
       if (!this.poolData.sharedEscrowAccount.equals(PublicKey.default)) {
@@ -378,6 +421,10 @@ export class MMMClient {
         mintContext.mintAccount,
         this.conn,
       ));
+    const fulfillFeeAccounts = await this.getFulfillFeeAccounts(
+      payer,
+      mintContext.collection,
+    );
 
     const assetMetadata = this.mpl.nfts().pdas().metadata({ mint: assetMint });
     const ownerTokenAccount = getAssociatedTokenAddressSync(
@@ -414,6 +461,11 @@ export class MMMClient {
         tokenProgram: mintContext.tokenProgram,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         rent: SYSVAR_RENT_PUBKEY,
+        extensionPolicy: getMMMExtensionPolicyPDA(
+          MMMProgramID,
+          this.poolData.pool,
+        ).key,
+        ...fulfillFeeAccounts,
       });
     } else if (ocpMintState) {
       builder = this.program.methods.solOcpFulfillBuy(args).accountsStrict({
@@ -436,6 +488,7 @@ export class MMMClient {
         rent: SYSVAR_RENT_PUBKEY,
 
         ...this.getOcpAccounts(ocpMintState),
+        ...fulfillFeeAccounts,
       });
     } else {
       const assetMasterEdition = this.mpl
@@ -481,6 +534,7 @@ export class MMMClient {
           poolOwnerTokenRecord,
           poolTokenRecord,
           ...filteredMip1Accounts,
+          ...fulfillFeeAccounts,
         });
       } else {
         builder = this.program.methods.solFulfillBuy(args).accountsStrict({
@@ -502,6 +556,7 @@ export class MMMClient {
           tokenProgram: mintContext.tokenProgram,
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
           rent: SYSVAR_RENT_PUBKEY,
+          ...fulfillFeeAccounts,
         });
       }
     }
@@ -581,6 +636,7 @@ export class MMMClient {
         convertAccountInfoToRpcAccount(assetMint, mintOrCoreAsset),
       );
       const creators = asset.royalties?.creators ?? [];
+      const collection = collectionAddress(asset);
       const mplCoreArgs = {
         maxPaymentAmount: args.maxPaymentAmount,
         allowlistAux: args.allowlistAux,
@@ -600,8 +656,12 @@ export class MMMClient {
           asset: asset.publicKey,
           sellState,
           assetProgram: MPL_CORE_PROGRAM_ID,
-          collection: collectionAddress(asset) || PublicKey.default,
+          collection: collection || PublicKey.default,
           systemProgram: SystemProgram.programId,
+          ...(await this.getFulfillFeeAccounts(
+            payer,
+            collection ? new PublicKey(collection) : undefined,
+          )),
         });
 
       builder = builder.remainingAccounts(
@@ -624,6 +684,10 @@ export class MMMClient {
         mintContext.mintAccount,
         this.conn,
       ));
+    const fulfillFeeAccounts = await this.getFulfillFeeAccounts(
+      payer,
+      mintContext.collection,
+    );
     const sellsideEscrowTokenAccount = getAssociatedTokenAddressSync(
       assetMint,
       this.poolData.pool,
@@ -654,6 +718,11 @@ export class MMMClient {
         systemProgram: SystemProgram.programId,
         tokenProgram: mintContext.tokenProgram,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        extensionPolicy: getMMMExtensionPolicyPDA(
+          MMMProgramID,
+          this.poolData.pool,
+        ).key,
+        ...fulfillFeeAccounts,
       });
     } else if (ocpMintState) {
       builder = this.program.methods
@@ -683,6 +752,7 @@ export class MMMClient {
           rent: SYSVAR_RENT_PUBKEY,
 
           ...this.getOcpAccounts(ocpMintState),
+          ...fulfillFeeAccounts,
         });
     } else {
       const assetMasterEdition = this.mpl
@@ -698,6 +768,7 @@ export class MMMClient {
             allowlistAux: args.allowlistAux,
             makerFeeBp: args.makerFeeBp,
             takerFeeBp: args.takerFeeBp,
+            authorizationData: null,
           })
           .accountsStrict({
             payer,
@@ -724,6 +795,7 @@ export class MMMClient {
               ownerTokenAccount: sellsideEscrowTokenAccount,
               destinationTokenAccount: payerAssetAccount,
             }),
+            ...fulfillFeeAccounts,
           });
       } else {
         builder = this.program.methods.solFulfillSell(args).accountsStrict({
@@ -744,6 +816,7 @@ export class MMMClient {
           tokenProgram: mintContext.tokenProgram,
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
           rent: SYSVAR_RENT_PUBKEY,
+          ...fulfillFeeAccounts,
         });
       }
     }
@@ -799,11 +872,13 @@ export class MMMClient {
       );
       const mplCoreArgs = {
         allowlistAux: args.allowlistAux,
+        priceOverride: args.priceOverride,
       } as anchor.IdlTypes<Mmm>['MplCoreDepositSellArgs'];
       builder = this.program.methods
         .mplCoreDepositSell(mplCoreArgs)
         .accountsStrict({
           owner: this.poolData.owner,
+          globalConfig: getMMMGlobalConfigPDA(MMMProgramID).key,
           cosigner: this.poolData.cosigner,
           pool: this.poolData.pool,
           asset: assetMint,
@@ -854,6 +929,11 @@ export class MMMClient {
     if (doesTokenExtensionExist(mintContext)) {
       builder = this.program.methods.extDepositSell(args).accountsStrict({
         owner: this.poolData.owner,
+        globalConfig: getMMMGlobalConfigPDA(MMMProgramID).key,
+        extensionPolicy: getMMMExtensionPolicyPDA(
+          MMMProgramID,
+          this.poolData.pool,
+        ).key,
         cosigner: this.poolData.cosigner,
         pool: this.poolData.pool,
         assetMint,
@@ -867,6 +947,7 @@ export class MMMClient {
     } else if (ocpMintState) {
       builder = this.program.methods.ocpDepositSell(args).accountsStrict({
         owner: this.poolData.owner,
+        globalConfig: getMMMGlobalConfigPDA(MMMProgramID).key,
         pool: this.poolData.pool,
         assetMint,
         assetMetadata,
@@ -892,6 +973,7 @@ export class MMMClient {
         const ruleset = mintContext.ruleset;
         builder = this.program.methods.mip1DepositSell(args).accountsStrict({
           owner: this.poolData.owner,
+          globalConfig: getMMMGlobalConfigPDA(MMMProgramID).key,
           cosigner: this.poolData.cosigner,
           pool: this.poolData.pool,
           assetMint,
@@ -916,6 +998,7 @@ export class MMMClient {
       } else {
         builder = this.program.methods.depositSell(args).accountsStrict({
           owner: this.poolData.owner,
+          globalConfig: getMMMGlobalConfigPDA(MMMProgramID).key,
           pool: this.poolData.pool,
           assetMint,
           assetMasterEdition,
@@ -943,8 +1026,9 @@ export class MMMClient {
     return await builder.instruction();
   }
 
+  // authorizationData is only used by mip1WithdrawSell
   async getInsWithdrawSell(
-    args: anchor.IdlTypes<Mmm>['WithdrawSellArgs'],
+    args: anchor.IdlTypes<Mmm>['Mip1WithdrawSellArgs'],
     assetMint: PublicKey,
     allowlistAuxAccount?: PublicKey,
     metadataProvider?: MetadataProvider,
@@ -1129,6 +1213,7 @@ export class MMMClient {
     let builder = this.program.methods.closeIfBalanceInvalid().accountsStrict({
       pool: this.poolData.pool,
       owner: this.poolData.owner,
+      globalConfig: getMMMGlobalConfigPDA(MMMProgramID).key,
       buysideSolEscrowAccount,
       authority,
       systemProgram: SystemProgram.programId,
@@ -38,6 +38,73 @@ export const getMMMBuysideSolEscrowPDA = (
   return { key, bump };
 };
 
+export const getMMMExtensionPolicyPDA = (
+  programId: PublicKey,
+  pool: PublicKey,
+) => {
+  const [key, bump] = PublicKey.findProgramAddressSync(
+    [Buffer.from(PREFIXES.EXTENSION_POLICY), pool.toBuffer()],
+    programId,
+  );
+  return { key, bump };
+};
+
+export const getMMMRoyaltyVaultPDA = (
+  programId: PublicKey,
+  creator: PublicKey,
+) => {
+  const [key, bump] = PublicKey.findProgramAddressSync(
+    [Buffer.from(PREFIXES.ROYALTY_VAULT), creator.toBuffer()],
+    programId,
+  );
+  return { key, bump };
+};
+
+// assets without a verified collection use the pda of the default pubkey
+export const getMMMRoyaltySchedulePDA = (
+  programId: PublicKey,
+  collection: PublicKey | undefined,
+) => {
+  const [key, bump] = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(PREFIXES.ROYALTY_SCHEDULE),
+      (collection ?? PublicKey.default).toBuffer(),
+    ],
+    programId,
+  );
+  return { key, bump };
+};
+
+export const getMMMGlobalConfigPDA = (programId: PublicKey) => {
+  const [key, bump] = PublicKey.findProgramAddressSync(
+    [Buffer.from(PREFIXES.GLOBAL_CONFIG)],
+    programId,
+  );
+  return { key, bump };
+};
+
+export const getMMMReferralRegistryPDA = (
+  programId: PublicKey,
+  referrer: PublicKey,
+) => {
+  const [key, bump] = PublicKey.findProgramAddressSync(
+    [Buffer.from(PREFIXES.REFERRAL_REGISTRY), referrer.toBuffer()],
+    programId,
+  );
+  return { key, bump };
+};
+
+export const getMMMFeeSchedulePDA = (
+  programId: PublicKey,
+  referral: PublicKey,
+) => {
+  const [key, bump] = PublicKey.findProgramAddressSync(
+    [Buffer.from(PREFIXES.FEE_SCHEDULE), referral.toBuffer()],
+    programId,
+  );
+  return { key, bump };
+};
+
 export const getTokenRecordPDA = (mint: PublicKey, tokenAccount: PublicKey) => {
   const [key, bump] = PublicKey.findProgramAddressSync(
     [
@@ -1,23 +1,70 @@
+import * as anchor from '@project-serum/anchor';
 import { BN } from '@project-serum/anchor';
+import { Mmm } from './idl/mmm';
 
-export interface GetFulfillBuyPricesParams {
+export type LpFeePool = Pick<
+  anchor.IdlAccounts<Mmm>['pool'],
+  | 'spotPrice'
+  | 'sellsideAssetAmount'
+  | 'lpFeeBp'
+  | 'dynamicLpFeeMinBp'
+  | 'dynamicLpFeeMaxBp'
+>;
+
+// port of get_lp_fee_bp of the program, the dynamic lp fee grows with the
+// share of the pool value that the fulfill adds to the side it fills
+export const getLpFeeBp = (
+  pool: LpFeePool,
+  buysideSolEscrowBalance: BN | number,
+  fulfillBuy: boolean,
+): number => {
+  const solValue = new BN(buysideSolEscrowBalance);
+  if (pool.sellsideAssetAmount.ltn(1) || solValue.lt(pool.spotPrice)) {
+    return 0;
+  }
+  if (pool.dynamicLpFeeMaxBp === 0) {
+    return pool.lpFeeBp;
+  }
+
+  const assetValue = pool.sellsideAssetAmount.mul(pool.spotPrice);
+  const addedValue = fulfillBuy ? assetValue : solValue;
+  const addedShareBp = addedValue.muln(10000).div(assetValue.add(solValue));
+  const dynamicBp = new BN(pool.dynamicLpFeeMaxBp - pool.dynamicLpFeeMinBp)
+    .mul(addedShareBp)
+    .divn(10000);
+  return pool.dynamicLpFeeMinBp + dynamicBp.toNumber();
+};
+
+interface FulfillBuyFeeParams {
   totalPriceLamports: number;
-  lpFeeBp: number;
   metadataRoyaltyBp: number;
   buysideCreatorRoyaltyBp: number;
   takerFeeBp: number;
   makerFeeBp: number;
+  protocolFeeBp?: number; // of the global config, 0 for exempt pools
 }
 
+// either a flat lpFeeBp, or the pool and its escrow balance before the
+// fulfill to derive the lp fee like the program does
+export type GetFulfillBuyPricesParams = FulfillBuyFeeParams &
+  (
+    | { lpFeeBp: number }
+    | { pool: LpFeePool; buysideSolEscrowBalance: BN | number }
+  );
+
 export const getSolFulfillBuyPrices = (args: GetFulfillBuyPricesParams) => {
   const {
     totalPriceLamports,
-    lpFeeBp,
     metadataRoyaltyBp,
     buysideCreatorRoyaltyBp,
     takerFeeBp,
     makerFeeBp,
+    protocolFeeBp = 0,
   } = args;
+  const lpFeeBp =
+    'pool' in args
+      ? getLpFeeBp(args.pool, args.buysideSolEscrowBalance, true)
+      : args.lpFeeBp;
   const bpValue = new BN(10000);
   const feeDivisor = bpValue
     .mul(bpValue)
@@ -35,15 +82,18 @@ export const getSolFulfillBuyPrices = (args: GetFulfillBuyPricesParams) => {
     .divn(10000);
   const takerFeePaid = sellerReceives.muln(takerFeeBp).divn(10000);
   const makerFeePaid = sellerReceives.muln(makerFeeBp).divn(10000);
+  const protocolFeePaid = sellerReceives.muln(protocolFeeBp).divn(10000);
   const effectiveSellerReceives = new BN(totalPriceLamports)
     .sub(lpFeePaid)
     .sub(royaltyPaid)
-    .sub(takerFeePaid);
+    .sub(takerFeePaid)
+    .sub(protocolFeePaid);
   return {
     sellerReceives: effectiveSellerReceives,
     lpFeePaid,
     royaltyPaid,
     takerFeePaid,
     makerFeePaid,
+    protocolFeePaid,
   };
 };
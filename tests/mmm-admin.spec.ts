@@ -14,6 +14,7 @@ import {
   getMMMPoolPDA,
   MMMProgramID,
   getMMMBuysideSolEscrowPDA,
+  getMMMGlobalConfigPDA,
 } from '../sdk/src';
 import { airdrop, getEmptyAllowLists } from './utils';
 
@@ -66,12 +67,19 @@ describe('mmm-admin', () => {
           uuid: uuid.publicKey,
           paymentMint: PublicKey.default,
           allowlists,
+          maxTraitMultiplierBp: null,
+          traitPremiumRoot: null,
+          transferHookProgramId: null,
+          compoundLpFee: null,
+          dynamicLpFeeMinBp: null,
+          dynamicLpFeeMaxBp: null,
         })
         .accountsStrict({
           owner: wallet.publicKey,
           cosigner: cosigner.publicKey,
           pool: poolKey,
           systemProgram: SystemProgram.programId,
+          globalConfig: getMMMGlobalConfigPDA(program.programId).key,
         })
         .signers([cosigner])
         .rpc();
@@ -130,12 +138,19 @@ describe('mmm-admin', () => {
             uuid: uuid.publicKey,
             paymentMint: PublicKey.default,
             allowlists,
+            maxTraitMultiplierBp: null,
+            traitPremiumRoot: null,
+            transferHookProgramId: null,
+            compoundLpFee: null,
+            dynamicLpFeeMinBp: null,
+            dynamicLpFeeMaxBp: null,
           })
           .accountsStrict({
             owner: wallet.publicKey,
             cosigner: cosigner.publicKey,
             pool: poolKey,
             systemProgram: SystemProgram.programId,
+            globalConfig: getMMMGlobalConfigPDA(program.programId).key,
           })
           .signers([cosigner])
           .rpc();
@@ -182,12 +197,19 @@ describe('mmm-admin', () => {
           uuid: uuid.publicKey,
           paymentMint: PublicKey.default,
           allowlists,
+          maxTraitMultiplierBp: null,
+          traitPremiumRoot: null,
+          transferHookProgramId: null,
+          compoundLpFee: null,
+          dynamicLpFeeMinBp: null,
+          dynamicLpFeeMaxBp: null,
         })
         .accountsStrict({
           owner: wallet.publicKey,
           cosigner: cosigner.publicKey,
           pool: poolKey,
           systemProgram: SystemProgram.programId,
+          globalConfig: getMMMGlobalConfigPDA(program.programId).key,
         })
         .signers([cosigner])
         .rpc();
@@ -204,11 +226,18 @@ describe('mmm-admin', () => {
           referral: PublicKey.default,
           cosignerAnnotation: new Array(32).fill(0).map((_, index) => index),
           buysideCreatorRoyaltyBp: 0,
+          maxTraitMultiplierBp: null,
+          traitPremiumRoot: null,
+          transferHookProgramId: null,
+          compoundLpFee: null,
+          dynamicLpFeeMinBp: null,
+          dynamicLpFeeMaxBp: null,
         })
         .accountsStrict({
           owner: wallet.publicKey,
           cosigner: cosigner.publicKey,
           pool: poolKey,
+          globalConfig: getMMMGlobalConfigPDA(program.programId).key,
         })
         .signers([cosigner])
         .rpc();
@@ -257,6 +286,12 @@ describe('mmm-admin', () => {
         referral: referral.publicKey,
         cosignerAnnotation: new Array(32).fill(0),
         buysideCreatorRoyaltyBp: 0,
+        maxTraitMultiplierBp: null,
+        traitPremiumRoot: null,
+        transferHookProgramId: null,
+        compoundLpFee: null,
+        dynamicLpFeeMinBp: null,
+        dynamicLpFeeMaxBp: null,
 
         uuid: uuid.publicKey,
         paymentMint: PublicKey.default,
@@ -296,6 +331,7 @@ describe('mmm-admin', () => {
           cosigner: cosigner.publicKey,
           pool: poolKey,
           systemProgram: SystemProgram.programId,
+          globalConfig: getMMMGlobalConfigPDA(program.programId).key,
         })
         .signers([cosigner])
         .rpc();
@@ -313,6 +349,7 @@ describe('mmm-admin', () => {
           cosigner: cosigner.publicKey,
           pool: poolKey,
           systemProgram: SystemProgram.programId,
+          globalConfig: getMMMGlobalConfigPDA(program.programId).key,
         })
         .signers([cosigner])
         .rpc();
@@ -376,12 +413,19 @@ describe('mmm-admin', () => {
           uuid: uuid.publicKey,
           paymentMint: PublicKey.default,
           allowlists,
+          maxTraitMultiplierBp: null,
+          traitPremiumRoot: null,
+          transferHookProgramId: null,
+          compoundLpFee: null,
+          dynamicLpFeeMinBp: null,
+          dynamicLpFeeMaxBp: null,
         })
         .accountsStrict({
           owner: wallet.publicKey,
           cosigner: cosigner.publicKey,
           pool: poolKey,
           systemProgram: SystemProgram.programId,
+          globalConfig: getMMMGlobalConfigPDA(program.programId).key,
         })
         .signers([cosigner])
         .rpc();
@@ -469,12 +513,19 @@ describe('mmm-admin', () => {
           uuid: uuid.publicKey,
           paymentMint: PublicKey.default,
           allowlists,
+          maxTraitMultiplierBp: null,
+          traitPremiumRoot: null,
+          transferHookProgramId: null,
+          compoundLpFee: null,
+          dynamicLpFeeMinBp: null,
+          dynamicLpFeeMaxBp: null,
         })
         .accountsStrict({
           owner: wallet.publicKey,
           cosigner: cosigner.publicKey,
           pool: poolKey,
           systemProgram: SystemProgram.programId,
+          globalConfig: getMMMGlobalConfigPDA(program.programId).key,
         })
         .signers([cosigner])
         .rpc();
@@ -549,12 +600,19 @@ describe('mmm-admin', () => {
           uuid: uuid.publicKey,
           paymentMint: PublicKey.default,
           allowlists,
+          maxTraitMultiplierBp: null,
+          traitPremiumRoot: null,
+          transferHookProgramId: null,
+          compoundLpFee: null,
+          dynamicLpFeeMinBp: null,
+          dynamicLpFeeMaxBp: null,
         })
         .accountsStrict({
           owner: wallet.publicKey,
           cosigner: cosigner.publicKey,
           pool: poolKey,
           systemProgram: SystemProgram.programId,
+          globalConfig: getMMMGlobalConfigPDA(program.programId).key,
         })
         .signers([cosigner])
         .rpc();
@@ -629,12 +687,19 @@ describe('mmm-admin', () => {
           uuid: uuid.publicKey,
           paymentMint: PublicKey.default,
           allowlists,
+          maxTraitMultiplierBp: null,
+          traitPremiumRoot: null,
+          transferHookProgramId: null,
+          compoundLpFee: null,
+          dynamicLpFeeMinBp: null,
+          dynamicLpFeeMaxBp: null,
         })
         .accountsStrict({
           owner: wallet.publicKey,
           cosigner: wallet.publicKey,
           pool: poolKey,
           systemProgram: SystemProgram.programId,
+          globalConfig: getMMMGlobalConfigPDA(program.programId).key,
         })
         .rpc();
 
@@ -714,12 +779,19 @@ describe('mmm-admin', () => {
           uuid: uuid.publicKey,
           paymentMint: PublicKey.default,
           allowlists,
+          maxTraitMultiplierBp: null,
+          traitPremiumRoot: null,
+          transferHookProgramId: null,
+          compoundLpFee: null,
+          dynamicLpFeeMinBp: null,
+          dynamicLpFeeMaxBp: null,
         })
         .accountsStrict({
           owner: wallet.publicKey,
           cosigner: cosigner.publicKey,
           pool: poolKey,
           systemProgram: SystemProgram.programId,
+          globalConfig: getMMMGlobalConfigPDA(program.programId).key,
         })
         .signers([cosigner])
         .rpc();
@@ -741,12 +813,19 @@ describe('mmm-admin', () => {
           uuid: uuid2.publicKey,
           paymentMint: PublicKey.default,
           allowlists,
+          maxTraitMultiplierBp: null,
+          traitPremiumRoot: null,
+          transferHookProgramId: null,
+          compoundLpFee: null,
+          dynamicLpFeeMinBp: null,
+          dynamicLpFeeMaxBp: null,
         })
         .accountsStrict({
           owner: owner2.publicKey,
           cosigner: cosigner2.publicKey,
           pool: poolKey2,
           systemProgram: SystemProgram.programId,
+          globalConfig: getMMMGlobalConfigPDA(program.programId).key,
         })
         .signers([owner2, cosigner2])
         .rpc();
@@ -22,11 +22,15 @@ import {
   getMMMSellStatePDA,
   IDL,
   MMMProgramID,
+  getMMMGlobalConfigPDA,
+  getMMMFeeSchedulePDA,
+  getMMMReferralRegistryPDA,
 } from '../sdk/src';
 import {
   airdrop,
   createPoolWithExampleDepositsUmi,
   getEmptyAllowLists,
+  getRoyaltySchedulePDAForMint,
   sendAndAssertTx,
 } from './utils';
 import { toWeb3JsPublicKey } from '@metaplex-foundation/umi-web3js-adapters';
@@ -101,6 +105,8 @@ describe('mmm-any-allowlist', () => {
             allowlistAux: null,
             takerFeeBp: 100,
             makerFeeBp: 0,
+            traitPremium: null,
+            authorizationData: null,
           })
           .accountsStrict({
             payer: seller.publicKey,
@@ -125,6 +131,20 @@ describe('mmm-any-allowlist', () => {
             associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
             rent: SYSVAR_RENT_PUBKEY,
             sellState: extraNftSellState,
+            globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+            treasury: seller.publicKey,
+            feeSchedule: getMMMFeeSchedulePDA(
+              program.programId,
+              poolData.referral.publicKey,
+            ).key,
+            royaltySchedule: await getRoyaltySchedulePDAForMint(
+              program,
+              toWeb3JsPublicKey(poolData.extraNft.mintAddress),
+            ),
+            referralRegistry: getMMMReferralRegistryPDA(
+              program.programId,
+              poolData.referral.publicKey,
+            ).key,
           })
           .preInstructions([
             ComputeBudgetProgram.setComputeUnitLimit({ units: 500_0000 }),
@@ -197,6 +217,20 @@ describe('mmm-any-allowlist', () => {
             tokenProgram: tokenProgramId,
             associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
             rent: SYSVAR_RENT_PUBKEY,
+            globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+            treasury: buyer.publicKey,
+            feeSchedule: getMMMFeeSchedulePDA(
+              program.programId,
+              poolData.referral.publicKey,
+            ).key,
+            royaltySchedule: await getRoyaltySchedulePDAForMint(
+              program,
+              toWeb3JsPublicKey(poolData.nft.mintAddress),
+            ),
+            referralRegistry: getMMMReferralRegistryPDA(
+              program.programId,
+              poolData.referral.publicKey,
+            ).key,
           })
           .transaction();
         buyTx.feePayer = buyer.publicKey;
@@ -23,6 +23,10 @@ import {
   getByteArray,
   getM2BuyerSharedEscrow,
   getMMMBuysideSolEscrowPDA,
+  getMMMFeeSchedulePDA,
+  getMMMGlobalConfigPDA,
+  getMMMReferralRegistryPDA,
+  getMMMRoyaltySchedulePDA,
   getMMMSellStatePDA,
   getProofPath,
   getSolFulfillBuyPrices,
@@ -51,6 +55,18 @@ import { BN } from '@project-serum/anchor';
 import { ConcurrentMerkleTreeAccount } from '@solana/spl-account-compression';
 import { assert } from 'chai';
 
+// the royalty schedule pda of the verified collection of the cnft
+function getCnftRoyaltySchedulePDA(
+  program: anchor.Program<Mmm>,
+  metadataArgs: MetadataArgs,
+): PublicKey {
+  const collection =
+    isSome(metadataArgs.collection) && metadataArgs.collection.value.verified
+      ? new PublicKey(metadataArgs.collection.value.key)
+      : undefined;
+  return getMMMRoyaltySchedulePDA(program.programId, collection).key;
+}
+
 async function createCNftCollectionOffer(
   program: anchor.Program<Mmm>,
   poolArgs: Parameters<typeof createPool>[1],
@@ -81,6 +97,7 @@ async function createCNftCollectionOffer(
         cosigner: poolArgs.cosigner?.publicKey ?? poolArgs.owner,
         pool: poolKey,
         sharedEscrowAccount,
+        globalConfig: getMMMGlobalConfigPDA(program.programId).key,
       })
       .signers([...(poolArgs.cosigner ? [poolArgs.cosigner] : [])])
       .rpc();
@@ -93,6 +110,7 @@ async function createCNftCollectionOffer(
         pool: poolKey,
         buysideSolEscrowAccount,
         systemProgram: SystemProgram.programId,
+        globalConfig: getMMMGlobalConfigPDA(program.programId).key,
       })
       .signers([...(poolArgs.cosigner ? [poolArgs.cosigner] : [])])
       .rpc({ skipPreflight: true });
@@ -273,6 +291,9 @@ describe('cnft tests', () => {
               share: c.share,
             })),
           },
+          traitPremium: null,
+          royaltyVaultCount: 0,
+          referralRecipientCount: 0,
         })
         .accountsStrict({
           payer: new PublicKey(seller.publicKey),
@@ -288,6 +309,17 @@ describe('cnft tests', () => {
           compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
           sellState,
           systemProgram: SystemProgram.programId,
+          globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+          treasury: new PublicKey(seller.publicKey),
+          feeSchedule: getMMMFeeSchedulePDA(
+            program.programId,
+            poolData.referral.publicKey,
+          ).key,
+          royaltySchedule: getCnftRoyaltySchedulePDA(program, metadataArgs),
+          referralRegistry: getMMMReferralRegistryPDA(
+            program.programId,
+            poolData.referral.publicKey,
+          ).key,
         })
         .remainingAccounts([...creatorAccounts, ...proofPath])
         .signers([cosigner, seller.payer])
@@ -517,6 +549,9 @@ describe('cnft tests', () => {
               share: c.share,
             })),
           },
+          traitPremium: null,
+          royaltyVaultCount: 0,
+          referralRecipientCount: 0,
         })
         .accountsStrict({
           payer: new PublicKey(seller.publicKey),
@@ -532,6 +567,17 @@ describe('cnft tests', () => {
           compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
           sellState,
           systemProgram: SystemProgram.programId,
+          globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+          treasury: new PublicKey(seller.publicKey),
+          feeSchedule: getMMMFeeSchedulePDA(
+            program.programId,
+            poolData.referral.publicKey,
+          ).key,
+          royaltySchedule: getCnftRoyaltySchedulePDA(program, metadataArgs),
+          referralRegistry: getMMMReferralRegistryPDA(
+            program.programId,
+            poolData.referral.publicKey,
+          ).key,
         })
         .remainingAccounts([
           {
@@ -769,6 +815,9 @@ describe('cnft tests', () => {
               share: c.share,
             })),
           },
+          traitPremium: null,
+          royaltyVaultCount: 0,
+          referralRecipientCount: 0,
         })
         .accountsStrict({
           payer: new PublicKey(seller.publicKey),
@@ -784,6 +833,17 @@ describe('cnft tests', () => {
           compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
           sellState,
           systemProgram: SystemProgram.programId,
+          globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+          treasury: new PublicKey(seller.publicKey),
+          feeSchedule: getMMMFeeSchedulePDA(
+            program.programId,
+            poolData.referral.publicKey,
+          ).key,
+          royaltySchedule: getCnftRoyaltySchedulePDA(program, metadataArgs),
+          referralRegistry: getMMMReferralRegistryPDA(
+            program.programId,
+            poolData.referral.publicKey,
+          ).key,
         })
         .remainingAccounts([...creatorAccounts, ...proofPath])
         .signers([cosigner, seller.payer])
@@ -982,6 +1042,9 @@ describe('cnft tests', () => {
               share: c.share,
             })),
           },
+          traitPremium: null,
+          royaltyVaultCount: 0,
+          referralRecipientCount: 0,
         })
         .accountsStrict({
           payer: new PublicKey(seller.publicKey),
@@ -997,6 +1060,17 @@ describe('cnft tests', () => {
           compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
           sellState,
           systemProgram: SystemProgram.programId,
+          globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+          treasury: new PublicKey(seller.publicKey),
+          feeSchedule: getMMMFeeSchedulePDA(
+            program.programId,
+            poolData.referral.publicKey,
+          ).key,
+          royaltySchedule: getCnftRoyaltySchedulePDA(program, metadataArgs),
+          referralRegistry: getMMMReferralRegistryPDA(
+            program.programId,
+            poolData.referral.publicKey,
+          ).key,
         })
         .remainingAccounts([...creatorAccounts, ...proofPath])
         .signers([cosigner, seller.payer])
@@ -21,6 +21,9 @@ import {
   IDL,
   MMMProgramID,
   getSolFulfillBuyPrices,
+  getMMMGlobalConfigPDA,
+  getMMMFeeSchedulePDA,
+  getMMMReferralRegistryPDA,
 } from '../sdk/src';
 import {
   airdrop,
@@ -34,6 +37,7 @@ import {
   SIGNATURE_FEE_LAMPORTS,
   getTokenAccount2022,
   IMMUTABLE_OWNER_EXTENSION_LAMPORTS,
+  getRoyaltySchedulePDAForMint,
 } from './utils';
 import { toWeb3JsPublicKey } from '@metaplex-foundation/umi-web3js-adapters';
 
@@ -134,6 +138,8 @@ describe('mmm-creator-royalty', () => {
               allowlistAux: null,
               takerFeeBp: 100,
               makerFeeBp: 0,
+              traitPremium: null,
+              authorizationData: null,
             })
             .accountsStrict({
               payer: seller.publicKey,
@@ -160,6 +166,20 @@ describe('mmm-creator-royalty', () => {
               associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
               rent: SYSVAR_RENT_PUBKEY,
               sellState: extraNftSellState,
+              globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+              treasury: seller.publicKey,
+              feeSchedule: getMMMFeeSchedulePDA(
+                program.programId,
+                poolData.referral.publicKey,
+              ).key,
+              royaltySchedule: await getRoyaltySchedulePDAForMint(
+                program,
+                toWeb3JsPublicKey(poolData.extraNft.mintAddress),
+              ),
+              referralRegistry: getMMMReferralRegistryPDA(
+                program.programId,
+                poolData.referral.publicKey,
+              ).key,
             })
             .remainingAccounts([
               {
@@ -282,6 +302,20 @@ describe('mmm-creator-royalty', () => {
               tokenProgram: tokenProgramId,
               associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
               rent: SYSVAR_RENT_PUBKEY,
+              globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+              treasury: buyer.publicKey,
+              feeSchedule: getMMMFeeSchedulePDA(
+                program.programId,
+                poolData.referral.publicKey,
+              ).key,
+              royaltySchedule: await getRoyaltySchedulePDAForMint(
+                program,
+                toWeb3JsPublicKey(poolData.nft.mintAddress),
+              ),
+              referralRegistry: getMMMReferralRegistryPDA(
+                program.programId,
+                poolData.referral.publicKey,
+              ).key,
             })
             .remainingAccounts([
               {
@@ -26,6 +26,9 @@ import {
   IDL,
   MMMProgramID,
   CurveKind,
+  getMMMGlobalConfigPDA,
+  getMMMFeeSchedulePDA,
+  getMMMReferralRegistryPDA,
 } from '../sdk/src';
 import {
   PoolData,
@@ -35,6 +38,7 @@ import {
   getEmptyAllowLists,
   getMetadataURI,
   getPoolRent,
+  getRoyaltySchedulePDAForMint,
   getTokenAccount2022,
   sendAndAssertTx,
   umiMintCollection,
@@ -90,6 +94,7 @@ describe('mmm-deposit', () => {
             pool: poolKey,
             buysideSolEscrowAccount: solEscrowKey,
             systemProgram: SystemProgram.programId,
+            globalConfig: getMMMGlobalConfigPDA(program.programId).key,
           })
           .signers([cosigner])
           .rpc();
@@ -128,6 +133,7 @@ describe('mmm-deposit', () => {
             pool: poolKey,
             buysideSolEscrowAccount: solEscrowKey,
             systemProgram: SystemProgram.programId,
+            globalConfig: getMMMGlobalConfigPDA(program.programId).key,
           })
           .signers([cosigner])
           .rpc();
@@ -147,6 +153,7 @@ describe('mmm-deposit', () => {
             pool: poolKey,
             buysideSolEscrowAccount: solEscrowKey,
             systemProgram: SystemProgram.programId,
+            globalConfig: getMMMGlobalConfigPDA(program.programId).key,
           })
           .signers([cosigner])
           .rpc();
@@ -167,6 +174,7 @@ describe('mmm-deposit', () => {
             pool: poolKey,
             buysideSolEscrowAccount: solEscrowKey,
             systemProgram: SystemProgram.programId,
+            globalConfig: getMMMGlobalConfigPDA(program.programId).key,
           })
           .signers([cosigner])
           .rpc();
@@ -203,6 +211,8 @@ describe('mmm-deposit', () => {
                 allowlistAux: '',
                 makerFeeBp: 0,
                 takerFeeBp: 0,
+                traitPremium: null,
+                authorizationData: null,
               })
               .accountsStrict({
                 payer: seller.publicKey,
@@ -223,6 +233,20 @@ describe('mmm-deposit', () => {
                 tokenProgram: tokenProgramId,
                 associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
                 rent: SYSVAR_RENT_PUBKEY,
+                globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+                treasury: seller.publicKey,
+                feeSchedule: getMMMFeeSchedulePDA(
+                  program.programId,
+                  poolData.referral.publicKey,
+                ).key,
+                royaltySchedule: await getRoyaltySchedulePDAForMint(
+                  program,
+                  poolData.extraNft.mintAddress,
+                ),
+                referralRegistry: getMMMReferralRegistryPDA(
+                  program.programId,
+                  poolData.referral.publicKey,
+                ).key,
               })
               .transaction();
 
@@ -264,6 +288,7 @@ describe('mmm-deposit', () => {
               pool: poolKey,
               buysideSolEscrowAccount: solEscrowKey,
               systemProgram: SystemProgram.programId,
+              globalConfig: getMMMGlobalConfigPDA(program.programId).key,
             })
             .signers([cosigner])
             .rpc();
@@ -283,6 +308,7 @@ describe('mmm-deposit', () => {
               pool: poolKey,
               buysideSolEscrowAccount: solEscrowKey,
               systemProgram: SystemProgram.programId,
+              globalConfig: getMMMGlobalConfigPDA(program.programId).key,
             })
             .signers([cosigner])
             .rpc();
@@ -505,7 +531,12 @@ describe('mmm-deposit', () => {
         );
 
         await program.methods
-          .depositSell({ assetAmount: new anchor.BN(1), allowlistAux: '' })
+          .depositSell({
+            assetAmount: new anchor.BN(1),
+            allowlistAux: '',
+            priceOverride: null,
+            authorizationData: null,
+          })
           .accountsStrict({
             owner: wallet.publicKey,
             cosigner: cosigner.publicKey,
@@ -523,6 +554,7 @@ describe('mmm-deposit', () => {
             tokenProgram: tokenProgramId,
             associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
             rent: SYSVAR_RENT_PUBKEY,
+            globalConfig: getMMMGlobalConfigPDA(program.programId).key,
           })
           .signers([cosigner])
           .rpc({ skipPreflight: true });
@@ -571,7 +603,12 @@ describe('mmm-deposit', () => {
           toWeb3JsPublicKey(mintAddress2),
         );
         await program.methods
-          .depositSell({ assetAmount: new anchor.BN(5), allowlistAux: '' })
+          .depositSell({
+            assetAmount: new anchor.BN(5),
+            allowlistAux: '',
+            priceOverride: null,
+            authorizationData: null,
+          })
           .accountsStrict({
             owner: wallet.publicKey,
             cosigner: cosigner.publicKey,
@@ -589,6 +626,7 @@ describe('mmm-deposit', () => {
             tokenProgram: tokenProgramId,
             associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
             rent: SYSVAR_RENT_PUBKEY,
+            globalConfig: getMMMGlobalConfigPDA(program.programId).key,
           })
           .signers([cosigner])
           .rpc();
@@ -720,7 +758,12 @@ describe('mmm-deposit', () => {
           toWeb3JsPublicKey(mintAddress1),
         );
         await program.methods
-          .depositSell({ assetAmount: new anchor.BN(1), allowlistAux: '' })
+          .depositSell({
+            assetAmount: new anchor.BN(1),
+            allowlistAux: '',
+            priceOverride: null,
+            authorizationData: null,
+          })
           .accountsStrict({
             owner: wallet.publicKey,
             cosigner: cosigner.publicKey,
@@ -740,6 +783,7 @@ describe('mmm-deposit', () => {
             tokenProgram: tokenProgramId,
             associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
             rent: SYSVAR_RENT_PUBKEY,
+            globalConfig: getMMMGlobalConfigPDA(program.programId).key,
           })
           .signers([cosigner])
           .rpc();
@@ -770,7 +814,12 @@ describe('mmm-deposit', () => {
           toWeb3JsPublicKey(mintAddress2),
         );
         await program.methods
-          .depositSell({ assetAmount: new anchor.BN(5), allowlistAux: '' })
+          .depositSell({
+            assetAmount: new anchor.BN(5),
+            allowlistAux: '',
+            priceOverride: null,
+            authorizationData: null,
+          })
           .accountsStrict({
             owner: wallet.publicKey,
             cosigner: cosigner.publicKey,
@@ -788,6 +837,7 @@ describe('mmm-deposit', () => {
             tokenProgram: tokenProgramId,
             associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
             rent: SYSVAR_RENT_PUBKEY,
+            globalConfig: getMMMGlobalConfigPDA(program.programId).key,
           })
           .signers([cosigner])
           .rpc();
@@ -909,6 +959,8 @@ describe('mmm-deposit', () => {
             .depositSell({
               assetAmount: new anchor.BN(1),
               allowlistAux: aux,
+              priceOverride: null,
+              authorizationData: null,
             })
             .accountsStrict({
               owner: wallet.publicKey,
@@ -927,6 +979,7 @@ describe('mmm-deposit', () => {
               tokenProgram: tokenProgramId,
               associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
               rent: SYSVAR_RENT_PUBKEY,
+              globalConfig: getMMMGlobalConfigPDA(program.programId).key,
             })
             .signers([cosigner])
             .rpc();
@@ -1033,7 +1086,12 @@ describe('mmm-deposit', () => {
           toWeb3JsPublicKey(mintAddress1),
         );
         await program.methods
-          .depositSell({ assetAmount: new anchor.BN(1), allowlistAux: '' })
+          .depositSell({
+            assetAmount: new anchor.BN(1),
+            allowlistAux: '',
+            priceOverride: null,
+            authorizationData: null,
+          })
           .accountsStrict({
             owner: wallet.publicKey,
             cosigner: cosigner.publicKey,
@@ -1051,6 +1109,7 @@ describe('mmm-deposit', () => {
             tokenProgram: tokenProgramId,
             associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
             rent: SYSVAR_RENT_PUBKEY,
+            globalConfig: getMMMGlobalConfigPDA(program.programId).key,
           })
           .signers([cosigner])
           .rpc();
@@ -1081,7 +1140,12 @@ describe('mmm-deposit', () => {
           toWeb3JsPublicKey(mintAddress2),
         );
         await program.methods
-          .depositSell({ assetAmount: new anchor.BN(5), allowlistAux: '' })
+          .depositSell({
+            assetAmount: new anchor.BN(5),
+            allowlistAux: '',
+            priceOverride: null,
+            authorizationData: null,
+          })
           .accountsStrict({
             owner: wallet.publicKey,
             cosigner: cosigner.publicKey,
@@ -1099,6 +1163,7 @@ describe('mmm-deposit', () => {
             tokenProgram: tokenProgramId,
             associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
             rent: SYSVAR_RENT_PUBKEY,
+            globalConfig: getMMMGlobalConfigPDA(program.programId).key,
           })
           .signers([cosigner])
           .rpc();
@@ -0,0 +1,88 @@
+import * as anchor from '@project-serum/anchor';
+import { Keypair, LAMPORTS_PER_SOL } from '@solana/web3.js';
+import { assert } from 'chai';
+import {
+  getLpFeeBp,
+  getSolFulfillBuyPrices,
+  IDL,
+  Mmm,
+  MMMProgramID,
+} from '../sdk/src';
+import {
+  airdrop,
+  createTwoSidedPool,
+  getSellStatePDARent,
+  solFulfillBuy,
+} from './utils';
+
+describe('mmm-dynamic-lp-fee', () => {
+  const { connection } = anchor.AnchorProvider.env();
+  const wallet = new anchor.Wallet(Keypair.generate());
+  const provider = new anchor.AnchorProvider(connection, wallet, {
+    commitment: 'processed',
+  });
+  const program = new anchor.Program(
+    IDL,
+    MMMProgramID,
+    provider,
+  ) as anchor.Program<Mmm>;
+  const cosigner = Keypair.generate();
+
+  beforeEach(async () => {
+    await airdrop(connection, wallet.publicKey, 50);
+  });
+
+  it('charges the dynamic lp fee of the pool on fulfill buy', async () => {
+    const seller = Keypair.generate();
+    const poolData = await createTwoSidedPool(program, seller, {
+      owner: wallet.publicKey,
+      cosigner,
+      dynamicLpFeeMinBp: 100,
+      dynamicLpFeeMaxBp: 1000,
+    });
+
+    const [pool, buysideSolEscrowBalance, initSellerBalance] =
+      await Promise.all([
+        program.account.pool.fetch(poolData.poolKey),
+        connection.getBalance(poolData.poolPaymentEscrow),
+        connection.getBalance(seller.publicKey),
+      ]);
+    const lpFeeBp = getLpFeeBp(pool, buysideSolEscrowBalance, true);
+    // the flat lp fee of the pool is ignored
+    assert.notEqual(lpFeeBp, pool.lpFeeBp);
+    assert.isAbove(lpFeeBp, 100);
+    assert.isBelow(lpFeeBp, 1000);
+
+    await solFulfillBuy(program, {
+      poolData,
+      owner: wallet.publicKey,
+      seller,
+      cosigner,
+    });
+
+    const expectedBuyPrices = getSolFulfillBuyPrices({
+      totalPriceLamports: LAMPORTS_PER_SOL,
+      pool,
+      buysideSolEscrowBalance,
+      takerFeeBp: 100,
+      metadataRoyaltyBp: 0,
+      buysideCreatorRoyaltyBp: 0,
+      makerFeeBp: 0,
+    });
+    const sellStatePDARent = await getSellStatePDARent(connection);
+    const [sellerBalance, poolAccountInfo] = await Promise.all([
+      connection.getBalance(seller.publicKey),
+      program.account.pool.fetch(poolData.poolKey),
+    ]);
+    assert.equal(
+      poolAccountInfo.lpFeeEarned.toNumber(),
+      expectedBuyPrices.lpFeePaid.toNumber(),
+    );
+    assert.equal(
+      sellerBalance,
+      initSellerBalance +
+        expectedBuyPrices.sellerReceives.toNumber() -
+        sellStatePDARent,
+    );
+  });
+});
@@ -3,6 +3,7 @@ import {
   getAssociatedTokenAddress,
   ASSOCIATED_TOKEN_PROGRAM_ID,
   TOKEN_2022_PROGRAM_ID,
+  ExtensionType,
 } from '@solana/spl-token';
 import {
   ComputeBudgetProgram,
@@ -17,6 +18,8 @@ import {
   getMMMSellStatePDA,
   IDL,
   MMMProgramID,
+  getMMMGlobalConfigPDA,
+  getMMMExtensionPolicyPDA,
 } from '../sdk/src';
 import {
   airdrop,
@@ -74,6 +77,8 @@ describe('mmm-ext-deposit', () => {
         .extDepositSell({
           assetAmount: new anchor.BN(1),
           allowlistAux: 'example.com',
+          priceOverride: null,
+          authorizationData: null,
         })
         .accountsStrict({
           owner: wallet.publicKey,
@@ -86,6 +91,11 @@ describe('mmm-ext-deposit', () => {
           systemProgram: SystemProgram.programId,
           tokenProgram: TOKEN_2022_PROGRAM_ID,
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          extensionPolicy: getMMMExtensionPolicyPDA(
+            program.programId,
+            poolData.poolKey,
+          ).key,
+          globalConfig: getMMMGlobalConfigPDA(program.programId).key,
         })
         .signers([cosigner])
         .rpc({ skipPreflight: true });
@@ -141,6 +151,8 @@ describe('mmm-ext-deposit', () => {
         .extDepositSell({
           assetAmount: new anchor.BN(1),
           allowlistAux: '',
+          priceOverride: null,
+          authorizationData: null,
         })
         .accountsStrict({
           owner: wallet.publicKey,
@@ -153,6 +165,11 @@ describe('mmm-ext-deposit', () => {
           systemProgram: SystemProgram.programId,
           tokenProgram: TOKEN_2022_PROGRAM_ID,
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          extensionPolicy: getMMMExtensionPolicyPDA(
+            program.programId,
+            poolData.poolKey,
+          ).key,
+          globalConfig: getMMMGlobalConfigPDA(program.programId).key,
         })
         .signers([cosigner])
         .rpc({ skipPreflight: true });
@@ -224,6 +241,8 @@ describe('mmm-ext-deposit', () => {
         .extDepositSell({
           assetAmount: new anchor.BN(1),
           allowlistAux: 'example.com',
+          priceOverride: null,
+          authorizationData: null,
         })
         .accountsStrict({
           owner: wallet.publicKey,
@@ -236,6 +255,11 @@ describe('mmm-ext-deposit', () => {
           systemProgram: SystemProgram.programId,
           tokenProgram: TOKEN_2022_PROGRAM_ID,
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          extensionPolicy: getMMMExtensionPolicyPDA(
+            program.programId,
+            poolData.poolKey,
+          ).key,
+          globalConfig: getMMMGlobalConfigPDA(program.programId).key,
         })
         .remainingAccounts(
           await generateRemainingAccounts(
@@ -311,6 +335,8 @@ describe('mmm-ext-deposit', () => {
         .extDepositSell({
           assetAmount: new anchor.BN(1),
           allowlistAux: '',
+          priceOverride: null,
+          authorizationData: null,
         })
         .accountsStrict({
           owner: wallet.publicKey,
@@ -323,6 +349,11 @@ describe('mmm-ext-deposit', () => {
           systemProgram: SystemProgram.programId,
           tokenProgram: TOKEN_2022_PROGRAM_ID,
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          extensionPolicy: getMMMExtensionPolicyPDA(
+            program.programId,
+            poolData.poolKey,
+          ).key,
+          globalConfig: getMMMGlobalConfigPDA(program.programId).key,
         })
         .remainingAccounts(
           await generateRemainingAccounts(
@@ -408,6 +439,8 @@ describe('mmm-ext-deposit', () => {
         .extDepositSell({
           assetAmount: new anchor.BN(1),
           allowlistAux: '',
+          priceOverride: null,
+          authorizationData: null,
         })
         .accountsStrict({
           owner: wallet.publicKey,
@@ -420,6 +453,11 @@ describe('mmm-ext-deposit', () => {
           systemProgram: SystemProgram.programId,
           tokenProgram: TOKEN_2022_PROGRAM_ID,
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          extensionPolicy: getMMMExtensionPolicyPDA(
+            program.programId,
+            poolData.poolKey,
+          ).key,
+          globalConfig: getMMMGlobalConfigPDA(program.programId).key,
         })
         .signers([cosigner])
         .rpc({ skipPreflight: true });
@@ -475,6 +513,8 @@ describe('mmm-ext-deposit', () => {
         .extDepositSell({
           assetAmount: new anchor.BN(1),
           allowlistAux: '',
+          priceOverride: null,
+          authorizationData: null,
         })
         .accountsStrict({
           owner: wallet.publicKey,
@@ -487,6 +527,11 @@ describe('mmm-ext-deposit', () => {
           systemProgram: SystemProgram.programId,
           tokenProgram: TOKEN_2022_PROGRAM_ID,
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          extensionPolicy: getMMMExtensionPolicyPDA(
+            program.programId,
+            poolData.poolKey,
+          ).key,
+          globalConfig: getMMMGlobalConfigPDA(program.programId).key,
         })
         .signers([cosigner])
         .rpc({ skipPreflight: true });
@@ -543,6 +588,8 @@ describe('mmm-ext-deposit', () => {
           .extDepositSell({
             assetAmount: new anchor.BN(1),
             allowlistAux: 'wrong-aux',
+            priceOverride: null,
+            authorizationData: null,
           })
           .accountsStrict({
             owner: wallet.publicKey,
@@ -555,6 +602,11 @@ describe('mmm-ext-deposit', () => {
             systemProgram: SystemProgram.programId,
             tokenProgram: TOKEN_2022_PROGRAM_ID,
             associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            extensionPolicy: getMMMExtensionPolicyPDA(
+              program.programId,
+              poolData.poolKey,
+            ).key,
+            globalConfig: getMMMGlobalConfigPDA(program.programId).key,
           })
           .signers([cosigner])
           .rpc({ skipPreflight: true });
@@ -582,6 +634,8 @@ describe('mmm-ext-deposit', () => {
           .extDepositSell({
             assetAmount: new anchor.BN(1),
             allowlistAux: '',
+            priceOverride: null,
+            authorizationData: null,
           })
           .accountsStrict({
             owner: wallet.publicKey,
@@ -594,6 +648,11 @@ describe('mmm-ext-deposit', () => {
             systemProgram: SystemProgram.programId,
             tokenProgram: TOKEN_2022_PROGRAM_ID,
             associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            extensionPolicy: getMMMExtensionPolicyPDA(
+              program.programId,
+              poolData.poolKey,
+            ).key,
+            globalConfig: getMMMGlobalConfigPDA(program.programId).key,
           })
           .signers([cosigner])
           .rpc({ skipPreflight: true });
@@ -660,6 +719,8 @@ describe('mmm-ext-deposit', () => {
           .extDepositSell({
             assetAmount: new anchor.BN(1),
             allowlistAux: '',
+            priceOverride: null,
+            authorizationData: null,
           })
           .accountsStrict({
             owner: wallet.publicKey,
@@ -672,6 +733,11 @@ describe('mmm-ext-deposit', () => {
             systemProgram: SystemProgram.programId,
             tokenProgram: TOKEN_2022_PROGRAM_ID,
             associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            extensionPolicy: getMMMExtensionPolicyPDA(
+              program.programId,
+              poolData.poolKey,
+            ).key,
+            globalConfig: getMMMGlobalConfigPDA(program.programId).key,
           })
           .signers([cosigner])
           .rpc({ skipPreflight: true });
@@ -732,6 +798,8 @@ describe('mmm-ext-deposit', () => {
           .extDepositSell({
             assetAmount: new anchor.BN(1),
             allowlistAux: '',
+            priceOverride: null,
+            authorizationData: null,
           })
           .accountsStrict({
             owner: wallet.publicKey,
@@ -744,6 +812,11 @@ describe('mmm-ext-deposit', () => {
             systemProgram: SystemProgram.programId,
             tokenProgram: TOKEN_2022_PROGRAM_ID,
             associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            extensionPolicy: getMMMExtensionPolicyPDA(
+              program.programId,
+              poolData.poolKey,
+            ).key,
+            globalConfig: getMMMGlobalConfigPDA(program.programId).key,
           })
           .signers([cosigner])
           .rpc({ skipPreflight: true });
@@ -751,6 +824,267 @@ describe('mmm-ext-deposit', () => {
         assertProgramError(err, 'invalid allowlists');
       }
     });
+
+    // a hook program that is neither in the global allow list nor set on the
+    // pool, the mint is created without its ExtraAccountMetaList
+    const transferHookProgramId = Keypair.generate().publicKey;
+
+    it('failed to verify depositing nfts with a transfer hook not allowed by the pool', async () => {
+      const transferHookArgs: TransferHookArgs = {
+        transferHookProgramId,
+      };
+      const { mint, recipientTokenAccount, poolData, poolAta, sellState } =
+        await createPoolWithExampleT22ExtDeposits(
+          program,
+          connection,
+          wallet.payer,
+          'none',
+          {
+            owner: wallet.publicKey,
+            cosigner,
+          },
+          undefined,
+          undefined,
+          transferHookArgs,
+        );
+
+      try {
+        await program.methods
+          .extDepositSell({
+            assetAmount: new anchor.BN(1),
+            allowlistAux: 'example.com',
+            priceOverride: null,
+            authorizationData: null,
+          })
+          .accountsStrict({
+            owner: wallet.publicKey,
+            cosigner: cosigner.publicKey,
+            pool: poolData.poolKey,
+            assetMint: mint,
+            assetTokenAccount: recipientTokenAccount,
+            sellsideEscrowTokenAccount: poolAta,
+            sellState,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            extensionPolicy: getMMMExtensionPolicyPDA(
+              program.programId,
+              poolData.poolKey,
+            ).key,
+            globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+          })
+          .remainingAccounts([
+            {
+              pubkey: transferHookProgramId,
+              isWritable: false,
+              isSigner: false,
+            },
+          ])
+          .signers([cosigner])
+          .rpc({ skipPreflight: true });
+      } catch (err) {
+        assertProgramError(err, 'Transfer hook program is not allowed');
+      }
+    });
+
+    it('failed to verify depositing nfts with missing transfer hook accounts', async () => {
+      const transferHookArgs: TransferHookArgs = {
+        transferHookProgramId,
+      };
+      const { mint, recipientTokenAccount, poolData, poolAta, sellState } =
+        await createPoolWithExampleT22ExtDeposits(
+          program,
+          connection,
+          wallet.payer,
+          'none',
+          {
+            owner: wallet.publicKey,
+            cosigner,
+            transferHookProgramId,
+          },
+          undefined,
+          undefined,
+          transferHookArgs,
+        );
+
+      try {
+        await program.methods
+          .extDepositSell({
+            assetAmount: new anchor.BN(1),
+            allowlistAux: 'example.com',
+            priceOverride: null,
+            authorizationData: null,
+          })
+          .accountsStrict({
+            owner: wallet.publicKey,
+            cosigner: cosigner.publicKey,
+            pool: poolData.poolKey,
+            assetMint: mint,
+            assetTokenAccount: recipientTokenAccount,
+            sellsideEscrowTokenAccount: poolAta,
+            sellState,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            extensionPolicy: getMMMExtensionPolicyPDA(
+              program.programId,
+              poolData.poolKey,
+            ).key,
+            globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+          })
+          .remainingAccounts([
+            {
+              pubkey: transferHookProgramId,
+              isWritable: false,
+              isSigner: false,
+            },
+          ])
+          .signers([cosigner])
+          .rpc({ skipPreflight: true });
+      } catch (err) {
+        assertProgramError(err, 'Invalid transfer hook accounts');
+      }
+    });
+  });
+
+  describe('set_extension_policy', () => {
+    const extensionMask = (extensions: ExtensionType[]) =>
+      extensions.reduce(
+        (mask, ext) => mask.or(new anchor.BN(1).shln(ext)),
+        new anchor.BN(0),
+      );
+
+    const setExtensionPolicy = async (
+      poolKey: PublicKey,
+      allowedExtensions: anchor.BN,
+    ) => {
+      await program.methods
+        .setExtensionPolicy({
+          allowedExtensions,
+          permanentDelegate: PublicKey.default,
+        })
+        .accountsStrict({
+          owner: wallet.publicKey,
+          cosigner: cosigner.publicKey,
+          pool: poolKey,
+          extensionPolicy: getMMMExtensionPolicyPDA(program.programId, poolKey)
+            .key,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([cosigner])
+        .rpc();
+    };
+
+    it('correctly enforces the extension policy of the pool', async () => {
+      const { mint, recipientTokenAccount, poolData, poolAta, sellState } =
+        await createPoolWithExampleT22ExtDeposits(
+          program,
+          connection,
+          wallet.payer,
+          'none',
+          {
+            owner: wallet.publicKey,
+            cosigner,
+          },
+        );
+      const extDepositSell = () =>
+        program.methods
+          .extDepositSell({
+            assetAmount: new anchor.BN(1),
+            allowlistAux: 'example.com',
+            priceOverride: null,
+            authorizationData: null,
+          })
+          .accountsStrict({
+            owner: wallet.publicKey,
+            cosigner: cosigner.publicKey,
+            pool: poolData.poolKey,
+            assetMint: mint,
+            assetTokenAccount: recipientTokenAccount,
+            sellsideEscrowTokenAccount: poolAta,
+            sellState,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            extensionPolicy: getMMMExtensionPolicyPDA(
+              program.programId,
+              poolData.poolKey,
+            ).key,
+            globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+          })
+          .signers([cosigner])
+          .rpc({ skipPreflight: true });
+
+      // the mint has a MetadataPointer which is not allowed anymore
+      await setExtensionPolicy(
+        poolData.poolKey,
+        extensionMask([
+          ExtensionType.GroupMemberPointer,
+          ExtensionType.TokenMetadata,
+          ExtensionType.TokenGroupMember,
+        ]),
+      );
+      try {
+        await extDepositSell();
+        assert.fail('deposit of a mint with a denied extension should fail');
+      } catch (err) {
+        assertProgramError(err, 'Invalid token extension');
+      }
+
+      const allowedExtensions = extensionMask([
+        ExtensionType.GroupMemberPointer,
+        ExtensionType.MetadataPointer,
+        ExtensionType.TokenMetadata,
+        ExtensionType.TokenGroupMember,
+      ]);
+      await setExtensionPolicy(poolData.poolKey, allowedExtensions);
+      const extensionPolicy = await program.account.extensionPolicy.fetch(
+        getMMMExtensionPolicyPDA(program.programId, poolData.poolKey).key,
+      );
+      assert.equal(
+        extensionPolicy.pool.toBase58(),
+        poolData.poolKey.toBase58(),
+      );
+      assert.equal(
+        extensionPolicy.allowedExtensions.toString(),
+        allowedExtensions.toString(),
+      );
+
+      await extDepositSell();
+      const nftEscrow = await getTokenAccount2022(
+        connection,
+        poolAta,
+        TOKEN_2022_PROGRAM_ID,
+      );
+      assert.equal(Number(nftEscrow.amount), 1);
+    });
+
+    it('failed to allow non-transferable mints', async () => {
+      const { poolData } = await createPoolWithExampleT22ExtDeposits(
+        program,
+        connection,
+        wallet.payer,
+        'none',
+        {
+          owner: wallet.publicKey,
+          cosigner,
+        },
+      );
+
+      try {
+        await setExtensionPolicy(
+          poolData.poolKey,
+          extensionMask([
+            ExtensionType.MetadataPointer,
+            ExtensionType.NonTransferable,
+          ]),
+        );
+        assert.fail('allowing non-transferable mints should fail');
+      } catch (e) {
+        expect(e).toBeInstanceOf(anchor.AnchorError);
+        expect((e as anchor.AnchorError).error.errorCode.number).toBe(6043);
+      }
+    });
   });
 });
 
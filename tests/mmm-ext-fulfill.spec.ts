@@ -23,6 +23,10 @@ import {
   getSolFulfillBuyPrices,
   getM2BuyerSharedEscrow,
   M2_PROGRAM,
+  getMMMGlobalConfigPDA,
+  getMMMExtensionPolicyPDA,
+  getMMMFeeSchedulePDA,
+  getMMMReferralRegistryPDA,
 } from '../sdk/src';
 import {
   IMMUTABLE_OWNER_EXTENSION_LAMPORTS,
@@ -37,6 +41,7 @@ import {
   createPoolWithExampleT22ExtDeposits,
   createTestMintAndTokenT22VanillaExt,
   generateRemainingAccounts,
+  getRoyaltySchedulePDAForMint,
   getSellStatePDARent,
   getTokenAccount2022,
   getTokenAccountRent,
@@ -93,6 +98,21 @@ describe('mmm-ext-fulfill', () => {
         systemProgram: SystemProgram.programId,
         tokenProgram: TOKEN_2022_PROGRAM_ID,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+        treasury: buyer.publicKey,
+        feeSchedule: getMMMFeeSchedulePDA(
+          program.programId,
+          referral.publicKey,
+        ).key,
+        royaltySchedule: await getRoyaltySchedulePDAForMint(program, mint),
+        referralRegistry: getMMMReferralRegistryPDA(
+          program.programId,
+          referral.publicKey,
+        ).key,
+        extensionPolicy: getMMMExtensionPolicyPDA(
+          program.programId,
+          poolKey,
+        ).key,
       })
       .remainingAccounts(
         transferHookArgs
@@ -582,6 +602,8 @@ describe('mmm-ext-fulfill', () => {
           allowlistAux: '',
           takerFeeBp: 0,
           makerFeeBp: 150,
+          traitPremium: null,
+          authorizationData: null,
         })
         .accountsStrict({
           payer: seller.publicKey,
@@ -600,6 +622,24 @@ describe('mmm-ext-fulfill', () => {
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
           rent: SYSVAR_RENT_PUBKEY,
           sellState: extraNftSellState,
+          globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+          treasury: seller.publicKey,
+          feeSchedule: getMMMFeeSchedulePDA(
+            program.programId,
+            poolData.referral.publicKey,
+          ).key,
+          royaltySchedule: await getRoyaltySchedulePDAForMint(
+            program,
+            extraMint,
+          ),
+          referralRegistry: getMMMReferralRegistryPDA(
+            program.programId,
+            poolData.referral.publicKey,
+          ).key,
+          extensionPolicy: getMMMExtensionPolicyPDA(
+            program.programId,
+            poolData.poolKey,
+          ).key,
         })
         .transaction();
 
@@ -744,6 +784,8 @@ describe('mmm-ext-fulfill', () => {
           allowlistAux: '',
           takerFeeBp: 0,
           makerFeeBp: 150,
+          traitPremium: null,
+          authorizationData: null,
         })
         .accountsStrict({
           payer: seller.publicKey,
@@ -762,6 +804,24 @@ describe('mmm-ext-fulfill', () => {
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
           rent: SYSVAR_RENT_PUBKEY,
           sellState: extraNftSellState,
+          globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+          treasury: seller.publicKey,
+          feeSchedule: getMMMFeeSchedulePDA(
+            program.programId,
+            poolData.referral.publicKey,
+          ).key,
+          royaltySchedule: await getRoyaltySchedulePDAForMint(
+            program,
+            extraMint,
+          ),
+          referralRegistry: getMMMReferralRegistryPDA(
+            program.programId,
+            poolData.referral.publicKey,
+          ).key,
+          extensionPolicy: getMMMExtensionPolicyPDA(
+            program.programId,
+            poolData.poolKey,
+          ).key,
         })
         .remainingAccounts(
           await generateRemainingAccounts(
@@ -934,6 +994,8 @@ describe('mmm-ext-fulfill', () => {
           allowlistAux: '',
           takerFeeBp: 0,
           makerFeeBp: 150,
+          traitPremium: null,
+          authorizationData: null,
         })
         .accountsStrict({
           payer: seller.publicKey,
@@ -952,6 +1014,24 @@ describe('mmm-ext-fulfill', () => {
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
           rent: SYSVAR_RENT_PUBKEY,
           sellState: extraNftSellState,
+          globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+          treasury: seller.publicKey,
+          feeSchedule: getMMMFeeSchedulePDA(
+            program.programId,
+            poolData.referral.publicKey,
+          ).key,
+          royaltySchedule: await getRoyaltySchedulePDAForMint(
+            program,
+            extraMint,
+          ),
+          referralRegistry: getMMMReferralRegistryPDA(
+            program.programId,
+            poolData.referral.publicKey,
+          ).key,
+          extensionPolicy: getMMMExtensionPolicyPDA(
+            program.programId,
+            poolData.poolKey,
+          ).key,
         })
         .remainingAccounts(
           await generateRemainingAccounts(
@@ -1117,6 +1197,8 @@ describe('mmm-ext-fulfill', () => {
           allowlistAux: '',
           takerFeeBp: 0,
           makerFeeBp: 150,
+          traitPremium: null,
+          authorizationData: null,
         })
         .accountsStrict({
           payer: seller.publicKey,
@@ -1135,6 +1217,24 @@ describe('mmm-ext-fulfill', () => {
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
           rent: SYSVAR_RENT_PUBKEY,
           sellState: extraNftSellState,
+          globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+          treasury: seller.publicKey,
+          feeSchedule: getMMMFeeSchedulePDA(
+            program.programId,
+            poolData.referral.publicKey,
+          ).key,
+          royaltySchedule: await getRoyaltySchedulePDAForMint(
+            program,
+            extraMint,
+          ),
+          referralRegistry: getMMMReferralRegistryPDA(
+            program.programId,
+            poolData.referral.publicKey,
+          ).key,
+          extensionPolicy: getMMMExtensionPolicyPDA(
+            program.programId,
+            poolData.poolKey,
+          ).key,
         })
         .remainingAccounts([
           {
@@ -1280,6 +1380,8 @@ describe('mmm-ext-fulfill', () => {
           allowlistAux: '',
           takerFeeBp: 0,
           makerFeeBp: 150,
+          traitPremium: null,
+          authorizationData: null,
         })
         .accountsStrict({
           payer: seller.publicKey,
@@ -1298,6 +1400,24 @@ describe('mmm-ext-fulfill', () => {
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
           rent: SYSVAR_RENT_PUBKEY,
           sellState: extraNftSellState,
+          globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+          treasury: seller.publicKey,
+          feeSchedule: getMMMFeeSchedulePDA(
+            program.programId,
+            poolData.referral.publicKey,
+          ).key,
+          royaltySchedule: await getRoyaltySchedulePDAForMint(
+            program,
+            extraMint,
+          ),
+          referralRegistry: getMMMReferralRegistryPDA(
+            program.programId,
+            poolData.referral.publicKey,
+          ).key,
+          extensionPolicy: getMMMExtensionPolicyPDA(
+            program.programId,
+            poolData.poolKey,
+          ).key,
         })
         .remainingAccounts([
           {
@@ -1446,6 +1566,8 @@ describe('mmm-ext-fulfill', () => {
           allowlistAux: '',
           takerFeeBp: 0,
           makerFeeBp: 150,
+          traitPremium: null,
+          authorizationData: null,
         })
         .accountsStrict({
           payer: seller.publicKey,
@@ -1464,6 +1586,24 @@ describe('mmm-ext-fulfill', () => {
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
           rent: SYSVAR_RENT_PUBKEY,
           sellState: extraNftSellState,
+          globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+          treasury: seller.publicKey,
+          feeSchedule: getMMMFeeSchedulePDA(
+            program.programId,
+            poolData.referral.publicKey,
+          ).key,
+          royaltySchedule: await getRoyaltySchedulePDAForMint(
+            program,
+            extraMint,
+          ),
+          referralRegistry: getMMMReferralRegistryPDA(
+            program.programId,
+            poolData.referral.publicKey,
+          ).key,
+          extensionPolicy: getMMMExtensionPolicyPDA(
+            program.programId,
+            poolData.poolKey,
+          ).key,
         })
         .remainingAccounts([
           {
@@ -1626,6 +1766,8 @@ describe('mmm-ext-fulfill', () => {
           allowlistAux: null,
           takerFeeBp: 100,
           makerFeeBp: 0,
+          traitPremium: null,
+          authorizationData: null,
         })
         .accountsStrict({
           payer: seller.publicKey,
@@ -1644,6 +1786,24 @@ describe('mmm-ext-fulfill', () => {
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
           rent: SYSVAR_RENT_PUBKEY,
           sellState: extraNftSellState,
+          globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+          treasury: seller.publicKey,
+          feeSchedule: getMMMFeeSchedulePDA(
+            program.programId,
+            poolData.referral.publicKey,
+          ).key,
+          royaltySchedule: await getRoyaltySchedulePDAForMint(
+            program,
+            extraMint,
+          ),
+          referralRegistry: getMMMReferralRegistryPDA(
+            program.programId,
+            poolData.referral.publicKey,
+          ).key,
+          extensionPolicy: getMMMExtensionPolicyPDA(
+            program.programId,
+            poolData.poolKey,
+          ).key,
         })
         .transaction();
 
@@ -1732,6 +1892,21 @@ describe('mmm-ext-fulfill', () => {
             systemProgram: SystemProgram.programId,
             tokenProgram: TOKEN_2022_PROGRAM_ID,
             associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+            treasury: buyer.publicKey,
+            feeSchedule: getMMMFeeSchedulePDA(
+              program.programId,
+              poolData.referral.publicKey,
+            ).key,
+            royaltySchedule: await getRoyaltySchedulePDAForMint(program, mint),
+            referralRegistry: getMMMReferralRegistryPDA(
+              program.programId,
+              poolData.referral.publicKey,
+            ).key,
+            extensionPolicy: getMMMExtensionPolicyPDA(
+              program.programId,
+              poolData.poolKey,
+            ).key,
           })
           .transaction();
 
@@ -1881,6 +2056,8 @@ describe('mmm-ext-fulfill', () => {
           allowlistAux: null,
           takerFeeBp: 100,
           makerFeeBp: 0,
+          traitPremium: null,
+          authorizationData: null,
         })
         .accountsStrict({
           payer: seller.publicKey,
@@ -1899,6 +2076,24 @@ describe('mmm-ext-fulfill', () => {
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
           rent: SYSVAR_RENT_PUBKEY,
           sellState: extraNftSellState,
+          globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+          treasury: seller.publicKey,
+          feeSchedule: getMMMFeeSchedulePDA(
+            program.programId,
+            poolData.referral.publicKey,
+          ).key,
+          royaltySchedule: await getRoyaltySchedulePDAForMint(
+            program,
+            extraMint,
+          ),
+          referralRegistry: getMMMReferralRegistryPDA(
+            program.programId,
+            poolData.referral.publicKey,
+          ).key,
+          extensionPolicy: getMMMExtensionPolicyPDA(
+            program.programId,
+            poolData.poolKey,
+          ).key,
         })
         .remainingAccounts(
           await generateRemainingAccounts(
@@ -2005,6 +2200,21 @@ describe('mmm-ext-fulfill', () => {
             systemProgram: SystemProgram.programId,
             tokenProgram: TOKEN_2022_PROGRAM_ID,
             associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+            treasury: buyer.publicKey,
+            feeSchedule: getMMMFeeSchedulePDA(
+              program.programId,
+              poolData.referral.publicKey,
+            ).key,
+            royaltySchedule: await getRoyaltySchedulePDAForMint(program, mint),
+            referralRegistry: getMMMReferralRegistryPDA(
+              program.programId,
+              poolData.referral.publicKey,
+            ).key,
+            extensionPolicy: getMMMExtensionPolicyPDA(
+              program.programId,
+              poolData.poolKey,
+            ).key,
           })
           .remainingAccounts(
             await generateRemainingAccounts(
@@ -54,7 +54,10 @@ describe('mmm-ext-withdraw', () => {
     );
 
     await program.methods
-      .extWithdrawSell({ assetAmount: new anchor.BN(1), allowlistAux: null })
+      .extWithdrawSell({
+        assetAmount: new anchor.BN(1),
+        allowlistAux: null,
+      })
       .accountsStrict({
         owner: wallet.publicKey,
         cosigner: cosigner.publicKey,
@@ -112,7 +115,10 @@ describe('mmm-ext-withdraw', () => {
     );
 
     await program.methods
-      .extWithdrawSell({ assetAmount: new anchor.BN(1), allowlistAux: null })
+      .extWithdrawSell({
+        assetAmount: new anchor.BN(1),
+        allowlistAux: null,
+      })
       .accountsStrict({
         owner: wallet.publicKey,
         cosigner: cosigner.publicKey,
@@ -182,7 +188,10 @@ describe('mmm-ext-withdraw', () => {
     );
 
     await program.methods
-      .extWithdrawSell({ assetAmount: new anchor.BN(1), allowlistAux: null })
+      .extWithdrawSell({
+        assetAmount: new anchor.BN(1),
+        allowlistAux: null,
+      })
       .accountsStrict({
         owner: wallet.publicKey,
         cosigner: cosigner.publicKey,
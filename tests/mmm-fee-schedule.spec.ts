@@ -0,0 +1,150 @@
+import * as anchor from '@project-serum/anchor';
+import {
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+} from '@solana/web3.js';
+import { assert } from 'chai';
+import {
+  getMMMFeeSchedulePDA,
+  getMMMGlobalConfigPDA,
+  getSolFulfillBuyPrices,
+  IDL,
+  Mmm,
+  MMMProgramID,
+} from '../sdk/src';
+import {
+  airdrop,
+  createTwoSidedPool,
+  initGlobalConfigIfNeeded,
+  solFulfillBuy,
+} from './utils';
+
+describe('mmm-fee-schedule', () => {
+  const { connection } = anchor.AnchorProvider.env();
+  const wallet = new anchor.Wallet(Keypair.generate());
+  const provider = new anchor.AnchorProvider(connection, wallet, {
+    commitment: 'processed',
+  });
+  const program = new anchor.Program(
+    IDL,
+    MMMProgramID,
+    provider,
+  ) as anchor.Program<Mmm>;
+  const cosigner = Keypair.generate();
+  let admin: Keypair;
+
+  beforeAll(async () => {
+    await airdrop(connection, wallet.publicKey, 50);
+    admin = await initGlobalConfigIfNeeded(program);
+    // the admin pays for the fee schedules
+    await airdrop(connection, admin.publicKey, 1);
+  });
+
+  beforeEach(async () => {
+    await airdrop(connection, wallet.publicKey, 50);
+  });
+
+  const setFeeSchedule = async (
+    signer: Keypair,
+    referral: PublicKey,
+    makerFeeBp: number,
+    takerFeeBp: number,
+  ) => {
+    await program.methods
+      .setFeeSchedule({ makerFeeBp, takerFeeBp })
+      .accountsStrict({
+        admin: signer.publicKey,
+        globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+        referral,
+        feeSchedule: getMMMFeeSchedulePDA(program.programId, referral).key,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([signer])
+      .rpc();
+  };
+
+  it('caps the fees charged for the referral', async () => {
+    const seller = Keypair.generate();
+    const poolData = await createTwoSidedPool(program, seller, {
+      owner: wallet.publicKey,
+      cosigner,
+    });
+    const referral = poolData.referral.publicKey;
+
+    await setFeeSchedule(admin, referral, 0, 50);
+    const feeSchedule = await program.account.feeSchedule.fetch(
+      getMMMFeeSchedulePDA(program.programId, referral).key,
+    );
+    assert.equal(feeSchedule.referral.toBase58(), referral.toBase58());
+    assert.equal(feeSchedule.makerFeeBp, 0);
+    assert.equal(feeSchedule.takerFeeBp, 50);
+
+    // within the default of the global config, but above the schedule
+    try {
+      await solFulfillBuy(program, {
+        poolData,
+        owner: wallet.publicKey,
+        seller,
+        cosigner,
+        args: { takerFeeBp: 100 },
+      });
+      assert.fail('fulfill buy above the fee schedule should fail');
+    } catch (e) {
+      expect(e).toBeInstanceOf(anchor.AnchorError);
+      expect((e as anchor.AnchorError).error.errorCode.number).toBe(6022);
+    }
+
+    const initReferralBalance = await connection.getBalance(referral);
+    await solFulfillBuy(program, {
+      poolData,
+      owner: wallet.publicKey,
+      seller,
+      cosigner,
+      args: { takerFeeBp: 50 },
+    });
+    const expectedBuyPrices = getSolFulfillBuyPrices({
+      totalPriceLamports: LAMPORTS_PER_SOL,
+      lpFeeBp: 200,
+      takerFeeBp: 50,
+      metadataRoyaltyBp: 0,
+      buysideCreatorRoyaltyBp: 0,
+      makerFeeBp: 0,
+    });
+    assert.equal(
+      await connection.getBalance(referral),
+      initReferralBalance + expectedBuyPrices.takerFeePaid.toNumber(),
+    );
+  });
+
+  it('rejects invalid fee schedules', async () => {
+    const referral = PublicKey.unique();
+
+    // outside of the max_referral_fee_bp of the global config
+    const invalidFees = [
+      { makerFeeBp: 0, takerFeeBp: 501 },
+      { makerFeeBp: -501, takerFeeBp: 0 },
+      { makerFeeBp: 0, takerFeeBp: -1 },
+    ];
+    for (const { makerFeeBp, takerFeeBp } of invalidFees) {
+      try {
+        await setFeeSchedule(admin, referral, makerFeeBp, takerFeeBp);
+        assert.fail('invalid fee schedule should fail');
+      } catch (e) {
+        expect(e).toBeInstanceOf(anchor.AnchorError);
+        expect((e as anchor.AnchorError).error.errorCode.number).toBe(6022);
+      }
+    }
+  });
+
+  it('only the admin can set fee schedules', async () => {
+    try {
+      await setFeeSchedule(wallet.payer, PublicKey.unique(), 0, 50);
+      assert.fail('fee schedule by another signer should fail');
+    } catch (e) {
+      expect(e).toBeInstanceOf(anchor.AnchorError);
+      expect((e as anchor.AnchorError).error.errorCode.number).toBe(6049);
+    }
+  });
+});
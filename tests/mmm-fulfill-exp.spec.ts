@@ -21,12 +21,16 @@ import {
   IDL,
   MMMProgramID,
   getSolFulfillBuyPrices,
+  getMMMGlobalConfigPDA,
+  getMMMFeeSchedulePDA,
+  getMMMReferralRegistryPDA,
 } from '../sdk/src';
 import {
   airdrop,
   assertIsBetween,
   assertTx,
   createPoolWithExampleDepositsUmi,
+  getRoyaltySchedulePDAForMint,
   getSellStatePDARent,
   getTokenAccount2022,
   getTokenAccountRent,
@@ -145,6 +149,20 @@ describe('mmm-fulfill-exp', () => {
             tokenProgram: tokenProgramId,
             associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
             rent: SYSVAR_RENT_PUBKEY,
+            globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+            treasury: buyer.publicKey,
+            feeSchedule: getMMMFeeSchedulePDA(
+              program.programId,
+              poolData.referral.publicKey,
+            ).key,
+            royaltySchedule: await getRoyaltySchedulePDAForMint(
+              program,
+              poolData.nft.mintAddress,
+            ),
+            referralRegistry: getMMMReferralRegistryPDA(
+              program.programId,
+              poolData.referral.publicKey,
+            ).key,
           })
           .transaction();
 
@@ -251,11 +269,18 @@ describe('mmm-fulfill-exp', () => {
             referral: poolData.referral.publicKey,
             cosignerAnnotation: new Array(32).fill(0).map((_, index) => index),
             buysideCreatorRoyaltyBp: 0,
+            maxTraitMultiplierBp: null,
+            traitPremiumRoot: null,
+            transferHookProgramId: null,
+            compoundLpFee: null,
+            dynamicLpFeeMinBp: null,
+            dynamicLpFeeMaxBp: null,
           })
           .accountsStrict({
             owner: wallet.publicKey,
             cosigner: cosigner.publicKey,
             pool: poolData.poolKey,
+            globalConfig: getMMMGlobalConfigPDA(program.programId).key,
           })
           .signers([cosigner])
           .rpc();
@@ -297,6 +322,20 @@ describe('mmm-fulfill-exp', () => {
             tokenProgram: tokenProgramId,
             associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
             rent: SYSVAR_RENT_PUBKEY,
+            globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+            treasury: buyer.publicKey,
+            feeSchedule: getMMMFeeSchedulePDA(
+              program.programId,
+              poolData.referral.publicKey,
+            ).key,
+            royaltySchedule: await getRoyaltySchedulePDAForMint(
+              program,
+              poolData.sft.mintAddress,
+            ),
+            referralRegistry: getMMMReferralRegistryPDA(
+              program.programId,
+              poolData.referral.publicKey,
+            ).key,
           })
           .transaction();
 
@@ -426,6 +465,20 @@ describe('mmm-fulfill-exp', () => {
             tokenProgram: tokenProgramId,
             associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
             rent: SYSVAR_RENT_PUBKEY,
+            globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+            treasury: buyer.publicKey,
+            feeSchedule: getMMMFeeSchedulePDA(
+              program.programId,
+              poolData.referral.publicKey,
+            ).key,
+            royaltySchedule: await getRoyaltySchedulePDAForMint(
+              program,
+              poolData.sft.mintAddress,
+            ),
+            referralRegistry: getMMMReferralRegistryPDA(
+              program.programId,
+              poolData.referral.publicKey,
+            ).key,
           })
           .transaction();
 
@@ -535,6 +588,20 @@ describe('mmm-fulfill-exp', () => {
             tokenProgram: tokenProgramId,
             associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
             rent: SYSVAR_RENT_PUBKEY,
+            globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+            treasury: buyer.publicKey,
+            feeSchedule: getMMMFeeSchedulePDA(
+              program.programId,
+              poolData.referral.publicKey,
+            ).key,
+            royaltySchedule: await getRoyaltySchedulePDAForMint(
+              program,
+              poolData.sft.mintAddress,
+            ),
+            referralRegistry: getMMMReferralRegistryPDA(
+              program.programId,
+              poolData.referral.publicKey,
+            ).key,
           })
           .transaction();
 
@@ -683,6 +750,8 @@ describe('mmm-fulfill-exp', () => {
             allowlistAux: '',
             takerFeeBp: 400,
             makerFeeBp: 100,
+            traitPremium: null,
+            authorizationData: null,
           })
           .accountsStrict({
             payer: seller.publicKey,
@@ -703,6 +772,20 @@ describe('mmm-fulfill-exp', () => {
             tokenProgram: tokenProgramId,
             associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
             rent: SYSVAR_RENT_PUBKEY,
+            globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+            treasury: seller.publicKey,
+            feeSchedule: getMMMFeeSchedulePDA(
+              program.programId,
+              poolData.referral.publicKey,
+            ).key,
+            royaltySchedule: await getRoyaltySchedulePDAForMint(
+              program,
+              poolData.extraSft.mintAddress,
+            ),
+            referralRegistry: getMMMReferralRegistryPDA(
+              program.programId,
+              poolData.referral.publicKey,
+            ).key,
           })
           .transaction();
 
@@ -823,11 +906,18 @@ describe('mmm-fulfill-exp', () => {
             referral: poolData.referral.publicKey,
             cosignerAnnotation: new Array(32).fill(0).map((_, index) => index),
             buysideCreatorRoyaltyBp: 0,
+            maxTraitMultiplierBp: null,
+            traitPremiumRoot: null,
+            transferHookProgramId: null,
+            compoundLpFee: null,
+            dynamicLpFeeMinBp: null,
+            dynamicLpFeeMaxBp: null,
           })
           .accountsStrict({
             owner: wallet.publicKey,
             cosigner: cosigner.publicKey,
             pool: poolData.poolKey,
+            globalConfig: getMMMGlobalConfigPDA(program.programId).key,
           })
           .signers([cosigner])
           .rpc();
@@ -848,6 +938,8 @@ describe('mmm-fulfill-exp', () => {
             allowlistAux: '',
             makerFeeBp: 150,
             takerFeeBp: 0,
+            traitPremium: null,
+            authorizationData: null,
           })
           .accountsStrict({
             payer: seller.publicKey,
@@ -868,6 +960,20 @@ describe('mmm-fulfill-exp', () => {
             tokenProgram: tokenProgramId,
             associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
             rent: SYSVAR_RENT_PUBKEY,
+            globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+            treasury: seller.publicKey,
+            feeSchedule: getMMMFeeSchedulePDA(
+              program.programId,
+              poolData.referral.publicKey,
+            ).key,
+            royaltySchedule: await getRoyaltySchedulePDAForMint(
+              program,
+              poolData.extraNft.mintAddress,
+            ),
+            referralRegistry: getMMMReferralRegistryPDA(
+              program.programId,
+              poolData.referral.publicKey,
+            ).key,
           })
           .transaction();
 
@@ -1038,6 +1144,8 @@ describe('mmm-fulfill-exp', () => {
             allowlistAux: null,
             takerFeeBp: 100,
             makerFeeBp: 0,
+            traitPremium: null,
+            authorizationData: null,
           })
           .accountsStrict({
             payer: seller.publicKey,
@@ -1058,6 +1166,20 @@ describe('mmm-fulfill-exp', () => {
             associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
             rent: SYSVAR_RENT_PUBKEY,
             sellState: extraNftSellState,
+            globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+            treasury: seller.publicKey,
+            feeSchedule: getMMMFeeSchedulePDA(
+              program.programId,
+              poolData.referral.publicKey,
+            ).key,
+            royaltySchedule: await getRoyaltySchedulePDAForMint(
+              program,
+              poolData.extraNft.mintAddress,
+            ),
+            referralRegistry: getMMMReferralRegistryPDA(
+              program.programId,
+              poolData.referral.publicKey,
+            ).key,
           })
           .transaction();
 
@@ -1159,6 +1281,20 @@ describe('mmm-fulfill-exp', () => {
             tokenProgram: tokenProgramId,
             associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
             rent: SYSVAR_RENT_PUBKEY,
+            globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+            treasury: buyer.publicKey,
+            feeSchedule: getMMMFeeSchedulePDA(
+              program.programId,
+              poolData.referral.publicKey,
+            ).key,
+            royaltySchedule: await getRoyaltySchedulePDAForMint(
+              program,
+              poolData.nft.mintAddress,
+            ),
+            referralRegistry: getMMMReferralRegistryPDA(
+              program.programId,
+              poolData.referral.publicKey,
+            ).key,
           })
           .transaction();
 
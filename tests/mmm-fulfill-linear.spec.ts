@@ -20,6 +20,9 @@ import {
   IDL,
   MMMProgramID,
   getSolFulfillBuyPrices,
+  getMMMGlobalConfigPDA,
+  getMMMFeeSchedulePDA,
+  getMMMReferralRegistryPDA,
 } from '../sdk/src';
 import {
   airdrop,
@@ -28,6 +31,7 @@ import {
   assertTx,
   createPoolWithExampleDepositsUmi,
   getMetadataURI,
+  getRoyaltySchedulePDAForMint,
   getSellStatePDARent,
   getTokenAccount2022,
   getTokenAccountRent,
@@ -133,6 +137,20 @@ describe('mmm-fulfill-linear', () => {
               tokenProgram: tokenProgramId,
               associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
               rent: SYSVAR_RENT_PUBKEY,
+              globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+              treasury: buyer.publicKey,
+              feeSchedule: getMMMFeeSchedulePDA(
+                program.programId,
+                poolData.referral.publicKey,
+              ).key,
+              royaltySchedule: await getRoyaltySchedulePDAForMint(
+                program,
+                poolData.nft.mintAddress,
+              ),
+              referralRegistry: getMMMReferralRegistryPDA(
+                program.programId,
+                poolData.referral.publicKey,
+              ).key,
             })
             .transaction();
 
@@ -247,11 +265,18 @@ describe('mmm-fulfill-linear', () => {
                 .fill(0)
                 .map((_, index) => index),
               buysideCreatorRoyaltyBp: 0,
+              maxTraitMultiplierBp: null,
+              traitPremiumRoot: null,
+              transferHookProgramId: null,
+              compoundLpFee: null,
+              dynamicLpFeeMinBp: null,
+              dynamicLpFeeMaxBp: null,
             })
             .accountsStrict({
               owner: wallet.publicKey,
               cosigner: cosigner.publicKey,
               pool: poolData.poolKey,
+              globalConfig: getMMMGlobalConfigPDA(program.programId).key,
             })
             .signers([cosigner])
             .rpc();
@@ -292,6 +317,20 @@ describe('mmm-fulfill-linear', () => {
               tokenProgram: tokenProgramId,
               associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
               rent: SYSVAR_RENT_PUBKEY,
+              globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+              treasury: buyer.publicKey,
+              feeSchedule: getMMMFeeSchedulePDA(
+                program.programId,
+                poolData.referral.publicKey,
+              ).key,
+              royaltySchedule: await getRoyaltySchedulePDAForMint(
+                program,
+                poolData.sft.mintAddress,
+              ),
+              referralRegistry: getMMMReferralRegistryPDA(
+                program.programId,
+                poolData.referral.publicKey,
+              ).key,
             })
             .transaction();
 
@@ -419,6 +458,20 @@ describe('mmm-fulfill-linear', () => {
               tokenProgram: tokenProgramId,
               associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
               rent: SYSVAR_RENT_PUBKEY,
+              globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+              treasury: buyer.publicKey,
+              feeSchedule: getMMMFeeSchedulePDA(
+                program.programId,
+                poolData.referral.publicKey,
+              ).key,
+              royaltySchedule: await getRoyaltySchedulePDAForMint(
+                program,
+                poolData.sft.mintAddress,
+              ),
+              referralRegistry: getMMMReferralRegistryPDA(
+                program.programId,
+                poolData.referral.publicKey,
+              ).key,
             })
             .transaction();
 
@@ -549,6 +602,8 @@ describe('mmm-fulfill-linear', () => {
               allowlistAux: '',
               takerFeeBp: 400,
               makerFeeBp: -100,
+              traitPremium: null,
+              authorizationData: null,
             })
             .accountsStrict({
               payer: seller.publicKey,
@@ -569,6 +624,20 @@ describe('mmm-fulfill-linear', () => {
               tokenProgram: tokenProgramId,
               associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
               rent: SYSVAR_RENT_PUBKEY,
+              globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+              treasury: seller.publicKey,
+              feeSchedule: getMMMFeeSchedulePDA(
+                program.programId,
+                poolData.referral.publicKey,
+              ).key,
+              royaltySchedule: await getRoyaltySchedulePDAForMint(
+                program,
+                poolData.extraSft.mintAddress,
+              ),
+              referralRegistry: getMMMReferralRegistryPDA(
+                program.programId,
+                poolData.referral.publicKey,
+              ).key,
             })
             .transaction();
 
@@ -684,11 +753,18 @@ describe('mmm-fulfill-linear', () => {
                 .fill(0)
                 .map((_, index) => index),
               buysideCreatorRoyaltyBp: 0,
+              maxTraitMultiplierBp: null,
+              traitPremiumRoot: null,
+              transferHookProgramId: null,
+              compoundLpFee: null,
+              dynamicLpFeeMinBp: null,
+              dynamicLpFeeMaxBp: null,
             })
             .accountsStrict({
               owner: wallet.publicKey,
               cosigner: cosigner.publicKey,
               pool: poolData.poolKey,
+              globalConfig: getMMMGlobalConfigPDA(program.programId).key,
             })
             .signers([cosigner])
             .rpc();
@@ -706,6 +782,8 @@ describe('mmm-fulfill-linear', () => {
               allowlistAux: '',
               makerFeeBp: 150,
               takerFeeBp: 0,
+              traitPremium: null,
+              authorizationData: null,
             })
             .accountsStrict({
               payer: seller.publicKey,
@@ -726,6 +804,20 @@ describe('mmm-fulfill-linear', () => {
               tokenProgram: tokenProgramId,
               associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
               rent: SYSVAR_RENT_PUBKEY,
+              globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+              treasury: seller.publicKey,
+              feeSchedule: getMMMFeeSchedulePDA(
+                program.programId,
+                poolData.referral.publicKey,
+              ).key,
+              royaltySchedule: await getRoyaltySchedulePDAForMint(
+                program,
+                poolData.extraNft.mintAddress,
+              ),
+              referralRegistry: getMMMReferralRegistryPDA(
+                program.programId,
+                poolData.referral.publicKey,
+              ).key,
             })
             .transaction();
 
@@ -895,6 +987,8 @@ describe('mmm-fulfill-linear', () => {
               allowlistAux: null,
               takerFeeBp: 100,
               makerFeeBp: 0,
+              traitPremium: null,
+              authorizationData: null,
             })
             .accountsStrict({
               payer: seller.publicKey,
@@ -915,6 +1009,20 @@ describe('mmm-fulfill-linear', () => {
               associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
               rent: SYSVAR_RENT_PUBKEY,
               sellState: extraNftSellState,
+              globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+              treasury: seller.publicKey,
+              feeSchedule: getMMMFeeSchedulePDA(
+                program.programId,
+                poolData.referral.publicKey,
+              ).key,
+              royaltySchedule: await getRoyaltySchedulePDAForMint(
+                program,
+                poolData.extraNft.mintAddress,
+              ),
+              referralRegistry: getMMMReferralRegistryPDA(
+                program.programId,
+                poolData.referral.publicKey,
+              ).key,
             })
             .transaction();
 
@@ -1021,6 +1129,20 @@ describe('mmm-fulfill-linear', () => {
               tokenProgram: tokenProgramId,
               associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
               rent: SYSVAR_RENT_PUBKEY,
+              globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+              treasury: buyer.publicKey,
+              feeSchedule: getMMMFeeSchedulePDA(
+                program.programId,
+                poolData.referral.publicKey,
+              ).key,
+              royaltySchedule: await getRoyaltySchedulePDAForMint(
+                program,
+                poolData.nft.mintAddress,
+              ),
+              referralRegistry: getMMMReferralRegistryPDA(
+                program.programId,
+                poolData.referral.publicKey,
+              ).key,
             })
             .transaction();
 
@@ -1125,7 +1247,7 @@ describe('mmm-fulfill-linear', () => {
             extraSftMintAddress,
           );
 
-          const fulfillBuyCall = (allowlistAux: string) => {
+          const fulfillBuyCall = async (allowlistAux: string) => {
             return program.methods
               .solFulfillBuy({
                 assetAmount: new anchor.BN(3),
@@ -1135,6 +1257,8 @@ describe('mmm-fulfill-linear', () => {
                 allowlistAux: allowlistAux,
                 takerFeeBp: 400,
                 makerFeeBp: 100,
+                traitPremium: null,
+                authorizationData: null,
               })
               .accountsStrict({
                 payer: seller.publicKey,
@@ -1155,6 +1279,20 @@ describe('mmm-fulfill-linear', () => {
                 tokenProgram: tokenProgramId,
                 associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
                 rent: SYSVAR_RENT_PUBKEY,
+                globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+                treasury: seller.publicKey,
+                feeSchedule: getMMMFeeSchedulePDA(
+                  program.programId,
+                  poolData.referral.publicKey,
+                ).key,
+                royaltySchedule: await getRoyaltySchedulePDAForMint(
+                  program,
+                  poolData.extraSft.mintAddress,
+                ),
+                referralRegistry: getMMMReferralRegistryPDA(
+                  program.programId,
+                  poolData.referral.publicKey,
+                ).key,
               })
               .transaction();
           };
@@ -23,6 +23,9 @@ import {
   MMMProgramID,
   Mmm,
   getM2BuyerSharedEscrow,
+  getMMMFeeSchedulePDA,
+  getMMMGlobalConfigPDA,
+  getMMMReferralRegistryPDA,
   getMMMSellStatePDA,
   getSolFulfillBuyPrices,
   getTokenRecordPDA,
@@ -36,6 +39,7 @@ import {
   createPoolWithExampleDeposits,
   createPoolWithExampleMip1Deposits,
   getMetaplexInstance,
+  getRoyaltySchedulePDAForMint,
   getSellStatePDARent,
   getTokenAccountRent,
   sendAndAssertTx,
@@ -117,6 +121,8 @@ describe('shared-escrow mmm-fulfill-linear', () => {
           allowlistAux: '',
           takerFeeBp: 400,
           makerFeeBp: -100,
+          traitPremium: null,
+          authorizationData: null,
         })
         .accountsStrict({
           payer: seller.publicKey,
@@ -140,6 +146,20 @@ describe('shared-escrow mmm-fulfill-linear', () => {
           tokenProgram: TOKEN_PROGRAM_ID,
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
           rent: SYSVAR_RENT_PUBKEY,
+          globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+          treasury: seller.publicKey,
+          feeSchedule: getMMMFeeSchedulePDA(
+            program.programId,
+            poolData.referral.publicKey,
+          ).key,
+          royaltySchedule: await getRoyaltySchedulePDAForMint(
+            program,
+            poolData.extraSft.mintAddress,
+          ),
+          referralRegistry: getMMMReferralRegistryPDA(
+            program.programId,
+            poolData.referral.publicKey,
+          ).key,
         })
         .remainingAccounts([
           {
@@ -314,6 +334,8 @@ describe('shared-escrow mmm-fulfill-linear', () => {
         allowlistAux: null,
         makerFeeBp: 350,
         takerFeeBp: 50,
+        traitPremium: null,
+        authorizationData: null,
       })
       .accountsStrict({
         payer: seller.publicKey,
@@ -344,6 +366,20 @@ describe('shared-escrow mmm-fulfill-linear', () => {
         ).key,
         authorizationRules: defaultRules,
         ...DEFAULT_ACCOUNTS,
+        globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+        treasury: seller.publicKey,
+        feeSchedule: getMMMFeeSchedulePDA(
+          program.programId,
+          poolData.referral.publicKey,
+        ).key,
+        royaltySchedule: await getRoyaltySchedulePDAForMint(
+          program,
+          poolData.extraNft.mintAddress,
+        ),
+        referralRegistry: getMMMReferralRegistryPDA(
+          program.programId,
+          poolData.referral.publicKey,
+        ).key,
       })
       .preInstructions([
         ComputeBudgetProgram.setComputeUnitLimit({ units: MIP1_COMPUTE_UNITS }),
@@ -519,6 +555,8 @@ describe('shared-escrow mmm-fulfill-linear', () => {
         allowlistAux: null,
         makerFeeBp: 0,
         takerFeeBp: 50,
+        traitPremium: null,
+        authorizationData: null,
       })
       .accountsStrict({
         payer: seller.publicKey,
@@ -549,6 +587,20 @@ describe('shared-escrow mmm-fulfill-linear', () => {
         ).key,
         authorizationRules: defaultRules,
         ...DEFAULT_ACCOUNTS,
+        globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+        treasury: seller.publicKey,
+        feeSchedule: getMMMFeeSchedulePDA(
+          program.programId,
+          poolData.referral.publicKey,
+        ).key,
+        royaltySchedule: await getRoyaltySchedulePDAForMint(
+          program,
+          poolData.extraNft.mintAddress,
+        ),
+        referralRegistry: getMMMReferralRegistryPDA(
+          program.programId,
+          poolData.referral.publicKey,
+        ).key,
       })
       .preInstructions([
         ComputeBudgetProgram.setComputeUnitLimit({ units: MIP1_COMPUTE_UNITS }),
@@ -0,0 +1,184 @@
+import * as anchor from '@project-serum/anchor';
+import { Keypair, PublicKey } from '@solana/web3.js';
+import { assert } from 'chai';
+import { getMMMGlobalConfigPDA, IDL, Mmm, MMMProgramID } from '../sdk/src';
+import { airdrop, initGlobalConfigIfNeeded, updateGlobalConfig } from './utils';
+
+describe('mmm-global-config', () => {
+  const { connection } = anchor.AnchorProvider.env();
+  const wallet = new anchor.Wallet(Keypair.generate());
+  const provider = new anchor.AnchorProvider(connection, wallet, {
+    commitment: 'processed',
+  });
+  const program = new anchor.Program(
+    IDL,
+    MMMProgramID,
+    provider,
+  ) as anchor.Program<Mmm>;
+  const { key: globalConfig } = getMMMGlobalConfigPDA(program.programId);
+  let admin: Keypair;
+
+  beforeAll(async () => {
+    await airdrop(connection, wallet.publicKey, 50);
+    admin = await initGlobalConfigIfNeeded(program);
+  });
+
+  beforeEach(async () => {
+    await airdrop(connection, wallet.publicKey, 50);
+  });
+
+  const acceptGlobalConfigAdmin = async (pendingAdmin: Keypair) => {
+    await program.methods
+      .acceptGlobalConfigAdmin()
+      .accountsStrict({ pendingAdmin: pendingAdmin.publicKey, globalConfig })
+      .signers([pendingAdmin])
+      .rpc();
+  };
+
+  it('admin can update the limits', async () => {
+    const initGlobalConfig = await program.account.globalConfig.fetch(
+      globalConfig,
+    );
+
+    await updateGlobalConfig(program, admin, {
+      maxLpFeeBp: 1500,
+      defaultTakerFeeBp: 50,
+    });
+    const globalConfigAccountInfo = await program.account.globalConfig.fetch(
+      globalConfig,
+    );
+    assert.equal(globalConfigAccountInfo.maxLpFeeBp, 1500);
+    assert.equal(globalConfigAccountInfo.defaultTakerFeeBp, 50);
+    // fields that are not set are left unchanged
+    assert.equal(
+      globalConfigAccountInfo.treasury.toBase58(),
+      initGlobalConfig.treasury.toBase58(),
+    );
+    assert.equal(
+      globalConfigAccountInfo.maxMetadataCreatorRoyaltyBp,
+      initGlobalConfig.maxMetadataCreatorRoyaltyBp,
+    );
+
+    // the global config is shared with the other specs
+    await updateGlobalConfig(program, admin, {
+      maxLpFeeBp: initGlobalConfig.maxLpFeeBp,
+      defaultTakerFeeBp: initGlobalConfig.defaultTakerFeeBp,
+    });
+  });
+
+  it('admin can update the transfer hook program allow list', async () => {
+    const initGlobalConfig = await program.account.globalConfig.fetch(
+      globalConfig,
+    );
+    const transferHookProgramId = Keypair.generate().publicKey;
+
+    await updateGlobalConfig(program, admin, {
+      transferHookProgramAllowList: [
+        initGlobalConfig.transferHookProgramAllowList[0],
+        transferHookProgramId,
+        PublicKey.default,
+      ],
+    });
+    const globalConfigAccountInfo = await program.account.globalConfig.fetch(
+      globalConfig,
+    );
+    assert.deepEqual(
+      globalConfigAccountInfo.transferHookProgramAllowList.map((p) =>
+        p.toBase58(),
+      ),
+      [
+        initGlobalConfig.transferHookProgramAllowList[0].toBase58(),
+        transferHookProgramId.toBase58(),
+        PublicKey.default.toBase58(),
+      ],
+    );
+
+    // the global config is shared with the other specs
+    await updateGlobalConfig(program, admin, {
+      transferHookProgramAllowList:
+        initGlobalConfig.transferHookProgramAllowList,
+    });
+  });
+
+  it('rejects invalid global configs', async () => {
+    const invalidUpdates = [
+      { protocolFeeBp: 1001 }, // above MAX_PROTOCOL_FEE_BP
+      { maxLpFeeBp: 10001 },
+      { maxReferralFeeBp: -1 },
+      { minSolEscrowBalanceBp: 10001 },
+    ];
+    for (const args of invalidUpdates) {
+      try {
+        await updateGlobalConfig(program, admin, args);
+        assert.fail('invalid global config should fail');
+      } catch (e) {
+        expect(e).toBeInstanceOf(anchor.AnchorError);
+        expect((e as anchor.AnchorError).error.errorCode.number).toBe(6050);
+      }
+    }
+  });
+
+  it('only the admin can update the global config', async () => {
+    try {
+      await updateGlobalConfig(program, wallet.payer, { protocolFeeBp: 0 });
+      assert.fail('update by another signer should fail');
+    } catch (e) {
+      expect(e).toBeInstanceOf(anchor.AnchorError);
+      expect((e as anchor.AnchorError).error.errorCode.number).toBe(6049);
+    }
+  });
+
+  it('hands the admin over once the pending admin accepts', async () => {
+    const newAdmin = Keypair.generate();
+
+    await updateGlobalConfig(program, admin, {
+      pendingAdmin: newAdmin.publicKey,
+    });
+    let globalConfigAccountInfo = await program.account.globalConfig.fetch(
+      globalConfig,
+    );
+    assert.equal(
+      globalConfigAccountInfo.admin.toBase58(),
+      admin.publicKey.toBase58(),
+    );
+    assert.equal(
+      globalConfigAccountInfo.pendingAdmin.toBase58(),
+      newAdmin.publicKey.toBase58(),
+    );
+
+    try {
+      await acceptGlobalConfigAdmin(wallet.payer);
+      assert.fail('accept by another signer should fail');
+    } catch (e) {
+      expect(e).toBeInstanceOf(anchor.AnchorError);
+      expect((e as anchor.AnchorError).error.errorCode.number).toBe(6049);
+    }
+
+    await acceptGlobalConfigAdmin(newAdmin);
+    globalConfigAccountInfo = await program.account.globalConfig.fetch(
+      globalConfig,
+    );
+    assert.equal(
+      globalConfigAccountInfo.admin.toBase58(),
+      newAdmin.publicKey.toBase58(),
+    );
+    assert.equal(
+      globalConfigAccountInfo.pendingAdmin.toBase58(),
+      PublicKey.default.toBase58(),
+    );
+
+    // the previous admin lost its rights, hand the admin back for the other
+    // specs
+    try {
+      await updateGlobalConfig(program, admin, { protocolFeeBp: 0 });
+      assert.fail('update by the previous admin should fail');
+    } catch (e) {
+      expect(e).toBeInstanceOf(anchor.AnchorError);
+      expect((e as anchor.AnchorError).error.errorCode.number).toBe(6049);
+    }
+    await updateGlobalConfig(program, newAdmin, {
+      pendingAdmin: admin.publicKey,
+    });
+    await acceptGlobalConfigAdmin(admin);
+  });
+});
@@ -1,6 +1,7 @@
 import * as anchor from '@project-serum/anchor';
 import {
   getAssociatedTokenAddress,
+  getAssociatedTokenAddressSync,
   ASSOCIATED_TOKEN_PROGRAM_ID,
   TOKEN_PROGRAM_ID,
   TOKEN_2022_PROGRAM_ID,
@@ -16,13 +17,18 @@ import {
 } from '@solana/web3.js';
 import { assert } from 'chai';
 import {
+  createSignerFromKeypair,
   generateSigner,
   publicKey,
   some,
   Program as UmiProgram,
 } from '@metaplex-foundation/umi';
 import { createUmi } from '@metaplex-foundation/umi-bundle-tests';
-import { mplTokenMetadata } from '@metaplex-foundation/mpl-token-metadata';
+import {
+  mplTokenMetadata,
+  TokenStandard,
+  transferV1,
+} from '@metaplex-foundation/mpl-token-metadata';
 import {
   Mmm,
   AllowlistKind,
@@ -33,6 +39,9 @@ import {
   CurveKind,
   getTokenRecordPDA,
   getSolFulfillBuyPrices,
+  getMMMGlobalConfigPDA,
+  getMMMFeeSchedulePDA,
+  getMMMReferralRegistryPDA,
 } from '../sdk/src';
 import {
   airdrop,
@@ -41,6 +50,7 @@ import {
   createPoolWithExampleMip1Deposits,
   createProgrammableNftUmi,
   getEmptyAllowLists,
+  getRoyaltySchedulePDAForMint,
   getSellStatePDARent,
   getTokenAccount2022,
   getTokenAccountRent,
@@ -52,6 +62,7 @@ import {
 import { PROGRAM_ID as AUTH_RULES_PROGRAM_ID } from '@metaplex-foundation/mpl-token-auth-rules';
 import { MPL_TOKEN_METADATA_PROGRAM_ID as TOKEN_METADATA_PROGRAM_ID } from '@metaplex-foundation/mpl-token-metadata';
 import {
+  fromWeb3JsKeypair,
   fromWeb3JsPublicKey,
   toWeb3JsPublicKey,
 } from '@metaplex-foundation/umi-web3js-adapters';
@@ -161,6 +172,8 @@ describe('mmm-mip1', () => {
           .mip1DepositSell({
             assetAmount: new anchor.BN(1),
             allowlistAux: null,
+            priceOverride: null,
+            authorizationData: null,
           })
           .accountsStrict({
             owner: wallet.publicKey,
@@ -184,6 +197,7 @@ describe('mmm-mip1', () => {
             ).key,
 
             ...DEFAULT_ACCOUNTS,
+            globalConfig: getMMMGlobalConfigPDA(program.programId).key,
           })
           .preInstructions([
             ComputeBudgetProgram.setComputeUnitLimit({
@@ -223,6 +237,7 @@ describe('mmm-mip1', () => {
           .mip1WithdrawSell({
             assetAmount: new anchor.BN(1),
             allowlistAux: null,
+            authorizationData: null,
           })
           .accountsStrict({
             owner: wallet.publicKey,
@@ -272,6 +287,332 @@ describe('mmm-mip1', () => {
         assert.equal(await connection.getBalance(poolData.poolKey), 0);
       });
 
+      // lists a new pNFT of the wallet in a new pool, without depositing it
+      const createPoolWithMip1Listing = async () => {
+        const umi = (await createUmi('http://127.0.0.1:8899')).use(
+          mplTokenMetadata(),
+        );
+        const token2022Program: UmiProgram = {
+          name: 'splToken2022',
+          publicKey: publicKey('TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb'),
+          getErrorFromCode: () => null,
+          getErrorFromName: () => null,
+          isOnCluster: () => true,
+        };
+        umi.programs.add(token2022Program);
+        DEFAULT_ACCOUNTS.tokenProgram = tokenProgramId;
+
+        const creator = generateSigner(umi);
+        const nftRes = await createProgrammableNftUmi(
+          umi,
+          creator,
+          wallet.publicKey,
+          tokenProgramId,
+          some(fromWeb3JsPublicKey(defaultRules)),
+        );
+        const poolData = await createPool(program, {
+          owner: wallet.publicKey,
+          cosigner,
+          allowlists: [
+            {
+              value: toWeb3JsPublicKey(creator.publicKey),
+              kind: AllowlistKind.fvca,
+            },
+            ...getEmptyAllowLists(5),
+          ],
+        });
+        const { key: sellState } = getMMMSellStatePDA(
+          program.programId,
+          poolData.poolKey,
+          nftRes.mintAddress,
+        );
+
+        await program.methods
+          .mip1ListSell({
+            assetAmount: new anchor.BN(1),
+            allowlistAux: null,
+            priceOverride: null,
+            authorizationData: null,
+          })
+          .accountsStrict({
+            owner: wallet.publicKey,
+            cosigner: cosigner.publicKey,
+            pool: poolData.poolKey,
+            assetMetadata: nftRes.metadataAddress,
+            assetMint: nftRes.mintAddress,
+            assetMasterEdition: nftRes.masterEditionAddress,
+            assetTokenAccount: nftRes.tokenAddress,
+            sellState,
+            allowlistAuxAccount: SystemProgram.programId,
+            ownerTokenRecord: getTokenRecordPDA(
+              nftRes.mintAddress,
+              nftRes.tokenAddress,
+            ).key,
+            authorizationRules: defaultRules,
+            tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+            authorizationRulesProgram: AUTH_RULES_PROGRAM_ID,
+            instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+            globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: tokenProgramId,
+          })
+          .preInstructions([
+            ComputeBudgetProgram.setComputeUnitLimit({
+              units: MIP1_COMPUTE_UNITS,
+            }),
+          ])
+          .signers([cosigner])
+          .rpc({ skipPreflight: true });
+
+        return {
+          umi,
+          nftRes,
+          poolData,
+          sellState,
+          creator: toWeb3JsPublicKey(creator.publicKey),
+        };
+      };
+
+      it('can list and delist mip1 NFTs - happy path', async () => {
+        const { nftRes, poolData, sellState } =
+          await createPoolWithMip1Listing();
+
+        // the pNFT stays in the wallet with the pool as the transfer delegate
+        const [ownerTokenAccount, sellStateAccountInfo, poolAccountInfo] =
+          await Promise.all([
+            getTokenAccount2022(
+              connection,
+              nftRes.tokenAddress,
+              tokenProgramId,
+            ),
+            program.account.sellState.fetch(sellState),
+            program.account.pool.fetch(poolData.poolKey),
+          ]);
+        assert.equal(Number(ownerTokenAccount.amount), 1);
+        assert.equal(
+          ownerTokenAccount.owner.toBase58(),
+          wallet.publicKey.toBase58(),
+        );
+        assert.equal(
+          ownerTokenAccount.delegate?.toBase58(),
+          poolData.poolKey.toBase58(),
+        );
+        assert.equal(
+          sellStateAccountInfo.listingTokenAccount.toBase58(),
+          nftRes.tokenAddress.toBase58(),
+        );
+        assert.equal(sellStateAccountInfo.assetAmount.toNumber(), 1);
+        assert.equal(poolAccountInfo.sellsideAssetAmount.toNumber(), 1);
+
+        await program.methods
+          .mip1DelistSell()
+          .accountsStrict({
+            owner: wallet.publicKey,
+            cosigner: cosigner.publicKey,
+            pool: poolData.poolKey,
+            assetMint: nftRes.mintAddress,
+            assetMasterEdition: nftRes.masterEditionAddress,
+            assetMetadata: nftRes.metadataAddress,
+            assetTokenAccount: nftRes.tokenAddress,
+            sellState,
+            ownerTokenRecord: getTokenRecordPDA(
+              nftRes.mintAddress,
+              nftRes.tokenAddress,
+            ).key,
+            authorizationRules: defaultRules,
+            tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+            authorizationRulesProgram: AUTH_RULES_PROGRAM_ID,
+            instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: tokenProgramId,
+          })
+          .preInstructions([
+            ComputeBudgetProgram.setComputeUnitLimit({
+              units: MIP1_COMPUTE_UNITS,
+            }),
+          ])
+          .signers([cosigner])
+          .rpc({ skipPreflight: true });
+
+        const delistedTokenAccount = await getTokenAccount2022(
+          connection,
+          nftRes.tokenAddress,
+          tokenProgramId,
+        );
+        assert.equal(Number(delistedTokenAccount.amount), 1);
+        assert.isNull(delistedTokenAccount.delegate);
+        assert.equal(await connection.getBalance(sellState), 0);
+        // pool should now be closed as a consequence of having no NFTs and no payment
+        assert.equal(await connection.getBalance(poolData.poolKey), 0);
+      });
+
+      it('anyone can release a stale mip1 listing', async () => {
+        const cranker = Keypair.generate();
+        const [{ umi, nftRes, poolData, sellState }] = await Promise.all([
+          createPoolWithMip1Listing(),
+          airdrop(connection, cranker.publicKey, 1),
+        ]);
+        // neither the owner nor the cosigner sign the release
+        const crankerProgram = new anchor.Program(
+          IDL,
+          MMMProgramID,
+          new anchor.AnchorProvider(connection, new anchor.Wallet(cranker), {
+            commitment: 'processed',
+          }),
+        ) as anchor.Program<Mmm>;
+        const releaseStaleListing = () =>
+          crankerProgram.methods
+            .mip1ReleaseStaleListing()
+            .accountsStrict({
+              owner: wallet.publicKey,
+              pool: poolData.poolKey,
+              assetMint: nftRes.mintAddress,
+              assetTokenAccount: nftRes.tokenAddress,
+              sellState,
+              tokenProgram: tokenProgramId,
+            })
+            .rpc();
+
+        // the listing is still available, only the owner can delist it
+        try {
+          await releaseStaleListing();
+          assert.fail('release of an available listing should fail');
+        } catch (e) {
+          expect(e).toBeInstanceOf(anchor.AnchorError);
+          expect((e as anchor.AnchorError).error.errorCode.number).toBe(6024);
+        }
+
+        // the owner moves the pNFT out of the listed token account, which
+        // also clears the transfer delegate of the pool
+        const ownerSigner = createSignerFromKeypair(
+          umi,
+          fromWeb3JsKeypair(wallet.payer),
+        );
+        const recipient = Keypair.generate().publicKey;
+        await transferV1(umi, {
+          mint: fromWeb3JsPublicKey(nftRes.mintAddress),
+          authority: ownerSigner,
+          payer: ownerSigner,
+          tokenOwner: fromWeb3JsPublicKey(wallet.publicKey),
+          token: fromWeb3JsPublicKey(nftRes.tokenAddress),
+          destinationOwner: fromWeb3JsPublicKey(recipient),
+          destinationToken: fromWeb3JsPublicKey(
+            getAssociatedTokenAddressSync(
+              nftRes.mintAddress,
+              recipient,
+              true,
+              tokenProgramId,
+            ),
+          ),
+          tokenStandard: TokenStandard.ProgrammableNonFungible,
+          authorizationRules: fromWeb3JsPublicKey(defaultRules),
+          splTokenProgram: fromWeb3JsPublicKey(tokenProgramId),
+        }).sendAndConfirm(umi, { send: { skipPreflight: true } });
+
+        const [initOwnerBalance, sellStateRent] = await Promise.all([
+          connection.getBalance(wallet.publicKey),
+          connection.getBalance(sellState),
+        ]);
+        await releaseStaleListing();
+
+        const [ownerBalance, poolAccountInfo] = await Promise.all([
+          connection.getBalance(wallet.publicKey),
+          program.account.pool.fetch(poolData.poolKey),
+        ]);
+        assert.equal(await connection.getBalance(sellState), 0);
+        assert.equal(ownerBalance, initOwnerBalance + sellStateRent);
+        assert.equal(poolAccountInfo.sellsideAssetAmount.toNumber(), 0);
+      });
+
+      it('can fulfill sell a listed mip1 NFT - happy path', async () => {
+        const buyer = Keypair.generate();
+        const [{ nftRes, poolData, sellState, creator }] = await Promise.all([
+          createPoolWithMip1Listing(),
+          airdrop(connection, buyer.publicKey, 10),
+        ]);
+        const buyerNftAtaAddress = await getAssociatedTokenAddress(
+          nftRes.mintAddress,
+          buyer.publicKey,
+          true,
+          tokenProgramId,
+        );
+
+        await program.methods
+          .solMip1FulfillSell({
+            assetAmount: new anchor.BN(1),
+            maxPaymentAmount: new anchor.BN(2 * LAMPORTS_PER_SOL),
+            allowlistAux: null,
+            makerFeeBp: 0,
+            takerFeeBp: 100,
+            authorizationData: null,
+          })
+          .accountsStrict({
+            payer: buyer.publicKey,
+            owner: wallet.publicKey,
+            cosigner: cosigner.publicKey,
+            referral: poolData.referral.publicKey,
+            pool: poolData.poolKey,
+            buysideSolEscrowAccount: poolData.poolPaymentEscrow,
+            assetMetadata: nftRes.metadataAddress,
+            assetMint: nftRes.mintAddress,
+            assetMasterEdition: nftRes.masterEditionAddress,
+            // the listed token account of the owner instead of the pool escrow
+            sellsideEscrowTokenAccount: nftRes.tokenAddress,
+            payerAssetAccount: buyerNftAtaAddress,
+            allowlistAuxAccount: SystemProgram.programId,
+            sellState,
+            authorizationRules: defaultRules,
+            ownerTokenRecord: getTokenRecordPDA(
+              nftRes.mintAddress,
+              nftRes.tokenAddress,
+            ).key,
+            destinationTokenRecord: getTokenRecordPDA(
+              nftRes.mintAddress,
+              buyerNftAtaAddress,
+            ).key,
+            ...DEFAULT_ACCOUNTS,
+            globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+            treasury: buyer.publicKey,
+            feeSchedule: getMMMFeeSchedulePDA(
+              program.programId,
+              poolData.referral.publicKey,
+            ).key,
+            royaltySchedule: await getRoyaltySchedulePDAForMint(
+              program,
+              nftRes.mintAddress,
+            ),
+            referralRegistry: getMMMReferralRegistryPDA(
+              program.programId,
+              poolData.referral.publicKey,
+            ).key,
+          })
+          .preInstructions([
+            ComputeBudgetProgram.setComputeUnitLimit({
+              units: MIP1_COMPUTE_UNITS,
+            }),
+          ])
+          .remainingAccounts([
+            {
+              pubkey: creator,
+              isSigner: false,
+              isWritable: true,
+            },
+          ])
+          .signers([buyer, cosigner])
+          .rpc({ skipPreflight: true });
+
+        const [buyerAta, ownerTokenAccount] = await Promise.all([
+          getTokenAccount2022(connection, buyerNftAtaAddress, tokenProgramId),
+          getTokenAccount2022(connection, nftRes.tokenAddress, tokenProgramId),
+        ]);
+        assert.equal(Number(buyerAta.amount), 1);
+        assert.equal(buyerAta.owner.toBase58(), buyer.publicKey.toBase58());
+        assert.equal(Number(ownerTokenAccount.amount), 0);
+        assert.equal(await connection.getBalance(sellState), 0);
+        // pool should now be closed as a consequence of having no NFTs and no payment
+        assert.equal(await connection.getBalance(poolData.poolKey), 0);
+      });
+
       it(`can fulfill sell - happy path: ${tokenProgramId}`, async () => {
         DEFAULT_ACCOUNTS.tokenProgram = tokenProgramId;
 
@@ -343,6 +684,7 @@ describe('mmm-mip1', () => {
             allowlistAux: null,
             makerFeeBp: 150,
             takerFeeBp: 200,
+            authorizationData: null,
           })
           .accountsStrict({
             payer: buyer.publicKey,
@@ -368,6 +710,20 @@ describe('mmm-mip1', () => {
               buyerNftAtaAddress,
             ).key,
             ...DEFAULT_ACCOUNTS,
+            globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+            treasury: buyer.publicKey,
+            feeSchedule: getMMMFeeSchedulePDA(
+              program.programId,
+              poolData.referral.publicKey,
+            ).key,
+            royaltySchedule: await getRoyaltySchedulePDAForMint(
+              program,
+              poolData.nft.mintAddress,
+            ),
+            referralRegistry: getMMMReferralRegistryPDA(
+              program.programId,
+              poolData.referral.publicKey,
+            ).key,
           })
           .preInstructions([
             ComputeBudgetProgram.setComputeUnitLimit({
@@ -509,6 +865,8 @@ describe('mmm-mip1', () => {
             allowlistAux: null,
             makerFeeBp: 350,
             takerFeeBp: 50,
+            traitPremium: null,
+            authorizationData: null,
           })
           .accountsStrict({
             payer: seller.publicKey,
@@ -539,6 +897,20 @@ describe('mmm-mip1', () => {
             ).key,
             authorizationRules: defaultRules,
             ...DEFAULT_ACCOUNTS,
+            globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+            treasury: seller.publicKey,
+            feeSchedule: getMMMFeeSchedulePDA(
+              program.programId,
+              poolData.referral.publicKey,
+            ).key,
+            royaltySchedule: await getRoyaltySchedulePDAForMint(
+              program,
+              poolData.extraNft.mintAddress,
+            ),
+            referralRegistry: getMMMReferralRegistryPDA(
+              program.programId,
+              poolData.referral.publicKey,
+            ).key,
           })
           .preInstructions([
             ComputeBudgetProgram.setComputeUnitLimit({
@@ -717,6 +1089,8 @@ describe('mmm-mip1', () => {
               allowlistAux: null,
               makerFeeBp: 250,
               takerFeeBp: 30,
+              traitPremium: null,
+              authorizationData: null,
             })
             .accountsStrict({
               payer: seller.publicKey,
@@ -747,6 +1121,20 @@ describe('mmm-mip1', () => {
               ).key,
               authorizationRules: defaultRules,
               ...DEFAULT_ACCOUNTS,
+              globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+              treasury: seller.publicKey,
+              feeSchedule: getMMMFeeSchedulePDA(
+                program.programId,
+                poolData.referral.publicKey,
+              ).key,
+              royaltySchedule: await getRoyaltySchedulePDAForMint(
+                program,
+                poolData.extraNft.mintAddress,
+              ),
+              referralRegistry: getMMMReferralRegistryPDA(
+                program.programId,
+                poolData.referral.publicKey,
+              ).key,
             })
             .preInstructions([
               ComputeBudgetProgram.setComputeUnitLimit({
@@ -866,6 +1254,7 @@ describe('mmm-mip1', () => {
               allowlistAux: null,
               makerFeeBp: 100,
               takerFeeBp: 350,
+              authorizationData: null,
             })
             .accountsStrict({
               payer: buyer.publicKey,
@@ -891,6 +1280,20 @@ describe('mmm-mip1', () => {
               ).key,
               authorizationRules: defaultRules,
               ...DEFAULT_ACCOUNTS,
+              globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+              treasury: buyer.publicKey,
+              feeSchedule: getMMMFeeSchedulePDA(
+                program.programId,
+                poolData.referral.publicKey,
+              ).key,
+              royaltySchedule: await getRoyaltySchedulePDAForMint(
+                program,
+                poolData.nft.mintAddress,
+              ),
+              referralRegistry: getMMMReferralRegistryPDA(
+                program.programId,
+                poolData.referral.publicKey,
+              ).key,
             })
             .preInstructions([
               ComputeBudgetProgram.setComputeUnitLimit({
@@ -1062,6 +1465,8 @@ describe('mmm-mip1', () => {
               allowlistAux: null,
               makerFeeBp: -30,
               takerFeeBp: 50,
+              traitPremium: null,
+              authorizationData: null,
             })
             .accountsStrict({
               payer: seller.publicKey,
@@ -1092,6 +1497,20 @@ describe('mmm-mip1', () => {
               ).key,
               authorizationRules: defaultRules,
               ...DEFAULT_ACCOUNTS,
+              globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+              treasury: seller.publicKey,
+              feeSchedule: getMMMFeeSchedulePDA(
+                program.programId,
+                poolData.referral.publicKey,
+              ).key,
+              royaltySchedule: await getRoyaltySchedulePDAForMint(
+                program,
+                poolData.extraNft.mintAddress,
+              ),
+              referralRegistry: getMMMReferralRegistryPDA(
+                program.programId,
+                poolData.referral.publicKey,
+              ).key,
             })
             .preInstructions([
               ComputeBudgetProgram.setComputeUnitLimit({
@@ -1211,6 +1630,7 @@ describe('mmm-mip1', () => {
               allowlistAux: null,
               makerFeeBp: -350,
               takerFeeBp: 350,
+              authorizationData: null,
             })
             .accountsStrict({
               payer: buyer.publicKey,
@@ -1236,6 +1656,20 @@ describe('mmm-mip1', () => {
               ).key,
               authorizationRules: defaultRules,
               ...DEFAULT_ACCOUNTS,
+              globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+              treasury: buyer.publicKey,
+              feeSchedule: getMMMFeeSchedulePDA(
+                program.programId,
+                poolData.referral.publicKey,
+              ).key,
+              royaltySchedule: await getRoyaltySchedulePDAForMint(
+                program,
+                poolData.nft.mintAddress,
+              ),
+              referralRegistry: getMMMReferralRegistryPDA(
+                program.programId,
+                poolData.referral.publicKey,
+              ).key,
             })
             .preInstructions([
               ComputeBudgetProgram.setComputeUnitLimit({
@@ -13,6 +13,10 @@ import {
   MMMProgramID,
   Mmm,
   getMMMBuysideSolEscrowPDA,
+  getMMMFeeSchedulePDA,
+  getMMMGlobalConfigPDA,
+  getMMMReferralRegistryPDA,
+  getMMMRoyaltySchedulePDA,
   getMMMSellStatePDA,
   getSolFulfillBuyPrices,
 } from '../sdk/src';
@@ -102,6 +106,7 @@ describe('mmm-mpl-core', () => {
       .mplCoreDepositSell({
         allowlistAux: null,
         compressionProof: null,
+        priceOverride: null,
       })
       .accountsStrict({
         owner: wallet.publicKey,
@@ -112,6 +117,7 @@ describe('mmm-mpl-core', () => {
         collection: collection ? collection.publicKey : SystemProgram.programId,
         systemProgram: SystemProgram.programId,
         assetProgram: MPL_CORE_PROGRAM_ID,
+        globalConfig: getMMMGlobalConfigPDA(program.programId).key,
       })
       .preInstructions([
         ComputeBudgetProgram.setComputeUnitLimit({
@@ -179,6 +185,7 @@ describe('mmm-mpl-core', () => {
         .mplCoreDepositSell({
           allowlistAux: null,
           compressionProof: null,
+          priceOverride: null,
         })
         .accountsStrict({
           owner: wallet.publicKey,
@@ -189,6 +196,7 @@ describe('mmm-mpl-core', () => {
           collection: collection!.publicKey,
           systemProgram: SystemProgram.programId,
           assetProgram: MPL_CORE_PROGRAM_ID,
+          globalConfig: getMMMGlobalConfigPDA(program.programId).key,
         })
         .preInstructions([
           ComputeBudgetProgram.setComputeUnitLimit({
@@ -256,6 +264,90 @@ describe('mmm-mpl-core', () => {
       );
     });
 
+    it("can't release a listing that is still available", async () => {
+      const { asset, collection } = await createTestMplCoreAsset(
+        publicKey(wallet.publicKey),
+        {
+          collectionConfig: {}, // use default collection config
+        },
+      );
+      const poolData = await createPool(program, {
+        owner: wallet.publicKey,
+        cosigner,
+        allowlists: [
+          {
+            value: toWeb3JsPublicKey(collection!.publicKey),
+            kind: AllowlistKind.mpl_core_collection,
+          },
+          ...getEmptyAllowLists(5),
+        ],
+      });
+      const { key: sellState } = getMMMSellStatePDA(
+        program.programId,
+        poolData.poolKey,
+        toWeb3JsPublicKey(asset.publicKey),
+      );
+
+      await program.methods
+        .mplCoreListSell({
+          allowlistAux: null,
+          compressionProof: null,
+          priceOverride: null,
+        })
+        .accountsStrict({
+          owner: wallet.publicKey,
+          cosigner: cosigner.publicKey,
+          pool: poolData.poolKey,
+          asset: asset.publicKey,
+          sellState,
+          collection: collection!.publicKey,
+          globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+          systemProgram: SystemProgram.programId,
+          assetProgram: MPL_CORE_PROGRAM_ID,
+        })
+        .preInstructions([
+          ComputeBudgetProgram.setComputeUnitLimit({
+            units: 1_000_000,
+          }),
+        ])
+        .signers([cosigner])
+        .rpc({ skipPreflight: true });
+
+      // the release is permissionless, but only for stale listings
+      const cranker = Keypair.generate();
+      await airdrop(connection, cranker.publicKey, 1);
+      const crankerProgram = new anchor.Program(
+        IDL,
+        MMMProgramID,
+        new anchor.AnchorProvider(connection, new anchor.Wallet(cranker), {
+          commitment: 'processed',
+        }),
+      ) as anchor.Program<Mmm>;
+      try {
+        await crankerProgram.methods
+          .mplCoreReleaseStaleListing()
+          .accountsStrict({
+            owner: wallet.publicKey,
+            pool: poolData.poolKey,
+            asset: asset.publicKey,
+            sellState,
+            assetProgram: MPL_CORE_PROGRAM_ID,
+          })
+          .rpc();
+        assert.fail('release of an available listing should fail');
+      } catch (e) {
+        expect(e).toBeInstanceOf(anchor.AnchorError);
+        expect((e as anchor.AnchorError).error.errorCode.number).toBe(6024);
+      }
+
+      const [sellStateAccount, poolAccount] = await Promise.all([
+        program.account.sellState.fetch(sellState),
+        program.account.pool.fetch(poolData.poolKey),
+      ]);
+      assert.equal(sellStateAccount.assetAmount.toNumber(), 1);
+      assert.equal(poolAccount.sellsideAssetAmount.toNumber(), 1);
+    });
+
     it("can't deposit sell - asset from other collection", async () => {
       const { asset, collection } = await createTestMplCoreAsset(
         publicKey(wallet.publicKey),
@@ -287,6 +379,7 @@ describe('mmm-mpl-core', () => {
           .mplCoreDepositSell({
             allowlistAux: null,
             compressionProof: null,
+            priceOverride: null,
           })
           .accountsStrict({
             owner: wallet.publicKey,
@@ -297,6 +390,7 @@ describe('mmm-mpl-core', () => {
             collection: collection!.publicKey,
             systemProgram: SystemProgram.programId,
             assetProgram: MPL_CORE_PROGRAM_ID,
+            globalConfig: getMMMGlobalConfigPDA(program.programId).key,
           })
           .preInstructions([
             ComputeBudgetProgram.setComputeUnitLimit({
@@ -347,6 +441,7 @@ describe('mmm-mpl-core', () => {
           .mplCoreDepositSell({
             allowlistAux: null,
             compressionProof: null,
+            priceOverride: null,
           })
           .accountsStrict({
             owner: wallet.publicKey,
@@ -357,6 +452,7 @@ describe('mmm-mpl-core', () => {
             collection: SystemProgram.programId, // no collection
             systemProgram: SystemProgram.programId,
             assetProgram: MPL_CORE_PROGRAM_ID,
+            globalConfig: getMMMGlobalConfigPDA(program.programId).key,
           })
           .preInstructions([
             ComputeBudgetProgram.setComputeUnitLimit({
@@ -447,6 +543,7 @@ describe('mmm-mpl-core', () => {
           .mplCoreDepositSell({
             allowlistAux: null,
             compressionProof: null,
+            priceOverride: null,
           })
           .accountsStrict({
             owner: wallet.publicKey,
@@ -457,6 +554,7 @@ describe('mmm-mpl-core', () => {
             collection: collection!.publicKey,
             systemProgram: SystemProgram.programId,
             assetProgram: MPL_CORE_PROGRAM_ID,
+            globalConfig: getMMMGlobalConfigPDA(program.programId).key,
           })
           .preInstructions([
             ComputeBudgetProgram.setComputeUnitLimit({
@@ -581,6 +679,20 @@ describe('mmm-mpl-core', () => {
           buysideSolEscrowAccount,
           systemProgram: SystemProgram.programId,
           assetProgram: MPL_CORE_PROGRAM_ID,
+          globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+          treasury: buyer.publicKey,
+          feeSchedule: getMMMFeeSchedulePDA(
+            program.programId,
+            poolData.referral.publicKey,
+          ).key,
+          royaltySchedule: getMMMRoyaltySchedulePDA(
+            program.programId,
+            collection!.publicKey,
+          ).key,
+          referralRegistry: getMMMReferralRegistryPDA(
+            program.programId,
+            poolData.referral.publicKey,
+          ).key,
         })
         .remainingAccounts([
           {
@@ -751,6 +863,20 @@ describe('mmm-mpl-core', () => {
           buysideSolEscrowAccount,
           systemProgram: SystemProgram.programId,
           assetProgram: MPL_CORE_PROGRAM_ID,
+          globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+          treasury: buyer.publicKey,
+          feeSchedule: getMMMFeeSchedulePDA(
+            program.programId,
+            poolData.referral.publicKey,
+          ).key,
+          royaltySchedule: getMMMRoyaltySchedulePDA(
+            program.programId,
+            collection!.publicKey,
+          ).key,
+          referralRegistry: getMMMReferralRegistryPDA(
+            program.programId,
+            poolData.referral.publicKey,
+          ).key,
         })
         .remainingAccounts([
           {
@@ -923,6 +1049,20 @@ describe('mmm-mpl-core', () => {
           buysideSolEscrowAccount,
           systemProgram: SystemProgram.programId,
           assetProgram: MPL_CORE_PROGRAM_ID,
+          globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+          treasury: buyer.publicKey,
+          feeSchedule: getMMMFeeSchedulePDA(
+            program.programId,
+            poolData.referral.publicKey,
+          ).key,
+          royaltySchedule: getMMMRoyaltySchedulePDA(
+            program.programId,
+            collection!.publicKey,
+          ).key,
+          referralRegistry: getMMMReferralRegistryPDA(
+            program.programId,
+            poolData.referral.publicKey,
+          ).key,
         })
         .remainingAccounts([
           {
@@ -1067,6 +1207,20 @@ describe('mmm-mpl-core', () => {
           buysideSolEscrowAccount,
           systemProgram: SystemProgram.programId,
           assetProgram: MPL_CORE_PROGRAM_ID,
+          globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+          treasury: buyer.publicKey,
+          feeSchedule: getMMMFeeSchedulePDA(
+            program.programId,
+            poolData.referral.publicKey,
+          ).key,
+          royaltySchedule: getMMMRoyaltySchedulePDA(
+            program.programId,
+            collection!.publicKey,
+          ).key,
+          referralRegistry: getMMMReferralRegistryPDA(
+            program.programId,
+            poolData.referral.publicKey,
+          ).key,
         })
         .preInstructions([
           ComputeBudgetProgram.setComputeUnitLimit({
@@ -1209,6 +1363,7 @@ describe('mmm-mpl-core', () => {
           takerFeeBp: 100,
           makerFeeBp: 0,
           compressionProof: null,
+          traitPremium: null,
         })
         .accountsStrict({
           payer: seller.publicKey,
@@ -1222,6 +1377,20 @@ describe('mmm-mpl-core', () => {
           collection: collection!.publicKey,
           systemProgram: SystemProgram.programId,
           assetProgram: MPL_CORE_PROGRAM_ID,
+          globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+          treasury: seller.publicKey,
+          feeSchedule: getMMMFeeSchedulePDA(
+            program.programId,
+            poolData.referral.publicKey,
+          ).key,
+          royaltySchedule: getMMMRoyaltySchedulePDA(
+            program.programId,
+            collection!.publicKey,
+          ).key,
+          referralRegistry: getMMMReferralRegistryPDA(
+            program.programId,
+            poolData.referral.publicKey,
+          ).key,
         })
         .remainingAccounts([
           {
@@ -1396,6 +1565,7 @@ describe('mmm-mpl-core', () => {
           takerFeeBp: 100,
           makerFeeBp: 0,
           compressionProof: null,
+          traitPremium: null,
         })
         .accountsStrict({
           payer: seller.publicKey,
@@ -1409,6 +1579,20 @@ describe('mmm-mpl-core', () => {
           sellState,
           systemProgram: SystemProgram.programId,
           assetProgram: MPL_CORE_PROGRAM_ID,
+          globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+          treasury: seller.publicKey,
+          feeSchedule: getMMMFeeSchedulePDA(
+            program.programId,
+            poolData.referral.publicKey,
+          ).key,
+          royaltySchedule: getMMMRoyaltySchedulePDA(
+            program.programId,
+            collection!.publicKey,
+          ).key,
+          referralRegistry: getMMMReferralRegistryPDA(
+            program.programId,
+            poolData.referral.publicKey,
+          ).key,
         })
         .remainingAccounts([
           {
@@ -1570,6 +1754,7 @@ describe('mmm-mpl-core', () => {
             takerFeeBp: 100,
             makerFeeBp: 0,
             compressionProof: null,
+            traitPremium: null,
           })
           .accountsStrict({
             payer: seller.publicKey,
@@ -1583,6 +1768,20 @@ describe('mmm-mpl-core', () => {
             collection: collection!.publicKey,
             systemProgram: SystemProgram.programId,
             assetProgram: MPL_CORE_PROGRAM_ID,
+            globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+            treasury: seller.publicKey,
+            feeSchedule: getMMMFeeSchedulePDA(
+              program.programId,
+              poolData.referral.publicKey,
+            ).key,
+            royaltySchedule: getMMMRoyaltySchedulePDA(
+              program.programId,
+              collection!.publicKey,
+            ).key,
+            referralRegistry: getMMMReferralRegistryPDA(
+              program.programId,
+              poolData.referral.publicKey,
+            ).key,
           })
           .remainingAccounts([
             {
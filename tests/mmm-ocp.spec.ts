@@ -23,6 +23,9 @@ import {
   MMMProgramID,
   CurveKind,
   getSolFulfillBuyPrices,
+  getMMMGlobalConfigPDA,
+  getMMMFeeSchedulePDA,
+  getMMMReferralRegistryPDA,
 } from '../sdk/src';
 import {
   airdrop,
@@ -31,6 +34,7 @@ import {
   createPoolWithExampleOcpDeposits,
   createTestMintAndTokenOCP,
   getEmptyAllowLists,
+  getRoyaltySchedulePDAForMint,
   getSellStatePDARent,
   getTokenAccountRent,
   OCP_COMPUTE_UNITS,
@@ -106,6 +110,8 @@ describe.skip('mmm-ocp', () => {
       .ocpDepositSell({
         assetAmount: new anchor.BN(1),
         allowlistAux: null,
+        priceOverride: null,
+        authorizationData: null,
       })
       .accountsStrict({
         owner: wallet.publicKey,
@@ -122,6 +128,7 @@ describe.skip('mmm-ocp', () => {
         ocpPolicy: nftRes.ocpPolicy,
         ocpFreezeAuthority: nftRes.ocpFreezeAuth,
         ...DEFAULT_ACCOUNTS,
+        globalConfig: getMMMGlobalConfigPDA(program.programId).key,
       })
       .preInstructions([
         ComputeBudgetProgram.setComputeUnitLimit({ units: OCP_COMPUTE_UNITS }),
@@ -153,7 +160,10 @@ describe.skip('mmm-ocp', () => {
       poolData.poolKey,
     );
     await program.methods
-      .ocpWithdrawSell({ assetAmount: new anchor.BN(1), allowlistAux: null })
+      .ocpWithdrawSell({
+        assetAmount: new anchor.BN(1),
+        allowlistAux: null,
+      })
       .accountsStrict({
         owner: wallet.publicKey,
         cosigner: cosigner.publicKey,
@@ -268,6 +278,20 @@ describe.skip('mmm-ocp', () => {
         ocpPolicy: poolData.nft.ocpPolicy,
         ocpFreezeAuthority: poolData.nft.ocpFreezeAuth,
         ...DEFAULT_ACCOUNTS,
+        globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+        treasury: buyer.publicKey,
+        feeSchedule: getMMMFeeSchedulePDA(
+          program.programId,
+          poolData.referral.publicKey,
+        ).key,
+        royaltySchedule: await getRoyaltySchedulePDAForMint(
+          program,
+          poolData.nft.mintAddress,
+        ),
+        referralRegistry: getMMMReferralRegistryPDA(
+          program.programId,
+          poolData.referral.publicKey,
+        ).key,
       })
       .preInstructions([
         ComputeBudgetProgram.setComputeUnitLimit({
@@ -400,6 +424,8 @@ describe.skip('mmm-ocp', () => {
         allowlistAux: null,
         makerFeeBp: 350,
         takerFeeBp: 50,
+        traitPremium: null,
+        authorizationData: null,
       })
       .accountsStrict({
         payer: seller.publicKey,
@@ -419,6 +445,20 @@ describe.skip('mmm-ocp', () => {
         ocpPolicy: poolData.extraNft.ocpPolicy,
         ocpFreezeAuthority: poolData.extraNft.ocpFreezeAuth,
         ...DEFAULT_ACCOUNTS,
+        globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+        treasury: seller.publicKey,
+        feeSchedule: getMMMFeeSchedulePDA(
+          program.programId,
+          poolData.referral.publicKey,
+        ).key,
+        royaltySchedule: await getRoyaltySchedulePDAForMint(
+          program,
+          poolData.extraNft.mintAddress,
+        ),
+        referralRegistry: getMMMReferralRegistryPDA(
+          program.programId,
+          poolData.referral.publicKey,
+        ).key,
       })
       .preInstructions([
         ComputeBudgetProgram.setComputeUnitLimit({ units: OCP_COMPUTE_UNITS }),
@@ -575,6 +615,8 @@ describe.skip('mmm-ocp', () => {
           allowlistAux: null,
           makerFeeBp: -30,
           takerFeeBp: 30,
+          traitPremium: null,
+          authorizationData: null,
         })
         .accountsStrict({
           payer: seller.publicKey,
@@ -594,6 +636,20 @@ describe.skip('mmm-ocp', () => {
           ocpPolicy: poolData.extraNft.ocpPolicy,
           ocpFreezeAuthority: poolData.extraNft.ocpFreezeAuth,
           ...DEFAULT_ACCOUNTS,
+          globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+          treasury: seller.publicKey,
+          feeSchedule: getMMMFeeSchedulePDA(
+            program.programId,
+            poolData.referral.publicKey,
+          ).key,
+          royaltySchedule: await getRoyaltySchedulePDAForMint(
+            program,
+            poolData.extraNft.mintAddress,
+          ),
+          referralRegistry: getMMMReferralRegistryPDA(
+            program.programId,
+            poolData.referral.publicKey,
+          ).key,
         })
         .preInstructions([
           ComputeBudgetProgram.setComputeUnitLimit({
@@ -720,6 +776,20 @@ describe.skip('mmm-ocp', () => {
           ocpPolicy: poolData.nft.ocpPolicy,
           ocpFreezeAuthority: poolData.nft.ocpFreezeAuth,
           ...DEFAULT_ACCOUNTS,
+          globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+          treasury: buyer.publicKey,
+          feeSchedule: getMMMFeeSchedulePDA(
+            program.programId,
+            poolData.referral.publicKey,
+          ).key,
+          royaltySchedule: await getRoyaltySchedulePDAForMint(
+            program,
+            poolData.nft.mintAddress,
+          ),
+          referralRegistry: getMMMReferralRegistryPDA(
+            program.programId,
+            poolData.referral.publicKey,
+          ).key,
         })
         .preInstructions([
           ComputeBudgetProgram.setComputeUnitLimit({
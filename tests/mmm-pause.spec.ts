@@ -0,0 +1,154 @@
+import * as anchor from '@project-serum/anchor';
+import { Keypair, LAMPORTS_PER_SOL, SystemProgram } from '@solana/web3.js';
+import { assert } from 'chai';
+import { getMMMGlobalConfigPDA, IDL, Mmm, MMMProgramID } from '../sdk/src';
+import {
+  airdrop,
+  createTwoSidedPool,
+  initGlobalConfigIfNeeded,
+  PoolData,
+  solFulfillBuy,
+} from './utils';
+
+// PAUSE_FLAG_* of the program
+const PAUSE_FLAG_ALL = 1 << 0;
+const PAUSE_FLAG_EXT = 1 << 4;
+
+describe('mmm-pause', () => {
+  const { connection } = anchor.AnchorProvider.env();
+  const wallet = new anchor.Wallet(Keypair.generate());
+  const provider = new anchor.AnchorProvider(connection, wallet, {
+    commitment: 'processed',
+  });
+  const program = new anchor.Program(
+    IDL,
+    MMMProgramID,
+    provider,
+  ) as anchor.Program<Mmm>;
+  const cosigner = Keypair.generate();
+  const { key: globalConfig } = getMMMGlobalConfigPDA(program.programId);
+  let admin: Keypair;
+
+  beforeAll(async () => {
+    await airdrop(connection, wallet.publicKey, 50);
+    admin = await initGlobalConfigIfNeeded(program);
+  });
+
+  beforeEach(async () => {
+    await airdrop(connection, wallet.publicKey, 50);
+  });
+
+  // the global config is shared with the other specs
+  afterEach(async () => {
+    await setPauseFlags(admin, 0);
+  });
+
+  const setPauseFlags = async (signer: Keypair, pauseFlags: number) => {
+    await program.methods
+      .setPauseFlags({ pauseFlags })
+      .accountsStrict({ admin: signer.publicKey, globalConfig })
+      .signers([signer])
+      .rpc();
+  };
+
+  const solDepositOrWithdrawBuy = async (
+    poolData: PoolData,
+    method: 'solDepositBuy' | 'solWithdrawBuy',
+  ) => {
+    await program.methods[method]({
+      paymentAmount: new anchor.BN(LAMPORTS_PER_SOL),
+    })
+      .accountsStrict({
+        owner: wallet.publicKey,
+        cosigner: cosigner.publicKey,
+        pool: poolData.poolKey,
+        buysideSolEscrowAccount: poolData.poolPaymentEscrow,
+        globalConfig,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([cosigner])
+      .rpc();
+  };
+
+  it('halts fulfills and deposits but not withdrawals', async () => {
+    const seller = Keypair.generate();
+    const poolData = await createTwoSidedPool(program, seller, {
+      owner: wallet.publicKey,
+      cosigner,
+    });
+
+    await setPauseFlags(admin, PAUSE_FLAG_ALL);
+    const globalConfigAccountInfo = await program.account.globalConfig.fetch(
+      globalConfig,
+    );
+    assert.equal(globalConfigAccountInfo.pauseFlags, PAUSE_FLAG_ALL);
+
+    const pausedCalls = [
+      () => solFulfillBuy(program, {
+        poolData,
+        owner: wallet.publicKey,
+        seller,
+        cosigner,
+      }),
+      () => solDepositOrWithdrawBuy(poolData, 'solDepositBuy'),
+    ];
+    for (const pausedCall of pausedCalls) {
+      try {
+        await pausedCall();
+        assert.fail('paused instruction should fail');
+      } catch (e) {
+        expect(e).toBeInstanceOf(anchor.AnchorError);
+        expect((e as anchor.AnchorError).error.errorCode.number).toBe(6051);
+      }
+    }
+
+    // owners can always exit
+    const initEscrowBalance = await connection.getBalance(
+      poolData.poolPaymentEscrow,
+    );
+    await solDepositOrWithdrawBuy(poolData, 'solWithdrawBuy');
+    assert.equal(
+      await connection.getBalance(poolData.poolPaymentEscrow),
+      initEscrowBalance - LAMPORTS_PER_SOL,
+    );
+
+    // trading resumes without the flag
+    await setPauseFlags(admin, 0);
+    await solFulfillBuy(program, {
+      poolData,
+      owner: wallet.publicKey,
+      seller,
+      cosigner,
+    });
+    const poolAccountInfo = await program.account.pool.fetch(poolData.poolKey);
+    assert.equal(poolAccountInfo.sellsideAssetAmount.toNumber(), 7);
+  });
+
+  it('only halts the paused asset families', async () => {
+    const seller = Keypair.generate();
+    const poolData = await createTwoSidedPool(program, seller, {
+      owner: wallet.publicKey,
+      cosigner,
+    });
+
+    await setPauseFlags(admin, PAUSE_FLAG_EXT);
+    await solFulfillBuy(program, {
+      poolData,
+      owner: wallet.publicKey,
+      seller,
+      cosigner,
+    });
+    const poolAccountInfo = await program.account.pool.fetch(poolData.poolKey);
+    assert.equal(poolAccountInfo.sellsideAssetAmount.toNumber(), 7);
+  });
+
+  it('only the admin can pause', async () => {
+    try {
+      await setPauseFlags(wallet.payer, PAUSE_FLAG_ALL);
+      assert.fail('pause by another signer should fail');
+    } catch (e) {
+      expect(e).toBeInstanceOf(anchor.AnchorError);
+      expect((e as anchor.AnchorError).error.errorCode.number).toBe(6049);
+    }
+  });
+});
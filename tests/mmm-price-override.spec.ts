@@ -0,0 +1,274 @@
+import * as anchor from '@project-serum/anchor';
+import {
+  getAssociatedTokenAddress,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+} from '@solana/spl-token';
+import {
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+} from '@solana/web3.js';
+import { assert } from 'chai';
+import {
+  AllowlistKind,
+  CurveKind,
+  getMMMFeeSchedulePDA,
+  getMMMGlobalConfigPDA,
+  getMMMReferralRegistryPDA,
+  getMMMSellStatePDA,
+  IDL,
+  Mmm,
+  MMMProgramID,
+} from '../sdk/src';
+import {
+  airdrop,
+  createPoolWithExampleDepositsUmi,
+  getRoyaltySchedulePDAForMint,
+  getTokenAccountRent,
+  PoolData,
+} from './utils';
+import { toWeb3JsPublicKey } from '@metaplex-foundation/umi-web3js-adapters';
+
+describe('mmm-price-override', () => {
+  const { connection } = anchor.AnchorProvider.env();
+  const wallet = new anchor.Wallet(Keypair.generate());
+  const provider = new anchor.AnchorProvider(connection, wallet, {
+    commitment: 'processed',
+  });
+  const program = new anchor.Program(
+    IDL,
+    MMMProgramID,
+    provider,
+  ) as anchor.Program<Mmm>;
+  const cosigner = Keypair.generate();
+
+  beforeEach(async () => {
+    await airdrop(connection, wallet.publicKey, 50);
+  });
+
+  const createSellPool = async (buyer: Keypair) => {
+    const [poolData] = await Promise.all([
+      createPoolWithExampleDepositsUmi(
+        program,
+        [AllowlistKind.fvca],
+        {
+          owner: wallet.publicKey,
+          cosigner,
+          curveType: CurveKind.linear,
+          curveDelta: new anchor.BN(LAMPORTS_PER_SOL).div(
+            new anchor.BN(10),
+          ), // 0.1 SOL
+          expiry: new anchor.BN(0),
+          reinvestFulfillBuy: true,
+          reinvestFulfillSell: true,
+        },
+        'sell',
+        TOKEN_PROGRAM_ID,
+        buyer.publicKey,
+      ),
+      airdrop(connection, buyer.publicKey, 10),
+    ]);
+    return poolData;
+  };
+
+  const setPriceOverride = async (
+    poolData: PoolData,
+    mint: PublicKey,
+    kind: number,
+    value: anchor.BN,
+  ) => {
+    await program.methods
+      .setSellStatePriceOverride({ priceOverride: { kind, value } })
+      .accountsStrict({
+        owner: wallet.publicKey,
+        cosigner: cosigner.publicKey,
+        pool: poolData.poolKey,
+        sellState: getMMMSellStatePDA(program.programId, poolData.poolKey, mint)
+          .key,
+      })
+      .signers([cosigner])
+      .rpc();
+  };
+
+  const fulfillSell = async (
+    poolData: PoolData,
+    buyer: Keypair,
+    asset: PoolData['nft'],
+    sellsideEscrowTokenAccount: PublicKey,
+    maxPaymentAmount: anchor.BN,
+  ) => {
+    const mint = toWeb3JsPublicKey(asset.mintAddress);
+    await program.methods
+      .solFulfillSell({
+        assetAmount: new anchor.BN(1),
+        maxPaymentAmount,
+        buysideCreatorRoyaltyBp: 0,
+        allowlistAux: '',
+        takerFeeBp: 100,
+        makerFeeBp: 0,
+      })
+      .accountsStrict({
+        payer: buyer.publicKey,
+        owner: wallet.publicKey,
+        cosigner: cosigner.publicKey,
+        referral: poolData.referral.publicKey,
+        pool: poolData.poolKey,
+        buysideSolEscrowAccount: poolData.poolPaymentEscrow,
+        assetMetadata: asset.metadataAddress,
+        assetMasterEdition: asset.masterEditionAddress,
+        assetMint: mint,
+        sellsideEscrowTokenAccount,
+        payerAssetAccount: await getAssociatedTokenAddress(
+          mint,
+          buyer.publicKey,
+          true,
+          TOKEN_PROGRAM_ID,
+        ),
+        allowlistAuxAccount: SystemProgram.programId,
+        sellState: getMMMSellStatePDA(program.programId, poolData.poolKey, mint)
+          .key,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        rent: SYSVAR_RENT_PUBKEY,
+        globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+        treasury: buyer.publicKey,
+        feeSchedule: getMMMFeeSchedulePDA(
+          program.programId,
+          poolData.referral.publicKey,
+        ).key,
+        royaltySchedule: await getRoyaltySchedulePDAForMint(program, mint),
+        referralRegistry: getMMMReferralRegistryPDA(
+          program.programId,
+          poolData.referral.publicKey,
+        ).key,
+      })
+      .signers([buyer, cosigner])
+      .rpc();
+  };
+
+  it('sells at the absolute override without moving the curve', async () => {
+    const buyer = Keypair.generate();
+    const poolData = await createSellPool(buyer);
+    const nftMint = toWeb3JsPublicKey(poolData.nft.mintAddress);
+
+    await setPriceOverride(
+      poolData,
+      nftMint,
+      1,
+      new anchor.BN(3 * LAMPORTS_PER_SOL),
+    );
+    const sellState = await program.account.sellState.fetch(
+      getMMMSellStatePDA(program.programId, poolData.poolKey, nftMint).key,
+    );
+    assert.equal(sellState.priceOverride.kind, 1);
+    assert.equal(
+      sellState.priceOverride.value.toNumber(),
+      3 * LAMPORTS_PER_SOL,
+    );
+
+    // the curve price of 1.1 SOL is not enough anymore
+    try {
+      await fulfillSell(
+        poolData,
+        buyer,
+        poolData.nft,
+        poolData.poolAtaNft,
+        new anchor.BN(1.2 * LAMPORTS_PER_SOL),
+      );
+      assert.fail('fulfill sell below the override should fail');
+    } catch (e) {
+      expect(e).toBeInstanceOf(anchor.AnchorError);
+      expect((e as anchor.AnchorError).error.errorCode.number).toBe(6009);
+    }
+
+    const initBuyerBalance = await connection.getBalance(buyer.publicKey);
+    const expectedTakerFees = 3 * LAMPORTS_PER_SOL * 0.01;
+    await fulfillSell(
+      poolData,
+      buyer,
+      poolData.nft,
+      poolData.poolAtaNft,
+      new anchor.BN(3 * LAMPORTS_PER_SOL + expectedTakerFees),
+    );
+
+    const tokenAccountRent = await getTokenAccountRent(connection);
+    const [buyerBalance, poolEscrowBalance, poolAccountInfo] =
+      await Promise.all([
+        connection.getBalance(buyer.publicKey),
+        connection.getBalance(poolData.poolPaymentEscrow),
+        program.account.pool.fetch(poolData.poolKey),
+      ]);
+    assert.equal(
+      buyerBalance,
+      initBuyerBalance -
+        3 * LAMPORTS_PER_SOL -
+        expectedTakerFees -
+        tokenAccountRent,
+    );
+    assert.equal(poolEscrowBalance, 3 * LAMPORTS_PER_SOL);
+    assert.equal(poolAccountInfo.spotPrice.toNumber(), 1 * LAMPORTS_PER_SOL);
+    assert.equal(poolAccountInfo.sellsideAssetAmount.toNumber(), 5);
+  });
+
+  it('sells at a premium on top of the curve price', async () => {
+    const buyer = Keypair.generate();
+    const poolData = await createSellPool(buyer);
+    const sftMint = toWeb3JsPublicKey(poolData.sft.mintAddress);
+
+    // 10% premium on top of the 1.1 SOL curve price
+    await setPriceOverride(poolData, sftMint, 2, new anchor.BN(1000));
+
+    const initBuyerBalance = await connection.getBalance(buyer.publicKey);
+    const expectedPrice = 1.21 * LAMPORTS_PER_SOL;
+    const expectedTakerFees = expectedPrice * 0.01;
+    await fulfillSell(
+      poolData,
+      buyer,
+      poolData.sft,
+      poolData.poolAtaSft,
+      new anchor.BN(expectedPrice + expectedTakerFees),
+    );
+
+    const tokenAccountRent = await getTokenAccountRent(connection);
+    const [buyerBalance, poolAccountInfo, sellState] = await Promise.all([
+      connection.getBalance(buyer.publicKey),
+      program.account.pool.fetch(poolData.poolKey),
+      program.account.sellState.fetch(
+        getMMMSellStatePDA(program.programId, poolData.poolKey, sftMint).key,
+      ),
+    ]);
+    assert.equal(
+      buyerBalance,
+      initBuyerBalance - expectedPrice - expectedTakerFees - tokenAccountRent,
+    );
+    // the curve moves as usual and the override stays for the rest of the sft
+    assert.equal(poolAccountInfo.spotPrice.toNumber(), 1.1 * LAMPORTS_PER_SOL);
+    assert.equal(sellState.assetAmount.toNumber(), 4);
+    assert.equal(sellState.priceOverride.kind, 2);
+  });
+
+  it('rejects invalid overrides', async () => {
+    const buyer = Keypair.generate();
+    const poolData = await createSellPool(buyer);
+    const nftMint = toWeb3JsPublicKey(poolData.nft.mintAddress);
+
+    const invalidOverrides = [
+      { kind: 1, value: new anchor.BN(0) }, // absolute price of zero
+      { kind: 0, value: new anchor.BN(1) }, // none with a value
+      { kind: 3, value: new anchor.BN(1) }, // unknown kind
+    ];
+    for (const { kind, value } of invalidOverrides) {
+      try {
+        await setPriceOverride(poolData, nftMint, kind, value);
+        assert.fail('invalid price override should fail');
+      } catch (e) {
+        expect(e).toBeInstanceOf(anchor.AnchorError);
+        expect((e as anchor.AnchorError).error.errorCode.number).toBe(6037);
+      }
+    }
+  });
+});
@@ -0,0 +1,203 @@
+import * as anchor from '@project-serum/anchor';
+import { Keypair, LAMPORTS_PER_SOL } from '@solana/web3.js';
+import { assert } from 'chai';
+import {
+  getMMMGlobalConfigPDA,
+  getSolFulfillBuyPrices,
+  IDL,
+  Mmm,
+  MMMProgramID,
+} from '../sdk/src';
+import {
+  airdrop,
+  createTwoSidedPool,
+  getSellStatePDARent,
+  initGlobalConfigIfNeeded,
+  PoolData,
+  solFulfillBuy,
+  updateGlobalConfig,
+} from './utils';
+
+describe('mmm-protocol-fee', () => {
+  const { connection } = anchor.AnchorProvider.env();
+  const wallet = new anchor.Wallet(Keypair.generate());
+  const provider = new anchor.AnchorProvider(connection, wallet, {
+    commitment: 'processed',
+  });
+  const program = new anchor.Program(
+    IDL,
+    MMMProgramID,
+    provider,
+  ) as anchor.Program<Mmm>;
+  const cosigner = Keypair.generate();
+  const treasury = Keypair.generate();
+  const protocolFeeBp = 100;
+  let admin: Keypair;
+
+  beforeAll(async () => {
+    await airdrop(connection, wallet.publicKey, 50);
+    admin = await initGlobalConfigIfNeeded(program);
+    await updateGlobalConfig(program, admin, {
+      treasury: treasury.publicKey,
+      protocolFeeBp,
+    });
+  });
+
+  // the global config is shared with the other specs
+  afterAll(async () => {
+    await updateGlobalConfig(program, admin, {
+      treasury: admin.publicKey,
+      protocolFeeBp: 0,
+    });
+  });
+
+  beforeEach(async () => {
+    await airdrop(connection, wallet.publicKey, 50);
+  });
+
+  const setProtocolFeeExemption = async (
+    poolData: PoolData,
+    signer: Keypair,
+    exempt: boolean,
+  ) => {
+    await program.methods
+      .setProtocolFeeExemption({ exempt })
+      .accountsStrict({
+        admin: signer.publicKey,
+        globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+        pool: poolData.poolKey,
+      })
+      .signers([signer])
+      .rpc();
+  };
+
+  it('pays the protocol fee to the treasury', async () => {
+    const seller = Keypair.generate();
+    const poolData = await createTwoSidedPool(program, seller, {
+      owner: wallet.publicKey,
+      cosigner,
+    });
+
+    const [initSellerBalance, initTreasuryBalance] = await Promise.all([
+      connection.getBalance(seller.publicKey),
+      connection.getBalance(treasury.publicKey),
+    ]);
+
+    await solFulfillBuy(program, {
+      poolData,
+      owner: wallet.publicKey,
+      seller,
+      cosigner,
+      treasury: treasury.publicKey,
+    });
+
+    const expectedBuyPrices = getSolFulfillBuyPrices({
+      totalPriceLamports: LAMPORTS_PER_SOL,
+      lpFeeBp: 200,
+      takerFeeBp: 100,
+      metadataRoyaltyBp: 0,
+      buysideCreatorRoyaltyBp: 0,
+      makerFeeBp: 0,
+      protocolFeeBp,
+    });
+    const sellStatePDARent = await getSellStatePDARent(connection);
+    const [sellerBalance, treasuryBalance] = await Promise.all([
+      connection.getBalance(seller.publicKey),
+      connection.getBalance(treasury.publicKey),
+    ]);
+    assert.equal(
+      sellerBalance,
+      initSellerBalance +
+        expectedBuyPrices.sellerReceives.toNumber() -
+        sellStatePDARent,
+    );
+    assert.equal(
+      treasuryBalance,
+      initTreasuryBalance + expectedBuyPrices.protocolFeePaid.toNumber(),
+    );
+  });
+
+  it('does not charge the protocol fee to exempt pools', async () => {
+    const seller = Keypair.generate();
+    const poolData = await createTwoSidedPool(program, seller, {
+      owner: wallet.publicKey,
+      cosigner,
+    });
+
+    await setProtocolFeeExemption(poolData, admin, true);
+    const poolAccountInfo = await program.account.pool.fetch(poolData.poolKey);
+    assert.isTrue(poolAccountInfo.protocolFeeExempt);
+
+    const [initSellerBalance, initTreasuryBalance] = await Promise.all([
+      connection.getBalance(seller.publicKey),
+      connection.getBalance(treasury.publicKey),
+    ]);
+
+    await solFulfillBuy(program, {
+      poolData,
+      owner: wallet.publicKey,
+      seller,
+      cosigner,
+      treasury: treasury.publicKey,
+    });
+
+    const expectedBuyPrices = getSolFulfillBuyPrices({
+      totalPriceLamports: LAMPORTS_PER_SOL,
+      lpFeeBp: 200,
+      takerFeeBp: 100,
+      metadataRoyaltyBp: 0,
+      buysideCreatorRoyaltyBp: 0,
+      makerFeeBp: 0,
+    });
+    const sellStatePDARent = await getSellStatePDARent(connection);
+    const [sellerBalance, treasuryBalance] = await Promise.all([
+      connection.getBalance(seller.publicKey),
+      connection.getBalance(treasury.publicKey),
+    ]);
+    assert.equal(
+      sellerBalance,
+      initSellerBalance +
+        expectedBuyPrices.sellerReceives.toNumber() -
+        sellStatePDARent,
+    );
+    assert.equal(treasuryBalance, initTreasuryBalance);
+  });
+
+  it('fails with another treasury', async () => {
+    const seller = Keypair.generate();
+    const poolData = await createTwoSidedPool(program, seller, {
+      owner: wallet.publicKey,
+      cosigner,
+    });
+
+    try {
+      await solFulfillBuy(program, {
+        poolData,
+        owner: wallet.publicKey,
+        seller,
+        cosigner,
+        treasury: seller.publicKey,
+      });
+      assert.fail('fulfill buy with another treasury should fail');
+    } catch (e) {
+      expect(e).toBeInstanceOf(anchor.AnchorError);
+      expect((e as anchor.AnchorError).error.errorCode.number).toBe(6048);
+    }
+  });
+
+  it('only the admin can exempt pools', async () => {
+    const seller = Keypair.generate();
+    const poolData = await createTwoSidedPool(program, seller, {
+      owner: wallet.publicKey,
+      cosigner,
+    });
+
+    try {
+      await setProtocolFeeExemption(poolData, wallet.payer, true);
+      assert.fail('exemption by another signer should fail');
+    } catch (e) {
+      expect(e).toBeInstanceOf(anchor.AnchorError);
+      expect((e as anchor.AnchorError).error.errorCode.number).toBe(6049);
+    }
+  });
+});
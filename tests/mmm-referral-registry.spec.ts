@@ -0,0 +1,180 @@
+import * as anchor from '@project-serum/anchor';
+import {
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+} from '@solana/web3.js';
+import { assert } from 'chai';
+import {
+  getMMMReferralRegistryPDA,
+  getSolFulfillBuyPrices,
+  IDL,
+  Mmm,
+  MMMProgramID,
+} from '../sdk/src';
+import { airdrop, createTwoSidedPool, solFulfillBuy } from './utils';
+
+type ReferralRecipients = Parameters<
+  anchor.Program<Mmm>['methods']['setReferralRegistry']
+>[0]['recipients'];
+
+describe('mmm-referral-registry', () => {
+  const { connection } = anchor.AnchorProvider.env();
+  const wallet = new anchor.Wallet(Keypair.generate());
+  const provider = new anchor.AnchorProvider(connection, wallet, {
+    commitment: 'processed',
+  });
+  const program = new anchor.Program(
+    IDL,
+    MMMProgramID,
+    provider,
+  ) as anchor.Program<Mmm>;
+  const cosigner = Keypair.generate();
+
+  beforeEach(async () => {
+    await airdrop(connection, wallet.publicKey, 50);
+  });
+
+  const setReferralRegistry = async (
+    referrer: Keypair,
+    recipients: ReferralRecipients,
+  ) => {
+    await program.methods
+      .setReferralRegistry({ recipients })
+      .accountsStrict({
+        referrer: referrer.publicKey,
+        referralRegistry: getMMMReferralRegistryPDA(
+          program.programId,
+          referrer.publicKey,
+        ).key,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([referrer])
+      .rpc();
+  };
+
+  it('splits the referral fee between the recipients', async () => {
+    const seller = Keypair.generate();
+    const poolData = await createTwoSidedPool(program, seller, {
+      owner: wallet.publicKey,
+      cosigner,
+    });
+    // the referral of the pool is the referrer, it pays for the registry
+    await airdrop(connection, poolData.referral.publicKey, 1);
+    const referral = poolData.referral.publicKey;
+    const aggregator = Keypair.generate().publicKey;
+
+    await setReferralRegistry(poolData.referral, [
+      { address: aggregator, shareBp: 7000 },
+      { address: referral, shareBp: 3000 },
+    ]);
+    const referralRegistry = await program.account.referralRegistry.fetch(
+      getMMMReferralRegistryPDA(program.programId, referral).key,
+    );
+    assert.equal(referralRegistry.referrer.toBase58(), referral.toBase58());
+    assert.equal(referralRegistry.recipientCount, 2);
+    assert.equal(
+      referralRegistry.recipients[0].address.toBase58(),
+      aggregator.toBase58(),
+    );
+    assert.equal(referralRegistry.recipients[0].shareBp, 7000);
+
+    const initReferralBalance = await connection.getBalance(referral);
+    // the referral account is paid directly, only the other recipients are
+    // passed as remaining accounts
+    await solFulfillBuy(program, {
+      poolData,
+      owner: wallet.publicKey,
+      seller,
+      cosigner,
+      remainingAccounts: [
+        { pubkey: aggregator, isSigner: false, isWritable: true },
+      ],
+    });
+
+    const expectedTakerFee = getSolFulfillBuyPrices({
+      totalPriceLamports: LAMPORTS_PER_SOL,
+      lpFeeBp: 200,
+      takerFeeBp: 100,
+      metadataRoyaltyBp: 0,
+      buysideCreatorRoyaltyBp: 0,
+      makerFeeBp: 0,
+    }).takerFeePaid.toNumber();
+    const expectedAggregatorShare = Math.floor(
+      (expectedTakerFee * 7000) / 10000,
+    );
+    const [aggregatorBalance, referralBalance] = await Promise.all([
+      connection.getBalance(aggregator),
+      connection.getBalance(referral),
+    ]);
+    assert.equal(aggregatorBalance, expectedAggregatorShare);
+    // the last recipient gets the rounding remainder
+    assert.equal(
+      referralBalance,
+      initReferralBalance + expectedTakerFee - expectedAggregatorShare,
+    );
+
+    // an empty list disables the split again
+    await setReferralRegistry(poolData.referral, []);
+    const disabledReferralRegistry =
+      await program.account.referralRegistry.fetch(
+        getMMMReferralRegistryPDA(program.programId, referral).key,
+      );
+    assert.equal(disabledReferralRegistry.recipientCount, 0);
+  });
+
+  it('fails when a recipient account is missing', async () => {
+    const seller = Keypair.generate();
+    const poolData = await createTwoSidedPool(program, seller, {
+      owner: wallet.publicKey,
+      cosigner,
+    });
+    // the referral of the pool is the referrer, it pays for the registry
+    await airdrop(connection, poolData.referral.publicKey, 1);
+
+    await setReferralRegistry(poolData.referral, [
+      { address: PublicKey.unique(), shareBp: 5000 },
+      { address: poolData.referral.publicKey, shareBp: 5000 },
+    ]);
+    try {
+      await solFulfillBuy(program, {
+        poolData,
+        owner: wallet.publicKey,
+        seller,
+        cosigner,
+      });
+      assert.fail('fulfill buy without the recipients should fail');
+    } catch (e) {
+      expect(e).toBeInstanceOf(anchor.AnchorError);
+      expect((e as anchor.AnchorError).error.errorCode.number).toBe(6029);
+    }
+  });
+
+  it('rejects invalid recipients', async () => {
+    const referrer = Keypair.generate();
+    await airdrop(connection, referrer.publicKey, 1);
+
+    const recipient = (shareBp: number) => ({
+      address: PublicKey.unique(),
+      shareBp,
+    });
+    const invalidRecipients = [
+      // shares do not add up to 100%
+      [recipient(5000), recipient(4000)],
+      // empty shares
+      [recipient(10000), recipient(0)],
+      // above REFERRAL_REGISTRY_MAX_RECIPIENTS
+      [2000, 2000, 2000, 2000, 2000].map(recipient),
+    ];
+    for (const recipients of invalidRecipients) {
+      try {
+        await setReferralRegistry(referrer, recipients);
+        assert.fail('invalid referral registry should fail');
+      } catch (e) {
+        expect(e).toBeInstanceOf(anchor.AnchorError);
+        expect((e as anchor.AnchorError).error.errorCode.number).toBe(6052);
+      }
+    }
+  });
+});
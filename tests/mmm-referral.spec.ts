@@ -19,8 +19,15 @@ import {
   getMMMSellStatePDA,
   IDL,
   MMMProgramID,
+  getMMMGlobalConfigPDA,
+  getMMMFeeSchedulePDA,
+  getMMMReferralRegistryPDA,
 } from '../sdk/src';
-import { airdrop, createPoolWithExampleDepositsUmi } from './utils';
+import {
+  airdrop,
+  createPoolWithExampleDepositsUmi,
+  getRoyaltySchedulePDAForMint,
+} from './utils';
 import { toWeb3JsPublicKey } from '@metaplex-foundation/umi-web3js-adapters';
 import { mplTokenMetadata } from '@metaplex-foundation/mpl-token-metadata';
 import { createUmi } from '@metaplex-foundation/umi-bundle-tests';
@@ -118,6 +125,20 @@ describe('mmm-referral', () => {
             tokenProgram: TOKEN_PROGRAM_ID,
             associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
             rent: SYSVAR_RENT_PUBKEY,
+            globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+            treasury: buyer.publicKey,
+            feeSchedule: getMMMFeeSchedulePDA(
+              program.programId,
+              mockProxyAuthorityKey,
+            ).key,
+            royaltySchedule: await getRoyaltySchedulePDAForMint(
+              program,
+              poolData.nft.mintAddress,
+            ),
+            referralRegistry: getMMMReferralRegistryPDA(
+              program.programId,
+              mockProxyAuthorityKey,
+            ).key,
           })
           .signers([buyer, cosigner])
           .rpc();
@@ -153,6 +174,8 @@ describe('mmm-referral', () => {
         makerFeeBp: 0,
         takerFeeBp: 100,
         allowlistAux: null,
+        traitPremium: null,
+        authorizationData: null,
       })
       .accountsStrict({
         payer: buyer.publicKey,
@@ -178,6 +201,20 @@ describe('mmm-referral', () => {
         tokenProgram: TOKEN_PROGRAM_ID,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         rent: SYSVAR_RENT_PUBKEY,
+        globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+        treasury: buyer.publicKey,
+        feeSchedule: getMMMFeeSchedulePDA(
+          program.programId,
+          mockProxyAuthorityKey,
+        ).key,
+        royaltySchedule: await getRoyaltySchedulePDAForMint(
+          program,
+          poolData.nft.mintAddress,
+        ),
+        referralRegistry: getMMMReferralRegistryPDA(
+          program.programId,
+          mockProxyAuthorityKey,
+        ).key,
       })
       .signers([buyer, cosigner])
       .rpc();
@@ -0,0 +1,189 @@
+import * as anchor from '@project-serum/anchor';
+import { Keypair, LAMPORTS_PER_SOL, SystemProgram } from '@solana/web3.js';
+import { assert } from 'chai';
+import {
+  AllowlistKind,
+  getMMMGlobalConfigPDA,
+  getMMMRoyaltySchedulePDA,
+  getSolFulfillBuyPrices,
+  IDL,
+  Mmm,
+  MMMProgramID,
+} from '../sdk/src';
+import {
+  airdrop,
+  createTwoSidedPool,
+  getSellStatePDARent,
+  PoolData,
+  solFulfillBuy,
+} from './utils';
+import { toWeb3JsPublicKey } from '@metaplex-foundation/umi-web3js-adapters';
+
+type RoyaltyBands = Parameters<
+  anchor.Program<Mmm>['methods']['setRoyaltySchedule']
+>[0]['bands'];
+
+describe('mmm-royalty-schedule', () => {
+  const { connection } = anchor.AnchorProvider.env();
+  const wallet = new anchor.Wallet(Keypair.generate());
+  const provider = new anchor.AnchorProvider(connection, wallet, {
+    commitment: 'processed',
+  });
+  const program = new anchor.Program(
+    IDL,
+    MMMProgramID,
+    provider,
+  ) as anchor.Program<Mmm>;
+  const cosigner = Keypair.generate();
+
+  beforeEach(async () => {
+    await airdrop(connection, wallet.publicKey, 50);
+  });
+
+  const setRoyaltySchedule = async (
+    poolData: PoolData,
+    authority: Keypair,
+    bands: RoyaltyBands,
+  ) => {
+    const collection = toWeb3JsPublicKey(poolData.collection!.mintAddress);
+    await program.methods
+      .setRoyaltySchedule({ bands })
+      .accountsStrict({
+        authority: authority.publicKey,
+        collection,
+        collectionMetadata: poolData.collection!.metadataAddress,
+        royaltySchedule: getMMMRoyaltySchedulePDA(program.programId, collection)
+          .key,
+        globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  };
+
+  it('pays the royalty of the band matching the price', async () => {
+    const seller = Keypair.generate();
+    const poolData = await createTwoSidedPool(
+      program,
+      seller,
+      {
+        owner: wallet.publicKey,
+        cosigner,
+        buysideCreatorRoyaltyBp: 5000,
+      },
+      AllowlistKind.mcc,
+    );
+    const creator = poolData.nftCreator.publicKey;
+    const collection = toWeb3JsPublicKey(poolData.collection!.mintAddress);
+
+    await setRoyaltySchedule(poolData, poolData.collectionUpdateAuthority!, [
+      { minPrice: new anchor.BN(0), royaltyBp: 300 },
+      { minPrice: new anchor.BN(2 * LAMPORTS_PER_SOL), royaltyBp: 50 },
+    ]);
+    const royaltySchedule = await program.account.royaltySchedule.fetch(
+      getMMMRoyaltySchedulePDA(program.programId, collection).key,
+    );
+    assert.equal(royaltySchedule.collection.toBase58(), collection.toBase58());
+    assert.equal(
+      royaltySchedule.authority.toBase58(),
+      poolData.collectionUpdateAuthority!.publicKey.toBase58(),
+    );
+    assert.equal(royaltySchedule.bandCount, 2);
+    assert.equal(royaltySchedule.bands[0].royaltyBp, 300);
+    assert.equal(
+      royaltySchedule.bands[1].minPrice.toNumber(),
+      2 * LAMPORTS_PER_SOL,
+    );
+
+    // fund the creator so the royalty is paid out directly
+    await airdrop(connection, creator, 1);
+    const [initSellerBalance, initCreatorBalance] = await Promise.all([
+      connection.getBalance(seller.publicKey),
+      connection.getBalance(creator),
+    ]);
+
+    await solFulfillBuy(program, {
+      poolData,
+      owner: wallet.publicKey,
+      seller,
+      cosigner,
+      remainingAccounts: [
+        { pubkey: creator, isSigner: false, isWritable: true },
+      ],
+    });
+
+    // the 1 SOL price falls into the first band, 300bp instead of the 100bp
+    // seller fee of the metadata
+    const expectedBuyPrices = getSolFulfillBuyPrices({
+      totalPriceLamports: LAMPORTS_PER_SOL,
+      lpFeeBp: 200,
+      takerFeeBp: 100,
+      metadataRoyaltyBp: 300,
+      buysideCreatorRoyaltyBp: 5000,
+      makerFeeBp: 0,
+    });
+    const sellStatePDARent = await getSellStatePDARent(connection);
+    const [sellerBalance, creatorBalance] = await Promise.all([
+      connection.getBalance(seller.publicKey),
+      connection.getBalance(creator),
+    ]);
+    assert.equal(
+      creatorBalance,
+      initCreatorBalance + expectedBuyPrices.royaltyPaid.toNumber(),
+    );
+    assert.equal(
+      sellerBalance,
+      initSellerBalance +
+        expectedBuyPrices.sellerReceives.toNumber() -
+        sellStatePDARent,
+    );
+  });
+
+  it('rejects invalid royalty schedules', async () => {
+    const seller = Keypair.generate();
+    const poolData = await createTwoSidedPool(
+      program,
+      seller,
+      {
+        owner: wallet.publicKey,
+        cosigner,
+        buysideCreatorRoyaltyBp: 5000,
+      },
+      AllowlistKind.mcc,
+    );
+
+    const invalidBands = [
+      // min prices are not ascending
+      [
+        { minPrice: new anchor.BN(LAMPORTS_PER_SOL), royaltyBp: 300 },
+        { minPrice: new anchor.BN(LAMPORTS_PER_SOL), royaltyBp: 200 },
+      ],
+      // above the global max_metadata_creator_royalty_bp
+      [{ minPrice: new anchor.BN(0), royaltyBp: 3001 }],
+    ];
+    for (const bands of invalidBands) {
+      try {
+        await setRoyaltySchedule(
+          poolData,
+          poolData.collectionUpdateAuthority!,
+          bands,
+        );
+        assert.fail('invalid royalty schedule should fail');
+      } catch (e) {
+        expect(e).toBeInstanceOf(anchor.AnchorError);
+        expect((e as anchor.AnchorError).error.errorCode.number).toBe(6046);
+      }
+    }
+
+    // only the collection update authority can set the schedule
+    try {
+      await setRoyaltySchedule(poolData, wallet.payer, [
+        { minPrice: new anchor.BN(0), royaltyBp: 300 },
+      ]);
+      assert.fail('royalty schedule by another authority should fail');
+    } catch (e) {
+      expect(e).toBeInstanceOf(anchor.AnchorError);
+      expect((e as anchor.AnchorError).error.errorCode.number).toBe(6007);
+    }
+  });
+});
@@ -0,0 +1,165 @@
+import * as anchor from '@project-serum/anchor';
+import {
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+} from '@solana/web3.js';
+import { assert } from 'chai';
+import {
+  getMMMRoyaltyVaultPDA,
+  getSolFulfillBuyPrices,
+  IDL,
+  Mmm,
+  MMMProgramID,
+} from '../sdk/src';
+import {
+  airdrop,
+  createTwoSidedPool,
+  getSellStatePDARent,
+  solFulfillBuy,
+} from './utils';
+
+describe('mmm-royalty-vault', () => {
+  const { connection } = anchor.AnchorProvider.env();
+  const wallet = new anchor.Wallet(Keypair.generate());
+  const provider = new anchor.AnchorProvider(connection, wallet, {
+    commitment: 'processed',
+  });
+  const program = new anchor.Program(
+    IDL,
+    MMMProgramID,
+    provider,
+  ) as anchor.Program<Mmm>;
+  const cosigner = Keypair.generate();
+
+  beforeEach(async () => {
+    await airdrop(connection, wallet.publicKey, 50);
+  });
+
+  // the 1% royalty at 5% buyside creator royalty is below the rent-exempt
+  // minimum of the creator account
+  const poolArgs = {
+    owner: wallet.publicKey,
+    cosigner,
+    lpFeeBp: 200,
+    buysideCreatorRoyaltyBp: 500,
+  };
+
+  const claimRoyalties = async (creator: PublicKey) => {
+    await program.methods
+      .claimRoyalties()
+      .accountsStrict({
+        payer: wallet.publicKey,
+        creator,
+        royaltyVault: getMMMRoyaltyVaultPDA(program.programId, creator).key,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  };
+
+  it('accrues royalties of underfunded creators until they are claimed', async () => {
+    const seller = Keypair.generate();
+    const poolData = await createTwoSidedPool(program, seller, poolArgs);
+    const creator = poolData.nftCreator.publicKey;
+    const { key: royaltyVault } = getMMMRoyaltyVaultPDA(
+      program.programId,
+      creator,
+    );
+
+    // the vault is created by the first claim
+    await claimRoyalties(creator);
+    const vaultRent = await connection.getBalance(royaltyVault);
+
+    await solFulfillBuy(program, {
+      poolData,
+      owner: wallet.publicKey,
+      seller,
+      cosigner,
+      remainingAccounts: [
+        { pubkey: creator, isSigner: false, isWritable: true },
+        { pubkey: royaltyVault, isSigner: false, isWritable: true },
+      ],
+    });
+
+    const expectedBuyPrices = getSolFulfillBuyPrices({
+      totalPriceLamports: LAMPORTS_PER_SOL,
+      lpFeeBp: 200,
+      takerFeeBp: 100,
+      metadataRoyaltyBp: 100,
+      buysideCreatorRoyaltyBp: 500,
+      makerFeeBp: 0,
+    });
+    const expectedRoyalty = expectedBuyPrices.royaltyPaid.toNumber();
+    let [creatorBalance, vaultBalance, vaultAccountInfo] = await Promise.all([
+      connection.getBalance(creator),
+      connection.getBalance(royaltyVault),
+      program.account.royaltyVault.fetch(royaltyVault),
+    ]);
+    assert.equal(creatorBalance, 0);
+    assert.equal(vaultBalance, vaultRent + expectedRoyalty);
+    assert.equal(vaultAccountInfo.creator.toBase58(), creator.toBase58());
+    assert.equal(vaultAccountInfo.accrued.toNumber(), expectedRoyalty);
+    assert.equal(vaultAccountInfo.claimed.toNumber(), 0);
+
+    // the claim would leave the creator below the rent-exempt minimum
+    try {
+      await claimRoyalties(creator);
+      assert.fail('claim below the rent-exempt minimum should fail');
+    } catch (e) {
+      expect(e).toBeInstanceOf(anchor.AnchorError);
+      expect((e as anchor.AnchorError).error.errorCode.number).toBe(6016);
+    }
+
+    await airdrop(connection, creator, 1);
+    await claimRoyalties(creator);
+    [creatorBalance, vaultBalance, vaultAccountInfo] = await Promise.all([
+      connection.getBalance(creator),
+      connection.getBalance(royaltyVault),
+      program.account.royaltyVault.fetch(royaltyVault),
+    ]);
+    assert.equal(creatorBalance, LAMPORTS_PER_SOL + expectedRoyalty);
+    assert.equal(vaultBalance, vaultRent);
+    assert.equal(vaultAccountInfo.claimed.toNumber(), expectedRoyalty);
+  });
+
+  it('skips the royalty of an underfunded creator without a royalty vault', async () => {
+    const seller = Keypair.generate();
+    const poolData = await createTwoSidedPool(program, seller, poolArgs);
+    const creator = poolData.nftCreator.publicKey;
+
+    const initSellerBalance = await connection.getBalance(seller.publicKey);
+    await solFulfillBuy(program, {
+      poolData,
+      owner: wallet.publicKey,
+      seller,
+      cosigner,
+      remainingAccounts: [
+        { pubkey: creator, isSigner: false, isWritable: true },
+      ],
+    });
+
+    const expectedBuyPrices = getSolFulfillBuyPrices({
+      totalPriceLamports: LAMPORTS_PER_SOL,
+      lpFeeBp: 200,
+      takerFeeBp: 100,
+      metadataRoyaltyBp: 100,
+      buysideCreatorRoyaltyBp: 500,
+      makerFeeBp: 0,
+    });
+    const sellStatePDARent = await getSellStatePDARent(connection);
+    const [creatorBalance, sellerBalance] = await Promise.all([
+      connection.getBalance(creator),
+      connection.getBalance(seller.publicKey),
+    ]);
+    // the royalty is neither paid nor charged to the seller
+    assert.equal(creatorBalance, 0);
+    assert.equal(
+      sellerBalance,
+      initSellerBalance +
+        expectedBuyPrices.sellerReceives.toNumber() +
+        expectedBuyPrices.royaltyPaid.toNumber() -
+        sellStatePDARent,
+    );
+  });
+});
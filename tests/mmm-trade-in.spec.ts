@@ -0,0 +1,243 @@
+import * as anchor from '@project-serum/anchor';
+import {
+  getAssociatedTokenAddress,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+} from '@solana/spl-token';
+import {
+  Keypair,
+  LAMPORTS_PER_SOL,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+} from '@solana/web3.js';
+import { assert } from 'chai';
+import {
+  getMMMFeeSchedulePDA,
+  getMMMGlobalConfigPDA,
+  getMMMReferralRegistryPDA,
+  getMMMSellStatePDA,
+  getSolFulfillBuyPrices,
+  IDL,
+  Mmm,
+  MMMProgramID,
+} from '../sdk/src';
+import {
+  airdrop,
+  createTwoSidedPool,
+  getRoyaltySchedulePDAForMint,
+  getSellStatePDARent,
+  getTokenAccountRent,
+  PoolData,
+} from './utils';
+import { toWeb3JsPublicKey } from '@metaplex-foundation/umi-web3js-adapters';
+
+describe('mmm-trade-in', () => {
+  const { connection } = anchor.AnchorProvider.env();
+  const wallet = new anchor.Wallet(Keypair.generate());
+  const provider = new anchor.AnchorProvider(connection, wallet, {
+    commitment: 'processed',
+  });
+  const program = new anchor.Program(
+    IDL,
+    MMMProgramID,
+    provider,
+  ) as anchor.Program<Mmm>;
+  const cosigner = Keypair.generate();
+
+  beforeEach(async () => {
+    await airdrop(connection, wallet.publicKey, 50);
+  });
+
+  // trades the extraNft held by the trader for the nft held by the pool
+  const fulfillTradeIn = async (
+    poolData: PoolData,
+    trader: Keypair,
+    maxNetPaymentAmount: anchor.BN,
+  ) => {
+    const inMint = toWeb3JsPublicKey(poolData.extraNft.mintAddress);
+    const outMint = toWeb3JsPublicKey(poolData.nft.mintAddress);
+    await program.methods
+      .solFulfillTradeIn({
+        maxNetPaymentAmount,
+        buysideCreatorRoyaltyBp: 0,
+        allowlistAux: null,
+        makerFeeBp: 0,
+        takerFeeBp: 100,
+      })
+      .accountsStrict({
+        payer: trader.publicKey,
+        owner: wallet.publicKey,
+        cosigner: cosigner.publicKey,
+        referral: poolData.referral.publicKey,
+        globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+        treasury: trader.publicKey,
+        feeSchedule: getMMMFeeSchedulePDA(
+          program.programId,
+          poolData.referral.publicKey,
+        ).key,
+        inRoyaltySchedule: await getRoyaltySchedulePDAForMint(program, inMint),
+        outRoyaltySchedule: await getRoyaltySchedulePDAForMint(
+          program,
+          outMint,
+        ),
+        referralRegistry: getMMMReferralRegistryPDA(
+          program.programId,
+          poolData.referral.publicKey,
+        ).key,
+        pool: poolData.poolKey,
+        buysideSolEscrowAccount: poolData.poolPaymentEscrow,
+        inAssetMetadata: poolData.extraNft.metadataAddress,
+        inAssetMasterEdition: poolData.extraNft.masterEditionAddress,
+        inAssetMint: inMint,
+        payerInAssetAccount: poolData.extraNft.tokenAddress!,
+        inSellsideEscrowTokenAccount: poolData.poolAtaExtraNft,
+        ownerInTokenAccount: await getAssociatedTokenAddress(
+          inMint,
+          wallet.publicKey,
+          true,
+          TOKEN_PROGRAM_ID,
+        ),
+        inSellState: getMMMSellStatePDA(
+          program.programId,
+          poolData.poolKey,
+          inMint,
+        ).key,
+        outAssetMetadata: poolData.nft.metadataAddress,
+        outAssetMasterEdition: poolData.nft.masterEditionAddress,
+        outAssetMint: outMint,
+        outSellsideEscrowTokenAccount: poolData.poolAtaNft,
+        payerOutAssetAccount: await getAssociatedTokenAddress(
+          outMint,
+          trader.publicKey,
+          true,
+          TOKEN_PROGRAM_ID,
+        ),
+        outSellState: getMMMSellStatePDA(
+          program.programId,
+          poolData.poolKey,
+          outMint,
+        ).key,
+        allowlistAuxAccount: SystemProgram.programId,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .remainingAccounts([
+        // creator of the in_asset, then creator of the out_asset
+        {
+          pubkey: poolData.nftCreator.publicKey,
+          isSigner: false,
+          isWritable: true,
+        },
+        {
+          pubkey: poolData.nftCreator.publicKey,
+          isSigner: false,
+          isWritable: true,
+        },
+      ])
+      .signers([trader, cosigner])
+      .rpc();
+  };
+
+  // leg 1 buys the in_asset at the 1 SOL spot price, leg 2 sells the
+  // out_asset at 1 SOL again since the curve went down by one delta
+  const expectedBuyPrices = getSolFulfillBuyPrices({
+    totalPriceLamports: LAMPORTS_PER_SOL,
+    lpFeeBp: 200,
+    takerFeeBp: 100,
+    metadataRoyaltyBp: 0,
+    buysideCreatorRoyaltyBp: 0,
+    makerFeeBp: 0,
+  });
+  const expectedSellLpFee = LAMPORTS_PER_SOL * 0.02;
+  const expectedSellTakerFee = LAMPORTS_PER_SOL * 0.01;
+  const expectedNetPayment =
+    LAMPORTS_PER_SOL +
+    expectedSellLpFee +
+    expectedSellTakerFee -
+    expectedBuyPrices.sellerReceives.toNumber();
+
+  it('only settles the difference of the two legs', async () => {
+    const trader = Keypair.generate();
+    const poolData = await createTwoSidedPool(program, trader, {
+      owner: wallet.publicKey,
+      cosigner,
+    });
+    const inMint = toWeb3JsPublicKey(poolData.extraNft.mintAddress);
+    const outMint = toWeb3JsPublicKey(poolData.nft.mintAddress);
+
+    const [initTraderBalance, initReferralBalance] = await Promise.all([
+      connection.getBalance(trader.publicKey),
+      connection.getBalance(poolData.referral.publicKey),
+    ]);
+
+    await fulfillTradeIn(poolData, trader, new anchor.BN(expectedNetPayment));
+
+    const [tokenAccountRent, sellStatePDARent] = await Promise.all([
+      getTokenAccountRent(connection),
+      getSellStatePDARent(connection),
+    ]);
+    const [
+      traderBalance,
+      referralBalance,
+      poolAccountInfo,
+      inSellState,
+      traderOutAccount,
+    ] = await Promise.all([
+      connection.getBalance(trader.publicKey),
+      connection.getBalance(poolData.referral.publicKey),
+      program.account.pool.fetch(poolData.poolKey),
+      program.account.sellState.fetch(
+        getMMMSellStatePDA(program.programId, poolData.poolKey, inMint).key,
+      ),
+      connection.getTokenAccountBalance(
+        await getAssociatedTokenAddress(outMint, trader.publicKey),
+      ),
+    ]);
+    // the in_asset ata of the trader was closed and the pool ata opened, the
+    // trader pays for the out_asset ata and the in_asset sell state
+    assert.equal(
+      traderBalance,
+      initTraderBalance -
+        expectedNetPayment -
+        tokenAccountRent -
+        sellStatePDARent,
+    );
+    assert.equal(
+      referralBalance,
+      initReferralBalance +
+        expectedBuyPrices.takerFeePaid.toNumber() +
+        expectedSellTakerFee,
+    );
+    assert.equal(traderOutAccount.value.amount, '1');
+    assert.equal(inSellState.assetAmount.toNumber(), 1);
+    // the curve went down on the buy leg and back up on the sell leg
+    assert.equal(poolAccountInfo.spotPrice.toNumber(), LAMPORTS_PER_SOL);
+    assert.equal(poolAccountInfo.sellsideAssetAmount.toNumber(), 6);
+    assert.equal(
+      poolAccountInfo.lpFeeEarned.toNumber(),
+      expectedBuyPrices.lpFeePaid.toNumber() + expectedSellLpFee,
+    );
+  });
+
+  it('fails when the net payment is above the max', async () => {
+    const trader = Keypair.generate();
+    const poolData = await createTwoSidedPool(program, trader, {
+      owner: wallet.publicKey,
+      cosigner,
+    });
+
+    try {
+      await fulfillTradeIn(
+        poolData,
+        trader,
+        new anchor.BN(expectedNetPayment - 1),
+      );
+      assert.fail('trade-in above the max net payment should fail');
+    } catch (e) {
+      expect(e).toBeInstanceOf(anchor.AnchorError);
+      expect((e as anchor.AnchorError).error.errorCode.number).toBe(6009);
+    }
+  });
+});
@@ -0,0 +1,158 @@
+import * as anchor from '@project-serum/anchor';
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from '@solana/web3.js';
+import { assert } from 'chai';
+import { getSolFulfillBuyPrices, IDL, Mmm, MMMProgramID } from '../sdk/src';
+import {
+  airdrop,
+  createTwoSidedPool,
+  getSellStatePDARent,
+  solFulfillBuy,
+} from './utils';
+import { toWeb3JsPublicKey } from '@metaplex-foundation/umi-web3js-adapters';
+
+describe('mmm-trait-premium', () => {
+  const { connection } = anchor.AnchorProvider.env();
+  const wallet = new anchor.Wallet(Keypair.generate());
+  const provider = new anchor.AnchorProvider(connection, wallet, {
+    commitment: 'processed',
+  });
+  const program = new anchor.Program(
+    IDL,
+    MMMProgramID,
+    provider,
+  ) as anchor.Program<Mmm>;
+  const cosigner = Keypair.generate();
+
+  beforeEach(async () => {
+    await airdrop(connection, wallet.publicKey, 50);
+  });
+
+  it('pays the trait premium on top of the curve price', async () => {
+    const seller = Keypair.generate();
+    const poolData = await createTwoSidedPool(program, seller, {
+      owner: wallet.publicKey,
+      cosigner,
+      maxTraitMultiplierBp: 15000,
+      traitPremiumRoot: new Array(32).fill(1),
+    });
+    const mint = toWeb3JsPublicKey(poolData.extraNft.mintAddress);
+
+    const [initSellerBalance, initReferralBalance] = await Promise.all([
+      connection.getBalance(seller.publicKey),
+      connection.getBalance(poolData.referral.publicKey),
+    ]);
+
+    // 20% premium on top of the 1 SOL curve price
+    const expectedBuyPrices = getSolFulfillBuyPrices({
+      totalPriceLamports: 1.2 * LAMPORTS_PER_SOL,
+      lpFeeBp: 200,
+      takerFeeBp: 100,
+      metadataRoyaltyBp: 0,
+      buysideCreatorRoyaltyBp: 0,
+      makerFeeBp: 0,
+    });
+    await solFulfillBuy(program, {
+      poolData,
+      owner: wallet.publicKey,
+      seller,
+      cosigner,
+      args: {
+        minPaymentAmount: expectedBuyPrices.sellerReceives,
+        traitPremium: {
+          assetId: mint,
+          traitBucket: new Array(32).fill(0),
+          multiplierBp: 12000,
+          expiry: new anchor.BN(0),
+          proof: null,
+        },
+      },
+    });
+
+    const sellStatePDARent = await getSellStatePDARent(connection);
+    const [sellerBalance, referralBalance, poolAccountInfo] =
+      await Promise.all([
+        connection.getBalance(seller.publicKey),
+        connection.getBalance(poolData.referral.publicKey),
+        program.account.pool.fetch(poolData.poolKey),
+      ]);
+    assert.equal(
+      sellerBalance,
+      initSellerBalance +
+        expectedBuyPrices.sellerReceives.toNumber() -
+        sellStatePDARent, // no token account rent bc seller ata was closed and pool ata opened
+    );
+    assert.equal(
+      referralBalance,
+      initReferralBalance + expectedBuyPrices.takerFeePaid.toNumber(),
+    );
+    assert.equal(
+      poolAccountInfo.lpFeeEarned.toNumber(),
+      expectedBuyPrices.lpFeePaid.toNumber(),
+    );
+    // the curve moves as if there was no premium
+    assert.equal(poolAccountInfo.spotPrice.toNumber(), 0.9 * LAMPORTS_PER_SOL);
+    assert.equal(poolAccountInfo.sellsideAssetAmount.toNumber(), 7);
+  });
+
+  it('rejects invalid trait premiums', async () => {
+    const seller = Keypair.generate();
+    const poolData = await createTwoSidedPool(program, seller, {
+      owner: wallet.publicKey,
+      cosigner,
+      maxTraitMultiplierBp: 15000,
+      traitPremiumRoot: new Array(32).fill(1),
+    });
+    const mint = toWeb3JsPublicKey(poolData.extraNft.mintAddress);
+
+    const validTraitPremium = {
+      assetId: mint,
+      traitBucket: new Array(32).fill(0),
+      multiplierBp: 12000,
+      expiry: new anchor.BN(0),
+      proof: null,
+    };
+    const now = Math.floor(Date.now() / 1000);
+    const invalidTraitPremiums = [
+      // above the pool max_trait_multiplier_bp
+      {
+        traitPremium: { ...validTraitPremium, multiplierBp: 16000 },
+        code: 6038,
+      },
+      // discounts are not allowed
+      {
+        traitPremium: { ...validTraitPremium, multiplierBp: 9000 },
+        code: 6038,
+      },
+      // signed for another asset
+      {
+        traitPremium: { ...validTraitPremium, assetId: PublicKey.unique() },
+        code: 6038,
+      },
+      // proof does not match the pool trait_premium_root
+      {
+        traitPremium: { ...validTraitPremium, proof: [new Array(32).fill(0)] },
+        code: 6038,
+      },
+      // already expired
+      {
+        traitPremium: { ...validTraitPremium, expiry: new anchor.BN(now - 60) },
+        code: 6014,
+      },
+    ];
+    for (const { traitPremium, code } of invalidTraitPremiums) {
+      try {
+        await solFulfillBuy(program, {
+          poolData,
+          owner: wallet.publicKey,
+          seller,
+          cosigner,
+          args: { traitPremium },
+        });
+        assert.fail('fulfill buy with an invalid trait premium should fail');
+      } catch (e) {
+        expect(e).toBeInstanceOf(anchor.AnchorError);
+        expect((e as anchor.AnchorError).error.errorCode.number).toBe(code);
+      }
+    }
+  });
+});
@@ -19,6 +19,7 @@ import {
   getMMMSellStatePDA,
   IDL,
   MMMProgramID,
+  getMMMGlobalConfigPDA,
 } from '../sdk/src';
 import {
   airdrop,
@@ -80,6 +81,7 @@ describe('mmm-withdraw', () => {
             pool: poolData.poolKey,
             buysideSolEscrowAccount: poolData.poolPaymentEscrow,
             systemProgram: SystemProgram.programId,
+            globalConfig: getMMMGlobalConfigPDA(program.programId).key,
           })
           .signers([cosigner])
           .rpc({ skipPreflight: true });
@@ -113,6 +115,7 @@ describe('mmm-withdraw', () => {
             pool: poolData.poolKey,
             buysideSolEscrowAccount: poolData.poolPaymentEscrow,
             systemProgram: SystemProgram.programId,
+            globalConfig: getMMMGlobalConfigPDA(program.programId).key,
           })
           .signers([cosigner])
           .rpc();
@@ -159,6 +162,7 @@ describe('mmm-withdraw', () => {
             pool: poolData.poolKey,
             buysideSolEscrowAccount: poolData.poolPaymentEscrow,
             systemProgram: SystemProgram.programId,
+            globalConfig: getMMMGlobalConfigPDA(program.programId).key,
           })
           .signers([cosigner])
           .rpc();
@@ -206,7 +210,10 @@ describe('mmm-withdraw', () => {
           mintAddress,
         );
         await program.methods
-          .withdrawSell({ assetAmount: new anchor.BN(1), allowlistAux: null })
+          .withdrawSell({
+            assetAmount: new anchor.BN(1),
+            allowlistAux: null,
+          })
           .accountsStrict({
             owner: wallet.publicKey,
             cosigner: cosigner.publicKey,
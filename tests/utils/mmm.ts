@@ -11,12 +11,14 @@ import {
   Program as UmiProgram,
   none,
   createSignerFromKeypair,
+  KeypairSigner,
 } from '@metaplex-foundation/umi';
 import { createUmi } from '@metaplex-foundation/umi-bundle-tests';
 import { mplTokenMetadata } from '@metaplex-foundation/mpl-token-metadata';
 import * as anchor from '@project-serum/anchor';
 import { Program } from '@project-serum/anchor';
 import {
+  AccountMeta,
   ComputeBudgetProgram,
   Connection,
   Keypair,
@@ -44,15 +46,23 @@ import {
   CurveKind,
   getM2BuyerSharedEscrow,
   getMMMBuysideSolEscrowPDA,
+  getMMMExtensionPolicyPDA,
+  getMMMFeeSchedulePDA,
+  getMMMGlobalConfigPDA,
   getMMMPoolPDA,
+  getMMMReferralRegistryPDA,
+  getMMMRoyaltySchedulePDA,
   getMMMSellStatePDA,
   getTokenRecordPDA,
   Mmm,
+  RpcMetadataProvider,
 } from '../../sdk/src';
 import {
+  airdrop,
   fillAllowlists,
   getEmptyAllowLists,
   getKeypair,
+  getTestAuthorityKeypair,
   MIP1_COMPUTE_UNITS,
   OCP_COMPUTE_UNITS,
   TRANSFER_HOOK_COMPUTE_UNITS,
@@ -89,6 +99,9 @@ export interface PoolData {
   poolAtaExtraNft: PublicKey;
   poolPaymentEscrow: anchor.web3.PublicKey;
   nftCreator: anchor.web3.Keypair;
+  // only set for mcc pools, the update authority can set a royalty schedule
+  collection?: Nft;
+  collectionUpdateAuthority?: anchor.web3.Keypair;
 }
 
 export const createPool = async (
@@ -110,6 +123,12 @@ export const createPool = async (
     uuid?: PublicKey;
     paymentMint?: PublicKey;
     buysideCreatorRoyaltyBp?: number;
+    maxTraitMultiplierBp?: number;
+    traitPremiumRoot?: number[];
+    transferHookProgramId?: PublicKey;
+    compoundLpFee?: boolean;
+    dynamicLpFeeMinBp?: number;
+    dynamicLpFeeMaxBp?: number;
   },
 ) => {
   const referral = Keypair.generate();
@@ -135,6 +154,12 @@ export const createPool = async (
     referralBp: 300,
     cosignerAnnotation: new Array(32).fill(0),
     buysideCreatorRoyaltyBp: 0,
+    maxTraitMultiplierBp: null,
+    traitPremiumRoot: null,
+    transferHookProgramId: null,
+    compoundLpFee: null,
+    dynamicLpFeeMinBp: null,
+    dynamicLpFeeMaxBp: null,
 
     owner: args.owner,
     cosigner: args.cosigner?.publicKey ?? args.owner,
@@ -149,6 +174,7 @@ export const createPool = async (
     cosigner: finalArgs.cosigner,
     pool: poolKey,
     systemProgram: SystemProgram.programId,
+    globalConfig: getMMMGlobalConfigPDA(program.programId).key,
   });
   if (args.cosigner) {
     builder = builder.signers([args.cosigner]);
@@ -158,6 +184,80 @@ export const createPool = async (
   return { referral, uuid, poolKey };
 };
 
+// the royalty schedule pda of the verified collection or the token22 group of
+// the mint, or the one of the default pubkey when it has neither
+export const getRoyaltySchedulePDAForMint = async (
+  program: Program<Mmm>,
+  mint: PublicKey | string,
+) => {
+  const metadataProvider = await RpcMetadataProvider.loadFromRpc(
+    new PublicKey(mint),
+    program.provider.connection,
+  );
+  return getMMMRoyaltySchedulePDA(
+    program.programId,
+    metadataProvider.collection,
+  ).key;
+};
+
+// the global config is shared by all specs, it's initialized once with the
+// test authority as admin and treasury and without protocol fee. Returns the
+// admin keypair.
+export const initGlobalConfigIfNeeded = async (program: Program<Mmm>) => {
+  const admin = getTestAuthorityKeypair();
+  const { key: globalConfig } = getMMMGlobalConfigPDA(program.programId);
+  if (await program.account.globalConfig.fetchNullable(globalConfig)) {
+    return admin;
+  }
+  await airdrop(program.provider.connection, admin.publicKey, 10);
+  await program.methods
+    .initGlobalConfig({
+      admin: admin.publicKey,
+      treasury: admin.publicKey,
+      protocolFeeBp: 0,
+    })
+    .accountsStrict({
+      authority: admin.publicKey,
+      globalConfig,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([admin])
+    .rpc();
+  return admin;
+};
+
+export const updateGlobalConfig = async (
+  program: Program<Mmm>,
+  admin: Keypair,
+  args: Partial<Parameters<Program<Mmm>['methods']['updateGlobalConfig']>[0]>,
+) => {
+  await program.methods
+    .updateGlobalConfig({
+      treasury: null,
+      protocolFeeBp: null,
+      pendingAdmin: null,
+      cancelAuthority: null,
+      maxLpFeeBp: null,
+      maxReferralFeeBp: null,
+      maxMetadataCreatorRoyaltyBp: null,
+      minSolEscrowBalanceBp: null,
+      m2AuctionHouse: null,
+      paymentProxyProgramId: null,
+      paymentProxyDiscriminator: null,
+      paymentProxyMinLen: null,
+      transferHookProgramAllowList: null,
+      defaultMakerFeeBp: null,
+      defaultTakerFeeBp: null,
+      ...args,
+    })
+    .accountsStrict({
+      admin: admin.publicKey,
+      globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+    })
+    .signers([admin])
+    .rpc();
+};
+
 // create pool for T22 extension
 export const createPoolWithExampleT22ExtDeposits = async (
   program: Program<Mmm>,
@@ -213,6 +313,8 @@ export const createPoolWithExampleT22ExtDeposits = async (
       .extDepositSell({
         assetAmount: new anchor.BN(1),
         allowlistAux: 'example.com',
+        priceOverride: null,
+        authorizationData: null,
       })
       .accountsStrict({
         owner: poolArgs.owner,
@@ -225,6 +327,11 @@ export const createPoolWithExampleT22ExtDeposits = async (
         systemProgram: SystemProgram.programId,
         tokenProgram: TOKEN_2022_PROGRAM_ID,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        extensionPolicy: getMMMExtensionPolicyPDA(
+          program.programId,
+          poolData.poolKey,
+        ).key,
+        globalConfig: getMMMGlobalConfigPDA(program.programId).key,
       })
       .remainingAccounts(
         transferHookArgs
@@ -254,6 +361,7 @@ export const createPoolWithExampleT22ExtDeposits = async (
         pool: poolData.poolKey,
         buysideSolEscrowAccount: solEscrowKey,
         systemProgram: SystemProgram.programId,
+        globalConfig: getMMMGlobalConfigPDA(program.programId).key,
       })
       .remainingAccounts(
         transferHookArgs
@@ -280,6 +388,7 @@ export const createPoolWithExampleT22ExtDeposits = async (
         cosigner: poolArgs.cosigner?.publicKey ?? poolArgs.owner,
         pool: poolData.poolKey,
         sharedEscrowAccount,
+        globalConfig: getMMMGlobalConfigPDA(program.programId).key,
       })
       .signers([...(poolArgs.cosigner ? [poolArgs.cosigner] : [])])
       .rpc();
@@ -511,7 +620,12 @@ export const createPoolWithExampleDeposits = async (
       mintAddressNft,
     );
     await program.methods
-      .depositSell({ assetAmount: new anchor.BN(1), allowlistAux: '' })
+      .depositSell({
+        assetAmount: new anchor.BN(1),
+        allowlistAux: '',
+        priceOverride: null,
+        authorizationData: null,
+      })
       .accountsStrict({
         owner: poolArgs.owner,
         cosigner: poolArgs.cosigner?.publicKey ?? poolArgs.owner,
@@ -533,6 +647,7 @@ export const createPoolWithExampleDeposits = async (
         tokenProgram: tokenProgramId,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         rent: SYSVAR_RENT_PUBKEY,
+        globalConfig: getMMMGlobalConfigPDA(program.programId).key,
       })
       .signers([...(poolArgs.cosigner ? [poolArgs.cosigner] : [])])
       .rpc({ skipPreflight: true });
@@ -543,7 +658,12 @@ export const createPoolWithExampleDeposits = async (
       mintAddressSft,
     );
     await program.methods
-      .depositSell({ assetAmount: new anchor.BN(5), allowlistAux: '' })
+      .depositSell({
+        assetAmount: new anchor.BN(5),
+        allowlistAux: '',
+        priceOverride: null,
+        authorizationData: null,
+      })
       .accountsStrict({
         owner: poolArgs.owner,
         cosigner: poolArgs.cosigner?.publicKey ?? poolArgs.owner,
@@ -565,6 +685,7 @@ export const createPoolWithExampleDeposits = async (
         tokenProgram: tokenProgramId,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         rent: SYSVAR_RENT_PUBKEY,
+        globalConfig: getMMMGlobalConfigPDA(program.programId).key,
       })
       .signers([...(poolArgs.cosigner ? [poolArgs.cosigner] : [])])
       .rpc({ skipPreflight: true });
@@ -583,6 +704,7 @@ export const createPoolWithExampleDeposits = async (
         pool: poolKey,
         buysideSolEscrowAccount: solEscrowKey,
         systemProgram: SystemProgram.programId,
+        globalConfig: getMMMGlobalConfigPDA(program.programId).key,
       })
       .signers([...(poolArgs.cosigner ? [poolArgs.cosigner] : [])])
       .rpc({ skipPreflight: true });
@@ -599,6 +721,7 @@ export const createPoolWithExampleDeposits = async (
         cosigner: poolArgs.cosigner?.publicKey ?? poolArgs.owner,
         pool: poolKey,
         sharedEscrowAccount,
+        globalConfig: getMMMGlobalConfigPDA(program.programId).key,
       })
       .signers([...(poolArgs.cosigner ? [poolArgs.cosigner] : [])])
       .rpc();
@@ -678,6 +801,8 @@ export const createPoolWithExampleOcpDeposits = async (
       .ocpDepositSell({
         assetAmount: new anchor.BN(1),
         allowlistAux: null,
+        priceOverride: null,
+        authorizationData: null,
       })
       .accountsStrict({
         owner: poolArgs.owner,
@@ -700,6 +825,7 @@ export const createPoolWithExampleOcpDeposits = async (
         tokenProgram: TOKEN_PROGRAM_ID,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         rent: SYSVAR_RENT_PUBKEY,
+        globalConfig: getMMMGlobalConfigPDA(program.programId).key,
       })
       .preInstructions([
         ComputeBudgetProgram.setComputeUnitLimit({ units: OCP_COMPUTE_UNITS }),
@@ -721,6 +847,7 @@ export const createPoolWithExampleOcpDeposits = async (
         pool: poolKey,
         buysideSolEscrowAccount: solEscrowKey,
         systemProgram: SystemProgram.programId,
+        globalConfig: getMMMGlobalConfigPDA(program.programId).key,
       })
       .signers([...(poolArgs.cosigner ? [poolArgs.cosigner] : [])])
       .rpc({ skipPreflight: true });
@@ -737,6 +864,7 @@ export const createPoolWithExampleOcpDeposits = async (
         cosigner: poolArgs.cosigner?.publicKey ?? poolArgs.owner,
         pool: poolKey,
         sharedEscrowAccount,
+        globalConfig: getMMMGlobalConfigPDA(program.programId).key,
       })
       .signers([...(poolArgs.cosigner ? [poolArgs.cosigner] : [])])
       .rpc();
@@ -975,7 +1103,12 @@ export async function createPoolWithExampleDepositsUmi(
       mintAddressNft,
     );
     await program.methods
-      .depositSell({ assetAmount: new anchor.BN(1), allowlistAux: '' })
+      .depositSell({
+        assetAmount: new anchor.BN(1),
+        allowlistAux: '',
+        priceOverride: null,
+        authorizationData: null,
+      })
       .accountsStrict({
         owner: poolArgs.owner,
         cosigner: poolArgs.cosigner?.publicKey ?? poolArgs.owner,
@@ -991,6 +1124,7 @@ export async function createPoolWithExampleDepositsUmi(
         tokenProgram: tokenProgramId,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         rent: SYSVAR_RENT_PUBKEY,
+        globalConfig: getMMMGlobalConfigPDA(program.programId).key,
       })
       .signers([...(poolArgs.cosigner ? [poolArgs.cosigner] : [])])
       .rpc({ skipPreflight: true });
@@ -1001,7 +1135,12 @@ export async function createPoolWithExampleDepositsUmi(
       mintAddressSft,
     );
     await program.methods
-      .depositSell({ assetAmount: new anchor.BN(5), allowlistAux: '' })
+      .depositSell({
+        assetAmount: new anchor.BN(5),
+        allowlistAux: '',
+        priceOverride: null,
+        authorizationData: null,
+      })
       .accountsStrict({
         owner: poolArgs.owner,
         cosigner: poolArgs.cosigner?.publicKey ?? poolArgs.owner,
@@ -1017,6 +1156,7 @@ export async function createPoolWithExampleDepositsUmi(
         tokenProgram: tokenProgramId,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         rent: SYSVAR_RENT_PUBKEY,
+        globalConfig: getMMMGlobalConfigPDA(program.programId).key,
       })
       .signers([...(poolArgs.cosigner ? [poolArgs.cosigner] : [])])
       .rpc({ skipPreflight: true });
@@ -1035,6 +1175,7 @@ export async function createPoolWithExampleDepositsUmi(
         pool: poolKey,
         buysideSolEscrowAccount: solEscrowKey,
         systemProgram: SystemProgram.programId,
+        globalConfig: getMMMGlobalConfigPDA(program.programId).key,
       })
       .signers([...(poolArgs.cosigner ? [poolArgs.cosigner] : [])])
       .rpc({ skipPreflight: true });
@@ -1051,10 +1192,123 @@ export async function createPoolWithExampleDepositsUmi(
     poolAtaExtraNft,
     poolPaymentEscrow: solEscrowKey,
     nftCreator: toWeb3JsKeypair(creator),
+    ...(collection
+      ? {
+          collection,
+          // the test umi identity minted the collection parent
+          collectionUpdateAuthority: toWeb3JsKeypair(
+            umi.identity as KeypairSigner,
+          ),
+        }
+      : {}),
     ...poolData,
   };
 }
 
+// creates a two sided pool of example nfts with a 0.1 SOL linear curve from a
+// 1 SOL spot price, the seller gets the extraNft to sell into the pool
+export const createTwoSidedPool = async (
+  program: Program<Mmm>,
+  seller: Keypair,
+  poolArgs: Parameters<typeof createPool>[1],
+  allowlistKind: AllowlistKind = AllowlistKind.mint,
+) => {
+  const [poolData] = await Promise.all([
+    createPoolWithExampleDepositsUmi(
+      program,
+      [allowlistKind],
+      {
+        curveType: CurveKind.linear,
+        curveDelta: new anchor.BN(LAMPORTS_PER_SOL).div(
+          new anchor.BN(10),
+        ), // 0.1 SOL
+        ...poolArgs,
+      },
+      'both',
+      TOKEN_PROGRAM_ID,
+      seller.publicKey,
+    ),
+    airdrop(program.provider.connection, seller.publicKey, 10),
+  ]);
+  return poolData;
+};
+
+// sells the extraNft of the seller into a pool created by createTwoSidedPool,
+// the args default to a 1% taker fee
+export const solFulfillBuy = async (
+  program: Program<Mmm>,
+  {
+    poolData,
+    owner,
+    seller,
+    cosigner,
+    args = {},
+    treasury = seller.publicKey,
+    remainingAccounts = [],
+  }: {
+    poolData: PoolData;
+    owner: PublicKey;
+    seller: Keypair;
+    cosigner: Keypair;
+    args?: Partial<Parameters<Program<Mmm>['methods']['solFulfillBuy']>[0]>;
+    treasury?: PublicKey;
+    remainingAccounts?: AccountMeta[];
+  },
+) => {
+  const mint = toWeb3JsPublicKey(poolData.extraNft.mintAddress);
+  return await program.methods
+    .solFulfillBuy({
+      assetAmount: new anchor.BN(1),
+      minPaymentAmount: new anchor.BN(0),
+      allowlistAux: null,
+      takerFeeBp: 100,
+      makerFeeBp: 0,
+      traitPremium: null,
+      authorizationData: null,
+      ...args,
+    })
+    .accountsStrict({
+      payer: seller.publicKey,
+      owner,
+      cosigner: cosigner.publicKey,
+      referral: poolData.referral.publicKey,
+      pool: poolData.poolKey,
+      buysideSolEscrowAccount: poolData.poolPaymentEscrow,
+      assetMetadata: poolData.extraNft.metadataAddress,
+      assetMasterEdition: poolData.extraNft.masterEditionAddress,
+      assetMint: mint,
+      payerAssetAccount: poolData.extraNft.tokenAddress!,
+      sellsideEscrowTokenAccount: poolData.poolAtaExtraNft,
+      ownerTokenAccount: await getAssociatedTokenAddress(
+        mint,
+        owner,
+        true,
+        TOKEN_PROGRAM_ID,
+      ),
+      allowlistAuxAccount: SystemProgram.programId,
+      sellState: getMMMSellStatePDA(program.programId, poolData.poolKey, mint)
+        .key,
+      systemProgram: SystemProgram.programId,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      rent: SYSVAR_RENT_PUBKEY,
+      globalConfig: getMMMGlobalConfigPDA(program.programId).key,
+      treasury,
+      feeSchedule: getMMMFeeSchedulePDA(
+        program.programId,
+        poolData.referral.publicKey,
+      ).key,
+      royaltySchedule: await getRoyaltySchedulePDAForMint(program, mint),
+      referralRegistry: getMMMReferralRegistryPDA(
+        program.programId,
+        poolData.referral.publicKey,
+      ).key,
+    })
+    .remainingAccounts(remainingAccounts)
+    .signers([seller, cosigner])
+    .rpc();
+};
+
 export const createPoolWithExampleMip1Deposits = async (
   program: Program<Mmm>,
   poolArgs: Parameters<typeof createPool>[1],
@@ -1132,6 +1386,8 @@ export const createPoolWithExampleMip1Deposits = async (
       .mip1DepositSell({
         assetAmount: new anchor.BN(1),
         allowlistAux: null,
+        priceOverride: null,
+        authorizationData: null,
       })
       .accountsStrict({
         owner: poolArgs.owner,
@@ -1160,6 +1416,7 @@ export const createPoolWithExampleMip1Deposits = async (
         tokenProgram: tokenProgramId,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         rent: SYSVAR_RENT_PUBKEY,
+        globalConfig: getMMMGlobalConfigPDA(program.programId).key,
       })
       .preInstructions([
         ComputeBudgetProgram.setComputeUnitLimit({ units: MIP1_COMPUTE_UNITS }),
@@ -1181,6 +1438,7 @@ export const createPoolWithExampleMip1Deposits = async (
         pool: poolKey,
         buysideSolEscrowAccount: solEscrowKey,
         systemProgram: SystemProgram.programId,
+        globalConfig: getMMMGlobalConfigPDA(program.programId).key,
       })
       .signers([...(poolArgs.cosigner ? [poolArgs.cosigner] : [])])
       .rpc({ skipPreflight: true });
@@ -1197,6 +1455,7 @@ export const createPoolWithExampleMip1Deposits = async (
         cosigner: poolArgs.cosigner?.publicKey ?? poolArgs.owner,
         pool: poolKey,
         sharedEscrowAccount,
+        globalConfig: getMMMGlobalConfigPDA(program.programId).key,
       })
       .signers([...(poolArgs.cosigner ? [poolArgs.cosigner] : [])])
       .rpc();
@@ -1260,6 +1519,7 @@ export const createPoolWithExampleMplCoreDeposits = async (
         pool: poolKey,
         buysideSolEscrowAccount,
         systemProgram: SystemProgram.programId,
+        globalConfig: getMMMGlobalConfigPDA(program.programId).key,
       })
       .signers([...(poolArgs.cosigner ? [poolArgs.cosigner] : [])])
       .rpc({ skipPreflight: true });
@@ -1276,6 +1536,7 @@ export const createPoolWithExampleMplCoreDeposits = async (
         cosigner: poolArgs.cosigner?.publicKey ?? poolArgs.owner,
         pool: poolKey,
         sharedEscrowAccount,
+        globalConfig: getMMMGlobalConfigPDA(program.programId).key,
       })
       .signers([...(poolArgs.cosigner ? [poolArgs.cosigner] : [])])
       .rpc();
@@ -425,6 +425,15 @@ export async function createTestMintAndTokenT22VanillaExt(
         ? [updateMetadataCreatorIx!, updateMetadataRoyaltyIx!]
         : [updateMetadataLbpRoyaltyIx!]),
     );
+  } else if (transferHookArgs) {
+    // any other hook program, without creating its ExtraAccountMetaList
+    const createTransferHookIx = createInitializeTransferHookInstruction(
+      mintKeypair.publicKey,
+      payer.publicKey,
+      transferHookArgs.transferHookProgramId,
+      tokenProgramId,
+    );
+    ixs.push(createTransferHookIx, createInitMintIx, createMetadataIx);
   } else {
     ixs.push(createInitMintIx, createMetadataIx);
   }